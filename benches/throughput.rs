@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use grafton_ndi::{
+    run_loopback, AudioFrameBuilder, CaptureMode, LoopbackConfig, Pattern, SendMode,
+    SharedVideoFrame, NDI,
+};
+
+const RESOLUTIONS: [(i32, i32); 3] = [(1280, 720), (1920, 1080), (3840, 2160)];
+
+fn frame_bytes(width: i32, height: i32) -> u64 {
+    width as u64 * height as u64 * 4
+}
+
+/// Runs a loopback for `iters` frames and returns how long they took to arrive.
+fn loopback(ndi: &NDI, config: LoopbackConfig, iters: u64) -> Duration {
+    let config = config.with_frames(iters.clamp(2, u64::from(u32::MAX)) as u32);
+    run_loopback(ndi, &config)
+        .expect("loopback run failed; is the NDI runtime able to reach localhost?")
+        .elapsed
+}
+
+/// Owned against zero-copy capture: the receive rate with and without a copy per frame.
+fn capture(c: &mut Criterion, ndi: &NDI) {
+    let mut group = c.benchmark_group("loopback_capture");
+    group.sample_size(10);
+    for (width, height) in RESOLUTIONS {
+        group.throughput(Throughput::Bytes(frame_bytes(width, height)));
+        for mode in [CaptureMode::Ref, CaptureMode::Owned] {
+            let id = BenchmarkId::new(format!("{mode:?}"), format!("{width}x{height}"));
+            group.bench_function(id, |b| {
+                b.iter_custom(|iters| {
+                    loopback(
+                        ndi,
+                        LoopbackConfig::new(width, height).with_capture(mode),
+                        iters,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Synchronous sends against async sends with one, two and three buffers in rotation.
+fn pipeline_depth(c: &mut Criterion, ndi: &NDI) {
+    let mut group = c.benchmark_group("loopback_send");
+    group.sample_size(10);
+    let (width, height) = (1920, 1080);
+    group.throughput(Throughput::Bytes(frame_bytes(width, height)));
+    let modes = [
+        SendMode::Sync,
+        SendMode::Async { buffers: 1 },
+        SendMode::Async { buffers: 2 },
+        SendMode::Async { buffers: 3 },
+    ];
+    for mode in modes {
+        let id = match mode {
+            SendMode::Sync => "sync".to_string(),
+            SendMode::Async { buffers } => format!("async_{buffers}"),
+        };
+        group.bench_function(id, |b| {
+            b.iter_custom(|iters| {
+                loopback(
+                    ndi,
+                    LoopbackConfig::new(width, height).with_send(mode),
+                    iters,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+/// What fanning a frame out costs without the SDK involved: copying it against sharing it.
+fn fan_out(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fan_out");
+    for (width, height) in RESOLUTIONS {
+        let frame = Pattern::ColorBars.frame(width, height, 60).unwrap();
+        let shared = SharedVideoFrame::from(frame.clone());
+        group.throughput(Throughput::Bytes(frame_bytes(width, height)));
+        let size = format!("{width}x{height}");
+        group.bench_with_input(BenchmarkId::new("clone", &size), &frame, |b, frame| {
+            b.iter(|| frame.clone())
+        });
+        group.bench_with_input(BenchmarkId::new("shared", &size), &shared, |b, shared| {
+            b.iter(|| shared.clone())
+        });
+    }
+    group.finish();
+}
+
+/// The cost of the frame analysis checks, which a monitoring loop runs on every frame.
+fn analysis(c: &mut Criterion) {
+    let mut group = c.benchmark_group("analysis");
+    for (width, height) in RESOLUTIONS {
+        let bars = Pattern::ColorBars.frame(width, height, 60).unwrap();
+        let checker = Pattern::Checker.frame(width, height, 60).unwrap();
+        group.throughput(Throughput::Bytes(frame_bytes(width, height)));
+        let size = format!("{width}x{height}");
+        group.bench_with_input(BenchmarkId::new("is_black", &size), &bars, |b, frame| {
+            b.iter(|| frame.is_black(25).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("difference_score", &size),
+            &(bars, checker),
+            |b, (frame, previous)| b.iter(|| frame.difference_score(previous).unwrap()),
+        );
+    }
+    // A quiet second of stereo, which must be scanned to the end.
+    let audio = AudioFrameBuilder::new()
+        .sample_rate(48_000)
+        .channels(2)
+        .samples(48_000)
+        .data(vec![0.0001; 96_000])
+        .build()
+        .unwrap();
+    group.throughput(Throughput::Elements(96_000));
+    group.bench_function("is_silent", |b| b.iter(|| audio.is_silent(-60.0)));
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    fan_out(c);
+    analysis(c);
+    // The loopback benchmarks need the NDI runtime; without it only the above run.
+    match NDI::new() {
+        Ok(ndi) => {
+            capture(c, &ndi);
+            pipeline_depth(c, &ndi);
+        }
+        Err(e) => eprintln!("skipping loopback benchmarks: {e}"),
+    }
+}
+
+criterion_group!(throughput, benches);
+criterion_main!(throughput);
@@ -5,9 +5,14 @@ use std::path::PathBuf;
 
 fn main() {
     // Base path to the NDI SDK from the environment variable or default based on the platform
+    let advanced_sdk = env::var("CARGO_FEATURE_ADVANCED_SDK").is_ok();
     let ndi_sdk_path = env::var("NDI_SDK_DIR").unwrap_or_else(|_| {
-        if cfg!(unix) {
+        if cfg!(unix) && advanced_sdk {
+            "/usr/share/NDI Advanced SDK for Linux".to_string()
+        } else if cfg!(unix) {
             "/usr/share/NDI SDK for Linux".to_string()
+        } else if cfg!(windows) && advanced_sdk {
+            "C:\\Program Files\\NDI Advanced SDK for Windows".to_string()
         } else if cfg!(windows) {
             "C:\\Program Files\\NDI SDK for Windows".to_string()
         } else {
@@ -19,17 +24,39 @@ fn main() {
     let ndi_include_path = format!("{}/include", ndi_sdk_path);
     let main_header = format!("{}/Processing.NDI.Lib.h", ndi_include_path);
 
+    // Functions only some SDK releases have, enabled as `cfg`s when the headers declare them.
+    let optional_functions = [(
+        "has_async_audio_completion",
+        &[
+            "NDIlib_send_send_audio_v3_async",
+            "NDIlib_send_set_audio_async_completion",
+        ][..],
+    )];
+    for (cfg, symbols) in optional_functions {
+        if symbols
+            .iter()
+            .all(|symbol| headers_declare(&ndi_include_path, symbol))
+        {
+            println!("cargo:rustc-cfg={}", cfg);
+        }
+    }
+
     // Determine the library name and linking type based on the platform
     let (lib_name, link_type) = if cfg!(unix) {
-        // For Unix-like systems, use the shared library `libndi.so`
-        ("ndi", "dylib") // Use "dylib" for dynamic linking
+        // For Unix-like systems, use the shared library `libndi.so` (`libndi_advanced.so` for the Advanced SDK)
+        if advanced_sdk {
+            ("ndi_advanced", "dylib")
+        } else {
+            ("ndi", "dylib") // Use "dylib" for dynamic linking
+        }
     } else if cfg!(windows) {
         // For Windows systems, use the specific x86/x64 libraries with static linking
         let target = env::var("TARGET").expect("TARGET environment variable not set");
-        if target.contains("x86_64") {
-            ("Processing.NDI.Lib.x64", "static")
-        } else {
-            ("Processing.NDI.Lib.x86", "static")
+        match (target.contains("x86_64"), advanced_sdk) {
+            (true, true) => ("Processing.NDI.Lib.Advanced.x64", "static"),
+            (false, true) => ("Processing.NDI.Lib.Advanced.x86", "static"),
+            (true, false) => ("Processing.NDI.Lib.x64", "static"),
+            (false, false) => ("Processing.NDI.Lib.x86", "static"),
         }
     } else {
         panic!("Unsupported platform");
@@ -49,8 +76,11 @@ fn main() {
         println!("cargo:rustc-link-search=native={}", lib_path);
     }
 
-    // Inform cargo about the library to link against
-    println!("cargo:rustc-link-lib={}={}", link_type, lib_name);
+    // Inform cargo about the library to link against. With `dynamic_loading` the runtime is
+    // opened with libloading when `NDI::new` is called instead.
+    if !cfg!(feature = "dynamic_loading") {
+        println!("cargo:rustc-link-lib={}={}", link_type, lib_name);
+    }
 
     // Generate the bindings
     let bindings = bindgen::Builder::default()
@@ -63,7 +93,101 @@ fn main() {
     // Write the bindings to the $OUT_DIR/ndi_lib.rs file
     let out_path =
         PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR environment variable not set"));
+    #[cfg(not(feature = "dynamic_loading"))]
     bindings
         .write_to_file(out_path.join("ndi_lib.rs"))
         .expect("Couldn't write bindings!");
+    #[cfg(feature = "dynamic_loading")]
+    std::fs::write(
+        out_path.join("ndi_lib.rs"),
+        dynamic_bindings(&bindings.to_string()),
+    )
+    .expect("Couldn't write bindings!");
+}
+
+/// Whether any header in `include_path` mentions `symbol`.
+fn headers_declare(include_path: &str, symbol: &str) -> bool {
+    std::fs::read_dir(include_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "h"))
+        .any(|entry| {
+            std::fs::read_to_string(entry.path()).is_ok_and(|header| header.contains(symbol))
+        })
+}
+
+/// Rewrites the `extern "C"` declarations in `source` into a table of function pointers
+/// resolved from a loaded library, plus free functions with the original signatures that call
+/// through it, so the rest of the crate is unchanged.
+#[cfg(feature = "dynamic_loading")]
+fn dynamic_bindings(source: &str) -> String {
+    use quote::{format_ident, quote};
+    use syn::{FnArg, ForeignItem, Item, Pat};
+
+    let mut file = syn::parse_file(source).expect("Unable to parse generated bindings");
+    let mut functions = Vec::new();
+    file.items.retain(|item| match item {
+        Item::ForeignMod(foreign) => {
+            functions.extend(foreign.items.iter().filter_map(|item| match item {
+                ForeignItem::Fn(function) => Some(function.sig.clone()),
+                _ => None,
+            }));
+            false
+        }
+        _ => true,
+    });
+
+    let mut fields = Vec::new();
+    let mut loads = Vec::new();
+    let mut wrappers = Vec::new();
+    for sig in &functions {
+        let name = &sig.ident;
+        let symbol = format!("{}\0", name);
+        let missing = format!("{} is not exported by the loaded NDI runtime", name);
+        let output = &sig.output;
+        let mut types = Vec::new();
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+        for (i, input) in sig.inputs.iter().enumerate() {
+            if let FnArg::Typed(arg) = input {
+                let ident = match &*arg.pat {
+                    Pat::Ident(pat) => pat.ident.clone(),
+                    _ => format_ident!("arg{}", i),
+                };
+                let ty = &arg.ty;
+                types.push(quote!(#ty));
+                params.push(quote!(#ident: #ty));
+                args.push(quote!(#ident));
+            }
+        }
+        fields.push(quote! {
+            #name: Option<unsafe extern "C" fn(#(#types),*) #output>
+        });
+        loads.push(quote! {
+            #name: library.get(#symbol.as_bytes()).ok().map(|f: ::libloading::Symbol<unsafe extern "C" fn(#(#types),*) #output>| *f)
+        });
+        wrappers.push(quote! {
+            pub unsafe fn #name(#(#params),*) #output {
+                (crate::runtime::functions().#name.expect(#missing))(#(#args),*)
+            }
+        });
+    }
+
+    let dynamic = quote! {
+        pub(crate) struct RuntimeFunctions {
+            #(#fields,)*
+        }
+
+        impl RuntimeFunctions {
+            pub(crate) unsafe fn load(library: &::libloading::Library) -> Self {
+                RuntimeFunctions {
+                    #(#loads,)*
+                }
+            }
+        }
+
+        #(#wrappers)*
+    };
+    format!("{}\n{}", quote!(#file), dynamic)
 }
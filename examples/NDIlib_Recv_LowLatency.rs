@@ -0,0 +1,51 @@
+use grafton_ndi::{
+    Error, FieldMode, Find, Finder, LatencyMeter, Receiver, Recv, RecvBandwidth, RecvColorFormat,
+    NDI,
+};
+
+const MEASURED_FRAMES: usize = 300;
+
+fn main() -> Result<(), Error> {
+    let ndi = NDI::new()?;
+    let finder = Finder::new(false, None, None);
+    let ndi_find = Find::new(&ndi, finder)?;
+
+    let source = loop {
+        println!("Looking for sources ...");
+        ndi_find.wait_for_sources(5000);
+        if let Some(source) = ndi_find.get_sources(0)?.into_iter().next() {
+            break source;
+        }
+    };
+    println!("Receiving from {}", source.name);
+
+    let receiver = Receiver::new(
+        source,
+        RecvColorFormat::BGRX_BGRA,
+        RecvBandwidth::Highest,
+        FieldMode::Fielded,
+        None,
+    )
+    .low_latency_preset();
+    let ndi_recv = Recv::new(&ndi, receiver)?;
+
+    // Unless both machines follow the same clock (e.g. PTP), latencies are relative to the
+    // fastest frame seen, which still shows queueing and jitter.
+    let mut meter = LatencyMeter::new(MEASURED_FRAMES);
+    let mut frames = 0;
+    while frames < MEASURED_FRAMES {
+        if let Some(frame) = ndi_recv.capture_latest_video_ref(1000)? {
+            meter.observe(frame.timestamp());
+            frames += 1;
+        }
+    }
+
+    match meter.estimate() {
+        Some(estimate) => println!(
+            "latency over {} frames: mean {:?}, min {:?}, max {:?}, jitter {:?}",
+            estimate.samples, estimate.mean, estimate.min, estimate.max, estimate.jitter
+        ),
+        None => println!("the source doesn't timestamp its frames"),
+    }
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use std::fs::File;
 
 use grafton_ndi::{
-    Error, Find, Finder, FrameType, Receiver, Recv, RecvBandwidth, RecvColorFormat, VideoFrame, NDI,
+    Error, FieldMode, Find, Finder, FrameType, Receiver, Recv, RecvBandwidth, RecvColorFormat,
+    VideoFrame, NDI,
 };
 
 fn main() -> Result<(), Error> {
@@ -39,7 +40,7 @@ fn main() -> Result<(), Error> {
             source,
             RecvColorFormat::RGBX_RGBA,
             RecvBandwidth::Highest,
-            false,
+            FieldMode::Progressive,
             None,
         );
         let mut ndi_recv = Recv::new(&ndi, receiver)?;
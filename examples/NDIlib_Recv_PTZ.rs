@@ -1,6 +1,8 @@
 use std::time::{Duration, Instant};
 
-use grafton_ndi::{Find, Finder, FrameType, Receiver, Recv, RecvBandwidth, RecvColorFormat, NDI};
+use grafton_ndi::{
+    FieldMode, Find, Finder, FrameType, Receiver, Recv, RecvBandwidth, RecvColorFormat, NDI,
+};
 
 fn main() {
     if let Ok(ndi) = NDI::new() {
@@ -28,7 +30,7 @@ fn main() {
             source_to_connect_to,
             RecvColorFormat::UYVY_BGRA,
             RecvBandwidth::Highest,
-            true,
+            FieldMode::Fielded,
             Some("Example PTZ Receiver".to_string()),
         );
 
@@ -0,0 +1,91 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use grafton_ndi::{
+    Error, FieldMode, Find, Finder, Receiver, Recv, RecvBandwidth, RecvColorFormat, NDI,
+};
+
+// Counts every heap allocation made by the process so the capture loop can be checked.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const WARM_UP_FRAMES: usize = 30;
+const MEASURED_FRAMES: usize = 300;
+
+fn main() -> Result<(), Error> {
+    let ndi = NDI::new()?;
+    let finder = Finder::new(false, None, None);
+    let ndi_find = Find::new(&ndi, finder)?;
+
+    let source = loop {
+        println!("Looking for sources ...");
+        ndi_find.wait_for_sources(5000);
+        if let Some(source) = ndi_find.get_sources(0)?.into_iter().next() {
+            break source;
+        }
+    };
+    println!("Receiving from {}", source.name);
+
+    let receiver = Receiver::new(
+        source,
+        RecvColorFormat::UYVY_BGRA,
+        RecvBandwidth::Highest,
+        FieldMode::Progressive,
+        None,
+    );
+    let ndi_recv = Recv::new(&ndi, receiver)?;
+
+    // Let the connection settle before measuring; the SDK and first frames may allocate.
+    let mut frames = 0;
+    while frames < WARM_UP_FRAMES {
+        if ndi_recv.capture_video_ref(1000)?.is_some() {
+            frames += 1;
+        }
+    }
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let mut frames = 0;
+    let mut checksum = 0u64;
+    while frames < MEASURED_FRAMES {
+        if let Some(frame) = ndi_recv.capture_video_ref(1000)? {
+            checksum = checksum.wrapping_add(frame.data().first().copied().unwrap_or(0) as u64);
+            frames += 1;
+        }
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    println!(
+        "{} frames captured with {} heap allocations (checksum {})",
+        frames, allocations, checksum
+    );
+    if allocations == 0 {
+        Ok(())
+    } else {
+        Err(Error::CaptureFailed(format!(
+            "capture_video_ref allocated {} times over {} frames",
+            allocations, frames
+        )))
+    }
+}
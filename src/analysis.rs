@@ -0,0 +1,332 @@
+use crate::{AudioFrame, AudioFrameRef, Error, FourCCVideoType, VideoFrame, VideoFrameRef};
+
+/// Share of pixels that must be dark for [`VideoFrame::is_black`], as in FFmpeg's
+/// `blackdetect`, so a small logo or timecode burn-in doesn't hide a black picture.
+const BLACK_PIXEL_RATIO: f64 = 0.98;
+
+/// Where the luma of each pixel lives in a row of some format.
+#[derive(Debug, Clone, Copy)]
+enum LumaLayout {
+    /// 8-bit RGB-family pixels of four bytes; luma is computed from the color channels.
+    Rgb { bgr: bool },
+    /// Video-range Y samples `step` bytes apart starting at `offset`; for 16-bit formats,
+    /// `offset` is the sample's most significant byte.
+    Y { step: usize, offset: usize },
+}
+
+impl LumaLayout {
+    fn of(fourcc: FourCCVideoType, operation: &'static str) -> Result<Self, Error> {
+        Ok(match fourcc {
+            FourCCVideoType::BGRA | FourCCVideoType::BGRX => LumaLayout::Rgb { bgr: true },
+            FourCCVideoType::RGBA | FourCCVideoType::RGBX => LumaLayout::Rgb { bgr: false },
+            // U Y V Y; the alpha plane of UYVA follows the picture.
+            FourCCVideoType::UYVY | FourCCVideoType::UYVA => LumaLayout::Y { step: 2, offset: 1 },
+            // Little-endian 16-bit Y plane first.
+            FourCCVideoType::P216 | FourCCVideoType::PA16 => LumaLayout::Y { step: 2, offset: 1 },
+            FourCCVideoType::I420 | FourCCVideoType::YV12 | FourCCVideoType::NV12 => {
+                LumaLayout::Y { step: 1, offset: 0 }
+            }
+            FourCCVideoType::Max => return Err(Error::UnsupportedFormat { fourcc, operation }),
+        })
+    }
+
+    /// Full-range luma (0-255) of each pixel of `row`.
+    fn row<'d>(self, row: &'d [u8], xres: usize) -> impl Iterator<Item = u8> + 'd {
+        (0..xres).map(move |x| match self {
+            LumaLayout::Rgb { bgr } => {
+                let p = &row[x * 4..x * 4 + 3];
+                let (r, g, b) = if bgr {
+                    (p[2], p[1], p[0])
+                } else {
+                    (p[0], p[1], p[2])
+                };
+                // BT.709 weights in 8-bit fixed point.
+                ((54 * r as u32 + 183 * g as u32 + 19 * b as u32) >> 8) as u8
+            }
+            LumaLayout::Y { step, offset } => {
+                let y = row[x * step + offset] as i32;
+                ((y - 16).clamp(0, 219) * 255 / 219) as u8
+            }
+        })
+    }
+}
+
+/// The first plane of a frame, as needed to read its luma.
+struct LumaPlane<'d> {
+    layout: LumaLayout,
+    xres: usize,
+    yres: usize,
+    stride: usize,
+    data: &'d [u8],
+}
+
+impl<'d> LumaPlane<'d> {
+    fn new(
+        fourcc: FourCCVideoType,
+        xres: i32,
+        yres: i32,
+        stride: i32,
+        data: &'d [u8],
+        operation: &'static str,
+    ) -> Result<Self, Error> {
+        if xres <= 0 || yres <= 0 {
+            return Err(Error::InvalidResolution { xres, yres });
+        }
+        let min_stride = fourcc.line_stride(xres);
+        if stride < min_stride {
+            return Err(Error::InvalidStride {
+                fourcc,
+                xres,
+                expected: min_stride,
+                actual: stride,
+            });
+        }
+        let plane = LumaPlane {
+            layout: LumaLayout::of(fourcc, operation)?,
+            xres: xres as usize,
+            yres: yres as usize,
+            stride: stride as usize,
+            data,
+        };
+        let needed = plane.stride * (plane.yres - 1) + min_stride as usize;
+        if data.len() < needed {
+            return Err(Error::InvalidFrame(format!(
+                "{} bytes of data is too little for a {}x{} {:?} frame",
+                data.len(),
+                xres,
+                yres,
+                fourcc
+            )));
+        }
+        Ok(plane)
+    }
+
+    fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = u8> + 'd> + '_ {
+        (0..self.yres).map(move |y| {
+            let row = &self.data[y * self.stride..];
+            self.layout.row(row, self.xres)
+        })
+    }
+
+    fn is_black(&self, threshold: u8) -> bool {
+        let total = self.xres * self.yres;
+        // Stop as soon as too many bright pixels have been seen.
+        let allowed_bright = total - (total as f64 * BLACK_PIXEL_RATIO).ceil() as usize;
+        let mut bright = 0;
+        for row in self.rows() {
+            bright += row.filter(|&luma| luma > threshold).count();
+            if bright > allowed_bright {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn difference_score(&self, previous: &LumaPlane<'_>) -> Result<f32, Error> {
+        if (self.xres, self.yres) != (previous.xres, previous.yres) {
+            return Err(Error::InvalidFrame(format!(
+                "cannot compare a {}x{} frame with a {}x{} one",
+                self.xres, self.yres, previous.xres, previous.yres
+            )));
+        }
+        let sum: u64 = self
+            .rows()
+            .zip(previous.rows())
+            .map(|(row, previous_row)| {
+                row.zip(previous_row)
+                    .map(|(a, b)| a.abs_diff(b) as u64)
+                    .sum::<u64>()
+            })
+            .sum();
+        let total = (self.xres * self.yres) as f64 * 255.0;
+        Ok((sum as f64 / total) as f32)
+    }
+}
+
+impl VideoFrame {
+    /// Whether the picture is black: at least 98% of its pixels have a luma at or below
+    /// `threshold`, on a 0-255 scale where video-range YUV black (16) is 0.
+    ///
+    /// A `threshold` around 25 (10%) tolerates noise from cameras and encoders. Supports
+    /// every uncompressed format; only the luma is examined.
+    pub fn is_black(&self, threshold: u8) -> Result<bool, Error> {
+        Ok(self.luma_plane("detect black in")?.is_black(threshold))
+    }
+
+    /// How much the picture differs from `previous`: the mean absolute luma difference,
+    /// from 0.0 for identical pictures to 1.0 for black against white.
+    ///
+    /// A frozen source yields scores at or near zero frame after frame; compressed sources
+    /// rarely reach exactly zero, so compare against a small threshold such as 0.002. The
+    /// frames may be in different formats but must have the same resolution.
+    pub fn difference_score(&self, previous: &VideoFrame) -> Result<f32, Error> {
+        self.luma_plane("compare")?
+            .difference_score(&previous.luma_plane("compare")?)
+    }
+
+    fn luma_plane(&self, operation: &'static str) -> Result<LumaPlane<'_>, Error> {
+        let stride = unsafe { self.line_stride_or_size.line_stride_in_bytes };
+        LumaPlane::new(
+            self.fourcc,
+            self.xres,
+            self.yres,
+            stride,
+            &self.data,
+            operation,
+        )
+    }
+}
+
+impl VideoFrameRef<'_> {
+    /// As [`VideoFrame::is_black`], reading the SDK's buffer in place.
+    pub fn is_black(&self, threshold: u8) -> Result<bool, Error> {
+        Ok(self.luma_plane("detect black in")?.is_black(threshold))
+    }
+
+    /// As [`VideoFrame::difference_score`], against an earlier frame kept as a copy.
+    pub fn difference_score(&self, previous: &VideoFrame) -> Result<f32, Error> {
+        self.luma_plane("compare")?
+            .difference_score(&previous.luma_plane("compare")?)
+    }
+
+    fn luma_plane(&self, operation: &'static str) -> Result<LumaPlane<'_>, Error> {
+        let fourcc = self.fourcc();
+        let stride = self
+            .line_stride()
+            .ok_or(Error::UnsupportedFormat { fourcc, operation })?;
+        LumaPlane::new(
+            fourcc,
+            self.xres(),
+            self.yres(),
+            stride,
+            self.data(),
+            operation,
+        )
+    }
+}
+
+impl AudioFrame {
+    /// Whether every sample of every channel is below `threshold_dbfs`, e.g. -60.0.
+    pub fn is_silent(&self, threshold_dbfs: f32) -> bool {
+        let limit = dbfs_to_amplitude(threshold_dbfs);
+        (0..self.no_channels.max(0) as usize)
+            .filter_map(|c| self.channel_samples(c))
+            .flatten()
+            .all(|sample| sample.abs() < limit)
+    }
+}
+
+impl AudioFrameRef<'_> {
+    /// Whether every sample of every channel is below `threshold_dbfs`, e.g. -60.0.
+    pub fn is_silent(&self, threshold_dbfs: f32) -> bool {
+        let limit = dbfs_to_amplitude(threshold_dbfs);
+        (0..self.no_channels().max(0) as usize)
+            .filter_map(|c| self.channel(c))
+            .flatten()
+            .all(|sample| sample.abs() < limit)
+    }
+}
+
+fn dbfs_to_amplitude(dbfs: f32) -> f32 {
+    10f32.powf(dbfs / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioFrameBuilder, VideoFrameBuilder};
+
+    fn filled(fourcc: FourCCVideoType, xres: i32, yres: i32, byte: u8) -> VideoFrame {
+        let size = fourcc.line_stride(xres) as usize * yres as usize;
+        VideoFrameBuilder::new()
+            .resolution(xres, yres)
+            .fourcc(fourcc)
+            .data(vec![byte; size])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn black_and_white_rgb() {
+        assert!(filled(FourCCVideoType::BGRA, 10, 10, 0)
+            .is_black(25)
+            .unwrap());
+        assert!(!filled(FourCCVideoType::RGBA, 10, 10, 255)
+            .is_black(25)
+            .unwrap());
+    }
+
+    #[test]
+    fn video_range_black_counts_as_black() {
+        // UYVY with Y and chroma at 16 and 128 is video-range black.
+        let mut frame = filled(FourCCVideoType::UYVY, 4, 2, 128);
+        for y in frame.data.iter_mut().skip(1).step_by(2) {
+            *y = 16;
+        }
+        assert!(frame.is_black(0).unwrap());
+    }
+
+    #[test]
+    fn a_small_logo_does_not_hide_black() {
+        let mut frame = filled(FourCCVideoType::BGRA, 10, 10, 0);
+        // One bright pixel in a hundred is within the 2% allowance; three are not.
+        frame.data[..4].fill(255);
+        assert!(frame.is_black(25).unwrap());
+        frame.data[..12].fill(255);
+        assert!(!frame.is_black(25).unwrap());
+    }
+
+    #[test]
+    fn difference_ranges_from_identical_to_inverted() {
+        let black = filled(FourCCVideoType::BGRA, 8, 8, 0);
+        let white = filled(FourCCVideoType::BGRA, 8, 8, 255);
+        assert_eq!(black.difference_score(&black).unwrap(), 0.0);
+        assert!((black.difference_score(&white).unwrap() - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn difference_compares_across_formats() {
+        let mut uyvy = filled(FourCCVideoType::UYVY, 8, 8, 128);
+        for y in uyvy.data.iter_mut().skip(1).step_by(2) {
+            *y = 16;
+        }
+        let black = filled(FourCCVideoType::BGRA, 8, 8, 0);
+        assert_eq!(uyvy.difference_score(&black).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn difference_needs_matching_resolutions() {
+        let a = filled(FourCCVideoType::BGRA, 8, 8, 0);
+        let b = filled(FourCCVideoType::BGRA, 4, 8, 0);
+        assert!(matches!(
+            a.difference_score(&b),
+            Err(Error::InvalidFrame(_))
+        ));
+    }
+
+    #[test]
+    fn short_data_is_rejected() {
+        let mut frame = filled(FourCCVideoType::BGRA, 8, 8, 0);
+        frame.data.truncate(10);
+        assert!(matches!(frame.is_black(25), Err(Error::InvalidFrame(_))));
+    }
+
+    #[test]
+    fn silence_is_measured_against_the_threshold() {
+        let quiet = AudioFrameBuilder::new()
+            .samples(4)
+            .data(vec![0.0001; 8])
+            .build()
+            .unwrap();
+        // 0.0001 is -80 dBFS.
+        assert!(quiet.is_silent(-60.0));
+        assert!(!quiet.is_silent(-90.0));
+
+        let one_loud_sample = AudioFrameBuilder::new()
+            .samples(4)
+            .set_channel_data(1, &[0.0, 0.0, 0.5, 0.0])
+            .build()
+            .unwrap();
+        assert!(!one_loud_sample.is_silent(-60.0));
+    }
+}
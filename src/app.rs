@@ -0,0 +1,143 @@
+use std::{ops::ControlFlow, time::Duration};
+
+use crate::{
+    Error, FieldMode, Find, Finder, FramePacer, FrameType, Receiver, Recv, RecvBandwidth,
+    RecvColorFormat, Send, Sender, Source, VideoFrame, VideoFrameRef, NDI,
+};
+
+/// A small facade over the common discover, receive and send flows, driven by callbacks.
+///
+/// Everything here is built from [`Find`], [`Recv`], [`Send`] and [`FramePacer`], which remain
+/// available through [`NdiApp::ndi`] when more control is needed.
+pub struct NdiApp {
+    ndi: NDI,
+    finder: Finder,
+    color_format: RecvColorFormat,
+    bandwidth: RecvBandwidth,
+    poll_timeout_ms: u32,
+}
+
+impl NdiApp {
+    pub fn new() -> Result<Self, Error> {
+        Ok(NdiApp {
+            ndi: NDI::new()?,
+            finder: Finder::new(true, None, None),
+            color_format: RecvColorFormat::BGRX_BGRA,
+            bandwidth: RecvBandwidth::Highest,
+            poll_timeout_ms: 1000,
+        })
+    }
+
+    /// Discovery settings used by [`NdiApp::discover`] and [`NdiApp::find_source`].
+    pub fn with_finder(mut self, finder: Finder) -> Self {
+        self.finder = finder;
+        self
+    }
+
+    pub fn with_color_format(mut self, color_format: RecvColorFormat) -> Self {
+        self.color_format = color_format;
+        self
+    }
+
+    pub fn with_bandwidth(mut self, bandwidth: RecvBandwidth) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// The runtime, for dropping down to the lower-level APIs.
+    pub fn ndi(&self) -> &NDI {
+        &self.ndi
+    }
+
+    /// Returns the sources visible after waiting up to `timeout` for the network to settle.
+    pub fn discover(&self, timeout: Duration) -> Result<Vec<Source>, Error> {
+        let find = Find::new(&self.ndi, self.finder.clone())?;
+        find.wait_for_sources(timeout.as_millis().min(u32::MAX as u128) as u32);
+        find.get_sources(0)
+    }
+
+    /// Waits up to `timeout` for the source `name` names, as [`Find::find_source`] matches it.
+    pub fn find_source(&self, name: &str, timeout: Duration) -> Result<Source, Error> {
+        let find = Find::new(&self.ndi, self.finder.clone())?;
+        find.find_source(name, std::time::Instant::now() + timeout)
+    }
+
+    pub fn connect(&self, source: Source) -> Result<Recv<'_>, Error> {
+        let receiver = Receiver::new(
+            source,
+            self.color_format,
+            self.bandwidth,
+            FieldMode::Progressive,
+            None,
+        );
+        Recv::new(&self.ndi, receiver)
+    }
+
+    /// Receives every frame from `source`, owned, until `on_frame` breaks.
+    pub fn receive(
+        &self,
+        source: Source,
+        mut on_frame: impl FnMut(FrameType) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        let mut recv = self.connect(source)?;
+        loop {
+            let frame = recv.capture(self.poll_timeout_ms)?;
+            if let FrameType::None = frame {
+                continue;
+            }
+            if on_frame(frame).is_break() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Receives video from `source` without copying it until `on_frame` breaks.
+    pub fn receive_video(
+        &self,
+        source: Source,
+        mut on_frame: impl FnMut(&VideoFrameRef) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        let recv = self.connect(source)?;
+        loop {
+            if let Some(frame) = recv.capture_video_ref(self.poll_timeout_ms)? {
+                if on_frame(&frame).is_break() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Publishes a source called `name` and sends the frames `generate` fills in until it
+    /// breaks.
+    ///
+    /// `template` provides the format, frame rate and initial contents. `generate` is given the
+    /// frame number and the reusable frame to draw into.
+    pub fn generate(
+        &self,
+        name: &str,
+        template: VideoFrame,
+        mut generate: impl FnMut(u64, &mut VideoFrame) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        let send = Send::new(
+            &self.ndi,
+            Sender {
+                name: name.to_string(),
+                groups: None,
+                clock_video: false,
+                clock_audio: false,
+                config: None,
+            },
+        )?;
+        let mut pacer = FramePacer::new(
+            template.frame_rate_n.max(1) as u32,
+            template.frame_rate_d.max(1) as u32,
+        );
+        let mut frame = template;
+        loop {
+            if generate(pacer.frame(), &mut frame).is_break() {
+                return Ok(());
+            }
+            send.send_video_paced(&mut pacer, &mut frame)?;
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{shared_ndi::SharedNdi, Error, Find, Finder, Source};
+
+/// How long the change-watching thread waits in the SDK before checking for a closed stream.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+const POLL_TIMEOUT_MS: u32 = 250;
+
+/// A finder that can be shared with blocking tasks and watcher threads.
+pub(crate) struct SharedFind {
+    // Declared first so it is destroyed before the runtime reference below is released.
+    find: Find<'static>,
+    _ndi: SharedNdi,
+}
+
+// The SDK's discovery calls are thread-safe.
+unsafe impl std::marker::Send for SharedFind {}
+unsafe impl Sync for SharedFind {}
+
+impl SharedFind {
+    pub(crate) fn new(settings: Finder) -> Result<Arc<Self>, Error> {
+        let ndi = SharedNdi::acquire()?;
+        // SAFETY: `_ndi` is dropped after `find`.
+        let find = unsafe { Find::new_detached(settings)? };
+        Ok(Arc::new(SharedFind { find, _ndi: ndi }))
+    }
+
+    pub(crate) fn wait_for_sources(&self, timeout: Duration) -> bool {
+        self.find.wait_for_sources(timeout_ms(timeout))
+    }
+
+    /// Waits up to `timeout` for at least one source, then returns the current list.
+    pub(crate) fn find_sources(&self, timeout: Duration) -> Result<Vec<Source>, Error> {
+        let sources = self.find.get_sources(0)?;
+        if !sources.is_empty() || timeout.is_zero() {
+            return Ok(sources);
+        }
+        self.find.get_sources(timeout_ms(timeout))
+    }
+
+    /// Starts a thread that calls `publish` with the source list now and after every change.
+    /// The thread ends when `publish` returns `false` or `closed` returns `true`.
+    #[cfg(any(feature = "tokio", feature = "async-std"))]
+    pub(crate) fn watch(
+        self: &Arc<Self>,
+        mut publish: impl FnMut(Vec<Source>) -> bool + std::marker::Send + 'static,
+        closed: impl Fn() -> bool + std::marker::Send + 'static,
+    ) {
+        let shared = Arc::clone(self);
+        std::thread::spawn(move || {
+            let mut last: Option<Vec<Source>> = None;
+            while !closed() {
+                if last.is_some() && !shared.find.wait_for_sources(POLL_TIMEOUT_MS) {
+                    continue;
+                }
+                let Ok(sources) = shared.find.get_sources(0) else {
+                    return;
+                };
+                if last.as_ref() == Some(&sources) {
+                    continue;
+                }
+                ndi_debug!(count = sources.len(), "source list changed");
+                if !publish(sources.clone()) {
+                    return;
+                }
+                last = Some(sources);
+            }
+        });
+    }
+}
+
+pub(crate) fn timeout_ms(timeout: Duration) -> u32 {
+    timeout.as_millis().min(u32::MAX as u128) as u32
+}
@@ -0,0 +1,521 @@
+use std::{collections::VecDeque, ptr};
+
+use crate::{AudioFrame, Error, Send, VideoFrame};
+
+#[cfg(feature = "advanced_sdk")]
+use crate::ndi_lib::*;
+#[cfg(feature = "advanced_sdk")]
+use std::{
+    os::raw::c_void,
+    sync::{Arc, Condvar, Mutex, PoisonError},
+    time::Duration,
+};
+
+#[cfg(feature = "advanced_sdk")]
+type VideoAsyncCompletion = Option<
+    unsafe extern "C" fn(p_opaque: *mut c_void, p_video_data: *const NDIlib_video_frame_v2_t),
+>;
+
+#[cfg(feature = "advanced_sdk")]
+sdk_extern! {
+    fn NDIlib_send_set_video_async_completion(
+        p_instance: NDIlib_send_instance_t,
+        p_opaque: *mut c_void,
+        p_deallocator: VideoAsyncCompletion,
+    );
+}
+
+#[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+type AudioAsyncCompletion = Option<
+    unsafe extern "C" fn(p_opaque: *mut c_void, p_audio_data: *const NDIlib_audio_frame_v3_t),
+>;
+
+// Only in Advanced SDK releases whose headers declare them; see `build.rs`.
+#[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+sdk_extern! {
+    fn NDIlib_send_send_audio_v3_async(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v3_t,
+    );
+    fn NDIlib_send_set_audio_async_completion(
+        p_instance: NDIlib_send_instance_t,
+        p_opaque: *mut c_void,
+        p_deallocator: AudioAsyncCompletion,
+    );
+}
+
+/// Identifies a frame submitted to an [`AsyncVideoQueue`] or [`AsyncAudioQueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsyncSendHandle(u64);
+
+struct InFlight {
+    id: u64,
+    frame: VideoFrame,
+}
+
+/// A bounded pipeline of asynchronously sent video frames.
+///
+/// The queue owns every submitted frame until the SDK has finished with it, then makes the
+/// buffer available again through [`AsyncVideoQueue::reclaim`]. With the standard SDK a frame is
+/// released as soon as the next one is submitted, so at most one frame is in flight. With the
+/// `advanced_sdk` feature the SDK's completion callback is used instead, allowing up to `depth`
+/// frames to be in flight at once.
+pub struct AsyncVideoQueue<'s, 'a> {
+    send: &'s Send<'a>,
+    depth: usize,
+    next_id: u64,
+    in_flight: VecDeque<InFlight>,
+    released: VecDeque<(AsyncSendHandle, VideoFrame)>,
+    #[cfg(feature = "advanced_sdk")]
+    completions: Arc<Completions>,
+}
+
+/// Data addresses of frames the SDK reported finished, not yet collected by their queue.
+#[cfg(feature = "advanced_sdk")]
+#[derive(Debug, Default)]
+pub(crate) struct Completions {
+    done: Mutex<Vec<usize>>,
+    signal: Condvar,
+}
+
+#[cfg(feature = "advanced_sdk")]
+impl Completions {
+    /// Removes the addresses `owned` claims, calling `release` with each.
+    fn take_owned(&self, owned: impl Fn(usize) -> bool, mut release: impl FnMut(usize)) {
+        if let Ok(mut done) = self.done.lock() {
+            done.retain(|&address| {
+                let mine = owned(address);
+                if mine {
+                    release(address);
+                }
+                !mine
+            });
+        }
+    }
+
+    /// Waits up to a second for an address `owned` claims to be reported.
+    fn wait_for(&self, owned: impl Fn(usize) -> bool) {
+        if let Ok(done) = self.done.lock() {
+            let _ = self
+                .signal
+                .wait_timeout_while(done, Duration::from_secs(1), |d| {
+                    !d.iter().any(|&address| owned(address))
+                });
+        }
+    }
+}
+
+/// A sender's completion callback for one media type, shared by every queue on the sender.
+///
+/// The SDK takes a single callback per sender, so the first queue installs it and the last
+/// one to be dropped removes it, restoring the standard release-on-next-send behaviour for
+/// the sender's other async calls. Each queue collects only the completions of its own frames.
+#[cfg(feature = "advanced_sdk")]
+#[derive(Debug, Default)]
+pub(crate) struct CompletionDispatcher {
+    shared: Mutex<Option<(usize, Arc<Completions>)>>,
+}
+
+#[cfg(feature = "advanced_sdk")]
+impl CompletionDispatcher {
+    fn attach(&self, install: impl FnOnce(*mut c_void)) -> Arc<Completions> {
+        let mut shared = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        let (users, completions) = shared.get_or_insert_with(|| {
+            let completions = Arc::new(Completions::default());
+            install(Arc::as_ptr(&completions) as *mut c_void);
+            (0, completions)
+        });
+        *users += 1;
+        Arc::clone(completions)
+    }
+
+    fn detach(&self, uninstall: impl FnOnce()) {
+        let mut shared = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((users, _)) = shared.as_mut() {
+            *users -= 1;
+            if *users == 0 {
+                uninstall();
+                *shared = None;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "advanced_sdk")]
+unsafe extern "C" fn on_video_complete(
+    p_opaque: *mut c_void,
+    p_video_data: *const NDIlib_video_frame_v2_t,
+) {
+    if p_opaque.is_null() || p_video_data.is_null() {
+        return;
+    }
+    let completions = &*(p_opaque as *const Completions);
+    if let Ok(mut done) = completions.done.lock() {
+        done.push((*p_video_data).p_data as usize);
+        completions.signal.notify_all();
+    }
+}
+
+impl<'s, 'a> AsyncVideoQueue<'s, 'a> {
+    /// Creates a queue on `send`. Several queues may share a sender; each gets back only the
+    /// frames it submitted.
+    pub fn new(send: &'s Send<'a>, depth: usize) -> Self {
+        AsyncVideoQueue {
+            send,
+            depth: depth.max(1),
+            next_id: 0,
+            in_flight: VecDeque::new(),
+            released: VecDeque::new(),
+            #[cfg(feature = "advanced_sdk")]
+            completions: send.video_completions.attach(|opaque| unsafe {
+                NDIlib_send_set_video_async_completion(
+                    send.instance,
+                    opaque,
+                    Some(on_video_complete),
+                );
+            }),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Hands `frame` to the SDK for asynchronous sending.
+    ///
+    /// If `depth` frames are already in flight this waits for the oldest one to complete.
+    pub fn submit(&mut self, frame: VideoFrame) -> Result<AsyncSendHandle, Error> {
+        frame.validate()?;
+        self.collect_completions();
+        while self.in_flight.len() >= self.depth {
+            self.wait_for_oldest();
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.in_flight.push_back(InFlight { id, frame });
+        let raw = self.in_flight.back().map(|f| f.frame.to_raw());
+        if let Some(raw) = raw {
+            self.send.send_video_async_raw(&raw);
+        }
+
+        #[cfg(not(feature = "advanced_sdk"))]
+        {
+            // The standard SDK releases the previous frame once the next async call returns.
+            while self.in_flight.len() > 1 {
+                self.release_front();
+            }
+        }
+        Ok(AsyncSendHandle(id))
+    }
+
+    pub fn is_complete(&mut self, handle: AsyncSendHandle) -> bool {
+        self.collect_completions();
+        !self.in_flight.iter().any(|f| f.id == handle.0)
+    }
+
+    /// Takes back a frame the SDK has finished with, for reuse as the next buffer.
+    pub fn reclaim(&mut self) -> Option<VideoFrame> {
+        self.collect_completions();
+        self.released.pop_front().map(|(_, frame)| frame)
+    }
+
+    /// Waits until every submitted frame has been released by the SDK.
+    pub fn flush(&mut self) {
+        if self.in_flight.is_empty() {
+            return;
+        }
+        ndi_trace!(
+            in_flight = self.in_flight.len(),
+            "flushing async video queue"
+        );
+        self.send.send_video_async_raw(ptr::null());
+        // Drop our frames' completions, so a buffer later reused at the same address isn't
+        // taken for finished. Other queues' completions stay for them to collect.
+        #[cfg(feature = "advanced_sdk")]
+        self.completions
+            .take_owned(|address| self.owns(address), |_| {});
+        while let Some(front) = self.in_flight.pop_front() {
+            self.released
+                .push_back((AsyncSendHandle(front.id), front.frame));
+        }
+    }
+
+    #[cfg(feature = "advanced_sdk")]
+    fn owns(&self, address: usize) -> bool {
+        self.in_flight
+            .iter()
+            .any(|f| f.frame.data.as_ptr() as usize == address)
+    }
+
+    #[cfg(not(feature = "advanced_sdk"))]
+    fn release_front(&mut self) {
+        if let Some(front) = self.in_flight.pop_front() {
+            self.released
+                .push_back((AsyncSendHandle(front.id), front.frame));
+        }
+    }
+
+    #[cfg(not(feature = "advanced_sdk"))]
+    fn collect_completions(&mut self) {}
+
+    #[cfg(not(feature = "advanced_sdk"))]
+    fn wait_for_oldest(&mut self) {
+        self.flush();
+    }
+
+    #[cfg(feature = "advanced_sdk")]
+    fn collect_completions(&mut self) {
+        let mut done = Vec::new();
+        self.completions
+            .take_owned(|address| self.owns(address), |address| done.push(address));
+        for address in done {
+            if let Some(index) = self
+                .in_flight
+                .iter()
+                .position(|f| f.frame.data.as_ptr() as usize == address)
+            {
+                if let Some(f) = self.in_flight.remove(index) {
+                    self.released.push_back((AsyncSendHandle(f.id), f.frame));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "advanced_sdk")]
+    fn wait_for_oldest(&mut self) {
+        let before = self.in_flight.len();
+        self.completions.wait_for(|address| self.owns(address));
+        self.collect_completions();
+        if self.in_flight.len() == before {
+            // No completion arrived in time; fall back to a blocking flush.
+            ndi_debug!(
+                in_flight = before,
+                "no async completion within 1s, flushing"
+            );
+            self.flush();
+        }
+    }
+}
+
+impl<'s, 'a> Drop for AsyncVideoQueue<'s, 'a> {
+    fn drop(&mut self) {
+        self.flush();
+        #[cfg(feature = "advanced_sdk")]
+        self.send.video_completions.detach(|| unsafe {
+            NDIlib_send_set_video_async_completion(self.send.instance, ptr::null_mut(), None);
+        });
+    }
+}
+
+/// Whether [`AsyncAudioQueue`] sends asynchronously in this build: with the `advanced_sdk`
+/// feature, against an Advanced SDK whose headers declare async audio sending.
+pub const ASYNC_AUDIO_SUPPORTED: bool =
+    cfg!(all(feature = "advanced_sdk", has_async_audio_completion));
+
+/// A bounded pipeline of audio frames, the audio counterpart of [`AsyncVideoQueue`].
+///
+/// Where the SDK can send audio asynchronously ([`ASYNC_AUDIO_SUPPORTED`]), each submitted
+/// frame is owned by the queue until the SDK's completion callback reports it sent, so up to
+/// `depth` frames are in flight while the caller prepares the next. Otherwise every frame is
+/// sent synchronously on [`AsyncAudioQueue::submit`] and is ready to reclaim as soon as it
+/// returns, so the same code runs against either SDK.
+pub struct AsyncAudioQueue<'s, 'a> {
+    send: &'s Send<'a>,
+    depth: usize,
+    next_id: u64,
+    in_flight: VecDeque<(u64, AudioFrame)>,
+    released: VecDeque<(AsyncSendHandle, AudioFrame)>,
+    #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+    completions: Arc<Completions>,
+}
+
+#[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+unsafe extern "C" fn on_audio_complete(
+    p_opaque: *mut c_void,
+    p_audio_data: *const NDIlib_audio_frame_v3_t,
+) {
+    if p_opaque.is_null() || p_audio_data.is_null() {
+        return;
+    }
+    let completions = &*(p_opaque as *const Completions);
+    if let Ok(mut done) = completions.done.lock() {
+        done.push((*p_audio_data).p_data as usize);
+        completions.signal.notify_all();
+    }
+}
+
+impl<'s, 'a> AsyncAudioQueue<'s, 'a> {
+    /// Creates a queue on `send`. Several queues may share a sender; each gets back only the
+    /// frames it submitted.
+    pub fn new(send: &'s Send<'a>, depth: usize) -> Self {
+        AsyncAudioQueue {
+            send,
+            depth: depth.max(1),
+            next_id: 0,
+            in_flight: VecDeque::new(),
+            released: VecDeque::new(),
+            #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+            completions: send.audio_completions.attach(|opaque| unsafe {
+                NDIlib_send_set_audio_async_completion(
+                    send.instance,
+                    opaque,
+                    Some(on_audio_complete),
+                );
+            }),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Hands `frame` to the SDK, checking its data covers every channel first.
+    ///
+    /// If `depth` frames are already in flight this waits for the oldest one to complete.
+    pub fn submit(&mut self, frame: AudioFrame) -> Result<AsyncSendHandle, Error> {
+        frame.validate()?;
+        let id = self.next_id;
+        self.next_id += 1;
+
+        #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+        {
+            self.collect_completions();
+            while self.in_flight.len() >= self.depth {
+                self.wait_for_oldest();
+            }
+            self.in_flight.push_back((id, frame));
+            if let Some((_, frame)) = self.in_flight.back() {
+                unsafe { NDIlib_send_send_audio_v3_async(self.send.instance, &frame.to_raw()) };
+            }
+        }
+        #[cfg(not(all(feature = "advanced_sdk", has_async_audio_completion)))]
+        {
+            self.send.send_audio(&frame);
+            self.released.push_back((AsyncSendHandle(id), frame));
+        }
+        Ok(AsyncSendHandle(id))
+    }
+
+    pub fn is_complete(&mut self, handle: AsyncSendHandle) -> bool {
+        self.collect_completions();
+        !self.in_flight.iter().any(|(id, _)| *id == handle.0)
+    }
+
+    /// Takes back a frame the SDK has finished with, for reuse as the next buffer.
+    pub fn reclaim(&mut self) -> Option<AudioFrame> {
+        self.collect_completions();
+        self.released.pop_front().map(|(_, frame)| frame)
+    }
+
+    /// Waits until every submitted frame has been sent.
+    pub fn flush(&mut self) {
+        if self.in_flight.is_empty() {
+            return;
+        }
+        ndi_trace!(
+            in_flight = self.in_flight.len(),
+            "flushing async audio queue"
+        );
+        #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+        {
+            unsafe { NDIlib_send_send_audio_v3_async(self.send.instance, ptr::null()) };
+            self.completions
+                .take_owned(|address| self.owns(address), |_| {});
+        }
+        while let Some((id, frame)) = self.in_flight.pop_front() {
+            self.released.push_back((AsyncSendHandle(id), frame));
+        }
+    }
+
+    #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+    fn owns(&self, address: usize) -> bool {
+        self.in_flight
+            .iter()
+            .any(|(_, f)| f.data.as_ptr() as usize == address)
+    }
+
+    #[cfg(not(all(feature = "advanced_sdk", has_async_audio_completion)))]
+    fn collect_completions(&mut self) {}
+
+    #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+    fn collect_completions(&mut self) {
+        let mut done = Vec::new();
+        self.completions
+            .take_owned(|address| self.owns(address), |address| done.push(address));
+        for address in done {
+            if let Some(index) = self
+                .in_flight
+                .iter()
+                .position(|(_, f)| f.data.as_ptr() as usize == address)
+            {
+                if let Some((id, frame)) = self.in_flight.remove(index) {
+                    self.released.push_back((AsyncSendHandle(id), frame));
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+    fn wait_for_oldest(&mut self) {
+        let before = self.in_flight.len();
+        self.completions.wait_for(|address| self.owns(address));
+        self.collect_completions();
+        if self.in_flight.len() == before {
+            ndi_debug!(
+                in_flight = before,
+                "no async audio completion within 1s, flushing"
+            );
+            self.flush();
+        }
+    }
+}
+
+impl<'s, 'a> Drop for AsyncAudioQueue<'s, 'a> {
+    fn drop(&mut self) {
+        self.flush();
+        #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+        self.send.audio_completions.detach(|| unsafe {
+            NDIlib_send_set_audio_async_completion(self.send.instance, ptr::null_mut(), None);
+        });
+    }
+}
+
+#[cfg(all(test, feature = "advanced_sdk"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatcher_installs_once_and_removes_with_the_last_queue() {
+        let dispatcher = CompletionDispatcher::default();
+        let mut installs = 0;
+        let first = dispatcher.attach(|_| installs += 1);
+        let second = dispatcher.attach(|_| installs += 1);
+        assert_eq!(installs, 1);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let mut uninstalls = 0;
+        dispatcher.detach(|| uninstalls += 1);
+        assert_eq!(uninstalls, 0);
+        dispatcher.detach(|| uninstalls += 1);
+        assert_eq!(uninstalls, 1);
+    }
+
+    #[test]
+    fn queues_take_only_their_own_completions() {
+        let completions = Completions::default();
+        completions.done.lock().unwrap().extend([1, 2, 3]);
+        let mut taken = Vec::new();
+        completions.take_owned(|address| address != 2, |address| taken.push(address));
+        assert_eq!(taken, [1, 3]);
+        assert_eq!(*completions.done.lock().unwrap(), [2]);
+    }
+}
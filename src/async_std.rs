@@ -0,0 +1,68 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_std::{channel, task};
+use futures_core::Stream;
+
+use crate::{async_find::SharedFind, Error, Finder, Source};
+
+/// Source discovery for async-std applications.
+///
+/// The SDK's blocking waits run on async-std's blocking pool, so awaiting them never stalls
+/// the executor. The finder holds its own reference to the NDI runtime and can be cloned
+/// cheaply and moved between tasks.
+#[derive(Clone)]
+pub struct AsyncFinder {
+    find: Arc<SharedFind>,
+}
+
+impl AsyncFinder {
+    pub fn new(settings: Finder) -> Result<Self, Error> {
+        Ok(AsyncFinder {
+            find: SharedFind::new(settings)?,
+        })
+    }
+
+    /// Waits up to `timeout` for the source list to change; `false` if it didn't.
+    pub async fn wait_for_sources(&self, timeout: Duration) -> bool {
+        let find = Arc::clone(&self.find);
+        task::spawn_blocking(move || find.wait_for_sources(timeout)).await
+    }
+
+    /// The sources found so far, waiting up to `timeout` for the first one if there are none.
+    pub async fn find_sources(&self, timeout: Duration) -> Result<Vec<Source>, Error> {
+        let find = Arc::clone(&self.find);
+        task::spawn_blocking(move || find.find_sources(timeout)).await
+    }
+
+    /// A stream yielding the source list now and again whenever it changes.
+    ///
+    /// Changes are watched on a dedicated thread, which ends shortly after the stream is
+    /// dropped.
+    pub fn source_changes(&self) -> SourceChanges {
+        let (sender, receiver) = channel::unbounded();
+        let closed = sender.clone();
+        self.find.watch(
+            move |sources| sender.try_send(sources).is_ok(),
+            move || closed.is_closed(),
+        );
+        SourceChanges { receiver }
+    }
+}
+
+/// The stream returned by [`AsyncFinder::source_changes`].
+pub struct SourceChanges {
+    receiver: channel::Receiver<Vec<Source>>,
+}
+
+impl Stream for SourceChanges {
+    type Item = Vec<Source>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{timecode::TIMESTAMP_UNDEFINED, AudioFrame, AudioType, Error, Timecode};
+
+const BYTES_PER_SAMPLE: usize = 4;
+
+impl AudioFrame {
+    /// Splits the frame into its first `n` samples per channel and the rest, e.g. to send
+    /// audio in the ~10 ms frames the SDK recommends.
+    ///
+    /// Both halves are tightly packed. The second half's timecode and timestamp are advanced
+    /// by the duration of the first, unless they are unset or synthesized; only the first
+    /// half keeps the metadata.
+    pub fn split_at_sample(&self, n: usize) -> Result<(AudioFrame, AudioFrame), Error> {
+        let samples = self.checked_samples()?;
+        if n > samples {
+            return Err(Error::InvalidFrame(format!(
+                "cannot split a {samples}-sample audio frame at sample {n}"
+            )));
+        }
+        let mut first = self.with_samples(0..n);
+        let mut second = self.with_samples(n..samples);
+        first.metadata = self.metadata.clone();
+        let offset = self.sample_duration(n);
+        if self.timecode != 0 && self.timecode != Timecode::SYNTHESIZE.0 {
+            second.timecode = self.timecode.saturating_add(offset);
+        }
+        if self.timestamp != 0 && self.timestamp != TIMESTAMP_UNDEFINED {
+            second.timestamp = self.timestamp.saturating_add(offset);
+        }
+        Ok((first, second))
+    }
+
+    /// A frame holding this frame's samples followed by `other`'s, e.g. to accumulate
+    /// received audio into fixed-size processing blocks.
+    ///
+    /// The frames must have the same sample rate, channel count and sample format. The result
+    /// is tightly packed and keeps this frame's timing and metadata.
+    pub fn concat(&self, other: &AudioFrame) -> Result<AudioFrame, Error> {
+        if other.sample_rate != self.sample_rate {
+            return Err(Error::SampleRateMismatch {
+                expected: self.sample_rate,
+                actual: other.sample_rate,
+            });
+        }
+        if other.no_channels != self.no_channels {
+            return Err(Error::ChannelCountMismatch {
+                expected: self.no_channels,
+                actual: other.no_channels,
+            });
+        }
+        if other.fourcc != self.fourcc {
+            return Err(Error::InvalidFrame(format!(
+                "cannot join {:?} audio to {:?} audio",
+                other.fourcc, self.fourcc
+            )));
+        }
+        let (ours, theirs) = (self.checked_samples()?, other.checked_samples()?);
+        let total = ours + theirs;
+        let mut data = Vec::with_capacity(total * BYTES_PER_SAMPLE * self.channels());
+        for channel in 0..self.channels() {
+            data.extend_from_slice(self.channel_bytes(channel, 0..ours));
+            data.extend_from_slice(other.channel_bytes(channel, 0..theirs));
+        }
+        let mut joined = self.packed(total, data);
+        joined.metadata = self.metadata.clone();
+        Ok(joined)
+    }
+
+    fn channels(&self) -> usize {
+        self.no_channels.max(0) as usize
+    }
+
+    /// The sample count, checked against the format, stride and buffer size.
+    fn checked_samples(&self) -> Result<usize, Error> {
+        if self.fourcc != AudioType::FLTP {
+            return Err(Error::InvalidFrame(format!(
+                "cannot slice {:?} audio",
+                self.fourcc
+            )));
+        }
+        self.validate()?;
+        Ok(self.no_samples.max(0) as usize)
+    }
+
+    /// Bytes of samples `range` of `channel`; the range must have been checked.
+    fn channel_bytes(&self, channel: usize, range: std::ops::Range<usize>) -> &[u8] {
+        let start = channel * self.channel_stride_in_bytes as usize;
+        &self.data[start + range.start * BYTES_PER_SAMPLE..start + range.end * BYTES_PER_SAMPLE]
+    }
+
+    /// A tightly packed copy of samples `range` of every channel, without metadata.
+    fn with_samples(&self, range: std::ops::Range<usize>) -> AudioFrame {
+        let samples = range.len();
+        let mut data = Vec::with_capacity(samples * BYTES_PER_SAMPLE * self.channels());
+        for channel in 0..self.channels() {
+            data.extend_from_slice(self.channel_bytes(channel, range.clone()));
+        }
+        self.packed(samples, data)
+    }
+
+    /// A frame in this frame's format with this frame's timing, holding `samples` tightly
+    /// packed samples per channel and no metadata.
+    fn packed(&self, samples: usize, data: Vec<u8>) -> AudioFrame {
+        AudioFrame {
+            sample_rate: self.sample_rate,
+            no_channels: self.no_channels,
+            no_samples: samples as i32,
+            timecode: self.timecode,
+            fourcc: self.fourcc,
+            data,
+            channel_stride_in_bytes: (samples * BYTES_PER_SAMPLE) as i32,
+            metadata: None,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Length of `samples` samples in 100 ns units.
+    fn sample_duration(&self, samples: usize) -> i64 {
+        if self.sample_rate <= 0 {
+            return 0;
+        }
+        (samples as i128 * 10_000_000 / self.sample_rate as i128) as i64
+    }
+}
@@ -0,0 +1,473 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    timecode::TIMESTAMP_UNDEFINED, AudioFrame, Error, FrameType, Recv, Timestamped, VideoFrame,
+};
+
+const TICKS_PER_SECOND: f64 = 10_000_000.0;
+const DEFAULT_MAX_WAIT: Duration = Duration::from_millis(100);
+
+/// Passed to the resync callback when audio and video drift further apart than allowed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AvResync {
+    /// Smoothed audio lead over video in seconds (negative when audio lags).
+    pub skew: f64,
+    /// Rate at which the skew is changing, in seconds per second of stream time.
+    pub drift_rate: f64,
+}
+
+/// Captures audio and video from one receiver while tracking how far apart they are.
+///
+/// Skew is measured from the sender timestamps at the end of each frame, so it reflects the
+/// source's own A/V alignment plus anything lost or delayed on the way, not local scheduling.
+pub struct AvCapture<'r, 'a> {
+    recv: &'r mut Recv<'a>,
+    skew: SkewTracker<'r>,
+}
+
+impl<'r, 'a> AvCapture<'r, 'a> {
+    /// `resync_threshold` is the skew, in seconds, beyond which the resync callback fires.
+    pub fn new(recv: &'r mut Recv<'a>, resync_threshold: f64) -> Self {
+        AvCapture {
+            recv,
+            skew: SkewTracker::new(resync_threshold),
+        }
+    }
+
+    /// Weight given to each new skew measurement (0..=1); lower values smooth more.
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.skew.smoothing = smoothing.clamp(f64::EPSILON, 1.0);
+        self
+    }
+
+    /// Called once each time the skew moves beyond the threshold.
+    pub fn on_resync(mut self, callback: impl FnMut(AvResync) + 'r) -> Self {
+        self.skew.on_resync = Some(Box::new(callback));
+        self
+    }
+
+    /// Captures the next frame of any type, updating the skew measurement for audio and video.
+    pub fn capture(&mut self, timeout_ms: u32) -> Result<FrameType, Error> {
+        let frame = self.recv.capture(timeout_ms)?;
+        match &frame {
+            FrameType::Video(video) => self.skew.video_captured(video),
+            FrameType::Audio(audio) => self.skew.audio_captured(audio),
+            _ => {}
+        }
+        Ok(frame)
+    }
+
+    /// Smoothed audio lead over video in seconds, once both have been seen.
+    pub fn skew(&self) -> Option<f64> {
+        self.skew.skew
+    }
+
+    /// How fast the skew is changing, in seconds per second of stream time.
+    pub fn drift_rate(&self) -> Option<f64> {
+        self.skew.drift_rate()
+    }
+
+    /// Forgets all measurements, e.g. after the caller has realigned its output.
+    pub fn reset(&mut self) {
+        self.skew.reset();
+    }
+}
+
+/// The skew and drift measurement behind [`AvCapture`], fed the frames it captures.
+struct SkewTracker<'r> {
+    threshold: f64,
+    smoothing: f64,
+    on_resync: Option<Box<dyn FnMut(AvResync) + 'r>>,
+    video_end: Option<i64>,
+    audio_end: Option<i64>,
+    skew: Option<f64>,
+    reference: Option<(i64, f64)>,
+    out_of_sync: bool,
+}
+
+impl SkewTracker<'_> {
+    fn new(resync_threshold: f64) -> Self {
+        SkewTracker {
+            threshold: resync_threshold.abs(),
+            smoothing: 0.05,
+            on_resync: None,
+            video_end: None,
+            audio_end: None,
+            skew: None,
+            reference: None,
+            out_of_sync: false,
+        }
+    }
+
+    fn drift_rate(&self) -> Option<f64> {
+        let (start, start_skew) = self.reference?;
+        let elapsed = (self.video_end? - start) as f64 / TICKS_PER_SECOND;
+        let skew = self.skew?;
+        (elapsed > 0.0).then(|| (skew - start_skew) / elapsed)
+    }
+
+    fn reset(&mut self) {
+        self.video_end = None;
+        self.audio_end = None;
+        self.skew = None;
+        self.reference = None;
+        self.out_of_sync = false;
+    }
+
+    fn video_captured(&mut self, frame: &VideoFrame) {
+        if frame.timestamp == TIMESTAMP_UNDEFINED || frame.frame_rate_n <= 0 {
+            return;
+        }
+        let duration =
+            frame.frame_rate_d as i64 * TICKS_PER_SECOND as i64 / frame.frame_rate_n as i64;
+        self.video_end = Some(frame.timestamp + duration);
+        self.measure();
+    }
+
+    fn audio_captured(&mut self, frame: &AudioFrame) {
+        if frame.timestamp == TIMESTAMP_UNDEFINED || frame.sample_rate <= 0 {
+            return;
+        }
+        let duration = frame.no_samples as i64 * TICKS_PER_SECOND as i64 / frame.sample_rate as i64;
+        self.audio_end = Some(frame.timestamp + duration);
+        self.measure();
+    }
+
+    fn measure(&mut self) {
+        let (Some(video_end), Some(audio_end)) = (self.video_end, self.audio_end) else {
+            return;
+        };
+        let sample = (audio_end - video_end) as f64 / TICKS_PER_SECOND;
+        let skew = match self.skew {
+            Some(skew) => skew + (sample - skew) * self.smoothing,
+            None => sample,
+        };
+        self.skew = Some(skew);
+        if self.reference.is_none() {
+            self.reference = Some((video_end, skew));
+        }
+
+        let out_of_sync = skew.abs() > self.threshold;
+        if out_of_sync && !self.out_of_sync {
+            let event = AvResync {
+                skew,
+                drift_rate: self.drift_rate().unwrap_or(0.0),
+            };
+            if let Some(callback) = &mut self.on_resync {
+                callback(event);
+            }
+        }
+        self.out_of_sync = out_of_sync;
+    }
+}
+
+/// A video or audio frame from [`Recv::frames`].
+#[derive(Debug, Clone)]
+pub enum AvFrame {
+    Video(VideoFrame),
+    Audio(AudioFrame),
+}
+
+impl Timestamped for AvFrame {
+    fn timestamp(&self) -> Option<i64> {
+        match self {
+            AvFrame::Video(frame) => frame.timestamp(),
+            AvFrame::Audio(frame) => frame.timestamp(),
+        }
+    }
+}
+
+/// Video and audio from one receiver, interleaved in timestamp order.
+///
+/// Each stream arrives in order, so a frame is released as soon as a frame of the other kind
+/// at least as new has arrived: usually only the frame in hand is buffered. If the other kind
+/// stops arriving (an audio-only source, or a lagging stream), a frame is released after
+/// waiting `max_wait` regardless. Frames without a timestamp are released immediately, and
+/// metadata and status changes are skipped.
+///
+/// The iterator never ends. Each call waits up to the receiver's timeout and yields
+/// [`Error::Timeout`] if nothing arrived, or the capture error if capturing failed.
+pub struct AvFrames<'r, 'a> {
+    recv: &'r mut Recv<'a>,
+    timeout: Duration,
+    queue: AvQueue,
+}
+
+/// The frames [`AvFrames`] holds back, with when each arrived.
+struct AvQueue {
+    max_wait: Duration,
+    video: VecDeque<(VideoFrame, Instant)>,
+    audio: VecDeque<(AudioFrame, Instant)>,
+}
+
+impl<'a> Recv<'a> {
+    /// Captures video and audio interleaved in presentation order, waiting up to `timeout`
+    /// for each frame.
+    pub fn frames(&mut self, timeout: Duration) -> AvFrames<'_, 'a> {
+        AvFrames {
+            recv: self,
+            timeout,
+            queue: AvQueue {
+                max_wait: DEFAULT_MAX_WAIT,
+                video: VecDeque::new(),
+                audio: VecDeque::new(),
+            },
+        }
+    }
+}
+
+impl<'r, 'a> AvFrames<'r, 'a> {
+    /// How long a frame is held for a frame of the other kind; defaults to 100 ms.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.queue.max_wait = max_wait;
+        self
+    }
+
+    /// Number of frames held back waiting for the other kind.
+    pub fn buffered(&self) -> usize {
+        self.queue.video.len() + self.queue.audio.len()
+    }
+}
+
+impl AvQueue {
+    /// Whether the video frame at the front goes before the audio frame at the front.
+    fn video_first(&self) -> Option<bool> {
+        match (self.video.front(), self.audio.front()) {
+            (Some((video, _)), Some((audio, _))) => {
+                Some(match (video.timestamp(), audio.timestamp()) {
+                    (Some(video), Some(audio)) => video <= audio,
+                    (None, _) => true,
+                    (_, None) => false,
+                })
+            }
+            (Some(_), None) => Some(true),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        }
+    }
+
+    /// Takes the front frame that should go next, if any is queued.
+    fn take_next(&mut self) -> Option<AvFrame> {
+        if self.video_first()? {
+            self.video
+                .pop_front()
+                .map(|(frame, _)| AvFrame::Video(frame))
+        } else {
+            self.audio
+                .pop_front()
+                .map(|(frame, _)| AvFrame::Audio(frame))
+        }
+    }
+
+    /// The next frame if it can be released now.
+    fn ready(&mut self, now: Instant) -> Option<AvFrame> {
+        let release = match (self.video.front(), self.audio.front()) {
+            (Some(_), Some(_)) => true,
+            (Some((frame, queued)), None) => {
+                frame.timestamp().is_none() || now - *queued >= self.max_wait
+            }
+            (None, Some((frame, queued))) => {
+                frame.timestamp().is_none() || now - *queued >= self.max_wait
+            }
+            (None, None) => false,
+        };
+        if release {
+            self.take_next()
+        } else {
+            None
+        }
+    }
+
+    /// When the oldest waiting frame is released even without a frame of the other kind.
+    fn release_at(&self) -> Option<Instant> {
+        let video = self.video.front().map(|(_, queued)| *queued);
+        let audio = self.audio.front().map(|(_, queued)| *queued);
+        Some(video.into_iter().chain(audio).min()? + self.max_wait)
+    }
+}
+
+impl Iterator for AvFrames<'_, '_> {
+    type Item = Result<AvFrame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let now = Instant::now();
+            if let Some(frame) = self.queue.ready(now) {
+                return Some(Ok(frame));
+            }
+            if now >= deadline {
+                return Some(self.queue.take_next().ok_or(Error::Timeout {
+                    operation: "an audio or video frame",
+                    timeout_ms: self.timeout.as_millis().min(u32::MAX as u128) as u32,
+                }));
+            }
+            let until = self
+                .queue
+                .release_at()
+                .map_or(deadline, |at| at.min(deadline));
+            let timeout_ms = until.saturating_duration_since(now).as_millis() as u32;
+            match self.recv.capture(timeout_ms) {
+                Ok(FrameType::Video(frame)) => self.queue.video.push_back((frame, Instant::now())),
+                Ok(FrameType::Audio(frame)) => self.queue.audio.push_back((frame, Instant::now())),
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{AudioFrameBuilder, FourCCVideoType, VideoFrameBuilder};
+
+    // 25 fps video and 1920-sample 48 kHz audio frames both last 40 ms.
+    const FRAME_TICKS: i64 = 400_000;
+
+    fn video(timestamp: i64) -> VideoFrame {
+        VideoFrameBuilder::new()
+            .resolution(2, 2)
+            .fourcc(FourCCVideoType::BGRA)
+            .frame_rate(25, 1)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    fn audio(timestamp: i64) -> AudioFrame {
+        AudioFrameBuilder::new()
+            .sample_rate(48_000)
+            .channels(1)
+            .samples(1920)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    fn queue(max_wait: Duration) -> AvQueue {
+        AvQueue {
+            max_wait,
+            video: VecDeque::new(),
+            audio: VecDeque::new(),
+        }
+    }
+
+    #[test]
+    fn aligned_streams_have_no_skew() {
+        let mut tracker = SkewTracker::new(0.1);
+        tracker.smoothing = 1.0;
+        assert_eq!(tracker.skew, None);
+        for i in 0..10 {
+            tracker.video_captured(&video(i * FRAME_TICKS));
+            tracker.audio_captured(&audio(i * FRAME_TICKS));
+        }
+        assert_eq!(tracker.skew, Some(0.0));
+        assert_eq!(tracker.drift_rate(), Some(0.0));
+    }
+
+    #[test]
+    fn resync_fires_once_when_skew_crosses_the_threshold() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut tracker = SkewTracker::new(0.1);
+        let seen = events.clone();
+        tracker.on_resync = Some(Box::new(move |event| seen.borrow_mut().push(event)));
+
+        // Audio 200 ms ahead of video.
+        for i in 0..5 {
+            tracker.video_captured(&video(i * FRAME_TICKS));
+            tracker.audio_captured(&audio(i * FRAME_TICKS + 2_000_000));
+        }
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        assert!((events[0].skew - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn resync_fires_again_after_recovering() {
+        let count = Rc::new(RefCell::new(0));
+        let mut tracker = SkewTracker::new(0.1);
+        tracker.smoothing = 1.0;
+        let seen = count.clone();
+        tracker.on_resync = Some(Box::new(move |_| *seen.borrow_mut() += 1));
+
+        tracker.video_captured(&video(0));
+        tracker.audio_captured(&audio(2_000_000));
+        tracker.audio_captured(&audio(0));
+        assert_eq!(tracker.skew, Some(0.0));
+        tracker.audio_captured(&audio(2_000_000));
+        assert_eq!(*count.borrow(), 2);
+    }
+
+    #[test]
+    fn drift_rate_follows_a_growing_skew() {
+        let mut tracker = SkewTracker::new(1.0);
+        tracker.smoothing = 1.0;
+        // Audio gains 1 ms on video every 40 ms frame: 25 ms per second.
+        for i in 0..=10 {
+            tracker.video_captured(&video(i * FRAME_TICKS));
+            tracker.audio_captured(&audio(i * FRAME_TICKS + i * 10_000));
+        }
+        assert!((tracker.skew.unwrap() - 0.01).abs() < 1e-9);
+        assert!((tracker.drift_rate().unwrap() - 0.025).abs() < 1e-3);
+    }
+
+    #[test]
+    fn frames_without_timestamps_are_not_measured() {
+        let mut tracker = SkewTracker::new(0.1);
+        tracker.video_captured(&video(TIMESTAMP_UNDEFINED));
+        tracker.audio_captured(&audio(0));
+        assert_eq!(tracker.skew, None);
+
+        tracker.video_captured(&video(0));
+        assert_eq!(tracker.skew, Some(0.0));
+        tracker.reset();
+        assert_eq!(tracker.skew, None);
+        assert_eq!(tracker.drift_rate(), None);
+    }
+
+    #[test]
+    fn paired_frames_are_released_in_timestamp_order() {
+        let now = Instant::now();
+        let mut queue = queue(Duration::from_millis(100));
+        queue.audio.push_back((audio(0), now));
+        queue.video.push_back((video(FRAME_TICKS), now));
+        queue.audio.push_back((audio(FRAME_TICKS * 2), now));
+
+        assert!(matches!(queue.ready(now), Some(AvFrame::Audio(frame)) if frame.timestamp == 0));
+        assert!(
+            matches!(queue.ready(now), Some(AvFrame::Video(frame)) if frame.timestamp == FRAME_TICKS)
+        );
+        // The last audio frame waits for video that might come before it.
+        assert!(queue.ready(now).is_none());
+    }
+
+    #[test]
+    fn unpaired_frames_wait_until_max_wait() {
+        let now = Instant::now();
+        let max_wait = Duration::from_millis(100);
+        let mut queue = queue(max_wait);
+        queue.video.push_back((video(0), now));
+
+        assert_eq!(queue.release_at(), Some(now + max_wait));
+        assert!(queue.ready(now + max_wait / 2).is_none());
+        assert!(matches!(
+            queue.ready(now + max_wait),
+            Some(AvFrame::Video(_))
+        ));
+        assert_eq!(queue.release_at(), None);
+    }
+
+    #[test]
+    fn frames_without_timestamps_are_released_immediately() {
+        let now = Instant::now();
+        let mut queue = queue(Duration::from_millis(100));
+        queue.audio.push_back((audio(TIMESTAMP_UNDEFINED), now));
+        assert!(matches!(queue.ready(now), Some(AvFrame::Audio(_))));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::Recv;
+
+/// Length of each slot of the rolling window.
+const BUCKET: Duration = Duration::from_millis(250);
+/// Slots in the rolling window, which spans five seconds.
+const BUCKETS: usize = 20;
+
+/// Bytes a receiver has captured and the rate it is capturing them at, from
+/// [`Recv::bitrate_stats`].
+///
+/// The SDK doesn't report network traffic, so these are the sizes of the frames as delivered:
+/// decoded video and audio. That is what memory bandwidth and recording storage need to be
+/// planned for; the compressed stream on the wire is typically a tenth of it or less.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitrateStats {
+    pub video_bytes: u64,
+    pub audio_bytes: u64,
+    pub metadata_bytes: u64,
+    /// Rolling rates over the last five seconds, in bits per second.
+    pub video_bits_per_sec: f64,
+    pub audio_bits_per_sec: f64,
+    pub metadata_bits_per_sec: f64,
+}
+
+impl BitrateStats {
+    pub fn total_bytes(&self) -> u64 {
+        self.video_bytes + self.audio_bytes + self.metadata_bytes
+    }
+
+    pub fn total_bits_per_sec(&self) -> f64 {
+        self.video_bits_per_sec + self.audio_bits_per_sec + self.metadata_bits_per_sec
+    }
+
+    /// The total rate in megabits per second.
+    pub fn total_mbps(&self) -> f64 {
+        self.total_bits_per_sec() / 1_000_000.0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum FrameKind {
+    Video,
+    Audio,
+    Metadata,
+}
+
+/// Counts the bytes captured by a receiver. Recording never allocates, so it can sit on the
+/// zero-copy capture paths.
+#[derive(Debug)]
+pub(crate) struct ByteCounter {
+    totals: [AtomicU64; 3],
+    started: Instant,
+    window: Mutex<Window>,
+}
+
+#[derive(Debug)]
+struct Window {
+    /// Bytes per kind in each slot, indexed by slot number modulo `BUCKETS`.
+    buckets: [[u64; 3]; BUCKETS],
+    /// Number of the newest slot written, counted from `started`.
+    newest: u64,
+}
+
+impl ByteCounter {
+    pub(crate) fn new() -> Self {
+        ByteCounter {
+            totals: Default::default(),
+            started: Instant::now(),
+            window: Mutex::new(Window {
+                buckets: [[0; 3]; BUCKETS],
+                newest: 0,
+            }),
+        }
+    }
+
+    pub(crate) fn record(&self, kind: FrameKind, bytes: usize) {
+        let bytes = bytes as u64;
+        self.totals[kind as usize].fetch_add(bytes, Ordering::Relaxed);
+        let slot = self.slot_now();
+        if let Ok(mut window) = self.window.lock() {
+            window.advance(slot);
+            window.buckets[(slot % BUCKETS as u64) as usize][kind as usize] += bytes;
+        }
+    }
+
+    pub(crate) fn stats(&self) -> BitrateStats {
+        let slot = self.slot_now();
+        let mut sums = [0u64; 3];
+        if let Ok(mut window) = self.window.lock() {
+            window.advance(slot);
+            for bucket in &window.buckets {
+                for (sum, bytes) in sums.iter_mut().zip(bucket) {
+                    *sum += bytes;
+                }
+            }
+        }
+        // Until the window has filled, divide by the time actually covered.
+        let span = self
+            .started
+            .elapsed()
+            .min(BUCKET * BUCKETS as u32)
+            .as_secs_f64()
+            .max(BUCKET.as_secs_f64());
+        let rate = |kind: FrameKind| sums[kind as usize] as f64 * 8.0 / span;
+        let total = |kind: FrameKind| self.totals[kind as usize].load(Ordering::Relaxed);
+        BitrateStats {
+            video_bytes: total(FrameKind::Video),
+            audio_bytes: total(FrameKind::Audio),
+            metadata_bytes: total(FrameKind::Metadata),
+            video_bits_per_sec: rate(FrameKind::Video),
+            audio_bits_per_sec: rate(FrameKind::Audio),
+            metadata_bits_per_sec: rate(FrameKind::Metadata),
+        }
+    }
+
+    fn slot_now(&self) -> u64 {
+        (self.started.elapsed().as_nanos() / BUCKET.as_nanos()) as u64
+    }
+}
+
+impl Window {
+    /// Clears the slots that have fallen out of the window since `newest`.
+    fn advance(&mut self, slot: u64) {
+        if slot <= self.newest {
+            return;
+        }
+        let stale = (slot - self.newest).min(BUCKETS as u64);
+        for offset in 1..=stale {
+            self.buckets[((self.newest + offset) % BUCKETS as u64) as usize] = [0; 3];
+        }
+        self.newest = slot;
+    }
+}
+
+impl<'a> Recv<'a> {
+    /// Bytes captured so far and the rolling data rate per frame kind, e.g. for showing Mbps
+    /// per source.
+    ///
+    /// Counts frames captured through this receiver's own capture methods and
+    /// [`Recv::start_callbacks`]; frames discarded while waiting for another kind are not
+    /// seen.
+    pub fn bitrate_stats(&self) -> BitrateStats {
+        self.state.bytes.stats()
+    }
+}
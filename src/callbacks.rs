@@ -0,0 +1,204 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    ndi_lib::*, AudioFrameRef, FrameKind, LentRecv, MetadataFrameRef, Recv, VideoFrameRef,
+};
+
+/// Longest the capture thread waits in the SDK before checking whether it should stop.
+const POLL_TIMEOUT_MS: u32 = 100;
+
+type VideoCallback = Box<dyn FnMut(&VideoFrameRef<'_>) + std::marker::Send>;
+type AudioCallback = Box<dyn FnMut(&AudioFrameRef<'_>) + std::marker::Send>;
+type MetadataCallback = Box<dyn FnMut(&MetadataFrameRef<'_>) + std::marker::Send>;
+
+/// Delivers a receiver's frames to callbacks from a dedicated capture thread, as started by
+/// [`Recv::start_callbacks`].
+///
+/// Frames are passed without copying and returned to the SDK once their callback returns, so
+/// a slow callback holds up capture of every type. A callback that panics only loses the frame
+/// it was given; capture carries on with the next one. Dropping the handle stops the thread.
+pub struct CaptureHandle<'r> {
+    recv: &'r Recv<'r>,
+    shared: Arc<CallbackShared>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CallbackShared {
+    stopped: AtomicBool,
+    thread: Mutex<Option<JoinHandle<()>>>,
+    frames: AtomicU64,
+    panics: AtomicU64,
+}
+
+impl CallbackShared {
+    /// Stops the capture thread and waits for it, so the receiver can safely be destroyed.
+    pub(crate) fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        let thread = self.thread.lock().ok().and_then(|mut t| t.take());
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<'a> Recv<'a> {
+    /// Captures on a background thread, calling `on_video`, `on_audio` or `on_metadata` there
+    /// with each frame as it arrives.
+    ///
+    /// This is the push counterpart of [`Recv::capture_video_ref`] and friends, for
+    /// applications built around callbacks. Pass `|_| {}` for frame types that aren't needed.
+    /// Capture calls on the receiver itself compete with the thread for frames, so avoid them
+    /// while the handle is alive. The thread runs with the receiver's
+    /// [`CaptureThreadConfig`](crate::CaptureThreadConfig), if one was set.
+    pub fn start_callbacks(
+        &self,
+        on_video: impl FnMut(&VideoFrameRef<'_>) + std::marker::Send + 'static,
+        on_audio: impl FnMut(&AudioFrameRef<'_>) + std::marker::Send + 'static,
+        on_metadata: impl FnMut(&MetadataFrameRef<'_>) + std::marker::Send + 'static,
+    ) -> CaptureHandle<'_> {
+        let shared = Arc::new(CallbackShared {
+            stopped: AtomicBool::new(false),
+            thread: Mutex::new(None),
+            frames: AtomicU64::new(0),
+            panics: AtomicU64::new(0),
+        });
+        let callbacks = Callbacks {
+            video: Box::new(on_video),
+            audio: Box::new(on_audio),
+            metadata: Box::new(on_metadata),
+        };
+        let recv = self.lend();
+        let thread_shared = Arc::clone(&shared);
+        let thread_config = self.capture_thread.clone();
+        let thread = thread::spawn(move || {
+            thread_config.apply_or_warn();
+            run(&recv, &thread_shared, callbacks)
+        });
+        if let Ok(mut slot) = shared.thread.lock() {
+            *slot = Some(thread);
+        }
+        if let Ok(mut threads) = self.callback_threads.lock() {
+            threads.push(Arc::clone(&shared));
+        }
+        ndi_debug!(source = %self.source.name, "capture callbacks started");
+        CaptureHandle { recv: self, shared }
+    }
+}
+
+impl<'r> CaptureHandle<'r> {
+    /// Whether the capture thread is still running.
+    pub fn is_running(&self) -> bool {
+        !self.shared.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Frames delivered to a callback so far, including those whose callback panicked.
+    pub fn frames(&self) -> u64 {
+        self.shared.frames.load(Ordering::Relaxed)
+    }
+
+    /// Callback invocations that panicked so far.
+    pub fn panics(&self) -> u64 {
+        self.shared.panics.load(Ordering::Relaxed)
+    }
+
+    /// Stops the capture thread, waiting for a callback in progress to return.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for CaptureHandle<'_> {
+    fn drop(&mut self) {
+        self.shared.stop();
+        if let Ok(mut threads) = self.recv.callback_threads.lock() {
+            threads.retain(|t| !Arc::ptr_eq(t, &self.shared));
+        }
+        ndi_debug!(source = %self.recv.source.name, "capture callbacks stopped");
+    }
+}
+
+struct Callbacks {
+    video: VideoCallback,
+    audio: AudioCallback,
+    metadata: MetadataCallback,
+}
+
+fn run(recv: &LentRecv, shared: &CallbackShared, mut callbacks: Callbacks) {
+    let instance = recv.instance();
+    let state = recv.state();
+    while !shared.stopped.load(Ordering::Relaxed) {
+        let mut video_frame = NDIlib_video_frame_v2_t::default();
+        let mut audio_frame = NDIlib_audio_frame_v3_t::default();
+        let mut metadata_frame = NDIlib_metadata_frame_t::default();
+        let frame_type = unsafe {
+            NDIlib_recv_capture_v3(
+                instance,
+                &mut video_frame,
+                &mut audio_frame,
+                &mut metadata_frame,
+                POLL_TIMEOUT_MS,
+            )
+        };
+        // Each frame ref returns its frame to the SDK when dropped, whether or not its
+        // callback panicked.
+        let delivered = match frame_type {
+            NDIlib_frame_type_e_NDIlib_frame_type_video if !video_frame.p_data.is_null() => {
+                let frame = unsafe { VideoFrameRef::from_raw(instance, video_frame) };
+                state.bytes.record(FrameKind::Video, frame.data().len());
+                state.video_format.observe((&frame).into());
+                deliver(|| (callbacks.video)(&frame))
+            }
+            NDIlib_frame_type_e_NDIlib_frame_type_audio if !audio_frame.p_data.is_null() => {
+                let frame = unsafe { AudioFrameRef::from_raw(instance, audio_frame) };
+                state
+                    .bytes
+                    .record(FrameKind::Audio, std::mem::size_of_val(frame.data()));
+                deliver(|| (callbacks.audio)(&frame))
+            }
+            NDIlib_frame_type_e_NDIlib_frame_type_metadata if !metadata_frame.p_data.is_null() => {
+                let frame = unsafe { MetadataFrameRef::from_raw(instance, metadata_frame) };
+                state
+                    .bytes
+                    .record(FrameKind::Metadata, frame.data().to_bytes().len());
+                if let Ok(xml) = frame.as_str() {
+                    recv.observe_metadata(xml);
+                }
+                deliver(|| (callbacks.metadata)(&frame))
+            }
+            NDIlib_frame_type_e_NDIlib_frame_type_status_change => {
+                recv.on_status_change();
+                continue;
+            }
+            NDIlib_frame_type_e_NDIlib_frame_type_error => {
+                ndi_warn!(source = %state.source_name, "receiver returned an error frame");
+                // Don't spin while the connection is broken.
+                thread::sleep(Duration::from_millis(POLL_TIMEOUT_MS.into()));
+                continue;
+            }
+            _ => continue,
+        };
+        shared.frames.fetch_add(1, Ordering::Relaxed);
+        if !delivered {
+            shared.panics.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Runs a callback, reporting whether it returned without panicking.
+fn deliver(callback: impl FnOnce()) -> bool {
+    match panic::catch_unwind(AssertUnwindSafe(callback)) {
+        Ok(()) => true,
+        Err(_) => {
+            ndi_warn!("capture callback panicked; the frame was dropped");
+            false
+        }
+    }
+}
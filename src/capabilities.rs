@@ -0,0 +1,51 @@
+/// Capabilities a sender announces to receivers through connection metadata.
+///
+/// Serialized as an `<ndi_capabilities/>` element through [`MetadataXml`](crate::MetadataXml).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderCapabilities {
+    /// The sender accepts PTZ commands (pan/tilt, zoom, focus, white balance, exposure).
+    pub ptz: bool,
+    /// Which PTZ controls the sender accepts when `ptz` is set. Senders advertise every
+    /// control if this is left empty.
+    pub ptz_controls: PtzCapabilities,
+    /// The sender can be asked to record.
+    pub recording: bool,
+    /// The sender accepts keyboard and mouse events from receivers (Advanced SDK).
+    pub kvm: bool,
+    /// URL of a web configuration page. `%IP%` is replaced by receivers with the sender's address.
+    pub web_control: Option<String>,
+}
+
+/// The individual PTZ controls a camera accepts, as announced in its capability metadata.
+///
+/// [`Recv::ptz_is_supported`](crate::Recv::ptz_is_supported) only says whether a source takes
+/// PTZ commands at all; this tells a UI which controls to offer. The SDK has no way to read
+/// back the current focus or exposure, only to set them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PtzCapabilities {
+    pub pan_tilt: bool,
+    pub zoom: bool,
+    pub iris: bool,
+    pub white_balance: bool,
+    pub focus: bool,
+    pub exposure: bool,
+}
+
+impl PtzCapabilities {
+    /// Every control.
+    pub const fn all() -> Self {
+        PtzCapabilities {
+            pan_tilt: true,
+            zoom: true,
+            iris: true,
+            white_balance: true,
+            focus: true,
+            exposure: true,
+        }
+    }
+
+    /// Whether any control is supported.
+    pub fn any(&self) -> bool {
+        *self != PtzCapabilities::default()
+    }
+}
@@ -0,0 +1,707 @@
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+    time::Duration,
+};
+
+use crate::{
+    ndi_lib::NDIlib_FourCC_video_type_e, shared_ndi::SharedNdi, AudioFrame, BorrowedVideoFrame,
+    Error, FieldMode, Find, Finder, FourCCVideoType, FourCc, Receiver, Recv, RecvBandwidth,
+    RecvColorFormat, Send, Sender, Source, VideoFrame,
+};
+
+pub const GRAFTON_NDI_BANDWIDTH_METADATA_ONLY: u32 = 0;
+pub const GRAFTON_NDI_BANDWIDTH_AUDIO_ONLY: u32 = 1;
+pub const GRAFTON_NDI_BANDWIDTH_LOWEST: u32 = 2;
+pub const GRAFTON_NDI_BANDWIDTH_HIGHEST: u32 = 3;
+
+pub const GRAFTON_NDI_COLOR_BGRA: u32 = 0;
+pub const GRAFTON_NDI_COLOR_RGBA: u32 = 1;
+pub const GRAFTON_NDI_COLOR_UYVY_BGRA: u32 = 2;
+pub const GRAFTON_NDI_COLOR_FASTEST: u32 = 3;
+
+/// Result of every fallible call. Anything but `Ok` leaves a message for
+/// [`grafton_ndi_last_error`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraftonNdiStatus {
+    Ok = 0,
+    /// Nothing arrived in time. Worth calling again.
+    Timeout = 1,
+    InvalidArgument = 2,
+    /// The output buffer is too small; the required size has been written back. A captured
+    /// frame is kept and returned by the next call.
+    BufferTooSmall = 3,
+    /// The SDK or network failed in a way that may clear up if the call is repeated.
+    Retryable = 4,
+    Failed = 5,
+    /// A Rust panic was caught at the boundary.
+    Panic = 6,
+}
+
+/// Describes a video frame copied out by [`grafton_ndi_receiver_capture_video`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraftonNdiVideoInfo {
+    pub xres: i32,
+    pub yres: i32,
+    /// The NDI FourCC code, e.g. `'BGRA'`.
+    pub fourcc: u32,
+    pub line_stride: i32,
+    pub frame_rate_n: i32,
+    pub frame_rate_d: i32,
+    pub timecode: i64,
+    pub timestamp: i64,
+    /// Bytes of pixel data, every plane included.
+    pub data_size: usize,
+}
+
+/// Describes an audio frame copied out by [`grafton_ndi_receiver_capture_audio`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraftonNdiAudioInfo {
+    pub sample_rate: i32,
+    pub no_channels: i32,
+    pub no_samples: i32,
+    pub timecode: i64,
+    pub timestamp: i64,
+}
+
+/// A finder and the source list from its last refresh.
+pub struct GraftonNdiFinder {
+    find: Find<'static>,
+    sources: Vec<Source>,
+    _ndi: SharedNdi,
+}
+
+/// A receiver, plus a frame held back when the caller's buffer was too small for it.
+pub struct GraftonNdiReceiver {
+    recv: Recv<'static>,
+    pending_video: Option<VideoFrame>,
+    pending_audio: Option<AudioFrame>,
+    _ndi: SharedNdi,
+}
+
+pub struct GraftonNdiSender {
+    send: Send<'static>,
+    _ndi: SharedNdi,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The message for the last failed call on this thread, or null if there was none. The
+/// pointer stays valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn grafton_ndi_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Creates a finder; `groups` and `extra_ips` may be null. Returns null on failure.
+///
+/// # Safety
+///
+/// The strings must be null or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_finder_new(
+    show_local_sources: bool,
+    groups: *const c_char,
+    extra_ips: *const c_char,
+) -> *mut GraftonNdiFinder {
+    create(|| {
+        let settings = Finder::new(
+            show_local_sources,
+            opt_str(groups)?.as_deref(),
+            opt_str(extra_ips)?.as_deref(),
+        );
+        let ndi = SharedNdi::acquire()?;
+        // SAFETY: `_ndi` is dropped after `find`.
+        let find = Find::new_detached(settings)?;
+        Ok(GraftonNdiFinder {
+            find,
+            sources: Vec::new(),
+            _ndi: ndi,
+        })
+    })
+}
+
+/// # Safety
+///
+/// `finder` must be null or come from [`grafton_ndi_finder_new`], and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_finder_free(finder: *mut GraftonNdiFinder) {
+    free(finder)
+}
+
+/// Refreshes the source list, waiting up to `timeout_ms` for the first source if there are
+/// none, and writes the number of sources to `count`.
+///
+/// # Safety
+///
+/// `finder` must be valid and `count` writable.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_finder_refresh(
+    finder: *mut GraftonNdiFinder,
+    timeout_ms: u32,
+    count: *mut usize,
+) -> GraftonNdiStatus {
+    status(|| {
+        let finder = handle(finder)?;
+        let mut sources = finder.find.get_sources(0)?;
+        if sources.is_empty() && timeout_ms > 0 {
+            sources = finder.find.get_sources(timeout_ms)?;
+        }
+        finder.sources = sources;
+        write(count, finder.sources.len())
+    })
+}
+
+/// Copies the name of source `index` from the last refresh into `buf`, NUL-terminated. The
+/// bytes needed, terminator included, are written to `len`.
+///
+/// # Safety
+///
+/// `finder` must be valid, `buf` writable for `capacity` bytes (or null with a zero
+/// capacity) and `len` writable.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_finder_source_name(
+    finder: *mut GraftonNdiFinder,
+    index: usize,
+    buf: *mut c_char,
+    capacity: usize,
+    len: *mut usize,
+) -> GraftonNdiStatus {
+    status(|| {
+        let source = source_at(handle(finder)?, index)?;
+        copy_str(&source.name, buf, capacity, len)
+    })
+}
+
+/// Like [`grafton_ndi_finder_source_name`] for the source's URL address, which is empty if
+/// the SDK didn't report one.
+///
+/// # Safety
+///
+/// As for [`grafton_ndi_finder_source_name`].
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_finder_source_url(
+    finder: *mut GraftonNdiFinder,
+    index: usize,
+    buf: *mut c_char,
+    capacity: usize,
+    len: *mut usize,
+) -> GraftonNdiStatus {
+    status(|| {
+        let source = source_at(handle(finder)?, index)?;
+        copy_str(
+            source.url_address.as_deref().unwrap_or_default(),
+            buf,
+            capacity,
+            len,
+        )
+    })
+}
+
+/// Creates a receiver connected to the named source; `url_address` and `recv_name` may be
+/// null. `color_format` and `bandwidth` take the `GRAFTON_NDI_COLOR_*` and
+/// `GRAFTON_NDI_BANDWIDTH_*` constants. Returns null on failure.
+///
+/// # Safety
+///
+/// `source_name` must be NUL-terminated and the other strings null or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_receiver_new(
+    source_name: *const c_char,
+    url_address: *const c_char,
+    recv_name: *const c_char,
+    color_format: u32,
+    bandwidth: u32,
+) -> *mut GraftonNdiReceiver {
+    create(|| {
+        let source = Source {
+            name: opt_str(source_name)?.ok_or_else(|| null_argument("source_name"))?,
+            url_address: opt_str(url_address)?,
+            ip_address: None,
+        };
+        let settings = Receiver::new(
+            source,
+            color_format_from(color_format)?,
+            bandwidth_from(bandwidth)?,
+            FieldMode::Fielded,
+            opt_str(recv_name)?,
+        );
+        let ndi = SharedNdi::acquire()?;
+        // SAFETY: `_ndi` is dropped after `recv`.
+        let recv = Recv::new_detached(settings)?;
+        Ok(GraftonNdiReceiver {
+            recv,
+            pending_video: None,
+            pending_audio: None,
+            _ndi: ndi,
+        })
+    })
+}
+
+/// # Safety
+///
+/// `receiver` must be null or come from [`grafton_ndi_receiver_new`], and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_receiver_free(receiver: *mut GraftonNdiReceiver) {
+    free(receiver)
+}
+
+/// The number of senders the receiver is connected to, or -1 if `receiver` is null.
+///
+/// # Safety
+///
+/// `receiver` must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_receiver_connections(
+    receiver: *const GraftonNdiReceiver,
+) -> i32 {
+    receiver.as_ref().map_or(-1, |r| r.recv.connections())
+}
+
+/// Waits up to `timeout_ms` for a video frame, polling and retrying per the receiver's
+/// [`RetryPolicy`](crate::RetryPolicy), and copies its pixels into `buf`.
+///
+/// `info` is filled in whenever a frame arrived, including when `buf` is too small; size the
+/// buffer from `info.data_size` and call again to get the same frame.
+///
+/// # Safety
+///
+/// `receiver` must be valid, `buf` writable for `capacity` bytes (or null with a zero
+/// capacity) and `info` writable.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_receiver_capture_video(
+    receiver: *mut GraftonNdiReceiver,
+    timeout_ms: u32,
+    buf: *mut u8,
+    capacity: usize,
+    info: *mut GraftonNdiVideoInfo,
+) -> GraftonNdiStatus {
+    status(|| {
+        let receiver = handle(receiver)?;
+        let frame = match receiver.pending_video.take() {
+            Some(frame) => frame,
+            None => receiver
+                .recv
+                .capture_video(Duration::from_millis(timeout_ms.into()))?,
+        };
+        write(
+            info,
+            GraftonNdiVideoInfo {
+                xres: frame.xres,
+                yres: frame.yres,
+                fourcc: NDIlib_FourCC_video_type_e::from(frame.fourcc),
+                line_stride: unsafe { frame.line_stride_or_size.line_stride_in_bytes },
+                frame_rate_n: frame.frame_rate_n,
+                frame_rate_d: frame.frame_rate_d,
+                timecode: frame.timecode,
+                timestamp: frame.timestamp,
+                data_size: frame.data.len(),
+            },
+        )?;
+        if capacity < frame.data.len() {
+            receiver.pending_video = Some(frame);
+            return Err(CallError::BufferTooSmall);
+        }
+        if frame.data.is_empty() {
+            return Ok(());
+        }
+        ptr::copy_nonoverlapping(frame.data.as_ptr(), buf, frame.data.len());
+        Ok(())
+    })
+}
+
+/// Waits up to `timeout_ms` for an audio frame, polling and retrying per the receiver's
+/// policy, and copies its samples into `buf` interleaved.
+///
+/// `info` is filled in whenever a frame arrived; if `capacity`, in samples, is less than
+/// `no_samples * no_channels` the frame is kept for the next call.
+///
+/// # Safety
+///
+/// `receiver` must be valid, `buf` writable for `capacity` floats (or null with a zero
+/// capacity) and `info` writable.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_receiver_capture_audio(
+    receiver: *mut GraftonNdiReceiver,
+    timeout_ms: u32,
+    buf: *mut f32,
+    capacity: usize,
+    info: *mut GraftonNdiAudioInfo,
+) -> GraftonNdiStatus {
+    status(|| {
+        let receiver = handle(receiver)?;
+        let frame = match receiver.pending_audio.take() {
+            Some(frame) => frame,
+            None => receiver
+                .recv
+                .capture_audio(Duration::from_millis(timeout_ms.into()))?,
+        };
+        write(
+            info,
+            GraftonNdiAudioInfo {
+                sample_rate: frame.sample_rate,
+                no_channels: frame.no_channels,
+                no_samples: frame.no_samples,
+                timecode: frame.timecode,
+                timestamp: frame.timestamp,
+            },
+        )?;
+        let channels = frame.no_channels.max(0) as usize;
+        let samples = frame.no_samples.max(0) as usize;
+        if capacity < channels * samples {
+            receiver.pending_audio = Some(frame);
+            return Err(CallError::BufferTooSmall);
+        }
+        if channels * samples == 0 {
+            return Ok(());
+        }
+        let out = slice::from_raw_parts_mut(buf, channels * samples);
+        let stride = frame.channel_stride_in_bytes.max(0) as usize;
+        for channel in 0..channels {
+            let plane = &frame.data[channel * stride..];
+            for (sample, bytes) in plane.chunks_exact(4).take(samples).enumerate() {
+                out[sample * channels + channel] =
+                    f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Waits up to `timeout_ms` for a metadata frame and copies its XML into `buf`,
+/// NUL-terminated. The bytes needed are written to `len`; a frame that doesn't fit is lost.
+///
+/// # Safety
+///
+/// `receiver` must be valid, `buf` writable for `capacity` bytes (or null with a zero
+/// capacity) and `len` writable.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_receiver_capture_metadata(
+    receiver: *mut GraftonNdiReceiver,
+    timeout_ms: u32,
+    buf: *mut c_char,
+    capacity: usize,
+    len: *mut usize,
+) -> GraftonNdiStatus {
+    status(|| {
+        let receiver = handle(receiver)?;
+        let frame = receiver
+            .recv
+            .capture_metadata(Duration::from_millis(timeout_ms.into()))?;
+        copy_str(&frame.data().to_string_lossy(), buf, capacity, len)
+    })
+}
+
+/// Creates a sender publishing as `name`; `groups` may be null. Returns null on failure.
+///
+/// # Safety
+///
+/// `name` must be NUL-terminated and `groups` null or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_sender_new(
+    name: *const c_char,
+    groups: *const c_char,
+    clock_video: bool,
+    clock_audio: bool,
+) -> *mut GraftonNdiSender {
+    create(|| {
+        let settings = Sender {
+            name: opt_str(name)?.ok_or_else(|| null_argument("name"))?,
+            groups: opt_str(groups)?,
+            clock_video,
+            clock_audio,
+            config: None,
+        };
+        let ndi = SharedNdi::acquire()?;
+        // SAFETY: `_ndi` is dropped after `send`.
+        let send = Send::new_detached(settings)?;
+        Ok(GraftonNdiSender { send, _ndi: ndi })
+    })
+}
+
+/// # Safety
+///
+/// `sender` must be null or come from [`grafton_ndi_sender_new`], and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_sender_free(sender: *mut GraftonNdiSender) {
+    free(sender)
+}
+
+/// The number of receivers connected, waiting up to `timeout_ms` for one if there are
+/// none, or -1 if `sender` is null.
+///
+/// # Safety
+///
+/// `sender` must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_sender_connections(
+    sender: *const GraftonNdiSender,
+    timeout_ms: u32,
+) -> i32 {
+    sender
+        .as_ref()
+        .map_or(-1, |s| s.send.get_no_connections(timeout_ms))
+}
+
+/// Sends a video frame synchronously, after checking `len` covers every plane of `fourcc`
+/// (an NDI FourCC code such as `'BGRA'`) at this resolution and `line_stride`.
+///
+/// # Safety
+///
+/// `sender` must be valid and `data` readable for `len` bytes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn grafton_ndi_sender_send_video(
+    sender: *const GraftonNdiSender,
+    data: *const u8,
+    len: usize,
+    xres: i32,
+    yres: i32,
+    fourcc: u32,
+    line_stride: i32,
+    frame_rate_n: i32,
+    frame_rate_d: i32,
+) -> GraftonNdiStatus {
+    status(|| {
+        let sender = sender.as_ref().ok_or_else(|| null_argument("sender"))?;
+        let fourcc = FourCCVideoType::try_from(FourCc::from(fourcc))?;
+        let data = byte_slice(data, len)?;
+        let frame =
+            BorrowedVideoFrame::try_from_uncompressed(data, xres, yres, fourcc, line_stride)?
+                .with_frame_rate(frame_rate_n, frame_rate_d);
+        sender.send.send_video_borrowed(&frame);
+        Ok(())
+    })
+}
+
+/// Sends `no_samples` samples per channel of interleaved float audio.
+///
+/// # Safety
+///
+/// `sender` must be valid and `samples` readable for `no_samples * no_channels` floats.
+#[no_mangle]
+pub unsafe extern "C" fn grafton_ndi_sender_send_audio(
+    sender: *const GraftonNdiSender,
+    samples: *const f32,
+    sample_rate: i32,
+    no_channels: i32,
+    no_samples: i32,
+) -> GraftonNdiStatus {
+    status(|| {
+        let sender = sender.as_ref().ok_or_else(|| null_argument("sender"))?;
+        if sample_rate <= 0 || no_channels <= 0 || no_samples < 0 {
+            return Err(invalid_argument(format!(
+                "{} channels of {} samples at {} Hz",
+                no_channels, no_samples, sample_rate
+            )));
+        }
+        let (channels, count) = (no_channels as usize, no_samples as usize);
+        if samples.is_null() && count > 0 {
+            return Err(null_argument("samples"));
+        }
+        let interleaved = if count == 0 {
+            &[][..]
+        } else {
+            slice::from_raw_parts(samples, channels * count)
+        };
+        let mut planar = Vec::with_capacity(interleaved.len() * 4);
+        for channel in 0..channels {
+            for frame in interleaved.chunks_exact(channels) {
+                planar.extend_from_slice(&frame[channel].to_ne_bytes());
+            }
+        }
+        let frame = AudioFrame::with_data(
+            sample_rate,
+            no_channels,
+            no_samples,
+            0,
+            crate::AudioType::FLTP,
+            planar,
+            None,
+            0,
+        )?;
+        frame.validate()?;
+        sender.send.send_audio(&frame);
+        Ok(())
+    })
+}
+
+/// Why a call failed before it is reported to the caller.
+enum CallError {
+    Ndi(Error),
+    InvalidArgument(String),
+    BufferTooSmall,
+}
+
+impl From<Error> for CallError {
+    fn from(e: Error) -> Self {
+        CallError::Ndi(e)
+    }
+}
+
+fn null_argument(name: &str) -> CallError {
+    invalid_argument(format!("{} is null", name))
+}
+
+fn invalid_argument(message: String) -> CallError {
+    CallError::InvalidArgument(message)
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Runs `call`, turning errors and panics into a status and a last-error message.
+fn status(call: impl FnOnce() -> Result<(), CallError>) -> GraftonNdiStatus {
+    let result = match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(result) => result,
+        Err(_) => {
+            set_last_error("panic in grafton-ndi".into());
+            return GraftonNdiStatus::Panic;
+        }
+    };
+    match result {
+        Ok(()) => GraftonNdiStatus::Ok,
+        Err(CallError::BufferTooSmall) => {
+            set_last_error("buffer too small".into());
+            GraftonNdiStatus::BufferTooSmall
+        }
+        Err(CallError::InvalidArgument(message)) => {
+            set_last_error(message);
+            GraftonNdiStatus::InvalidArgument
+        }
+        Err(CallError::Ndi(e)) => {
+            let status = match &e {
+                Error::Timeout { .. } => GraftonNdiStatus::Timeout,
+                Error::InvalidCString(_)
+                | Error::InvalidFrame(_)
+                | Error::InvalidResolution { .. }
+                | Error::InvalidFrameRate { .. }
+                | Error::InvalidStride { .. }
+                | Error::InvalidDataSize { .. }
+                | Error::MisalignedBuffer { .. }
+                | Error::InvalidAudioLayout { .. }
+                | Error::InvalidChannelStride { .. }
+                | Error::InvalidAudioDataSize { .. }
+                | Error::ChannelOutOfRange { .. }
+                | Error::ChannelCountMismatch { .. }
+                | Error::BufferSizeMismatch { .. }
+                | Error::EmptyCompressedFrame
+                | Error::MissingCodecConfig
+                | Error::FrameTooLarge { .. }
+                | Error::UnknownFourCc(_)
+                | Error::InvalidDiscoveryServer { .. } => GraftonNdiStatus::InvalidArgument,
+                e if e.is_retryable() => GraftonNdiStatus::Retryable,
+                _ => GraftonNdiStatus::Failed,
+            };
+            set_last_error(e.to_string());
+            status
+        }
+    }
+}
+
+/// Boxes the handle built by `make`, or returns null with the error recorded.
+fn create<T>(make: impl FnOnce() -> Result<T, CallError>) -> *mut T {
+    let mut handle = ptr::null_mut();
+    status(|| {
+        handle = Box::into_raw(Box::new(make()?));
+        Ok(())
+    });
+    handle
+}
+
+unsafe fn free<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+unsafe fn handle<'h, T>(handle: *mut T) -> Result<&'h mut T, CallError> {
+    handle.as_mut().ok_or_else(|| null_argument("handle"))
+}
+
+unsafe fn write<T>(out: *mut T, value: T) -> Result<(), CallError> {
+    if out.is_null() {
+        return Err(null_argument("output pointer"));
+    }
+    out.write(value);
+    Ok(())
+}
+
+unsafe fn opt_str(s: *const c_char) -> Result<Option<String>, CallError> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map(|s| Some(s.to_owned()))
+        .map_err(|e| CallError::Ndi(Error::InvalidUtf8(e.to_string())))
+}
+
+unsafe fn byte_slice<'d>(data: *const u8, len: usize) -> Result<&'d [u8], CallError> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        return Err(null_argument("data"));
+    }
+    Ok(slice::from_raw_parts(data, len))
+}
+
+/// Copies `s` and a NUL terminator into `buf`, writing the bytes needed to `len` either way.
+unsafe fn copy_str(
+    s: &str,
+    buf: *mut c_char,
+    capacity: usize,
+    len: *mut usize,
+) -> Result<(), CallError> {
+    let needed = s.len() + 1;
+    write(len, needed)?;
+    if capacity < needed || buf.is_null() {
+        return Err(CallError::BufferTooSmall);
+    }
+    ptr::copy_nonoverlapping(s.as_ptr().cast(), buf, s.len());
+    *buf.add(s.len()) = 0;
+    Ok(())
+}
+
+fn source_at(finder: &GraftonNdiFinder, index: usize) -> Result<&Source, CallError> {
+    finder.sources.get(index).ok_or_else(|| {
+        invalid_argument(format!(
+            "source {} out of range for {} sources",
+            index,
+            finder.sources.len()
+        ))
+    })
+}
+
+fn color_format_from(value: u32) -> Result<RecvColorFormat, CallError> {
+    match value {
+        GRAFTON_NDI_COLOR_BGRA => Ok(RecvColorFormat::BGRX_BGRA),
+        GRAFTON_NDI_COLOR_RGBA => Ok(RecvColorFormat::RGBX_RGBA),
+        GRAFTON_NDI_COLOR_UYVY_BGRA => Ok(RecvColorFormat::UYVY_BGRA),
+        GRAFTON_NDI_COLOR_FASTEST => Ok(RecvColorFormat::Fastest),
+        other => Err(invalid_argument(format!("unknown color format {}", other))),
+    }
+}
+
+fn bandwidth_from(value: u32) -> Result<RecvBandwidth, CallError> {
+    match value {
+        GRAFTON_NDI_BANDWIDTH_METADATA_ONLY => Ok(RecvBandwidth::MetadataOnly),
+        GRAFTON_NDI_BANDWIDTH_AUDIO_ONLY => Ok(RecvBandwidth::AudioOnly),
+        GRAFTON_NDI_BANDWIDTH_LOWEST => Ok(RecvBandwidth::Lowest),
+        GRAFTON_NDI_BANDWIDTH_HIGHEST => Ok(RecvBandwidth::Highest),
+        other => Err(invalid_argument(format!("unknown bandwidth {}", other))),
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use crate::{AudioFrame, DropPolicy, Error, MetadataFrame, Recv, RetryPolicy, VideoFrame};
+
+/// A receiver's timing policy in one place: how long each kind of frame is waited for, how
+/// capture recovers from transient errors, and whether a video backlog is skipped.
+///
+/// Set it once with [`Recv::with_capture_config`] and call [`Recv::next_video`],
+/// [`Recv::next_audio`] and [`Recv::next_metadata`] without arguments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureConfig {
+    pub video_timeout: Duration,
+    pub audio_timeout: Duration,
+    pub metadata_timeout: Duration,
+    /// Also used by [`Recv::capture_video`] and its siblings.
+    pub retry_policy: RetryPolicy,
+    /// With [`DropPolicy::DropOldest`], [`Recv::next_video`] skips frames queued behind the
+    /// newest one, for a consumer that would rather be current than see every frame. The
+    /// other policies take frames in order. Audio and metadata are never skipped.
+    pub video_drop_policy: DropPolicy,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        CaptureConfig {
+            video_timeout: Duration::from_secs(1),
+            audio_timeout: Duration::from_secs(1),
+            metadata_timeout: Duration::from_secs(1),
+            retry_policy: RetryPolicy::default(),
+            video_drop_policy: DropPolicy::Block,
+        }
+    }
+}
+
+impl CaptureConfig {
+    /// Uses `timeout` for every kind of frame.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.video_timeout = timeout;
+        self.audio_timeout = timeout;
+        self.metadata_timeout = timeout;
+        self
+    }
+
+    pub fn with_video_timeout(mut self, timeout: Duration) -> Self {
+        self.video_timeout = timeout;
+        self
+    }
+
+    pub fn with_audio_timeout(mut self, timeout: Duration) -> Self {
+        self.audio_timeout = timeout;
+        self
+    }
+
+    pub fn with_metadata_timeout(mut self, timeout: Duration) -> Self {
+        self.metadata_timeout = timeout;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    pub fn with_video_drop_policy(mut self, policy: DropPolicy) -> Self {
+        self.video_drop_policy = policy;
+        self
+    }
+}
+
+impl<'a> Recv<'a> {
+    pub fn with_capture_config(mut self, config: CaptureConfig) -> Self {
+        self.capture_config = config;
+        self
+    }
+
+    pub fn capture_config(&self) -> &CaptureConfig {
+        &self.capture_config
+    }
+
+    pub fn set_capture_config(&mut self, config: CaptureConfig) {
+        self.capture_config = config;
+    }
+
+    /// Waits for a video frame as the receiver's [`CaptureConfig`] says.
+    pub fn next_video(&self) -> Result<VideoFrame, Error> {
+        let config = &self.capture_config;
+        if config.video_drop_policy != DropPolicy::DropOldest {
+            return self.capture_video(config.video_timeout);
+        }
+        config
+            .retry_policy
+            .run(config.video_timeout, "a video frame", |timeout_ms| {
+                Ok(self
+                    .capture_latest_video_ref(timeout_ms)?
+                    .map(|f| f.to_owned()))
+            })
+    }
+
+    /// Waits for an audio frame as the receiver's [`CaptureConfig`] says.
+    pub fn next_audio(&self) -> Result<AudioFrame, Error> {
+        self.capture_audio(self.capture_config.audio_timeout)
+    }
+
+    /// Waits for a metadata frame as the receiver's [`CaptureConfig`] says.
+    pub fn next_metadata(&self) -> Result<MetadataFrame, Error> {
+        self.capture_metadata(self.capture_config.metadata_timeout)
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{Error, FourCCVideoType, ScaleFilter, VideoFrame};
+
+const BYTES_PER_PIXEL: usize = 4;
+
+/// An 8-bit color with straight (not premultiplied) alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color { r, g, b, a }
+    }
+
+    /// The color's bytes in the channel order of `layout`.
+    fn to_pixel(self, layout: Layout) -> [u8; BYTES_PER_PIXEL] {
+        let a = if layout.alpha { self.a } else { 255 };
+        if layout.bgr {
+            [self.b, self.g, self.r, a]
+        } else {
+            [self.r, self.g, self.b, a]
+        }
+    }
+}
+
+/// How an overlay's color channels relate to its alpha in [`VideoFrame::blend_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlphaMode {
+    /// Color channels are independent of alpha, as NDI and most images carry them.
+    #[default]
+    Straight,
+    /// Color channels are already multiplied by alpha, as from many compositors and
+    /// renderers. The frame blended onto is treated as premultiplied too.
+    Premultiplied,
+}
+
+/// Channel order and alpha of a 4-byte RGB-family format.
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    bgr: bool,
+    alpha: bool,
+}
+
+impl Layout {
+    fn of(fourcc: FourCCVideoType) -> Self {
+        Layout {
+            bgr: matches!(fourcc, FourCCVideoType::BGRA | FourCCVideoType::BGRX),
+            alpha: matches!(fourcc, FourCCVideoType::BGRA | FourCCVideoType::RGBA),
+        }
+    }
+}
+
+impl VideoFrame {
+    /// Sets every pixel to `color`. Formats without alpha stay opaque.
+    ///
+    /// Supports the 8-bit RGB-family formats (RGBA, RGBX, BGRA, BGRX).
+    pub fn fill(&mut self, color: Color) -> Result<(), Error> {
+        let stride = self.rgb_stride("fill")?;
+        let pixel = color.to_pixel(Layout::of(self.fourcc));
+        let row_bytes = self.xres as usize * BYTES_PER_PIXEL;
+        for row in self.data.chunks_mut(stride).take(self.yres as usize) {
+            for dst in row[..row_bytes].chunks_exact_mut(BYTES_PER_PIXEL) {
+                dst.copy_from_slice(&pixel);
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws `overlay` over this frame with its top-left corner at (`x`, `y`), using its
+    /// straight alpha. Parts falling outside the frame are clipped.
+    ///
+    /// Both frames must be in an 8-bit RGB-family format (RGBA, RGBX, BGRA, BGRX), not
+    /// necessarily the same one; an overlay without alpha is drawn opaque.
+    pub fn blend(&mut self, overlay: &VideoFrame, x: i32, y: i32) -> Result<(), Error> {
+        self.blend_with(overlay, x, y, AlphaMode::Straight)
+    }
+
+    /// Like [`blend`](Self::blend) for an overlay whose alpha is `mode`.
+    pub fn blend_with(
+        &mut self,
+        overlay: &VideoFrame,
+        x: i32,
+        y: i32,
+        mode: AlphaMode,
+    ) -> Result<(), Error> {
+        let dst_stride = self.rgb_stride("blend")?;
+        let src_stride = overlay.rgb_stride("blend")?;
+        let (dst_layout, src_layout) = (Layout::of(self.fourcc), Layout::of(overlay.fourcc));
+        let swap = dst_layout.bgr != src_layout.bgr;
+
+        // The overlapping region in frame coordinates.
+        let left = x.max(0);
+        let top = y.max(0);
+        let right = x.saturating_add(overlay.xres).min(self.xres);
+        let bottom = y.saturating_add(overlay.yres).min(self.yres);
+        if left >= right || top >= bottom {
+            return Ok(());
+        }
+
+        for row in top..bottom {
+            let dst_row = row as usize * dst_stride;
+            let src_row = (row - y) as usize * src_stride;
+            for col in left..right {
+                let dst_at = dst_row + col as usize * BYTES_PER_PIXEL;
+                let src_at = src_row + (col - x) as usize * BYTES_PER_PIXEL;
+                let mut src: [u8; BYTES_PER_PIXEL] = overlay.data[src_at..src_at + BYTES_PER_PIXEL]
+                    .try_into()
+                    .unwrap();
+                if swap {
+                    src.swap(0, 2);
+                }
+                if !src_layout.alpha {
+                    src[3] = 255;
+                }
+                let dst = &mut self.data[dst_at..dst_at + BYTES_PER_PIXEL];
+                blend_pixel(dst, &src, mode, dst_layout.alpha);
+            }
+        }
+        Ok(())
+    }
+
+    /// Scales the frame to fit `width` x `height` without distorting it, centring it on
+    /// black bars (letterbox or pillarbox) as needed.
+    ///
+    /// Supports the 8-bit RGB-family formats (RGBA, RGBX, BGRA, BGRX).
+    pub fn letterbox_to(&self, width: i32, height: i32) -> Result<VideoFrame, Error> {
+        self.rgb_stride("letterbox")?;
+        if width <= 0 || height <= 0 {
+            return Err(Error::InvalidResolution {
+                xres: width,
+                yres: height,
+            });
+        }
+        // Fit by the frame's picture aspect ratio when it has one, so anamorphic video keeps
+        // its intended shape.
+        let aspect = if self.picture_aspect_ratio > 0.0 {
+            self.picture_aspect_ratio as f64
+        } else {
+            self.xres as f64 / self.yres as f64
+        };
+        let (fit_w, fit_h) = if width as f64 / height as f64 > aspect {
+            (
+                ((height as f64 * aspect).round() as i32).clamp(1, width),
+                height,
+            )
+        } else {
+            (
+                width,
+                ((width as f64 / aspect).round() as i32).clamp(1, height),
+            )
+        };
+
+        let mut canvas = self.derive(
+            width,
+            height,
+            vec![0; width as usize * height as usize * BYTES_PER_PIXEL],
+        )?;
+        canvas.fill(Color::BLACK)?;
+        let picture = if (fit_w, fit_h) == (self.xres, self.yres) {
+            self.clone()
+        } else {
+            self.scale(fit_w, fit_h, ScaleFilter::Bilinear)?
+        };
+        let (x, y) = ((width - fit_w) / 2, (height - fit_h) / 2);
+        let row_bytes = fit_w as usize * BYTES_PER_PIXEL;
+        let src_stride = picture.rgb_stride("letterbox")?;
+        let dst_stride = width as usize * BYTES_PER_PIXEL;
+        for row in 0..fit_h as usize {
+            let src = &picture.data[row * src_stride..row * src_stride + row_bytes];
+            let at = (y as usize + row) * dst_stride + x as usize * BYTES_PER_PIXEL;
+            canvas.data[at..at + row_bytes].copy_from_slice(src);
+        }
+        Ok(canvas)
+    }
+}
+
+/// Composites `src` over `dst` (Porter-Duff "over"). Both are in `dst`'s channel order.
+fn blend_pixel(dst: &mut [u8], src: &[u8; BYTES_PER_PIXEL], mode: AlphaMode, dst_alpha: bool) {
+    let src_a = src[3] as f32 / 255.0;
+    if src_a <= 0.0 && mode == AlphaMode::Straight {
+        return;
+    }
+    let dst_a = if dst_alpha {
+        dst[3] as f32 / 255.0
+    } else {
+        1.0
+    };
+    let out_a = src_a + dst_a * (1.0 - src_a);
+    for c in 0..3 {
+        let (s, d) = (src[c] as f32, dst[c] as f32);
+        let value = match mode {
+            AlphaMode::Straight if out_a > 0.0 => (s * src_a + d * dst_a * (1.0 - src_a)) / out_a,
+            AlphaMode::Straight => 0.0,
+            AlphaMode::Premultiplied => s + d * (1.0 - src_a),
+        };
+        dst[c] = value.round().clamp(0.0, 255.0) as u8;
+    }
+    dst[3] = if dst_alpha {
+        (out_a * 255.0).round() as u8
+    } else {
+        255
+    };
+}
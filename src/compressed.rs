@@ -0,0 +1,228 @@
+use std::{ffi::CString, ptr};
+
+use crate::{ndi_lib::*, Error, FourCc, FrameFormatType, Send, Timecode};
+
+/// `NDIlib_compressed_packet_version_0`, which is also the size of the packet header.
+const PACKET_HEADER_SIZE: usize = 44;
+const PACKET_FLAG_KEYFRAME: u32 = 1;
+
+/// Codec of a pre-compressed video frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressedCodec {
+    H264,
+    H264Alpha,
+    Hevc,
+    HevcAlpha,
+}
+
+/// Which of the two streams an NDI HX source publishes a compressed frame belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressedStream {
+    Highest,
+    Lowest,
+}
+
+/// Every codec and stream with the video FourCC the SDK uses for it.
+const VIDEO_FOURCCS: [(CompressedCodec, CompressedStream, FourCc); 8] = [
+    (
+        CompressedCodec::H264,
+        CompressedStream::Highest,
+        FourCc::new(b"H264"),
+    ),
+    (
+        CompressedCodec::H264,
+        CompressedStream::Lowest,
+        FourCc::new(b"h264"),
+    ),
+    (
+        CompressedCodec::H264Alpha,
+        CompressedStream::Highest,
+        FourCc::new(b"A264"),
+    ),
+    (
+        CompressedCodec::H264Alpha,
+        CompressedStream::Lowest,
+        FourCc::new(b"a264"),
+    ),
+    (
+        CompressedCodec::Hevc,
+        CompressedStream::Highest,
+        FourCc::new(b"HEVC"),
+    ),
+    (
+        CompressedCodec::Hevc,
+        CompressedStream::Lowest,
+        FourCc::new(b"hevc"),
+    ),
+    (
+        CompressedCodec::HevcAlpha,
+        CompressedStream::Highest,
+        FourCc::new(b"AEVC"),
+    ),
+    (
+        CompressedCodec::HevcAlpha,
+        CompressedStream::Lowest,
+        FourCc::new(b"aevc"),
+    ),
+];
+
+impl CompressedCodec {
+    /// The video FourCC of a frame of this codec in `stream`, e.g. `h264` for the low
+    /// bandwidth H.264 stream.
+    pub fn fourcc(self, stream: CompressedStream) -> FourCc {
+        VIDEO_FOURCCS
+            .iter()
+            .find(|(codec, s, _)| *codec == self && *s == stream)
+            .map(|&(_, _, fourcc)| fourcc)
+            .unwrap_or(FourCc::new(b"H264"))
+    }
+
+    /// The codec and stream a received frame's [`FourCc`] names, if it is a compressed one.
+    pub fn from_fourcc(fourcc: FourCc) -> Option<(Self, CompressedStream)> {
+        VIDEO_FOURCCS
+            .iter()
+            .find(|(_, _, f)| *f == fourcc)
+            .map(|&(codec, stream, _)| (codec, stream))
+    }
+
+    fn packet_fourcc(self) -> FourCc {
+        match self {
+            CompressedCodec::H264 | CompressedCodec::H264Alpha => FourCc::new(b"H264"),
+            CompressedCodec::Hevc | CompressedCodec::HevcAlpha => FourCc::new(b"HEVC"),
+        }
+    }
+}
+
+/// An already-encoded H.264 or HEVC access unit, sent without the SDK re-encoding it.
+///
+/// Requires the Advanced SDK. The encoded data is wrapped in the SDK's compressed packet
+/// layout when the frame is built.
+#[derive(Debug, Clone)]
+pub struct CompressedVideoFrame {
+    pub xres: i32,
+    pub yres: i32,
+    pub codec: CompressedCodec,
+    pub stream: CompressedStream,
+    pub frame_rate_n: i32,
+    pub frame_rate_d: i32,
+    pub picture_aspect_ratio: f32,
+    pub frame_format_type: FrameFormatType,
+    pub timecode: i64,
+    pub timestamp: i64,
+    keyframe: bool,
+    packet: Vec<u8>,
+    metadata: Option<CString>,
+}
+
+impl CompressedVideoFrame {
+    /// Wraps one encoded access unit.
+    ///
+    /// `pts` and `dts` are in 100 ns units. `extra_data` carries the codec configuration
+    /// (SPS/PPS, plus VPS for HEVC) and is required on keyframes so receivers can join mid-stream.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        codec: CompressedCodec,
+        stream: CompressedStream,
+        xres: i32,
+        yres: i32,
+        frame_rate_n: i32,
+        frame_rate_d: i32,
+        keyframe: bool,
+        pts: i64,
+        dts: i64,
+        data: &[u8],
+        extra_data: &[u8],
+    ) -> Result<Self, Error> {
+        if xres <= 0 || yres <= 0 {
+            return Err(Error::InvalidResolution { xres, yres });
+        }
+        if frame_rate_n <= 0 || frame_rate_d <= 0 {
+            return Err(Error::InvalidFrameRate {
+                numerator: frame_rate_n,
+                denominator: frame_rate_d,
+            });
+        }
+        if data.is_empty() {
+            return Err(Error::EmptyCompressedFrame);
+        }
+        if keyframe && extra_data.is_empty() {
+            return Err(Error::MissingCodecConfig);
+        }
+        let total = PACKET_HEADER_SIZE + data.len() + extra_data.len();
+        if total > i32::MAX as usize {
+            return Err(Error::FrameTooLarge {
+                size: total,
+                limit: i32::MAX as usize,
+            });
+        }
+
+        let mut packet = Vec::with_capacity(total);
+        packet.extend_from_slice(&(PACKET_HEADER_SIZE as u32).to_le_bytes());
+        packet.extend_from_slice(&codec.packet_fourcc().to_bytes());
+        packet.extend_from_slice(&pts.to_le_bytes());
+        packet.extend_from_slice(&dts.to_le_bytes());
+        packet.extend_from_slice(&0u64.to_le_bytes());
+        let flags = if keyframe { PACKET_FLAG_KEYFRAME } else { 0 };
+        packet.extend_from_slice(&flags.to_le_bytes());
+        packet.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        packet.extend_from_slice(&(extra_data.len() as u32).to_le_bytes());
+        packet.extend_from_slice(data);
+        packet.extend_from_slice(extra_data);
+
+        Ok(CompressedVideoFrame {
+            xres,
+            yres,
+            codec,
+            stream,
+            frame_rate_n,
+            frame_rate_d,
+            picture_aspect_ratio: xres as f32 / yres as f32,
+            frame_format_type: FrameFormatType::Progressive,
+            timecode: Timecode::SYNTHESIZE.0,
+            timestamp: 0,
+            keyframe,
+            packet,
+            metadata: None,
+        })
+    }
+
+    pub fn with_metadata(mut self, metadata: &str) -> Result<Self, Error> {
+        self.metadata = Some(CString::new(metadata).map_err(Error::InvalidCString)?);
+        Ok(self)
+    }
+
+    pub fn is_keyframe(&self) -> bool {
+        self.keyframe
+    }
+
+    /// Size of the packet handed to the SDK, header included.
+    pub fn data_size_in_bytes(&self) -> usize {
+        self.packet.len()
+    }
+
+    pub(crate) fn to_raw(&self) -> NDIlib_video_frame_v2_t {
+        NDIlib_video_frame_v2_t {
+            xres: self.xres,
+            yres: self.yres,
+            FourCC: self.codec.fourcc(self.stream).as_u32() as NDIlib_FourCC_video_type_e,
+            frame_rate_N: self.frame_rate_n,
+            frame_rate_D: self.frame_rate_d,
+            picture_aspect_ratio: self.picture_aspect_ratio,
+            frame_format_type: self.frame_format_type.into(),
+            timecode: self.timecode,
+            p_data: self.packet.as_ptr() as *mut u8,
+            __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+                data_size_in_bytes: self.packet.len() as i32,
+            },
+            p_metadata: self.metadata.as_ref().map_or(ptr::null(), |m| m.as_ptr()),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Sends an already-encoded frame; the SDK passes it through without re-encoding.
+    pub fn send_video_compressed(&self, frame: &CompressedVideoFrame) {
+        self.send_video_raw(&frame.to_raw());
+    }
+}
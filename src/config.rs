@@ -0,0 +1,392 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Write as _,
+    fs,
+    hash::{BuildHasher, Hasher},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "advanced_sdk")]
+use std::{ffi::CString, os::raw::c_char};
+
+use crate::{ndi_lib::*, Error};
+
+#[cfg(feature = "advanced_sdk")]
+sdk_extern! {
+    fn NDIlib_recv_create_v4(
+        p_create_settings: *const NDIlib_recv_create_v3_t,
+        p_config_data: *const c_char,
+    ) -> NDIlib_recv_instance_t;
+    fn NDIlib_send_create_v2(
+        p_create_settings: *const NDIlib_send_create_t,
+        p_config_data: *const c_char,
+    ) -> NDIlib_send_instance_t;
+}
+
+/// File name the SDK reads its configuration from inside `NDI_CONFIG_DIR`.
+pub const NDI_CONFIG_FILE: &str = "ndi-config.v1.json";
+
+/// Enables or disables a transport separately for sending and receiving.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TransportConfig {
+    pub send: Option<bool>,
+    pub recv: Option<bool>,
+}
+
+/// Typed builder for the SDK's JSON configuration (the contents of `ndi-config.v1.json`).
+///
+/// Settings left unset are omitted so the SDK's defaults apply. The configuration can be given
+/// to a single [`Receiver`](crate::Receiver) or [`Sender`](crate::Sender) with the Advanced
+/// SDK, or written to a directory for [`NDI::with_config_dir`](crate::NDI::with_config_dir).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NdiConfig {
+    pub machine_name: Option<String>,
+    /// Comma-separated discovery server addresses.
+    pub discovery_server: Option<String>,
+    /// Comma-separated addresses of remote machines to look for sources on.
+    pub extra_ips: Option<String>,
+    /// Local network adapters, by IP address, the SDK may use.
+    pub adapters: Vec<String>,
+    pub tcp: TransportConfig,
+    /// Reliable UDP, the SDK's default transport.
+    pub rudp: TransportConfig,
+    pub unicast: TransportConfig,
+    pub multicast: TransportConfig,
+    pub multicast_netprefix: Option<String>,
+    pub multicast_netmask: Option<String>,
+    pub multicast_ttl: Option<u8>,
+    pub send_groups: Option<String>,
+    pub recv_groups: Option<String>,
+}
+
+impl NdiConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_machine_name(mut self, name: &str) -> Self {
+        self.machine_name = Some(name.to_string());
+        self
+    }
+
+    pub fn with_discovery_server(mut self, servers: &str) -> Self {
+        self.discovery_server = Some(servers.to_string());
+        self
+    }
+
+    pub fn with_extra_ips(mut self, ips: &str) -> Self {
+        self.extra_ips = Some(ips.to_string());
+        self
+    }
+
+    /// Restricts the SDK to the network adapters with these IP addresses.
+    pub fn with_adapters<I, S>(mut self, adapters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.adapters = adapters.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_tcp(mut self, send: bool, recv: bool) -> Self {
+        self.tcp = TransportConfig {
+            send: Some(send),
+            recv: Some(recv),
+        };
+        self
+    }
+
+    pub fn with_rudp(mut self, send: bool, recv: bool) -> Self {
+        self.rudp = TransportConfig {
+            send: Some(send),
+            recv: Some(recv),
+        };
+        self
+    }
+
+    pub fn with_unicast(mut self, send: bool, recv: bool) -> Self {
+        self.unicast = TransportConfig {
+            send: Some(send),
+            recv: Some(recv),
+        };
+        self
+    }
+
+    pub fn with_multicast(mut self, send: bool, recv: bool) -> Self {
+        self.multicast = TransportConfig {
+            send: Some(send),
+            recv: Some(recv),
+        };
+        self
+    }
+
+    /// Address range and hop limit used when sending multicast.
+    pub fn with_multicast_range(mut self, netprefix: &str, netmask: &str, ttl: u8) -> Self {
+        self.multicast_netprefix = Some(netprefix.to_string());
+        self.multicast_netmask = Some(netmask.to_string());
+        self.multicast_ttl = Some(ttl);
+        self
+    }
+
+    /// Disables multicast in both directions, leaving unicast transports only.
+    pub fn unicast_only(self) -> Self {
+        self.with_multicast(false, false)
+    }
+
+    pub fn with_groups(mut self, send: &str, recv: &str) -> Self {
+        self.send_groups = Some(send.to_string());
+        self.recv_groups = Some(recv.to_string());
+        self
+    }
+
+    /// Serializes the configuration in the layout of `ndi-config.v1.json`.
+    pub fn to_json(&self) -> String {
+        let mut ndi = JsonObject::default();
+        if let Some(name) = &self.machine_name {
+            ndi.string("machinename", name);
+        }
+
+        let mut networks = JsonObject::default();
+        if let Some(ips) = &self.extra_ips {
+            networks.string("ips", ips);
+        }
+        if let Some(discovery) = &self.discovery_server {
+            networks.string("discovery", discovery);
+        }
+        ndi.object("networks", networks);
+
+        if !self.adapters.is_empty() {
+            let mut adapters = JsonObject::default();
+            let allowed = self
+                .adapters
+                .iter()
+                .map(|a| json_string(a))
+                .collect::<Vec<_>>()
+                .join(",");
+            adapters.raw("allowed", &format!("[{}]", allowed));
+            ndi.object("adapters", adapters);
+        }
+
+        for (name, transport) in [
+            ("tcp", &self.tcp),
+            ("rudp", &self.rudp),
+            ("unicast", &self.unicast),
+        ] {
+            ndi.object(name, transport_object(transport, JsonObject::default()));
+        }
+
+        let mut multicast_send = JsonObject::default();
+        if let Some(prefix) = &self.multicast_netprefix {
+            multicast_send.string("netprefix", prefix);
+        }
+        if let Some(mask) = &self.multicast_netmask {
+            multicast_send.string("netmask", mask);
+        }
+        if let Some(ttl) = self.multicast_ttl {
+            multicast_send.raw("ttl", &ttl.to_string());
+        }
+        ndi.object(
+            "multicast",
+            transport_object(&self.multicast, multicast_send),
+        );
+
+        let mut groups = JsonObject::default();
+        if let Some(send) = &self.send_groups {
+            groups.string("send", send);
+        }
+        if let Some(recv) = &self.recv_groups {
+            groups.string("recv", recv);
+        }
+        ndi.object("groups", groups);
+
+        let mut root = JsonObject::default();
+        root.object("ndi", ndi);
+        root.finish()
+    }
+
+    /// Writes the configuration as `ndi-config.v1.json` inside `dir`.
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(dir.as_ref().join(NDI_CONFIG_FILE), self.to_json())?;
+        Ok(())
+    }
+}
+
+/// Creates a new directory with a random name inside `parent`, accessible only to this user on
+/// Unix. Fails rather than reuse a directory that already exists.
+pub(crate) fn create_private_dir(parent: &Path) -> Result<PathBuf, Error> {
+    // `RandomState` is keyed from the operating system's random source.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    let dir = parent.join(format!("grafton-ndi-{:016x}", hasher.finish()));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+    Ok(dir)
+}
+
+/// Creates a receiver, applying `config` to this instance only when one is given.
+pub(crate) fn create_recv(
+    create: &NDIlib_recv_create_v3_t,
+    config: Option<&NdiConfig>,
+) -> Result<NDIlib_recv_instance_t, Error> {
+    match config {
+        None => Ok(unsafe { NDIlib_recv_create_v3(create) }),
+        #[cfg(feature = "advanced_sdk")]
+        Some(config) => {
+            let json = CString::new(config.to_json())?;
+            Ok(unsafe { NDIlib_recv_create_v4(create, json.as_ptr()) })
+        }
+        #[cfg(not(feature = "advanced_sdk"))]
+        Some(_) => Err(per_instance_unsupported()),
+    }
+}
+
+/// Creates a sender, applying `config` to this instance only when one is given.
+pub(crate) fn create_send(
+    create: &NDIlib_send_create_t,
+    config: Option<&NdiConfig>,
+) -> Result<NDIlib_send_instance_t, Error> {
+    match config {
+        None => Ok(unsafe { NDIlib_send_create(create) }),
+        #[cfg(feature = "advanced_sdk")]
+        Some(config) => {
+            let json = CString::new(config.to_json())?;
+            Ok(unsafe { NDIlib_send_create_v2(create, json.as_ptr()) })
+        }
+        #[cfg(not(feature = "advanced_sdk"))]
+        Some(_) => Err(per_instance_unsupported()),
+    }
+}
+
+#[cfg(not(feature = "advanced_sdk"))]
+fn per_instance_unsupported() -> Error {
+    Error::InitializationFailed(
+        "Per-instance NDI configuration requires the Advanced SDK; write it with \
+         NdiConfig::write_to_dir and use NDI::with_config_dir instead"
+            .into(),
+    )
+}
+
+fn transport_object(transport: &TransportConfig, send: JsonObject) -> JsonObject {
+    let mut send = send;
+    if let Some(enable) = transport.send {
+        send.raw("enable", &enable.to_string());
+    }
+    let mut recv = JsonObject::default();
+    if let Some(enable) = transport.recv {
+        recv.raw("enable", &enable.to_string());
+    }
+    let mut object = JsonObject::default();
+    object.object("send", send);
+    object.object("recv", recv);
+    object
+}
+
+/// Accumulates `"key":value` members, skipping empty nested objects.
+#[derive(Default)]
+struct JsonObject {
+    members: Vec<String>,
+}
+
+impl JsonObject {
+    fn raw(&mut self, key: &str, value: &str) {
+        self.members.push(format!("{}:{}", json_string(key), value));
+    }
+
+    fn string(&mut self, key: &str, value: &str) {
+        self.raw(key, &json_string(value));
+    }
+
+    fn object(&mut self, key: &str, object: JsonObject) {
+        if !object.members.is_empty() {
+            self.raw(key, &object.finish());
+        }
+    }
+
+    fn finish(self) -> String {
+        format!("{{{}}}", self.members.join(","))
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_is_an_empty_object() {
+        assert_eq!(NdiConfig::new().to_json(), "{}");
+    }
+
+    #[test]
+    fn settings_land_in_the_sdk_layout() {
+        let config = NdiConfig::new()
+            .with_machine_name("studio")
+            .with_discovery_server("10.0.0.1")
+            .with_adapters(["10.0.0.2"])
+            .with_tcp(true, false)
+            .with_multicast_range("239.255.0.0", "255.255.0.0", 4)
+            .unicast_only()
+            .with_groups("A", "B");
+        assert_eq!(
+            config.to_json(),
+            concat!(
+                r#"{"ndi":{"machinename":"studio","networks":{"discovery":"10.0.0.1"},"#,
+                r#""adapters":{"allowed":["10.0.0.2"]},"#,
+                r#""tcp":{"send":{"enable":true},"recv":{"enable":false}},"#,
+                r#""multicast":{"send":{"netprefix":"239.255.0.0","netmask":"255.255.0.0","ttl":4,"enable":false},"recv":{"enable":false}},"#,
+                r#""groups":{"send":"A","recv":"B"}}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(json_string("\n\r\t"), r#""\n\r\t""#);
+        assert_eq!(json_string("\u{1}\u{1f}"), r#""\u0001\u001f""#);
+        assert_eq!(json_string("é ✓"), "\"é ✓\"");
+    }
+
+    #[test]
+    fn private_dirs_are_fresh() {
+        let parent = std::env::temp_dir();
+        let first = create_private_dir(&parent).unwrap();
+        let second = create_private_dir(&parent).unwrap();
+        assert_ne!(first, second);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        fs::remove_dir(first).unwrap();
+        fs::remove_dir(second).unwrap();
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{
+    timecode::TIMESTAMP_UNDEFINED, Error, FourCCVideoType, FrameFormatType, VideoFrame,
+    VideoFrameBuilder,
+};
+
+const TICKS_PER_SECOND: i64 = 10_000_000;
+
+/// How a [`Deinterlacer`] turns fields into progressive frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeinterlaceMode {
+    /// Shows every field as a frame of its own, filling in the missing lines by interpolation.
+    /// Motion stays smooth at double the frame rate, at the cost of some vertical detail.
+    Bob,
+    /// Interleaves each pair of fields into one frame. Full vertical detail and no extra
+    /// frames, but moving content combs unless both fields were captured at the same instant.
+    #[default]
+    Weave,
+}
+
+/// Converts interlaced and field-based video into progressive frames.
+///
+/// Feed every received frame to [`Deinterlacer::push`] in order; progressive frames pass
+/// through untouched. Supports the packed formats (UYVY, RGBA, RGBX, BGRA, BGRX).
+#[derive(Debug, Default)]
+pub struct Deinterlacer {
+    mode: DeinterlaceMode,
+    /// Weaves separately delivered fields.
+    pairer: FieldPairer,
+}
+
+/// Reassembles the separate fields a receiver in [`FieldMode::Fielded`](crate::FieldMode)
+/// delivers into whole interlaced frames.
+///
+/// Each [`FrameFormatType::Field0`] frame is held until the [`FrameFormatType::Field1`] frame
+/// that follows it, and the two are interleaved line by line into one frame of twice the
+/// height, marked [`FrameFormatType::Interlaced`] and timed as the first field. Whole frames
+/// pass straight through. Supports the packed formats (UYVY, RGBA, RGBX, BGRA, BGRX).
+#[derive(Debug, Default)]
+pub struct FieldPairer {
+    /// A first field waiting for its second.
+    pending: Option<VideoFrame>,
+}
+
+impl FieldPairer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the whole frame `frame` completes: `None` while holding a first field, or for
+    /// a second field whose first was lost or doesn't match it.
+    pub fn push(&mut self, frame: VideoFrame) -> Result<Option<VideoFrame>, Error> {
+        match frame.frame_format_type {
+            FrameFormatType::Field0 => {
+                packed_stride(&frame)?;
+                self.pending = Some(frame);
+                Ok(None)
+            }
+            FrameFormatType::Field1 => {
+                let stride = packed_stride(&frame)?;
+                let Some(first) = self.pending.take() else {
+                    return Ok(None);
+                };
+                if !same_geometry(&first, &frame) {
+                    return Ok(None);
+                }
+                let first_stride = packed_stride(&first)?;
+                let row_bytes = frame.fourcc.line_stride(frame.xres) as usize;
+                let mut data = Vec::with_capacity(row_bytes * frame.yres as usize * 2);
+                for row in 0..frame.yres as usize {
+                    data.extend_from_slice(field_row(&first, first_stride, row));
+                    data.extend_from_slice(field_row(&frame, stride, row));
+                }
+                let mut paired = progressive(&first, frame.yres * 2, data, 1, 0)?;
+                paired.frame_format_type = FrameFormatType::Interlaced;
+                Ok(Some(paired))
+            }
+            _ => Ok(Some(frame)),
+        }
+    }
+
+    /// Whether a first field is waiting for its second.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Forgets any field waiting for its partner, e.g. after switching sources.
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+}
+
+impl Deinterlacer {
+    pub fn new(mode: DeinterlaceMode) -> Self {
+        Deinterlacer {
+            mode,
+            pairer: FieldPairer::new(),
+        }
+    }
+
+    pub fn mode(&self) -> DeinterlaceMode {
+        self.mode
+    }
+
+    /// Returns the progressive frames that `frame` completes: none while waiting for the second
+    /// field of a pair, two when bobbing an interlaced frame, otherwise one.
+    ///
+    /// Bobbed frames are reported at the field rate, with the second field of an interlaced
+    /// frame timestamped half a frame after the first.
+    pub fn push(&mut self, frame: VideoFrame) -> Result<Vec<VideoFrame>, Error> {
+        if frame.frame_format_type == FrameFormatType::Progressive {
+            return Ok(vec![frame]);
+        }
+        let stride = packed_stride(&frame)?;
+        match (self.mode, frame.frame_format_type) {
+            (DeinterlaceMode::Weave, FrameFormatType::Interlaced) => {
+                let mut frame = frame;
+                frame.frame_format_type = FrameFormatType::Progressive;
+                Ok(vec![frame])
+            }
+            // A second field whose first was lost can't be woven; wait for the next pair.
+            (DeinterlaceMode::Weave, _) => Ok(self
+                .pairer
+                .push(frame)?
+                .map(|mut woven| {
+                    woven.frame_format_type = FrameFormatType::Progressive;
+                    woven
+                })
+                .into_iter()
+                .collect()),
+            (DeinterlaceMode::Bob, FrameFormatType::Interlaced) => {
+                let field_rows = (frame.yres as usize).div_ceil(2);
+                let half_frame = frame_duration(&frame) / 2;
+                let first = bob(&frame, stride, 0, field_rows, 0, 2, 0)?;
+                // With an odd height the second field is a line short; its last line repeats.
+                let second = bob(&frame, stride, 1, field_rows, 1, 2, half_frame)?;
+                Ok(vec![first, second])
+            }
+            (DeinterlaceMode::Bob, field) => {
+                let parity = usize::from(field == FrameFormatType::Field1);
+                let rows = frame.yres as usize;
+                Ok(vec![bob(&frame, stride, 0, rows, parity, 1, 0)?])
+            }
+        }
+    }
+
+    /// Forgets any field waiting for its partner, e.g. after switching sources.
+    pub fn reset(&mut self) {
+        self.pairer.reset();
+    }
+}
+
+/// Checks the frame is in a packed format with valid data and returns its line stride.
+fn packed_stride(frame: &VideoFrame) -> Result<usize, Error> {
+    match frame.fourcc {
+        FourCCVideoType::UYVY
+        | FourCCVideoType::RGBA
+        | FourCCVideoType::RGBX
+        | FourCCVideoType::BGRA
+        | FourCCVideoType::BGRX => {}
+        fourcc => {
+            return Err(Error::UnsupportedFormat {
+                fourcc,
+                operation: "deinterlace",
+            })
+        }
+    }
+    frame.validate()?;
+    Ok(unsafe { frame.line_stride_or_size.line_stride_in_bytes } as usize)
+}
+
+fn same_geometry(a: &VideoFrame, b: &VideoFrame) -> bool {
+    a.xres == b.xres && a.yres == b.yres && a.fourcc == b.fourcc
+}
+
+fn field_row(frame: &VideoFrame, stride: usize, row: usize) -> &[u8] {
+    let start = row * stride;
+    &frame.data[start..start + frame.fourcc.line_stride(frame.xres) as usize]
+}
+
+/// Frame duration in 100 ns ticks, or zero if the frame rate is unknown.
+fn frame_duration(frame: &VideoFrame) -> i64 {
+    if frame.frame_rate_n > 0 && frame.frame_rate_d > 0 {
+        TICKS_PER_SECOND * i64::from(frame.frame_rate_d) / i64::from(frame.frame_rate_n)
+    } else {
+        0
+    }
+}
+
+/// Builds a frame of `rows * 2` lines from one field of `frame`: the field's lines start at
+/// `first_row` and are `step` lines apart, and land on output lines of `parity`. The other
+/// output lines average the field lines above and below them, and if the field has fewer
+/// than `rows` lines its last line is repeated.
+fn bob(
+    frame: &VideoFrame,
+    stride: usize,
+    first_row: usize,
+    rows: usize,
+    parity: usize,
+    step: usize,
+    offset: i64,
+) -> Result<VideoFrame, Error> {
+    let lines = (frame.yres as usize)
+        .saturating_sub(first_row)
+        .div_ceil(step);
+    if lines == 0 {
+        return Err(Error::InvalidResolution {
+            xres: frame.xres,
+            yres: frame.yres,
+        });
+    }
+    let line = |i: usize| field_row(frame, stride, first_row + i.min(lines - 1) * step);
+    let row_bytes = frame.fourcc.line_stride(frame.xres) as usize;
+    let height = rows * 2;
+    let mut data = Vec::with_capacity(row_bytes * height);
+    for row in 0..height {
+        if row % 2 == parity {
+            data.extend_from_slice(line(row / 2));
+        } else {
+            // Lines before the first field line or after the last repeat their neighbour.
+            let below = (row + 1 - parity) / 2;
+            let (above, below) = (line(below.saturating_sub(1)), line(below));
+            data.extend(
+                above
+                    .iter()
+                    .zip(below)
+                    .map(|(&a, &b)| (a as u16 + b as u16).div_ceil(2) as u8),
+            );
+        }
+    }
+    progressive(frame, height as i32, data, 2, offset)
+}
+
+/// A tightly packed progressive frame carrying `data`, with the timing of `source` at
+/// `rate_multiplier` times its frame rate and shifted by `offset` ticks.
+fn progressive(
+    source: &VideoFrame,
+    yres: i32,
+    data: Vec<u8>,
+    rate_multiplier: i32,
+    offset: i64,
+) -> Result<VideoFrame, Error> {
+    // Also leaves `Timecode::SYNTHESIZE`, which shares the undefined timestamp's value, alone.
+    let shift = |time: i64| {
+        if time == TIMESTAMP_UNDEFINED {
+            time
+        } else {
+            time.saturating_add(offset)
+        }
+    };
+    let mut frame = VideoFrameBuilder::new()
+        .resolution(source.xres, yres)
+        .fourcc(source.fourcc)
+        .frame_rate(source.frame_rate_n * rate_multiplier, source.frame_rate_d)
+        .aspect_ratio(source.picture_aspect_ratio)
+        .format(FrameFormatType::Progressive)
+        .timecode(shift(source.timecode))
+        .timestamp(shift(source.timestamp))
+        .data(data)
+        .build()?;
+    frame.metadata = source.metadata.clone();
+    Ok(frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A one-pixel-wide BGRA frame whose line `i` is filled with `lines[i]`.
+    fn frame(format: FrameFormatType, lines: &[u8], timestamp: i64) -> VideoFrame {
+        VideoFrameBuilder::new()
+            .resolution(1, lines.len() as i32)
+            .fourcc(FourCCVideoType::BGRA)
+            .frame_rate(25, 1)
+            .format(format)
+            .timestamp(timestamp)
+            .data(lines.iter().flat_map(|&value| [value; 4]).collect())
+            .build()
+            .unwrap()
+    }
+
+    fn lines(frame: &VideoFrame) -> Vec<u8> {
+        frame.data.chunks(4).map(|pixel| pixel[0]).collect()
+    }
+
+    #[test]
+    fn progressive_frames_pass_through() {
+        let mut deinterlacer = Deinterlacer::new(DeinterlaceMode::Bob);
+        let out = deinterlacer
+            .push(frame(FrameFormatType::Progressive, &[1, 2], 0))
+            .unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(lines(&out[0]), [1, 2]);
+    }
+
+    #[test]
+    fn weave_marks_interlaced_frames_progressive() {
+        let mut deinterlacer = Deinterlacer::new(DeinterlaceMode::Weave);
+        let out = deinterlacer
+            .push(frame(FrameFormatType::Interlaced, &[1, 2, 3, 4], 0))
+            .unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].frame_format_type, FrameFormatType::Progressive);
+        assert_eq!(lines(&out[0]), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bob_splits_an_interlaced_frame_into_two_fields() {
+        let mut deinterlacer = Deinterlacer::new(DeinterlaceMode::Bob);
+        let out = deinterlacer
+            .push(frame(FrameFormatType::Interlaced, &[10, 20, 30, 40], 1_000))
+            .unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!(lines(&out[0]), [10, 20, 30, 30]);
+        assert_eq!(lines(&out[1]), [20, 20, 30, 40]);
+        assert_eq!(out[0].timestamp, 1_000);
+        assert_eq!(out[1].timestamp, 1_000 + 200_000);
+        assert_eq!(out[0].frame_rate_n, 50);
+    }
+
+    #[test]
+    fn bob_pads_the_short_field_of_an_odd_height_frame() {
+        let mut deinterlacer = Deinterlacer::new(DeinterlaceMode::Bob);
+        let out = deinterlacer
+            .push(frame(FrameFormatType::Interlaced, &[10, 20, 30, 40, 50], 0))
+            .unwrap();
+        assert_eq!(lines(&out[0]), [10, 20, 30, 40, 50, 50]);
+        assert_eq!(lines(&out[1]), [20, 20, 30, 40, 40, 40]);
+    }
+
+    #[test]
+    fn bob_rejects_an_interlaced_frame_with_one_line() {
+        let mut deinterlacer = Deinterlacer::new(DeinterlaceMode::Bob);
+        let result = deinterlacer.push(frame(FrameFormatType::Interlaced, &[10], 0));
+        assert!(matches!(result, Err(Error::InvalidResolution { .. })));
+    }
+
+    #[test]
+    fn bob_doubles_a_single_field_on_its_own_parity() {
+        let mut deinterlacer = Deinterlacer::new(DeinterlaceMode::Bob);
+        let out = deinterlacer
+            .push(frame(FrameFormatType::Field1, &[10, 30], 0))
+            .unwrap();
+        assert_eq!(lines(&out[0]), [10, 10, 20, 30]);
+    }
+
+    #[test]
+    fn fields_are_paired_in_order() {
+        let mut pairer = FieldPairer::new();
+        // A second field without its first is dropped.
+        assert!(pairer
+            .push(frame(FrameFormatType::Field1, &[2, 4], 0))
+            .unwrap()
+            .is_none());
+        assert!(pairer
+            .push(frame(FrameFormatType::Field0, &[1, 3], 0))
+            .unwrap()
+            .is_none());
+        assert!(pairer.is_pending());
+        let paired = pairer
+            .push(frame(FrameFormatType::Field1, &[2, 4], 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(paired.frame_format_type, FrameFormatType::Interlaced);
+        assert_eq!(lines(&paired), [1, 2, 3, 4]);
+        assert!(!pairer.is_pending());
+    }
+
+    #[test]
+    fn planar_formats_are_rejected() {
+        let frame = VideoFrameBuilder::new()
+            .resolution(2, 2)
+            .fourcc(FourCCVideoType::NV12)
+            .format(FrameFormatType::Interlaced)
+            .build()
+            .unwrap();
+        let result = Deinterlacer::new(DeinterlaceMode::Weave).push(frame);
+        assert!(matches!(result, Err(Error::UnsupportedFormat { .. })));
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    fmt,
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{Error, NdiConfig, Receiver, Sender, NDI};
+
+/// How long [`NDI::with_discovery_servers`] waits for each server to accept a connection.
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A validated list of NDI Discovery Service addresses, `host` or `host:port`, in order of
+/// preference.
+///
+/// With a discovery server configured, sources register with and are found through the
+/// server instead of mDNS, which is what makes NDI work in containers and on networks that
+/// block multicast. Parse one from the comma-separated form the SDK uses, e.g.
+/// `"10.0.0.5,discovery.example.com:5960"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryServers {
+    servers: Vec<String>,
+    mdns_fallback: bool,
+}
+
+impl DiscoveryServers {
+    /// The port the discovery service listens on unless told otherwise.
+    pub const DEFAULT_PORT: u16 = 5959;
+
+    pub fn new<I, S>(servers: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let servers = servers
+            .into_iter()
+            .map(|s| validate(s.as_ref().trim()).map(|()| s.as_ref().trim().to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if servers.is_empty() {
+            return Err(Error::InvalidDiscoveryServer {
+                address: String::new(),
+                reason: "no servers given",
+            });
+        }
+        Ok(DiscoveryServers {
+            servers,
+            mdns_fallback: false,
+        })
+    }
+
+    /// Falls back to mDNS discovery, in [`NDI::with_discovery_servers`], when none of the
+    /// servers can be reached instead of failing.
+    pub fn with_mdns_fallback(mut self, fallback: bool) -> Self {
+        self.mdns_fallback = fallback;
+        self
+    }
+
+    pub fn servers(&self) -> &[String] {
+        &self.servers
+    }
+
+    pub fn mdns_fallback(&self) -> bool {
+        self.mdns_fallback
+    }
+
+    /// The servers that accept a TCP connection within `timeout`, in order.
+    pub fn reachable(&self, timeout: Duration) -> Vec<&str> {
+        self.servers
+            .iter()
+            .map(String::as_str)
+            .filter(|server| probe(server, timeout))
+            .collect()
+    }
+}
+
+impl FromStr for DiscoveryServers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::new(s.split(',').filter(|s| !s.trim().is_empty()))
+    }
+}
+
+impl fmt::Display for DiscoveryServers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.servers.join(","))
+    }
+}
+
+/// Splits `host:port`, `[v6]:port`, or a bare host into host and port.
+fn split_port(address: &str) -> Result<(&str, u16), Error> {
+    let invalid = |reason| Error::InvalidDiscoveryServer {
+        address: address.to_string(),
+        reason,
+    };
+    let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| invalid("unclosed '['"))?;
+        match rest.strip_prefix(':') {
+            Some(port) => (host, Some(port)),
+            None if rest.is_empty() => (host, None),
+            None => return Err(invalid("unexpected text after ']'")),
+        }
+    } else if address.matches(':').count() > 1 {
+        // A bare IPv6 address.
+        (address, None)
+    } else {
+        match address.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (address, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port
+            .parse::<u16>()
+            .ok()
+            .filter(|&p| p != 0)
+            .ok_or_else(|| invalid("port must be a number from 1 to 65535"))?,
+        None => DiscoveryServers::DEFAULT_PORT,
+    };
+    Ok((host, port))
+}
+
+fn validate(address: &str) -> Result<(), Error> {
+    let invalid = |reason| Error::InvalidDiscoveryServer {
+        address: address.to_string(),
+        reason,
+    };
+    if address.contains(',') {
+        return Err(invalid("addresses must not contain commas"));
+    }
+    let (host, _) = split_port(address)?;
+    if host.is_empty() {
+        return Err(invalid("host is empty"));
+    }
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+    let valid_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+    if host.len() > 253 || !host.trim_end_matches('.').split('.').all(valid_label) {
+        return Err(invalid("not an IP address or host name"));
+    }
+    Ok(())
+}
+
+fn probe(server: &str, timeout: Duration) -> bool {
+    let Ok((host, port)) = split_port(server) else {
+        return false;
+    };
+    let Ok(addresses) = (host, port).to_socket_addrs() else {
+        return false;
+    };
+    addresses
+        .into_iter()
+        .any(|address| TcpStream::connect_timeout(&address, timeout).is_ok())
+}
+
+impl NDI {
+    /// Initializes the runtime to find and announce sources through `servers` instead of
+    /// mDNS, for the whole process; finders can only be configured this way.
+    ///
+    /// Each server is probed first and those that answer are listed ahead of those that
+    /// don't, so the SDK tries a working one first and the rest stay as fallbacks. If none
+    /// answers, this fails with [`Error::DiscoveryServerUnreachable`], or initializes with
+    /// mDNS discovery if [`DiscoveryServers::with_mdns_fallback`] is set. Like
+    /// [`NDI::with_config`], it only takes effect before the runtime is first initialized.
+    ///
+    /// # Safety
+    ///
+    /// As [`NDI::with_config_dir`], which this configures the runtime through: call this
+    /// before the process starts any other threads.
+    pub unsafe fn with_discovery_servers(servers: &DiscoveryServers) -> Result<Self, Error> {
+        let reachable = servers.reachable(PROBE_TIMEOUT);
+        if reachable.is_empty() {
+            if servers.mdns_fallback {
+                ndi_warn!(%servers, "no discovery server reachable; falling back to mDNS");
+                return NDI::new();
+            }
+            return Err(Error::DiscoveryServerUnreachable(servers.to_string()));
+        }
+        let ordered = reachable
+            .iter()
+            .copied()
+            .chain(
+                servers
+                    .servers
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|s| !reachable.contains(s)),
+            )
+            .collect::<Vec<_>>()
+            .join(",");
+        ndi_info!(servers = %ordered, "using discovery servers");
+        unsafe { NDI::with_config(&NdiConfig::new().with_discovery_server(&ordered)) }
+    }
+}
+
+impl Receiver {
+    /// Discovers the source through `servers` for this receiver only, keeping the rest of any
+    /// per-instance configuration (Advanced SDK).
+    pub fn with_discovery_servers(mut self, servers: &DiscoveryServers) -> Self {
+        let config = self.config.take().unwrap_or_default();
+        self.config = Some(config.with_discovery_server(&servers.to_string()));
+        self
+    }
+}
+
+impl Sender {
+    /// Registers the source with `servers` for this sender only, keeping the rest of any
+    /// per-instance configuration (Advanced SDK).
+    pub fn with_discovery_servers(mut self, servers: &DiscoveryServers) -> Self {
+        let config = self.config.take().unwrap_or_default();
+        self.config = Some(config.with_discovery_server(&servers.to_string()));
+        self
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{AudioFrame, AudioType, Error, VideoFrame};
+
+const BYTES_PER_SAMPLE: usize = 4;
+
+/// Keeps audio produced on an independent clock (e.g. a sound card) locked to the video timeline.
+///
+/// Report every video frame with [`AudioDriftCompensator::video_frame_sent`] and pass every
+/// audio frame through [`AudioDriftCompensator::process`] before sending it. The compensator
+/// compares the amount of audio sent against the elapsed video time and resamples the audio by
+/// a small ratio (at most `max_correction`, ±0.1% by default) so the two never drift apart.
+#[derive(Debug, Clone)]
+pub struct AudioDriftCompensator {
+    sample_rate: i32,
+    max_correction: f64,
+    gain: f64,
+    warm_up: f64,
+    video_time: f64,
+    audio_in_time: f64,
+    audio_out_time: f64,
+    ratio: f64,
+    position: f64,
+    previous: Vec<f32>,
+}
+
+impl AudioDriftCompensator {
+    pub fn new(sample_rate: i32) -> Self {
+        AudioDriftCompensator {
+            sample_rate,
+            max_correction: 0.001,
+            gain: 0.01,
+            warm_up: 1.0,
+            video_time: 0.0,
+            audio_in_time: 0.0,
+            audio_out_time: 0.0,
+            ratio: 1.0,
+            position: 0.0,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Largest resampling correction, as a fraction (0.001 = ±0.1%).
+    pub fn with_max_correction(mut self, max_correction: f64) -> Self {
+        self.max_correction = max_correction.abs();
+        self
+    }
+
+    /// Seconds of video that must have been sent before the clock ratio is estimated.
+    pub fn with_warm_up(mut self, seconds: f64) -> Self {
+        self.warm_up = seconds.max(0.0);
+        self
+    }
+
+    pub fn video_frame_sent(&mut self, frame: &VideoFrame) {
+        if frame.frame_rate_n > 0 && frame.frame_rate_d > 0 {
+            self.video_time += frame.frame_rate_d as f64 / frame.frame_rate_n as f64;
+        }
+    }
+
+    /// Current audio lead over video in seconds (negative when the audio lags behind).
+    pub fn drift(&self) -> f64 {
+        self.audio_out_time - self.video_time
+    }
+
+    /// Current resampling ratio (input samples consumed per output sample).
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    pub fn reset(&mut self) {
+        *self = AudioDriftCompensator::new(self.sample_rate)
+            .with_max_correction(self.max_correction)
+            .with_warm_up(self.warm_up);
+    }
+
+    pub fn process(&mut self, frame: &AudioFrame) -> Result<AudioFrame, Error> {
+        if frame.fourcc != AudioType::FLTP {
+            return Err(Error::InvalidFrame(
+                "Drift compensation requires FLTP audio".into(),
+            ));
+        }
+        if frame.sample_rate != self.sample_rate {
+            return Err(Error::SampleRateMismatch {
+                expected: self.sample_rate,
+                actual: frame.sample_rate,
+            });
+        }
+
+        let channels = frame.no_channels.max(0) as usize;
+        let samples = frame.no_samples.max(0) as usize;
+        let stride = frame.channel_stride_in_bytes.max(0) as usize;
+        if stride < samples * BYTES_PER_SAMPLE
+            || frame.data.len() < stride * channels.saturating_sub(1) + samples * BYTES_PER_SAMPLE
+        {
+            return Err(Error::InvalidFrame(format!(
+                "Audio data is too short for {} channels of {} samples",
+                channels, samples
+            )));
+        }
+        if self.previous.len() != channels {
+            self.previous = vec![0.0; channels];
+            self.position = 0.0;
+        }
+
+        self.audio_in_time += samples as f64 / self.sample_rate as f64;
+        self.update_ratio();
+
+        let input: Vec<Vec<f32>> = (0..channels)
+            .map(|ch| {
+                frame.data[ch * stride..ch * stride + samples * BYTES_PER_SAMPLE]
+                    .chunks_exact(BYTES_PER_SAMPLE)
+                    .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect()
+            })
+            .collect();
+
+        let mut positions = Vec::new();
+        let mut pos = self.position;
+        while pos < samples as f64 - 1.0 {
+            positions.push(pos);
+            pos += self.ratio;
+        }
+        let out_samples = positions.len();
+
+        let mut data = Vec::with_capacity(out_samples * channels * BYTES_PER_SAMPLE);
+        for (ch, channel) in input.iter().enumerate() {
+            let sample_at = |i: isize| {
+                if i < 0 {
+                    self.previous[ch]
+                } else {
+                    channel[i as usize]
+                }
+            };
+            for &p in &positions {
+                let index = p.floor();
+                let frac = (p - index) as f32;
+                let a = sample_at(index as isize);
+                let b = sample_at(index as isize + 1);
+                data.extend_from_slice(&(a + (b - a) * frac).to_ne_bytes());
+            }
+        }
+
+        if samples > 0 {
+            self.position = pos - samples as f64;
+            for (ch, channel) in input.iter().enumerate() {
+                self.previous[ch] = channel[samples - 1];
+            }
+        }
+        self.audio_out_time += out_samples as f64 / self.sample_rate as f64;
+
+        AudioFrame::with_data(
+            frame.sample_rate,
+            frame.no_channels,
+            out_samples as i32,
+            frame.timecode,
+            frame.fourcc,
+            data,
+            frame
+                .metadata
+                .as_ref()
+                .map(|m| m.to_string_lossy().into_owned()),
+            frame.timestamp,
+        )
+    }
+
+    fn update_ratio(&mut self) {
+        if self.video_time < self.warm_up {
+            return;
+        }
+        let clock_ratio = self.audio_in_time / self.video_time;
+        let target = clock_ratio + self.gain * self.drift();
+        self.ratio = target.clamp(1.0 - self.max_correction, 1.0 + self.max_correction);
+    }
+}
@@ -1,10 +1,26 @@
-use std::ffi::NulError;
+use std::{ffi::NulError, io, path::PathBuf, time::Duration};
 use thiserror::Error;
 
+use crate::{FourCCVideoType, FourCc, NdiVersion};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Failed to initialize the NDI runtime: {0}")]
     InitializationFailed(String),
+    #[error(
+        "The NDI runtime could not be loaded (tried {searched}); install it from {download_url}"
+    )]
+    RuntimeNotFound {
+        searched: String,
+        download_url: &'static str,
+    },
+    #[error("NDI runtime {found} is older than the required {required}")]
+    UnsupportedRuntime {
+        found: NdiVersion,
+        required: NdiVersion,
+    },
+    #[error("No SDK version found in {0:?}")]
+    InvalidVersion(String),
     #[error("Encountered a null pointer in function: {0}")]
     NullPointer(String),
     #[error("Invalid UTF-8 string in data: {0}")]
@@ -13,4 +29,248 @@ pub enum Error {
     InvalidCString(#[from] NulError),
     #[error("Failed to capture frame: {0}")]
     CaptureFailed(String),
+    #[error("The receiver returned an error frame")]
+    ErrorFrame,
+    #[error("Unknown frame type: {0}")]
+    UnknownFrameType(i64),
+    #[error("Timed out after {timeout_ms} ms waiting for {operation}")]
+    Timeout {
+        operation: &'static str,
+        timeout_ms: u32,
+    },
+    #[error(
+        "No source matching {name:?} found within {timeout:?} ({attempts} attempts, saw {seen:?})"
+    )]
+    SourceNotFound {
+        name: String,
+        timeout: Duration,
+        attempts: u32,
+        /// Names of the sources that were visible at the last attempt.
+        seen: Vec<String>,
+    },
+    #[error("Invalid frame: {0}")]
+    InvalidFrame(String),
+    #[error("Invalid resolution {xres}x{yres}")]
+    InvalidResolution { xres: i32, yres: i32 },
+    #[error("Invalid frame rate {numerator}/{denominator}")]
+    InvalidFrameRate { numerator: i32, denominator: i32 },
+    #[error(
+        "Line stride of {actual} bytes is smaller than the {expected} bytes required for {fourcc} at width {xres}"
+    )]
+    InvalidStride {
+        fourcc: FourCCVideoType,
+        xres: i32,
+        expected: i32,
+        actual: i32,
+    },
+    #[error(
+        "Frame data is {actual} bytes but a {xres}x{yres} {fourcc} frame with stride {line_stride} needs {expected}"
+    )]
+    InvalidDataSize {
+        fourcc: FourCCVideoType,
+        xres: i32,
+        yres: i32,
+        line_stride: i32,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("{fourcc} frame data must be aligned to {required} bytes, in address and line stride")]
+    MisalignedBuffer {
+        fourcc: FourCCVideoType,
+        required: usize,
+    },
+    #[error(
+        "Invalid audio layout: {no_channels} channels of {no_samples} samples at {sample_rate} Hz"
+    )]
+    InvalidAudioLayout {
+        no_channels: i32,
+        no_samples: i32,
+        sample_rate: i32,
+    },
+    #[error(
+        "Channel stride of {actual} bytes is smaller than the {expected} bytes of {no_samples} samples"
+    )]
+    InvalidChannelStride {
+        no_samples: i32,
+        expected: usize,
+        actual: usize,
+    },
+    #[error(
+        "Audio data is {actual} bytes but {no_channels} channels of {no_samples} samples with channel stride {channel_stride} need {expected}"
+    )]
+    InvalidAudioDataSize {
+        no_channels: i32,
+        no_samples: i32,
+        channel_stride: i32,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Channel {channel} is out of range for a {channels}-channel frame")]
+    ChannelOutOfRange { channel: usize, channels: usize },
+    #[error("Expected {expected} channels but the frame has {actual}")]
+    ChannelCountMismatch { expected: i32, actual: i32 },
+    #[error("Expected {expected} {unit} but got {actual}")]
+    BufferSizeMismatch {
+        /// What the buffer counts, e.g. `"samples"`.
+        unit: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Compressed frame has no encoded data")]
+    EmptyCompressedFrame,
+    #[error("Compressed keyframes must carry the codec configuration as extra data")]
+    MissingCodecConfig,
+    #[error("Frame of {size} bytes is larger than the {limit} bytes the SDK accepts")]
+    FrameTooLarge { size: usize, limit: usize },
+    #[error("Region {width}x{height} at ({x}, {y}) does not fit in a {xres}x{yres} frame")]
+    InvalidRegion {
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        xres: i32,
+        yres: i32,
+    },
+    #[error("Cannot {operation} {fourcc} frames")]
+    UnsupportedFormat {
+        fourcc: FourCCVideoType,
+        operation: &'static str,
+    },
+    #[error("Unknown FourCC {0}")]
+    UnknownFourCc(FourCc),
+    #[error("Expected a sample rate of {expected} but the frame has {actual}")]
+    SampleRateMismatch { expected: i32, actual: i32 },
+    #[error("Invalid discovery server {address:?}: {reason}")]
+    InvalidDiscoveryServer {
+        address: String,
+        reason: &'static str,
+    },
+    #[error("None of the discovery servers {0} could be reached")]
+    DiscoveryServerUnreachable(String),
+    #[error("Invalid group name {0:?}: names must be non-empty and contain no commas")]
+    InvalidGroupName(String),
+    #[error("Failed to encode image: {0}")]
+    ImageEncoding(String),
+    #[error("GStreamer error: {0}")]
+    GStreamer(String),
+    #[error("PTZ command failed: {0}")]
+    PtzCommandFailed(String),
+    #[error("KVM command failed: {0}")]
+    KvmCommandFailed(String),
+    #[error("An async video frame is already in flight on this sender")]
+    AsyncVideoInFlight,
+    #[error("Invalid timecode: {0}")]
+    InvalidTimecode(String),
+    #[error("Failed to read or write JSON: {0}")]
+    Serialization(String),
+    #[error("Statistics in {path:?} are for source {found:?}, not {expected:?}")]
+    StatsSourceMismatch {
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl Error {
+    /// Whether repeating the same call may succeed, e.g. after waiting for the network or source.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::CaptureFailed(_)
+            | Error::ErrorFrame
+            | Error::Timeout { .. }
+            | Error::SourceNotFound { .. }
+            | Error::AsyncVideoInFlight
+            | Error::DiscoveryServerUnreachable(_)
+            | Error::PtzCommandFailed(_)
+            | Error::KvmCommandFailed(_) => true,
+            Error::Io(e) => matches!(
+                e.kind(),
+                io::ErrorKind::Interrupted
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::WouldBlock
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether the runtime or instance that produced the error should be torn down rather than
+    /// used again.
+    ///
+    /// Errors that are neither retryable nor fatal describe invalid input, such as a frame with
+    /// the wrong stride, and fail the same way until the caller changes what it passes.
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Error::InitializationFailed(_)
+                | Error::RuntimeNotFound { .. }
+                | Error::UnsupportedRuntime { .. }
+                | Error::NullPointer(_)
+                | Error::UnknownFrameType(_)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_input_is_neither_retryable_nor_fatal() {
+        let errors = [
+            Error::InvalidAudioLayout {
+                no_channels: 0,
+                no_samples: 0,
+                sample_rate: 48_000,
+            },
+            Error::InvalidChannelStride {
+                no_samples: 4,
+                expected: 16,
+                actual: 8,
+            },
+            Error::InvalidAudioDataSize {
+                no_channels: 2,
+                no_samples: 4,
+                channel_stride: 16,
+                expected: 32,
+                actual: 16,
+            },
+            Error::ChannelOutOfRange {
+                channel: 2,
+                channels: 2,
+            },
+            Error::ChannelCountMismatch {
+                expected: 2,
+                actual: 1,
+            },
+            Error::BufferSizeMismatch {
+                unit: "samples",
+                expected: 4,
+                actual: 3,
+            },
+            Error::EmptyCompressedFrame,
+            Error::MissingCodecConfig,
+            Error::FrameTooLarge {
+                size: 1 << 32,
+                limit: i32::MAX as usize,
+            },
+        ];
+        for error in errors {
+            assert!(!error.is_retryable(), "{error}");
+            assert!(!error.is_fatal(), "{error}");
+        }
+    }
+
+    #[test]
+    fn buffer_size_mismatch_names_its_unit() {
+        let error = Error::BufferSizeMismatch {
+            unit: "samples",
+            expected: 4,
+            actual: 3,
+        };
+        assert_eq!(error.to_string(), "Expected 4 samples but got 3");
+    }
 }
@@ -0,0 +1,256 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Error, FieldMode, Receiver, Recv, RecvBandwidth, RecvColorFormat, Send, Source, VideoFrame, NDI,
+};
+
+const DEFAULT_MAX_FRAME_GAP: Duration = Duration::from_secs(2);
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What makes a monitored source healthy for a [`FailoverManager`].
+///
+/// A source is healthy while it is connected and has delivered a good video frame within
+/// `max_frame_gap`. Every frame is good unless one of the optional checks rejects it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthCheck {
+    pub max_frame_gap: Duration,
+    /// Frames that are black at this threshold, as judged by [`VideoFrame::is_black`], don't
+    /// count.
+    pub black_threshold: Option<u8>,
+    /// Frames whose [`VideoFrame::difference_score`] from the last good frame is below this
+    /// don't count, so a frozen picture turns the source unhealthy.
+    pub freeze_threshold: Option<f32>,
+}
+
+impl Default for HealthCheck {
+    fn default() -> Self {
+        HealthCheck {
+            max_frame_gap: DEFAULT_MAX_FRAME_GAP,
+            black_threshold: None,
+            freeze_threshold: None,
+        }
+    }
+}
+
+/// A change noticed by a [`FailoverManager`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailoverEvent {
+    /// The upstream source feeding the sender failed its health check.
+    UpstreamLost(Source),
+    UpstreamRestored(Source),
+    BackupHealthChanged {
+        source: Source,
+        healthy: bool,
+    },
+    /// The sender's failover source was switched to `to`.
+    FailoverChanged {
+        from: Option<Source>,
+        to: Source,
+    },
+    /// No backup is healthy; the failover source is left as it was.
+    NoHealthyBackup,
+}
+
+type EventListener = Box<dyn FnMut(&FailoverEvent)>;
+
+/// Keeps a sender's failover source ([`Send::set_failover`]) pointed at a healthy backup.
+///
+/// Each backup, in priority order, is watched by a low-bandwidth receiver and checked against
+/// the [`HealthCheck`]; the failover source is moved to the first healthy one whenever that
+/// changes, so receivers of the sender fall over to something that is actually running.
+/// The sender's own upstream source can be watched too: the SDK only fails receivers over
+/// once the sender disappears, so losing the upstream is reported as an event for the
+/// application to act on, e.g. by stopping the sender.
+///
+/// Call [`FailoverManager::poll`] regularly, or [`FailoverManager::run`] on a dedicated
+/// thread.
+pub struct FailoverManager<'a> {
+    ndi: &'a NDI,
+    send: &'a Send<'a>,
+    upstream: Option<Monitor<'a>>,
+    backups: Vec<Monitor<'a>>,
+    check: HealthCheck,
+    current: Option<usize>,
+    no_backup_reported: bool,
+    listener: Option<EventListener>,
+}
+
+/// A receiver watching one source's health.
+struct Monitor<'a> {
+    recv: Recv<'a>,
+    last_good: Option<Instant>,
+    /// The last good frame, for freeze detection.
+    previous: Option<VideoFrame>,
+    healthy: bool,
+}
+
+impl<'a> FailoverManager<'a> {
+    pub fn new(ndi: &'a NDI, send: &'a Send<'a>) -> Self {
+        FailoverManager {
+            ndi,
+            send,
+            upstream: None,
+            backups: Vec::new(),
+            check: HealthCheck::default(),
+            current: None,
+            no_backup_reported: false,
+            listener: None,
+        }
+    }
+
+    /// Watches the source the sender is relaying, reporting when it fails or recovers.
+    pub fn with_upstream(mut self, source: Source) -> Result<Self, Error> {
+        self.upstream = Some(Monitor::new(self.ndi, source)?);
+        Ok(self)
+    }
+
+    /// Adds a backup; earlier backups are preferred.
+    pub fn with_backup(mut self, source: Source) -> Result<Self, Error> {
+        self.backups.push(Monitor::new(self.ndi, source)?);
+        Ok(self)
+    }
+
+    pub fn with_health_check(mut self, check: HealthCheck) -> Self {
+        self.check = check;
+        self
+    }
+
+    /// Calls `listener` with every event noticed by [`FailoverManager::poll`].
+    pub fn on_event(mut self, listener: impl FnMut(&FailoverEvent) + 'static) -> Self {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    /// The backup the sender currently fails over to, once one has been chosen.
+    pub fn current_failover(&self) -> Option<&Source> {
+        self.current.map(|i| self.backups[i].recv.source())
+    }
+
+    /// Whether the upstream source is healthy; `None` if it isn't watched.
+    pub fn upstream_healthy(&self) -> Option<bool> {
+        self.upstream.as_ref().map(|m| m.healthy)
+    }
+
+    /// Checks every watched source and moves the failover source if the best healthy backup
+    /// changed. Returns the events noticed, which also go to the listener.
+    pub fn poll(&mut self) -> Result<Vec<FailoverEvent>, Error> {
+        let now = Instant::now();
+        let mut events = Vec::new();
+
+        if let Some(upstream) = self.upstream.as_mut() {
+            if let Some(healthy) = upstream.update(&self.check, now)? {
+                let source = upstream.recv.source().clone();
+                if healthy {
+                    ndi_info!(source = %source.name, "failover upstream restored");
+                    events.push(FailoverEvent::UpstreamRestored(source));
+                } else {
+                    ndi_warn!(source = %source.name, "failover upstream lost");
+                    events.push(FailoverEvent::UpstreamLost(source));
+                }
+            }
+        }
+
+        for backup in &mut self.backups {
+            if let Some(healthy) = backup.update(&self.check, now)? {
+                let source = backup.recv.source().clone();
+                ndi_debug!(source = %source.name, healthy, "failover backup health changed");
+                events.push(FailoverEvent::BackupHealthChanged { source, healthy });
+            }
+        }
+
+        match self.backups.iter().position(|b| b.healthy) {
+            Some(best) if self.current != Some(best) => {
+                let to = self.backups[best].recv.source().clone();
+                self.send.set_failover(&to)?;
+                ndi_info!(source = %to.name, "failover source changed");
+                let from = self.current_failover().cloned();
+                self.current = Some(best);
+                self.no_backup_reported = false;
+                events.push(FailoverEvent::FailoverChanged { from, to });
+            }
+            Some(_) => {}
+            None if !self.backups.is_empty() && !self.no_backup_reported => {
+                ndi_warn!("no healthy failover backup");
+                self.no_backup_reported = true;
+                events.push(FailoverEvent::NoHealthyBackup);
+            }
+            None => {}
+        }
+
+        if let Some(listener) = self.listener.as_mut() {
+            for event in &events {
+                listener(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Polls every 100 ms until `stopped` is set or polling fails.
+    pub fn run(&mut self, stopped: &AtomicBool) -> Result<(), Error> {
+        while !stopped.load(Ordering::Relaxed) {
+            self.poll()?;
+            thread::sleep(DEFAULT_POLL_INTERVAL);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Monitor<'a> {
+    fn new(ndi: &'a NDI, source: Source) -> Result<Self, Error> {
+        let settings = Receiver::new(
+            source,
+            RecvColorFormat::UYVY_BGRA,
+            RecvBandwidth::Lowest,
+            FieldMode::Progressive,
+            Some("failover monitor".into()),
+        );
+        Ok(Monitor {
+            recv: Recv::new(ndi, settings)?,
+            last_good: None,
+            previous: None,
+            healthy: false,
+        })
+    }
+
+    /// Takes the newest frame, if any, and re-evaluates health; returns the new health if it
+    /// changed.
+    fn update(&mut self, check: &HealthCheck, now: Instant) -> Result<Option<bool>, Error> {
+        match self.recv.capture_latest_video_ref(0) {
+            Ok(Some(frame)) => {
+                let black = match check.black_threshold {
+                    Some(threshold) => frame.is_black(threshold)?,
+                    None => false,
+                };
+                let frozen = match (check.freeze_threshold, &self.previous) {
+                    (Some(threshold), Some(previous)) => frame
+                        .difference_score(previous)
+                        .is_ok_and(|d| d < threshold),
+                    _ => false,
+                };
+                if !black && !frozen {
+                    self.last_good = Some(now);
+                    if check.freeze_threshold.is_some() {
+                        self.previous = Some(frame.to_owned());
+                    }
+                }
+            }
+            Ok(None) => {}
+            // A dropped connection shows up in the connection count below.
+            Err(e) if e.is_retryable() => {}
+            Err(e) => return Err(e),
+        }
+        let healthy = self.recv.connections() > 0
+            && self
+                .last_good
+                .is_some_and(|at| now.saturating_duration_since(at) <= check.max_frame_gap);
+        if healthy == self.healthy {
+            return Ok(None);
+        }
+        self.healthy = healthy;
+        Ok(Some(healthy))
+    }
+}
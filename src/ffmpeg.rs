@@ -0,0 +1,251 @@
+use ffmpeg_next::{
+    format::{sample, Pixel, Sample},
+    frame, ChannelLayout,
+};
+
+use crate::{
+    copy_rows, AudioFrame, AudioType, Error, FourCCVideoType, FrameFormatType, VideoFrame,
+    VideoFrameRef,
+};
+
+/// FFmpeg gives planar audio at most this many directly addressable planes.
+const MAX_PLANAR_CHANNELS: usize = 8;
+
+/// The FFmpeg pixel format with the same memory layout as `fourcc`, if there is one.
+///
+/// YV12 maps to `YUV420P` with its chroma planes swapped during conversion.
+pub fn pixel_format(fourcc: FourCCVideoType) -> Option<Pixel> {
+    match fourcc {
+        FourCCVideoType::UYVY => Some(Pixel::UYVY422),
+        FourCCVideoType::BGRA => Some(Pixel::BGRA),
+        FourCCVideoType::BGRX => Some(Pixel::BGRZ),
+        FourCCVideoType::RGBA => Some(Pixel::RGBA),
+        FourCCVideoType::RGBX => Some(Pixel::RGBZ),
+        FourCCVideoType::NV12 => Some(Pixel::NV12),
+        FourCCVideoType::I420 | FourCCVideoType::YV12 => Some(Pixel::YUV420P),
+        _ => None,
+    }
+}
+
+/// The NDI video type with the same memory layout as `pixel`, if there is one.
+pub fn fourcc_for_pixel(pixel: Pixel) -> Option<FourCCVideoType> {
+    match pixel {
+        Pixel::UYVY422 => Some(FourCCVideoType::UYVY),
+        Pixel::BGRA => Some(FourCCVideoType::BGRA),
+        Pixel::BGRZ => Some(FourCCVideoType::BGRX),
+        Pixel::RGBA => Some(FourCCVideoType::RGBA),
+        Pixel::RGBZ => Some(FourCCVideoType::RGBX),
+        Pixel::NV12 => Some(FourCCVideoType::NV12),
+        Pixel::YUV420P => Some(FourCCVideoType::I420),
+        _ => None,
+    }
+}
+
+fn to_ffmpeg_video(
+    xres: i32,
+    yres: i32,
+    fourcc: FourCCVideoType,
+    line_stride: i32,
+    data: &[u8],
+    timecode: i64,
+) -> Result<frame::Video, Error> {
+    let pixel = pixel_format(fourcc).ok_or(Error::UnsupportedFormat {
+        fourcc,
+        operation: "convert to FFmpeg",
+    })?;
+    let required = fourcc.check_layout(xres, yres, line_stride)?;
+    if data.len() < required {
+        return Err(Error::InvalidDataSize {
+            fourcc,
+            xres,
+            yres,
+            line_stride,
+            expected: required,
+            actual: data.len(),
+        });
+    }
+
+    let mut video = frame::Video::new(pixel, xres as u32, yres as u32);
+    for (index, plane) in fourcc.planes(xres, yres, line_stride).iter().enumerate() {
+        let dst_stride = video.stride(index);
+        copy_rows(
+            &data[plane.offset..],
+            plane.stride,
+            video.data_mut(index),
+            dst_stride,
+            plane.row_bytes,
+            plane.rows,
+        );
+    }
+    video.set_pts(Some(timecode));
+    Ok(video)
+}
+
+/// Copies the frame into a newly allocated FFmpeg frame, carrying the timecode (in 100 ns
+/// units) as its `pts`.
+impl TryFrom<&VideoFrame> for frame::Video {
+    type Error = Error;
+
+    fn try_from(video: &VideoFrame) -> Result<Self, Error> {
+        to_ffmpeg_video(
+            video.xres,
+            video.yres,
+            video.fourcc,
+            unsafe { video.line_stride_or_size.line_stride_in_bytes },
+            &video.data,
+            video.timecode,
+        )
+    }
+}
+
+/// Copies a received frame straight from the SDK's buffer into a newly allocated FFmpeg frame.
+impl TryFrom<&VideoFrameRef<'_>> for frame::Video {
+    type Error = Error;
+
+    fn try_from(video: &VideoFrameRef<'_>) -> Result<Self, Error> {
+        let fourcc = video.fourcc();
+        to_ffmpeg_video(
+            video.xres(),
+            video.yres(),
+            fourcc,
+            video
+                .line_stride()
+                .unwrap_or_else(|| fourcc.line_stride(video.xres())),
+            video.data(),
+            video.timecode(),
+        )
+    }
+}
+
+/// Copies an FFmpeg frame into a tightly packed frame, taking the timecode from its `pts`.
+///
+/// The frame rate is not part of an FFmpeg frame and defaults to 60 fps.
+impl TryFrom<&frame::Video> for VideoFrame {
+    type Error = Error;
+
+    fn try_from(video: &frame::Video) -> Result<Self, Error> {
+        let format = video.format();
+        let fourcc = fourcc_for_pixel(format).ok_or_else(|| {
+            Error::InvalidFrame(format!(
+                "FFmpeg pixel format {:?} has no NDI equivalent; convert it with swscale first",
+                format
+            ))
+        })?;
+        let (xres, yres) = (video.width() as i32, video.height() as i32);
+        let sample_aspect = video.aspect_ratio();
+        let mut aspect_ratio = xres as f32 / yres.max(1) as f32;
+        if sample_aspect.numerator() > 0 && sample_aspect.denominator() > 0 {
+            aspect_ratio *= sample_aspect.numerator() as f32 / sample_aspect.denominator() as f32;
+        }
+        let frame_format = if video.is_interlaced() {
+            FrameFormatType::Interlaced
+        } else {
+            FrameFormatType::Progressive
+        };
+
+        let mut out = VideoFrame::new(xres, yres, fourcc, 60, 1, aspect_ratio, frame_format);
+        out.validate()?;
+        let stride = unsafe { out.line_stride_or_size.line_stride_in_bytes };
+        for (index, plane) in fourcc.planes(xres, yres, stride).iter().enumerate() {
+            copy_rows(
+                video.data(index),
+                video.stride(index),
+                &mut out.data[plane.offset..],
+                plane.stride,
+                plane.row_bytes,
+                plane.rows,
+            );
+        }
+        out.timecode = video.pts().unwrap_or(0);
+        Ok(out)
+    }
+}
+
+/// Copies planar float audio into an FFmpeg `FLTP` frame, carrying the timecode as its `pts`.
+impl TryFrom<&AudioFrame> for frame::Audio {
+    type Error = Error;
+
+    fn try_from(audio: &AudioFrame) -> Result<Self, Error> {
+        if audio.fourcc != AudioType::FLTP {
+            return Err(Error::InvalidFrame(format!(
+                "Only FLTP audio can be converted, not {:?}",
+                audio.fourcc
+            )));
+        }
+        let channels = audio.no_channels.max(0) as usize;
+        let samples = audio.no_samples.max(0) as usize;
+        let stride = audio.channel_stride_in_bytes.max(0) as usize;
+        if channels == 0 || channels > MAX_PLANAR_CHANNELS {
+            return Err(Error::InvalidFrame(format!(
+                "Cannot convert {} channels; FFmpeg planar frames hold 1 to {}",
+                channels, MAX_PLANAR_CHANNELS
+            )));
+        }
+        audio.validate()?;
+
+        let mut out = frame::Audio::new(
+            Sample::F32(sample::Type::Planar),
+            samples,
+            ChannelLayout::default(channels as i32),
+        );
+        out.set_rate(audio.sample_rate as u32);
+        for channel in 0..channels {
+            let bytes = &audio.data[channel * stride..channel * stride + samples * 4];
+            for (dst, src) in out
+                .plane_mut::<f32>(channel)
+                .iter_mut()
+                .zip(bytes.chunks_exact(4))
+            {
+                *dst = f32::from_ne_bytes([src[0], src[1], src[2], src[3]]);
+            }
+        }
+        out.set_pts(Some(audio.timecode));
+        Ok(out)
+    }
+}
+
+/// Copies planar or interleaved float audio into an FLTP frame, taking the timecode from its
+/// `pts`. Other sample formats need resampling to `FLTP` first.
+impl TryFrom<&frame::Audio> for AudioFrame {
+    type Error = Error;
+
+    fn try_from(audio: &frame::Audio) -> Result<Self, Error> {
+        let channels = audio.channels() as usize;
+        let samples = audio.samples();
+        let mut data = Vec::with_capacity(channels * samples * 4);
+        match audio.format() {
+            Sample::F32(sample::Type::Planar) if channels <= MAX_PLANAR_CHANNELS => {
+                for channel in 0..channels {
+                    for value in audio.plane::<f32>(channel) {
+                        data.extend_from_slice(&value.to_ne_bytes());
+                    }
+                }
+            }
+            Sample::F32(sample::Type::Packed) => {
+                let interleaved = &audio.data(0)[..channels * samples * 4];
+                for channel in 0..channels {
+                    for frame in interleaved.chunks_exact(channels * 4) {
+                        data.extend_from_slice(&frame[channel * 4..channel * 4 + 4]);
+                    }
+                }
+            }
+            format => {
+                return Err(Error::InvalidFrame(format!(
+                    "Cannot convert {:?} audio with {} channels; resample it to FLTP first",
+                    format, channels
+                )))
+            }
+        }
+
+        AudioFrame::with_data(
+            audio.rate() as i32,
+            channels as i32,
+            samples as i32,
+            audio.pts().unwrap_or(0),
+            AudioType::FLTP,
+            data,
+            None,
+            0,
+        )
+    }
+}
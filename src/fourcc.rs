@@ -0,0 +1,185 @@
+use std::fmt;
+
+use crate::{ndi_lib::*, AudioType, Error, FourCCVideoType};
+
+/// A four-character code as the SDK identifies video, audio and compressed formats by,
+/// e.g. `BGRA`, `FLTP` or `H264`.
+///
+/// Unlike [`FourCCVideoType`] and [`AudioType`], which can only name the formats the crate
+/// knows how to lay out, this holds any code a source sends, and displays it as its four
+/// characters so unknown formats show up in logs and errors as something recognisable.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FourCc(u32);
+
+impl FourCc {
+    pub const fn new(code: &[u8; 4]) -> Self {
+        FourCc(u32::from_le_bytes(*code))
+    }
+
+    pub const fn from_u32(value: u32) -> Self {
+        FourCc(value)
+    }
+
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// The four characters, first to last.
+    pub const fn to_bytes(self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+
+    /// Whether every character is printable ASCII, as in every code NDI defines.
+    pub fn is_printable(self) -> bool {
+        self.to_bytes()
+            .iter()
+            .all(|b| b.is_ascii_graphic() || *b == b' ')
+    }
+}
+
+impl fmt::Display for FourCc {
+    /// The four characters, or the hex value if any isn't printable.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_printable() {
+            self.to_bytes()
+                .iter()
+                .try_for_each(|&b| fmt::Write::write_char(f, b as char))
+        } else {
+            write!(f, "{:#010x}", self.0)
+        }
+    }
+}
+
+impl fmt::Debug for FourCc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FourCc({})", self)
+    }
+}
+
+impl From<u32> for FourCc {
+    fn from(value: u32) -> Self {
+        FourCc(value)
+    }
+}
+
+impl From<FourCc> for u32 {
+    fn from(fourcc: FourCc) -> Self {
+        fourcc.0
+    }
+}
+
+impl From<FourCCVideoType> for FourCc {
+    fn from(fourcc: FourCCVideoType) -> Self {
+        FourCc(NDIlib_FourCC_video_type_e::from(fourcc))
+    }
+}
+
+impl TryFrom<FourCc> for FourCCVideoType {
+    type Error = Error;
+
+    /// Fails with [`Error::UnknownFourCc`] for codes that aren't uncompressed video formats.
+    fn try_from(fourcc: FourCc) -> Result<Self, Error> {
+        match FourCCVideoType::from(fourcc.0) {
+            FourCCVideoType::Max => Err(Error::UnknownFourCc(fourcc)),
+            video_type => Ok(video_type),
+        }
+    }
+}
+
+impl From<AudioType> for FourCc {
+    fn from(audio_type: AudioType) -> Self {
+        FourCc(u32::from(audio_type))
+    }
+}
+
+impl TryFrom<FourCc> for AudioType {
+    type Error = Error;
+
+    fn try_from(fourcc: FourCc) -> Result<Self, Error> {
+        match AudioType::from(fourcc.0) {
+            AudioType::Max => Err(Error::UnknownFourCc(fourcc)),
+            audio_type => Ok(audio_type),
+        }
+    }
+}
+
+impl FourCCVideoType {
+    pub fn fourcc(self) -> FourCc {
+        self.into()
+    }
+}
+
+impl fmt::Display for FourCCVideoType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FourCCVideoType::Max => f.write_str("unknown format"),
+            _ => self.fourcc().fmt(f),
+        }
+    }
+}
+
+impl AudioType {
+    pub fn fourcc(self) -> FourCc {
+        self.into()
+    }
+}
+
+impl fmt::Display for AudioType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioType::Max => f.write_str("unknown format"),
+            _ => self.fourcc().fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_are_stored_first_byte_lowest() {
+        let fourcc = FourCc::new(b"BGRA");
+        assert_eq!(fourcc.to_bytes(), *b"BGRA");
+        assert_eq!(fourcc.as_u32(), u32::from_le_bytes(*b"BGRA"));
+        assert_eq!(FourCc::from_u32(fourcc.as_u32()), fourcc);
+    }
+
+    #[test]
+    fn displays_as_characters_or_hex() {
+        assert_eq!(FourCc::new(b"H264").to_string(), "H264");
+        assert_eq!(format!("{:?}", FourCc::new(b"H264")), "FourCc(H264)");
+        assert_eq!(FourCc::from_u32(0x0102_0304).to_string(), "0x01020304");
+        assert!(!FourCc::from_u32(0).is_printable());
+    }
+
+    #[test]
+    fn converts_to_and_from_the_known_formats() {
+        assert_eq!(FourCCVideoType::BGRA.fourcc(), FourCc::new(b"BGRA"));
+        assert_eq!(
+            FourCCVideoType::try_from(FourCc::new(b"UYVY")).unwrap(),
+            FourCCVideoType::UYVY
+        );
+        assert_eq!(AudioType::FLTP.fourcc(), FourCc::new(b"FLTp"));
+        assert_eq!(
+            AudioType::try_from(FourCc::new(b"FLTp")).unwrap(),
+            AudioType::FLTP
+        );
+    }
+
+    #[test]
+    fn unknown_codes_are_reported_as_such() {
+        let h264 = FourCc::new(b"H264");
+        assert!(matches!(
+            FourCCVideoType::try_from(h264),
+            Err(Error::UnknownFourCc(code)) if code == h264
+        ));
+        assert!(matches!(
+            AudioType::try_from(h264),
+            Err(Error::UnknownFourCc(_))
+        ));
+        assert_eq!(FourCCVideoType::Max.to_string(), "unknown format");
+        assert_eq!(FourCCVideoType::UYVY.to_string(), "UYVY");
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{CaptureThreadConfig, Error, FrameType, Receiver, Recv, NDI};
+
+/// Longest the capture thread blocks in the SDK, or on a full channel, before checking
+/// whether the consumer is gone.
+const POLL_TIMEOUT_MS: u32 = 100;
+const RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// What the capture thread of [`Receiver::into_channel`] does with a frame when the channel
+/// is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropPolicy {
+    /// Waits for the consumer to make room. Nothing is lost here, but frames queue up in the
+    /// SDK instead and it drops them once its own buffers fill.
+    #[default]
+    Block,
+    /// Discards the new frame, keeping the ones already buffered.
+    DropNewest,
+    /// Discards the oldest buffered frame to make room, so the consumer always sees the most
+    /// recent frames; usually the right choice for live monitoring.
+    DropOldest,
+}
+
+/// The consuming end of [`Receiver::into_channel`].
+///
+/// Frames arrive in capture order; timeouts are not forwarded, status changes are. Dropping
+/// this stops the capture thread.
+pub struct FrameReceiver {
+    shared: Arc<ChannelShared>,
+}
+
+struct ChannelShared {
+    queue: Mutex<VecDeque<FrameType>>,
+    /// Signalled when a frame is queued or the capture thread ends.
+    ready: Condvar,
+    /// Signalled when a frame is taken or the consumer goes away.
+    space: Condvar,
+    capacity: usize,
+    policy: DropPolicy,
+    /// The consumer is gone.
+    closed: AtomicBool,
+    /// The capture thread has ended.
+    finished: AtomicBool,
+    dropped: AtomicU64,
+}
+
+/// The receiver owned by the capture thread, with the runtime reference keeping it valid.
+struct OwnedRecv {
+    // Declared first so it is destroyed before the runtime reference below is released.
+    recv: Recv<'static>,
+    _ndi: NDI,
+}
+
+// The SDK allows capturing from any thread, and only the capture thread uses the receiver.
+unsafe impl std::marker::Send for OwnedRecv {}
+
+impl Receiver {
+    /// Connects and captures on a background thread into a channel holding up to `capacity`
+    /// frames, applying `policy` when the consumer falls behind.
+    ///
+    /// This decouples capture from processing: the SDK is always drained at the source's
+    /// rate however long each frame takes to handle. The thread runs until the
+    /// [`FrameReceiver`] is dropped or capture fails with an error that isn't retryable,
+    /// which the join handle returns. A `capacity` of zero is treated as one.
+    pub fn into_channel(
+        self,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> Result<(FrameReceiver, JoinHandle<Result<(), Error>>), Error> {
+        self.into_channel_with_thread(capacity, policy, CaptureThreadConfig::default())
+    }
+
+    /// As [`Receiver::into_channel`], running the capture thread with `thread`'s priority and
+    /// pinning.
+    pub fn into_channel_with_thread(
+        self,
+        capacity: usize,
+        policy: DropPolicy,
+        thread: CaptureThreadConfig,
+    ) -> Result<(FrameReceiver, JoinHandle<Result<(), Error>>), Error> {
+        let ndi = NDI::new()?;
+        // SAFETY: `_ndi` is dropped after `recv`.
+        let recv = unsafe { Recv::new_detached(self)? };
+        let owned = OwnedRecv { recv, _ndi: ndi };
+        let capacity = capacity.max(1);
+        let shared = Arc::new(ChannelShared {
+            queue: Mutex::new(VecDeque::with_capacity(capacity)),
+            ready: Condvar::new(),
+            space: Condvar::new(),
+            capacity,
+            policy,
+            closed: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+        });
+        let thread_shared = Arc::clone(&shared);
+        let thread = thread::spawn(move || {
+            thread.apply_or_warn();
+            let result = capture_loop(owned, &thread_shared);
+            thread_shared.finished.store(true, Ordering::Release);
+            // Take the lock so a consumer between checking `finished` and waiting is woken.
+            drop(thread_shared.queue.lock());
+            thread_shared.ready.notify_all();
+            result
+        });
+        Ok((FrameReceiver { shared }, thread))
+    }
+}
+
+impl FrameReceiver {
+    /// Waits for the next frame; `None` once the capture thread has ended and the channel is
+    /// drained.
+    pub fn recv(&self) -> Option<FrameType> {
+        self.take(None)
+    }
+
+    /// Waits up to `timeout` for the next frame.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<FrameType> {
+        self.take(Some(Instant::now() + timeout))
+    }
+
+    /// The next buffered frame, if one is waiting.
+    pub fn try_recv(&self) -> Option<FrameType> {
+        let frame = self.shared.queue.lock().ok()?.pop_front();
+        if frame.is_some() {
+            self.shared.space.notify_one();
+        }
+        frame
+    }
+
+    /// Frames buffered right now.
+    pub fn len(&self) -> usize {
+        self.shared.queue.lock().map(|q| q.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    pub fn policy(&self) -> DropPolicy {
+        self.shared.policy
+    }
+
+    /// Frames discarded so far because the channel was full.
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Whether the capture thread has ended, e.g. after a capture error.
+    pub fn is_finished(&self) -> bool {
+        self.shared.finished.load(Ordering::Acquire)
+    }
+
+    fn take(&self, deadline: Option<Instant>) -> Option<FrameType> {
+        let mut queue = self.shared.queue.lock().ok()?;
+        loop {
+            if let Some(frame) = queue.pop_front() {
+                self.shared.space.notify_one();
+                return Some(frame);
+            }
+            if self.shared.finished.load(Ordering::Acquire) {
+                return None;
+            }
+            queue = match deadline {
+                None => self.shared.ready.wait(queue).ok()?,
+                Some(deadline) => {
+                    let remaining = deadline.checked_duration_since(Instant::now())?;
+                    self.shared.ready.wait_timeout(queue, remaining).ok()?.0
+                }
+            };
+        }
+    }
+}
+
+impl Iterator for FrameReceiver {
+    type Item = FrameType;
+
+    fn next(&mut self) -> Option<FrameType> {
+        self.recv()
+    }
+}
+
+impl Drop for FrameReceiver {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Release);
+        drop(self.shared.queue.lock());
+        self.shared.space.notify_all();
+    }
+}
+
+fn capture_loop(mut owned: OwnedRecv, shared: &ChannelShared) -> Result<(), Error> {
+    while !shared.closed.load(Ordering::Acquire) {
+        let frame = match owned.recv.capture(POLL_TIMEOUT_MS) {
+            Ok(FrameType::None) => continue,
+            Ok(frame) => frame,
+            Err(e) if e.is_retryable() => {
+                thread::sleep(RETRY_DELAY);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if !push(shared, frame) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Queues `frame` according to the channel's policy; returns `false` once the consumer is
+/// gone.
+fn push(shared: &ChannelShared, frame: FrameType) -> bool {
+    let Ok(mut queue) = shared.queue.lock() else {
+        return false;
+    };
+    loop {
+        if shared.closed.load(Ordering::Acquire) {
+            return false;
+        }
+        if queue.len() < shared.capacity {
+            break;
+        }
+        match shared.policy {
+            DropPolicy::Block => {
+                let timeout = Duration::from_millis(POLL_TIMEOUT_MS.into());
+                queue = match shared.space.wait_timeout(queue, timeout) {
+                    Ok((queue, _)) => queue,
+                    Err(_) => return false,
+                };
+            }
+            DropPolicy::DropNewest => {
+                shared.dropped.fetch_add(1, Ordering::Relaxed);
+                ndi_trace!("channel full, dropping newest frame");
+                return true;
+            }
+            DropPolicy::DropOldest => {
+                queue.pop_front();
+                shared.dropped.fetch_add(1, Ordering::Relaxed);
+                ndi_trace!("channel full, dropping oldest frame");
+                break;
+            }
+        }
+    }
+    queue.push_back(frame);
+    drop(queue);
+    shared.ready.notify_one();
+    true
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use crate::{AudioFrame, Error, MetadataFrame, Recv, Send, VideoFrame};
+
+/// Something frames can be captured from: a connected [`Recv`], a
+/// [`FrameSync`](crate::FrameSync), or a test double such as `testing::MockReceiver` (with the
+/// `test_util` feature).
+///
+/// Writing capture loops against this trait rather than a concrete type lets them be wired to
+/// tests or alternative transports.
+pub trait FrameSource {
+    /// Waits up to `timeout` for a video frame.
+    fn capture_video(&self, timeout: Duration) -> Result<VideoFrame, Error>;
+    /// Waits up to `timeout` for an audio frame.
+    fn capture_audio(&self, timeout: Duration) -> Result<AudioFrame, Error>;
+    /// Waits up to `timeout` for a metadata frame.
+    fn capture_metadata(&self, timeout: Duration) -> Result<MetadataFrame, Error>;
+}
+
+/// Something frames can be sent to: an NDI [`Send`], or a test double such as
+/// `testing::MockSender` (with the `test_util` feature).
+pub trait FrameSink {
+    fn send_video(&self, frame: &VideoFrame);
+    fn send_audio(&self, frame: &AudioFrame);
+    fn send_metadata(&self, frame: &MetadataFrame);
+}
+
+impl FrameSource for Recv<'_> {
+    fn capture_video(&self, timeout: Duration) -> Result<VideoFrame, Error> {
+        Recv::capture_video(self, timeout)
+    }
+
+    fn capture_audio(&self, timeout: Duration) -> Result<AudioFrame, Error> {
+        Recv::capture_audio(self, timeout)
+    }
+
+    fn capture_metadata(&self, timeout: Duration) -> Result<MetadataFrame, Error> {
+        Recv::capture_metadata(self, timeout)
+    }
+}
+
+impl FrameSink for Send<'_> {
+    fn send_video(&self, frame: &VideoFrame) {
+        Send::send_video(self, frame)
+    }
+
+    fn send_audio(&self, frame: &AudioFrame) {
+        Send::send_audio(self, frame)
+    }
+
+    fn send_metadata(&self, frame: &MetadataFrame) {
+        Send::send_metadata(self, frame)
+    }
+}
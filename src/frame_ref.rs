@@ -0,0 +1,366 @@
+use std::{
+    ffi::{CStr, CString},
+    marker::PhantomData,
+    slice,
+};
+
+use crate::{
+    ndi_lib::*, AudioFrame, AudioType, BorrowedVideoFrame, Error, FourCCVideoType, FourCc,
+    FrameFormatType, MetadataFrame, VideoFrame,
+};
+
+/// Size in bytes of the data the SDK handed us for a video frame.
+pub(crate) fn raw_video_len(raw: &NDIlib_video_frame_v2_t) -> usize {
+    let fourcc = FourCCVideoType::from(raw.FourCC);
+    // The union holds the total size for formats we don't know the layout of.
+    let value = unsafe { raw.__bindgen_anon_1.line_stride_in_bytes };
+    match fourcc {
+        FourCCVideoType::Max => value.max(0) as usize,
+        _ => fourcc.buffer_size(value, raw.yres),
+    }
+}
+
+/// Size in bytes of the planar sample data the SDK handed us for an audio frame.
+pub(crate) fn raw_audio_len(raw: &NDIlib_audio_frame_v3_t) -> usize {
+    let stride = unsafe { raw.__bindgen_anon_1.channel_stride_in_bytes };
+    stride.max(0) as usize * raw.no_channels.max(0) as usize
+}
+
+unsafe fn raw_metadata<'f>(p_metadata: *const std::os::raw::c_char) -> Option<&'f CStr> {
+    if p_metadata.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(p_metadata))
+    }
+}
+
+/// A received video frame borrowed from the SDK without copying.
+///
+/// The frame is returned to the SDK when this is dropped. Use [`VideoFrameRef::to_owned`] to
+/// keep the data beyond that.
+pub struct VideoFrameRef<'rx> {
+    instance: NDIlib_recv_instance_t,
+    raw: NDIlib_video_frame_v2_t,
+    len: usize,
+    recv: PhantomData<&'rx ()>,
+}
+
+impl<'rx> VideoFrameRef<'rx> {
+    /// # Safety
+    ///
+    /// `raw` must be a frame captured from `instance` that has not been freed yet.
+    pub(crate) unsafe fn from_raw(
+        instance: NDIlib_recv_instance_t,
+        raw: NDIlib_video_frame_v2_t,
+    ) -> Self {
+        VideoFrameRef {
+            instance,
+            len: raw_video_len(&raw),
+            raw,
+            recv: PhantomData,
+        }
+    }
+
+    pub fn xres(&self) -> i32 {
+        self.raw.xres
+    }
+
+    pub fn yres(&self) -> i32 {
+        self.raw.yres
+    }
+
+    pub fn fourcc(&self) -> FourCCVideoType {
+        self.raw.FourCC.into()
+    }
+
+    /// The code the SDK gave for the frame's format, including those [`fourcc`](Self::fourcc)
+    /// reports as [`FourCCVideoType::Max`], such as compressed formats.
+    pub fn raw_fourcc(&self) -> FourCc {
+        FourCc::from_u32(self.raw.FourCC)
+    }
+
+    pub fn frame_rate(&self) -> (i32, i32) {
+        (self.raw.frame_rate_N, self.raw.frame_rate_D)
+    }
+
+    pub fn picture_aspect_ratio(&self) -> f32 {
+        self.raw.picture_aspect_ratio
+    }
+
+    pub fn frame_format_type(&self) -> FrameFormatType {
+        self.raw.frame_format_type.into()
+    }
+
+    pub fn timecode(&self) -> i64 {
+        self.raw.timecode
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.raw.timestamp
+    }
+
+    /// Stride of the first plane, or `None` for formats whose layout is not known.
+    pub fn line_stride(&self) -> Option<i32> {
+        match self.fourcc() {
+            FourCCVideoType::Max => None,
+            _ => Some(unsafe { self.raw.__bindgen_anon_1.line_stride_in_bytes }),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        if self.raw.p_data.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.raw.p_data, self.len) }
+        }
+    }
+
+    pub fn metadata(&self) -> Option<&CStr> {
+        unsafe { raw_metadata(self.raw.p_metadata) }
+    }
+
+    /// Copies the frame into an owned [`VideoFrame`].
+    pub fn to_owned(&self) -> VideoFrame {
+        unsafe { VideoFrame::from_raw(&self.raw) }
+    }
+
+    /// Copies the frame into `frame`, reusing its buffer.
+    ///
+    /// The buffer only grows when the new frame is larger than any it has held, so a
+    /// pipeline that keeps the occasional frame from a zero-copy capture loop stops
+    /// allocating once the first one is kept. Every other field of `frame` is overwritten.
+    pub fn to_owned_into(&self, frame: &mut VideoFrame) {
+        frame.xres = self.raw.xres;
+        frame.yres = self.raw.yres;
+        frame.fourcc = self.fourcc();
+        frame.frame_rate_n = self.raw.frame_rate_N;
+        frame.frame_rate_d = self.raw.frame_rate_D;
+        frame.picture_aspect_ratio = self.raw.picture_aspect_ratio;
+        frame.frame_format_type = self.frame_format_type();
+        frame.timecode = self.raw.timecode;
+        frame.timestamp = self.raw.timestamp;
+        frame.line_stride_or_size = self.raw.__bindgen_anon_1.into();
+        frame.data.clear();
+        frame.data.extend_from_slice(self.data());
+        match (self.metadata(), &frame.metadata) {
+            (Some(new), Some(old)) if new == old.as_c_str() => {}
+            (new, _) => frame.metadata = new.map(CString::from),
+        }
+    }
+}
+
+impl Drop for VideoFrameRef<'_> {
+    fn drop(&mut self) {
+        unsafe { NDIlib_recv_free_video_v2(self.instance, &self.raw) };
+    }
+}
+
+/// Borrows a received frame for sending on, e.g. to forward a source without copying it.
+///
+/// Every field carries over except the metadata, which a [`BorrowedVideoFrame`] doesn't hold.
+/// Frames in formats [`FourCCVideoType`] can't name, such as compressed ones, fail with
+/// [`Error::UnknownFourCc`].
+impl<'f> TryFrom<&'f VideoFrameRef<'_>> for BorrowedVideoFrame<'f> {
+    type Error = Error;
+
+    fn try_from(frame: &'f VideoFrameRef<'_>) -> Result<Self, Error> {
+        let fourcc = frame.fourcc();
+        if fourcc == FourCCVideoType::Max {
+            return Err(Error::UnknownFourCc(FourCc::from_u32(frame.raw.FourCC)));
+        }
+        // Safety: the data is exactly what the SDK handed us for this layout, and the format
+        // is uncompressed, so the union holds the line stride.
+        let borrowed = unsafe {
+            BorrowedVideoFrame::from_buffer_unchecked(
+                frame.data(),
+                frame.raw.xres,
+                frame.raw.yres,
+                fourcc,
+                frame.raw.__bindgen_anon_1.line_stride_in_bytes,
+            )
+        };
+        Ok(BorrowedVideoFrame {
+            frame_rate_n: frame.raw.frame_rate_N,
+            frame_rate_d: frame.raw.frame_rate_D,
+            picture_aspect_ratio: frame.raw.picture_aspect_ratio,
+            frame_format_type: frame.frame_format_type(),
+            timecode: frame.raw.timecode,
+            timestamp: frame.raw.timestamp,
+            ..borrowed
+        })
+    }
+}
+
+/// Timing and layout of an audio frame whose samples were copied into a caller's buffer by
+/// [`Recv::capture_audio_into`](crate::Recv::capture_audio_into).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFrameInfo {
+    pub sample_rate: i32,
+    pub no_channels: i32,
+    pub no_samples: i32,
+    pub timecode: i64,
+    pub timestamp: i64,
+}
+
+/// A received audio frame borrowed from the SDK without copying.
+///
+/// Samples are 32-bit float, one plane per channel, `channel_stride_in_bytes` apart.
+pub struct AudioFrameRef<'rx> {
+    instance: NDIlib_recv_instance_t,
+    raw: NDIlib_audio_frame_v3_t,
+    recv: PhantomData<&'rx ()>,
+}
+
+impl<'rx> AudioFrameRef<'rx> {
+    /// # Safety
+    ///
+    /// `raw` must be a frame captured from `instance` that has not been freed yet.
+    pub(crate) unsafe fn from_raw(
+        instance: NDIlib_recv_instance_t,
+        raw: NDIlib_audio_frame_v3_t,
+    ) -> Self {
+        AudioFrameRef {
+            instance,
+            raw,
+            recv: PhantomData,
+        }
+    }
+
+    pub fn sample_rate(&self) -> i32 {
+        self.raw.sample_rate
+    }
+
+    pub fn no_channels(&self) -> i32 {
+        self.raw.no_channels
+    }
+
+    pub fn no_samples(&self) -> i32 {
+        self.raw.no_samples
+    }
+
+    pub fn fourcc(&self) -> AudioType {
+        self.raw.FourCC.into()
+    }
+
+    pub fn channel_stride_in_bytes(&self) -> i32 {
+        unsafe { self.raw.__bindgen_anon_1.channel_stride_in_bytes }
+    }
+
+    pub fn timecode(&self) -> i64 {
+        self.raw.timecode
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.raw.timestamp
+    }
+
+    pub fn data(&self) -> &[f32] {
+        if self.raw.p_data.is_null() {
+            &[]
+        } else {
+            let len = raw_audio_len(&self.raw) / 4;
+            unsafe { slice::from_raw_parts(self.raw.p_data as *const f32, len) }
+        }
+    }
+
+    /// Samples of one channel, or `None` if `channel` is out of range.
+    pub fn channel(&self, channel: usize) -> Option<&[f32]> {
+        if channel >= self.raw.no_channels.max(0) as usize {
+            return None;
+        }
+        let stride = self.channel_stride_in_bytes().max(0) as usize / 4;
+        let samples = (self.raw.no_samples.max(0) as usize).min(stride);
+        let start = channel * stride;
+        self.data().get(start..start + samples)
+    }
+
+    pub fn metadata(&self) -> Option<&CStr> {
+        unsafe { raw_metadata(self.raw.p_metadata) }
+    }
+
+    pub fn info(&self) -> AudioFrameInfo {
+        AudioFrameInfo {
+            sample_rate: self.raw.sample_rate,
+            no_channels: self.raw.no_channels,
+            no_samples: self.raw.no_samples,
+            timecode: self.raw.timecode,
+            timestamp: self.raw.timestamp,
+        }
+    }
+
+    /// Appends the samples to `out` interleaved, one sample per channel in turn.
+    ///
+    /// Doesn't allocate when `out` already has room for them.
+    pub fn append_interleaved(&self, out: &mut Vec<f32>) {
+        let channels = self.raw.no_channels.max(0) as usize;
+        let stride = self.channel_stride_in_bytes().max(0) as usize / 4;
+        let samples = (self.raw.no_samples.max(0) as usize).min(stride);
+        let data = self.data();
+        if data.len() < channels * stride {
+            return;
+        }
+        out.reserve(channels * samples);
+        for sample in 0..samples {
+            out.extend((0..channels).map(|channel| data[channel * stride + sample]));
+        }
+    }
+
+    /// Copies the frame into an owned [`AudioFrame`].
+    pub fn to_owned(&self) -> AudioFrame {
+        AudioFrame::from_raw(self.raw)
+    }
+}
+
+impl Drop for AudioFrameRef<'_> {
+    fn drop(&mut self) {
+        unsafe { NDIlib_recv_free_audio_v3(self.instance, &self.raw) };
+    }
+}
+
+/// A received metadata frame borrowed from the SDK without copying.
+pub struct MetadataFrameRef<'rx> {
+    instance: NDIlib_recv_instance_t,
+    raw: NDIlib_metadata_frame_t,
+    recv: PhantomData<&'rx ()>,
+}
+
+impl<'rx> MetadataFrameRef<'rx> {
+    /// # Safety
+    ///
+    /// `raw` must be a frame captured from `instance` that has not been freed yet.
+    pub(crate) unsafe fn from_raw(
+        instance: NDIlib_recv_instance_t,
+        raw: NDIlib_metadata_frame_t,
+    ) -> Self {
+        MetadataFrameRef {
+            instance,
+            raw,
+            recv: PhantomData,
+        }
+    }
+
+    pub fn timecode(&self) -> i64 {
+        self.raw.timecode
+    }
+
+    pub fn data(&self) -> &CStr {
+        unsafe { raw_metadata(self.raw.p_data) }.unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> Result<&str, Error> {
+        self.data()
+            .to_str()
+            .map_err(|e| Error::InvalidUtf8(e.to_string()))
+    }
+
+    /// Copies the frame into an owned [`MetadataFrame`].
+    pub fn to_owned(&self) -> MetadataFrame {
+        unsafe { MetadataFrame::from_raw(&self.raw) }
+    }
+}
+
+impl Drop for MetadataFrameRef<'_> {
+    fn drop(&mut self) {
+        unsafe { NDIlib_recv_free_metadata(self.instance, &self.raw) };
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ndi_lib::*, AudioFrame, Error, FrameFormatType, FrameSource, MetadataFrame, Recv, VideoFrame,
+};
+
+/// How often [`FrameSource`] captures poll the frame synchronizer for new data.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// The SDK's frame synchronizer on top of a receiver.
+///
+/// Instead of waiting for frames as they arrive, a frame synchronizer is asked for video and
+/// audio on the caller's own clock: video repeats or drops frames and audio is resampled so a
+/// render or playout loop can pull exactly what it needs. While it exists, video and audio must
+/// be taken from it rather than from the receiver; metadata still comes from the receiver.
+pub struct FrameSync<'r> {
+    instance: NDIlib_framesync_instance_t,
+    recv: &'r Recv<'r>,
+}
+
+impl<'r> FrameSync<'r> {
+    pub fn new(recv: &'r Recv<'r>) -> Result<Self, Error> {
+        let instance = unsafe { NDIlib_framesync_create(recv.instance) };
+        if instance.is_null() {
+            return Err(Error::InitializationFailed(
+                "NDIlib_framesync_create failed".into(),
+            ));
+        }
+        Ok(FrameSync { instance, recv })
+    }
+
+    /// The most recent video frame, repeated if no new one arrived since the last call, or
+    /// `None` before the first frame.
+    pub fn latest_video(&self, field_type: FrameFormatType) -> Option<VideoFrame> {
+        let mut raw = NDIlib_video_frame_v2_t::default();
+        unsafe {
+            NDIlib_framesync_capture_video(self.instance, &mut raw, field_type.into());
+            let frame = (!raw.p_data.is_null()).then(|| VideoFrame::from_raw(&raw));
+            NDIlib_framesync_free_video(self.instance, &mut raw);
+            frame
+        }
+    }
+
+    /// Exactly `no_samples` samples, resampled to `sample_rate` and `no_channels` (pass 0 for
+    /// either to keep the source's), padded with silence if not enough audio has arrived.
+    pub fn pull_audio(&self, sample_rate: i32, no_channels: i32, no_samples: i32) -> AudioFrame {
+        let mut raw = NDIlib_audio_frame_v3_t::default();
+        unsafe {
+            NDIlib_framesync_capture_audio_v2(
+                self.instance,
+                &mut raw,
+                sample_rate,
+                no_channels,
+                no_samples,
+            );
+            let frame = if raw.p_data.is_null()
+                || raw.sample_rate <= 0
+                || raw.no_channels <= 0
+                || raw.no_samples <= 0
+            {
+                AudioFrame::new()
+            } else {
+                AudioFrame::from_raw(raw)
+            };
+            NDIlib_framesync_free_audio_v2(self.instance, &mut raw);
+            frame
+        }
+    }
+
+    /// Samples of audio currently queued, for pulling exactly what has arrived.
+    pub fn audio_queue_depth(&self) -> i32 {
+        unsafe { NDIlib_framesync_audio_queue_depth(self.instance) }
+    }
+
+    /// Calls `poll` every [`POLL_INTERVAL`] until it yields a value or `timeout` passes.
+    fn poll<T>(
+        timeout: Duration,
+        operation: &'static str,
+        mut poll: impl FnMut() -> Option<T>,
+    ) -> Result<T, Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(value) = poll() {
+                return Ok(value);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::Timeout {
+                    operation,
+                    timeout_ms: timeout.as_millis().min(u32::MAX as u128) as u32,
+                });
+            }
+            thread::sleep(remaining.min(POLL_INTERVAL));
+        }
+    }
+}
+
+impl Drop for FrameSync<'_> {
+    fn drop(&mut self) {
+        unsafe { NDIlib_framesync_destroy(self.instance) };
+    }
+}
+
+/// Captures return whatever the synchronizer holds rather than waiting for a new frame: the
+/// latest video frame, or all queued audio once some has arrived.
+impl FrameSource for FrameSync<'_> {
+    fn capture_video(&self, timeout: Duration) -> Result<VideoFrame, Error> {
+        Self::poll(timeout, "a video frame", || {
+            self.latest_video(FrameFormatType::Progressive)
+        })
+    }
+
+    fn capture_audio(&self, timeout: Duration) -> Result<AudioFrame, Error> {
+        Self::poll(timeout, "an audio frame", || {
+            let depth = self.audio_queue_depth();
+            (depth > 0).then(|| self.pull_audio(0, 0, depth))
+        })
+    }
+
+    fn capture_metadata(&self, timeout: Duration) -> Result<MetadataFrame, Error> {
+        self.recv.capture_metadata(timeout)
+    }
+}
@@ -0,0 +1,230 @@
+use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+
+use crate::{Error, Recv};
+
+/// Response curve applied to a stick axis after the dead zone has been removed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisCurve {
+    Linear,
+    Quadratic,
+    Cubic,
+    /// `sign(x) * |x|^exponent`, useful for fine control near the centre of the stick.
+    Power(f32),
+}
+
+impl AxisCurve {
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        let shaped = match self {
+            AxisCurve::Linear => magnitude,
+            AxisCurve::Quadratic => magnitude * magnitude,
+            AxisCurve::Cubic => magnitude * magnitude * magnitude,
+            AxisCurve::Power(exponent) => magnitude.powf(*exponent),
+        };
+        shaped.copysign(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisMapping {
+    pub axis: Axis,
+    pub curve: AxisCurve,
+    pub dead_zone: f32,
+    pub max_speed: f32,
+    pub invert: bool,
+}
+
+impl AxisMapping {
+    pub fn new(axis: Axis) -> Self {
+        AxisMapping {
+            axis,
+            curve: AxisCurve::Quadratic,
+            dead_zone: 0.1,
+            max_speed: 1.0,
+            invert: false,
+        }
+    }
+
+    /// Maps a raw axis value in `-1.0..=1.0` to a PTZ speed in `-max_speed..=max_speed`.
+    pub fn speed(&self, raw: f32) -> f32 {
+        let raw = raw.clamp(-1.0, 1.0);
+        if raw.abs() <= self.dead_zone {
+            return 0.0;
+        }
+        let rescaled = (raw.abs() - self.dead_zone) / (1.0 - self.dead_zone);
+        let speed = self.curve.apply(rescaled.copysign(raw)) * self.max_speed;
+        if self.invert {
+            -speed
+        } else {
+            speed
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetMapping {
+    pub button: Button,
+    pub preset: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtzGamepadMapping {
+    pub pan: AxisMapping,
+    pub tilt: AxisMapping,
+    pub zoom: AxisMapping,
+    pub presets: Vec<PresetMapping>,
+    pub preset_speed: f32,
+    /// When set, holding this button turns the preset buttons into "store preset" buttons.
+    pub store_modifier: Option<Button>,
+}
+
+impl Default for PtzGamepadMapping {
+    fn default() -> Self {
+        PtzGamepadMapping {
+            pan: AxisMapping::new(Axis::LeftStickX),
+            tilt: AxisMapping::new(Axis::LeftStickY),
+            zoom: AxisMapping::new(Axis::RightStickY),
+            presets: vec![
+                PresetMapping {
+                    button: Button::South,
+                    preset: 0,
+                },
+                PresetMapping {
+                    button: Button::East,
+                    preset: 1,
+                },
+                PresetMapping {
+                    button: Button::West,
+                    preset: 2,
+                },
+                PresetMapping {
+                    button: Button::North,
+                    preset: 3,
+                },
+            ],
+            preset_speed: 1.0,
+            store_modifier: Some(Button::LeftTrigger),
+        }
+    }
+}
+
+/// Drives the PTZ controls of a [`Recv`] from a game controller.
+///
+/// Call [`PtzGamepad::poll`] regularly (e.g. once per captured frame); speeds are only sent to
+/// the camera when they change.
+pub struct PtzGamepad {
+    gilrs: Gilrs,
+    mapping: PtzGamepadMapping,
+    active: Option<GamepadId>,
+    last_pan_tilt: (f32, f32),
+    last_zoom: f32,
+}
+
+impl PtzGamepad {
+    pub fn new(mapping: PtzGamepadMapping) -> Result<Self, Error> {
+        let gilrs = Gilrs::new().map_err(|e| {
+            Error::InitializationFailed(format!("Failed to initialize gamepad input: {}", e))
+        })?;
+        Ok(PtzGamepad {
+            gilrs,
+            mapping,
+            active: None,
+            last_pan_tilt: (0.0, 0.0),
+            last_zoom: 0.0,
+        })
+    }
+
+    pub fn mapping(&self) -> &PtzGamepadMapping {
+        &self.mapping
+    }
+
+    pub fn set_mapping(&mut self, mapping: PtzGamepadMapping) {
+        self.mapping = mapping;
+    }
+
+    /// Processes pending controller events and forwards them to the receiver's PTZ controls.
+    pub fn poll(&mut self, recv: &Recv) -> Result<(), Error> {
+        while let Some(event) = self.gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    self.active = Some(event.id);
+                    self.handle_button(recv, event.id, button)?;
+                }
+                EventType::AxisChanged(..) => self.active = Some(event.id),
+                EventType::Disconnected if self.active == Some(event.id) => self.active = None,
+                _ => {}
+            }
+        }
+
+        let Some(id) = self.active else {
+            return self.stop(recv);
+        };
+        let gamepad = self.gilrs.gamepad(id);
+        let pan = self.mapping.pan.speed(gamepad.value(self.mapping.pan.axis));
+        let tilt = self
+            .mapping
+            .tilt
+            .speed(gamepad.value(self.mapping.tilt.axis));
+        let zoom = self
+            .mapping
+            .zoom
+            .speed(gamepad.value(self.mapping.zoom.axis));
+
+        self.send_speeds(recv, pan, tilt, zoom)
+    }
+
+    /// Stops any pan, tilt or zoom movement started from the controller.
+    pub fn stop(&mut self, recv: &Recv) -> Result<(), Error> {
+        self.send_speeds(recv, 0.0, 0.0, 0.0)
+    }
+
+    fn handle_button(&mut self, recv: &Recv, id: GamepadId, button: Button) -> Result<(), Error> {
+        let Some(preset) = self
+            .mapping
+            .presets
+            .iter()
+            .find(|p| p.button == button)
+            .map(|p| p.preset)
+        else {
+            return Ok(());
+        };
+
+        let storing = self
+            .mapping
+            .store_modifier
+            .is_some_and(|modifier| self.gilrs.gamepad(id).is_pressed(modifier));
+        let ok = if storing {
+            recv.ptz_store_preset(preset as i32)
+        } else {
+            recv.ptz_recall_preset(preset, self.mapping.preset_speed)
+        };
+
+        if ok {
+            Ok(())
+        } else {
+            Err(Error::PtzCommandFailed(format!(
+                "Failed to {} preset {}",
+                if storing { "store" } else { "recall" },
+                preset
+            )))
+        }
+    }
+
+    fn send_speeds(&mut self, recv: &Recv, pan: f32, tilt: f32, zoom: f32) -> Result<(), Error> {
+        if (pan, tilt) != self.last_pan_tilt {
+            if !recv.ptz_pan_tilt_speed(pan, tilt) {
+                return Err(Error::PtzCommandFailed(
+                    "Failed to set pan/tilt speed".into(),
+                ));
+            }
+            self.last_pan_tilt = (pan, tilt);
+        }
+        if zoom != self.last_zoom {
+            if !recv.ptz_zoom_speed(zoom) {
+                return Err(Error::PtzCommandFailed("Failed to set zoom speed".into()));
+            }
+            self.last_zoom = zoom;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{Error, FourCCVideoType, VideoFrameRef};
+
+/// The texture format a frame of `fourcc` is uploaded as, if it can be uploaded directly.
+///
+/// UYVY frames become `Rg8Unorm` textures as wide as the frame: each texel holds a chroma sample
+/// in `r` (U on even columns, V on odd ones) and the pixel's luma in `g`, left for the shader
+/// to convert to RGB.
+pub fn texture_format(fourcc: FourCCVideoType) -> Option<wgpu::TextureFormat> {
+    match fourcc {
+        FourCCVideoType::BGRA | FourCCVideoType::BGRX => Some(wgpu::TextureFormat::Bgra8Unorm),
+        FourCCVideoType::RGBA | FourCCVideoType::RGBX => Some(wgpu::TextureFormat::Rgba8Unorm),
+        FourCCVideoType::UYVY => Some(wgpu::TextureFormat::Rg8Unorm),
+        _ => None,
+    }
+}
+
+/// A texture that received frames are copied into, recreated only when the resolution or
+/// format changes.
+///
+/// Frames are written straight from the SDK's buffer with the frame's own line stride, so no
+/// intermediate copy is made to satisfy the 256-byte row alignment of buffer copies.
+#[derive(Debug, Default)]
+pub struct FrameTexture {
+    texture: Option<wgpu::Texture>,
+    usage: Option<wgpu::TextureUsages>,
+}
+
+impl FrameTexture {
+    /// Textures are created for sampling and as copy destinations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates textures with `usage` in addition to `COPY_DST`.
+    pub fn with_usage(usage: wgpu::TextureUsages) -> Self {
+        FrameTexture {
+            texture: None,
+            usage: Some(usage),
+        }
+    }
+
+    /// The texture holding the last uploaded frame.
+    pub fn texture(&self) -> Option<&wgpu::Texture> {
+        self.texture.as_ref()
+    }
+
+    /// Queues `frame` to be written into the texture, (re)creating it on `device` if needed.
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        frame: &VideoFrameRef<'_>,
+    ) -> Result<&wgpu::Texture, Error> {
+        let fourcc = frame.fourcc();
+        let format = texture_format(fourcc).ok_or(Error::UnsupportedFormat {
+            fourcc,
+            operation: "upload",
+        })?;
+        let size = wgpu::Extent3d {
+            width: frame.xres() as u32,
+            height: frame.yres() as u32,
+            depth_or_array_layers: 1,
+        };
+        let usage = self.usage.unwrap_or(wgpu::TextureUsages::TEXTURE_BINDING)
+            | wgpu::TextureUsages::COPY_DST;
+
+        let texture = match self.texture.take() {
+            Some(texture) if texture.size() == size && texture.format() == format => texture,
+            _ => device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("ndi video frame"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage,
+                view_formats: &[],
+            }),
+        };
+        let texture = self.texture.insert(texture);
+        upload_frame(queue, texture, frame)?;
+        Ok(texture)
+    }
+}
+
+/// Queues `frame` to be written into `texture`, which must match its size and
+/// [`texture_format`].
+pub fn upload_frame(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    frame: &VideoFrameRef<'_>,
+) -> Result<(), Error> {
+    let (xres, yres, fourcc) = (frame.xres(), frame.yres(), frame.fourcc());
+    let stride = frame
+        .line_stride()
+        .unwrap_or_else(|| fourcc.line_stride(xres));
+    let data = frame.data();
+    let required = fourcc.check_layout(xres, yres, stride)?;
+    if data.len() < required {
+        return Err(Error::InvalidDataSize {
+            fourcc,
+            xres,
+            yres,
+            line_stride: stride,
+            expected: required,
+            actual: data.len(),
+        });
+    }
+
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(stride as u32),
+            rows_per_image: Some(yres as u32),
+        },
+        wgpu::Extent3d {
+            width: xres as u32,
+            height: yres as u32,
+            depth_or_array_layers: 1,
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::{fmt, str::FromStr};
+
+use crate::Error;
+
+/// A validated list of NDI group names, as used by finders and senders.
+///
+/// The SDK takes groups as one comma-separated string; this keeps the names separate so they
+/// can't be mistyped into an empty entry or a name containing a comma.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Groups(Vec<String>);
+
+impl Groups {
+    /// The group sources belong to when none is configured.
+    pub const PUBLIC: &'static str = "public";
+
+    pub fn new<I, S>(names: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut groups = Groups::default();
+        for name in names {
+            groups.push(name.as_ref())?;
+        }
+        Ok(groups)
+    }
+
+    /// Adds a group, ignoring names already present (groups are case-insensitive).
+    pub fn push(&mut self, name: &str) -> Result<(), Error> {
+        let name = name.trim();
+        if name.is_empty() || name.contains(',') {
+            return Err(Error::InvalidGroupName(name.to_string()));
+        }
+        if !self.contains(name) {
+            self.0.push(name.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|g| g.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl fmt::Display for Groups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.join(","))
+    }
+}
+
+impl FromStr for Groups {
+    type Err = Error;
+
+    /// Parses the SDK's comma-separated form; surrounding whitespace is ignored.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Ok(Groups::default());
+        }
+        Groups::new(value.split(','))
+    }
+}
@@ -0,0 +1,356 @@
+use std::time::Duration;
+
+use gstreamer as gst;
+use gstreamer_app as gst_app;
+use gstreamer_audio as gst_audio;
+use gstreamer_video as gst_video;
+
+use crate::{
+    copy_rows, AudioFrame, AudioType, Error, FourCCVideoType, FrameFormatType, Send, Timecode,
+    VideoFrame,
+};
+
+/// The GStreamer video format with the same memory layout as `fourcc`, if there is one.
+///
+/// YV12 is described as I420 with the chroma plane offsets swapped.
+pub fn video_format(fourcc: FourCCVideoType) -> Option<gst_video::VideoFormat> {
+    match fourcc {
+        FourCCVideoType::UYVY => Some(gst_video::VideoFormat::Uyvy),
+        FourCCVideoType::BGRA => Some(gst_video::VideoFormat::Bgra),
+        FourCCVideoType::BGRX => Some(gst_video::VideoFormat::Bgrx),
+        FourCCVideoType::RGBA => Some(gst_video::VideoFormat::Rgba),
+        FourCCVideoType::RGBX => Some(gst_video::VideoFormat::Rgbx),
+        FourCCVideoType::NV12 => Some(gst_video::VideoFormat::Nv12),
+        FourCCVideoType::I420 | FourCCVideoType::YV12 => Some(gst_video::VideoFormat::I420),
+        _ => None,
+    }
+}
+
+fn fourcc_for_format(format: gst_video::VideoFormat) -> Option<FourCCVideoType> {
+    match format {
+        gst_video::VideoFormat::Uyvy => Some(FourCCVideoType::UYVY),
+        gst_video::VideoFormat::Bgra => Some(FourCCVideoType::BGRA),
+        gst_video::VideoFormat::Bgrx => Some(FourCCVideoType::BGRX),
+        gst_video::VideoFormat::Rgba => Some(FourCCVideoType::RGBA),
+        gst_video::VideoFormat::Rgbx => Some(FourCCVideoType::RGBX),
+        gst_video::VideoFormat::Nv12 => Some(FourCCVideoType::NV12),
+        gst_video::VideoFormat::I420 => Some(FourCCVideoType::I420),
+        _ => None,
+    }
+}
+
+fn gst_error(context: &str, error: impl std::fmt::Display) -> Error {
+    Error::GStreamer(format!("{}: {}", context, error))
+}
+
+fn clock_time(duration: Duration) -> gst::ClockTime {
+    gst::ClockTime::from_nseconds(duration.as_nanos().min(u64::MAX as u128) as u64)
+}
+
+/// Describes the frame's format, frame rate, aspect ratio and plane strides and offsets.
+pub fn video_info(frame: &VideoFrame) -> Result<gst_video::VideoInfo, Error> {
+    let fourcc = frame.fourcc;
+    let format = video_format(fourcc).ok_or(Error::UnsupportedFormat {
+        fourcc,
+        operation: "describe as GStreamer caps",
+    })?;
+    frame.validate()?;
+    let interlace_mode = match frame.frame_format_type {
+        FrameFormatType::Progressive => gst_video::VideoInterlaceMode::Progressive,
+        FrameFormatType::Interlaced => gst_video::VideoInterlaceMode::Interleaved,
+        other => {
+            return Err(Error::InvalidFrame(format!(
+                "{:?} frames carry a single field; send whole interlaced frames to GStreamer",
+                other
+            )))
+        }
+    };
+
+    let (xres, yres) = (frame.xres, frame.yres);
+    let planes = fourcc.planes(xres, yres, unsafe {
+        frame.line_stride_or_size.line_stride_in_bytes
+    });
+    let strides: Vec<i32> = planes.iter().map(|p| p.stride as i32).collect();
+    let offsets: Vec<usize> = planes.iter().map(|p| p.offset).collect();
+    // NDI gives the picture aspect ratio; caps carry the pixel aspect ratio.
+    let par = (frame.picture_aspect_ratio > 0.0)
+        .then(|| {
+            gst::Fraction::approximate_f32(frame.picture_aspect_ratio * yres as f32 / xres as f32)
+        })
+        .flatten()
+        .unwrap_or_else(|| gst::Fraction::new(1, 1));
+
+    gst_video::VideoInfo::builder(format, xres as u32, yres as u32)
+        .fps(gst::Fraction::new(
+            frame.frame_rate_n,
+            frame.frame_rate_d.max(1),
+        ))
+        .par(par)
+        .interlace_mode(interlace_mode)
+        .stride(&strides)
+        .offset(&offsets)
+        .build()
+        .map_err(|e| gst_error("invalid video info", e))
+}
+
+pub fn video_caps(frame: &VideoFrame) -> Result<gst::Caps, Error> {
+    video_info(frame)?
+        .to_caps()
+        .map_err(|e| gst_error("invalid video caps", e))
+}
+
+/// Copies the frame into a buffer with its timecode as PTS, its frame duration, and a video
+/// meta describing the line stride.
+pub fn video_buffer(frame: &VideoFrame) -> Result<gst::Buffer, Error> {
+    buffer_for(frame, &video_info(frame)?)
+}
+
+fn buffer_for(frame: &VideoFrame, info: &gst_video::VideoInfo) -> Result<gst::Buffer, Error> {
+    let mut buffer = gst::Buffer::from_slice(frame.data.clone());
+    {
+        let buffer = buffer.make_mut();
+        buffer.set_pts(pts(frame.timecode));
+        if frame.frame_rate_n > 0 && frame.frame_rate_d > 0 {
+            buffer.set_duration(gst::ClockTime::from_nseconds(
+                1_000_000_000 * frame.frame_rate_d as u64 / frame.frame_rate_n as u64,
+            ));
+        }
+        gst_video::VideoMeta::add_full(
+            buffer,
+            gst_video::VideoFrameFlags::empty(),
+            info.format(),
+            info.width(),
+            info.height(),
+            info.offset(),
+            info.stride(),
+        )
+        .map_err(|e| gst_error("failed to add video meta", e))?;
+    }
+    Ok(buffer)
+}
+
+/// Pushes the frame into `appsrc`, updating its caps first when the format changes.
+pub fn push_video(appsrc: &gst_app::AppSrc, frame: &VideoFrame) -> Result<(), Error> {
+    let info = video_info(frame)?;
+    let caps = info
+        .to_caps()
+        .map_err(|e| gst_error("invalid video caps", e))?;
+    if appsrc.caps().as_ref() != Some(&caps) {
+        appsrc.set_caps(Some(&caps));
+    }
+    appsrc
+        .push_buffer(buffer_for(frame, &info)?)
+        .map_err(|e| gst_error("appsrc rejected the video buffer", e))?;
+    Ok(())
+}
+
+/// Describes NDI's planar float audio as the interleaved native-endian F32 GStreamer
+/// elements accept, which [`audio_buffer`] converts to.
+pub fn audio_info(frame: &AudioFrame) -> Result<gst_audio::AudioInfo, Error> {
+    if frame.fourcc != AudioType::FLTP {
+        return Err(Error::InvalidFrame(format!(
+            "Only FLTP audio can be sent to GStreamer, not {:?}",
+            frame.fourcc
+        )));
+    }
+    gst_audio::AudioInfo::builder(
+        gst_audio::AUDIO_FORMAT_F32,
+        frame.sample_rate.max(0) as u32,
+        frame.no_channels.max(0) as u32,
+    )
+    .layout(gst_audio::AudioLayout::Interleaved)
+    .build()
+    .map_err(|e| gst_error("invalid audio info", e))
+}
+
+pub fn audio_caps(frame: &AudioFrame) -> Result<gst::Caps, Error> {
+    audio_info(frame)?
+        .to_caps()
+        .map_err(|e| gst_error("invalid audio caps", e))
+}
+
+/// Interleaves the frame into a buffer with its timecode as PTS and its duration.
+pub fn audio_buffer(frame: &AudioFrame) -> Result<gst::Buffer, Error> {
+    frame.validate()?;
+    let channels = frame.no_channels.max(0) as usize;
+    let samples = frame.no_samples.max(0) as usize;
+    let stride = frame.channel_stride_in_bytes as usize;
+    let mut data = Vec::with_capacity(channels * samples * 4);
+    for sample in 0..samples {
+        for channel in 0..channels {
+            let offset = channel * stride + sample * 4;
+            data.extend_from_slice(&frame.data[offset..offset + 4]);
+        }
+    }
+
+    let mut buffer = gst::Buffer::from_mut_slice(data);
+    {
+        let buffer = buffer.make_mut();
+        buffer.set_pts(pts(frame.timecode));
+        if frame.sample_rate > 0 {
+            buffer.set_duration(gst::ClockTime::from_nseconds(
+                1_000_000_000 * samples as u64 / frame.sample_rate as u64,
+            ));
+        }
+    }
+    Ok(buffer)
+}
+
+/// Pushes the frame into `appsrc`, updating its caps first when the format changes.
+pub fn push_audio(appsrc: &gst_app::AppSrc, frame: &AudioFrame) -> Result<(), Error> {
+    let caps = audio_caps(frame)?;
+    if appsrc.caps().as_ref() != Some(&caps) {
+        appsrc.set_caps(Some(&caps));
+    }
+    appsrc
+        .push_buffer(audio_buffer(frame)?)
+        .map_err(|e| gst_error("appsrc rejected the audio buffer", e))?;
+    Ok(())
+}
+
+fn pts(timecode: i64) -> Option<gst::ClockTime> {
+    Timecode(timecode).as_duration().map(clock_time)
+}
+
+fn timecode(buffer: &gst::BufferRef) -> i64 {
+    buffer
+        .pts()
+        .map(|pts| Timecode::from_duration(Duration::from_nanos(pts.nseconds())))
+        .unwrap_or(Timecode::SYNTHESIZE)
+        .0
+}
+
+/// Copies a raw video sample into a tightly packed frame. Its PTS becomes the timecode, or the
+/// SDK synthesizes one when it has none.
+pub fn sample_to_video(sample: &gst::Sample) -> Result<VideoFrame, Error> {
+    let caps = sample
+        .caps()
+        .ok_or_else(|| Error::GStreamer("sample has no caps".into()))?;
+    let buffer = sample
+        .buffer()
+        .ok_or_else(|| Error::GStreamer("sample has no buffer".into()))?;
+    let info =
+        gst_video::VideoInfo::from_caps(caps).map_err(|e| gst_error("not raw video caps", e))?;
+    let fourcc = fourcc_for_format(info.format()).ok_or_else(|| {
+        Error::GStreamer(format!(
+            "{:?} video has no NDI equivalent; add a videoconvert before the appsink",
+            info.format()
+        ))
+    })?;
+    let frame = gst_video::VideoFrameRef::from_buffer_ref_readable(buffer, &info)
+        .map_err(|e| gst_error("failed to map video buffer", e))?;
+
+    let (xres, yres) = (info.width() as i32, info.height() as i32);
+    let (fps, par) = (info.fps(), info.par());
+    let (frame_rate_n, frame_rate_d) = if fps.numer() > 0 {
+        (fps.numer(), fps.denom())
+    } else {
+        (60, 1)
+    };
+    let mut aspect_ratio = xres as f32 / yres.max(1) as f32;
+    if par.numer() > 0 && par.denom() > 0 {
+        aspect_ratio *= par.numer() as f32 / par.denom() as f32;
+    }
+    let format = if info.is_interlaced() {
+        FrameFormatType::Interlaced
+    } else {
+        FrameFormatType::Progressive
+    };
+
+    let mut out = VideoFrame::new(
+        xres,
+        yres,
+        fourcc,
+        frame_rate_n,
+        frame_rate_d,
+        aspect_ratio,
+        format,
+    );
+    let stride = unsafe { out.line_stride_or_size.line_stride_in_bytes };
+    for (index, plane) in fourcc.planes(xres, yres, stride).iter().enumerate() {
+        let src = frame
+            .plane_data(index as u32)
+            .map_err(|e| gst_error("failed to read video plane", e))?;
+        copy_rows(
+            src,
+            frame.plane_stride()[index] as usize,
+            &mut out.data[plane.offset..],
+            plane.stride,
+            plane.row_bytes,
+            plane.rows,
+        );
+    }
+    out.timecode = timecode(buffer);
+    Ok(out)
+}
+
+/// Deinterleaves an F32 audio sample into a planar float frame. Its PTS becomes the timecode,
+/// or the SDK synthesizes one when it has none.
+pub fn sample_to_audio(sample: &gst::Sample) -> Result<AudioFrame, Error> {
+    let caps = sample
+        .caps()
+        .ok_or_else(|| Error::GStreamer("sample has no caps".into()))?;
+    let buffer = sample
+        .buffer()
+        .ok_or_else(|| Error::GStreamer("sample has no buffer".into()))?;
+    let info =
+        gst_audio::AudioInfo::from_caps(caps).map_err(|e| gst_error("not raw audio caps", e))?;
+    if info.format() != gst_audio::AUDIO_FORMAT_F32
+        || info.layout() != gst_audio::AudioLayout::Interleaved
+    {
+        return Err(Error::GStreamer(format!(
+            "{:?} {:?} audio is not supported; add an audioconvert to interleaved F32 before the appsink",
+            info.format(),
+            info.layout()
+        )));
+    }
+
+    let map = buffer
+        .map_readable()
+        .map_err(|e| gst_error("failed to map audio buffer", e))?;
+    let channels = info.channels() as usize;
+    let samples = map.len() / (channels * 4).max(1);
+    let mut data = Vec::with_capacity(channels * samples * 4);
+    for channel in 0..channels {
+        for frame in map.chunks_exact(channels * 4) {
+            data.extend_from_slice(&frame[channel * 4..channel * 4 + 4]);
+        }
+    }
+
+    AudioFrame::with_data(
+        info.rate() as i32,
+        channels as i32,
+        samples as i32,
+        timecode(buffer),
+        AudioType::FLTP,
+        data,
+        None,
+        0,
+    )
+}
+
+impl Send<'_> {
+    /// Pulls one sample from `appsink` and sends it as video or audio according to its caps.
+    ///
+    /// Returns `Ok(false)` when no sample arrived within `timeout` or the sink reached the end
+    /// of the stream.
+    pub fn send_from_appsink(
+        &self,
+        appsink: &gst_app::AppSink,
+        timeout: Duration,
+    ) -> Result<bool, Error> {
+        let Some(sample) = appsink.try_pull_sample(clock_time(timeout)) else {
+            return Ok(false);
+        };
+        let is_audio = sample
+            .caps()
+            .and_then(|caps| caps.structure(0))
+            .is_some_and(|s| s.has_name("audio/x-raw"));
+        if is_audio {
+            self.send_audio(&sample_to_audio(&sample)?);
+        } else {
+            self.send_video(&sample_to_video(&sample)?);
+        }
+        Ok(true)
+    }
+}
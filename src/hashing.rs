@@ -0,0 +1,86 @@
+use std::slice;
+
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::{ndi_lib::*, AudioFrame, AudioFrameRef, FourCCVideoType, VideoFrame, VideoFrameRef};
+
+impl VideoFrame {
+    /// A 64-bit XXH3 hash of the picture: its format, resolution and pixel data, excluding
+    /// any row padding, so equal pictures hash equally whatever their stride.
+    ///
+    /// For checking frames end to end in tests, or spotting a frozen source by consecutive
+    /// frames with the same hash. Timing, timecode and metadata are not included.
+    pub fn content_hash(&self) -> u64 {
+        let stride = unsafe { self.line_stride_or_size.line_stride_in_bytes };
+        video_hash(self.fourcc, self.xres, self.yres, stride, &self.data)
+    }
+}
+
+impl VideoFrameRef<'_> {
+    /// As [`VideoFrame::content_hash`], hashing the SDK's buffer in place.
+    pub fn content_hash(&self) -> u64 {
+        let stride = self
+            .line_stride()
+            .unwrap_or_else(|| self.fourcc().line_stride(self.xres()));
+        video_hash(self.fourcc(), self.xres(), self.yres(), stride, self.data())
+    }
+}
+
+impl AudioFrame {
+    /// A 64-bit XXH3 hash of the sample rate, channel count and samples of every channel,
+    /// excluding any padding between channels. Timing, timecode and metadata are not
+    /// included.
+    pub fn content_hash(&self) -> u64 {
+        let samples = (self.no_samples.max(0) as usize) * 4;
+        let stride = (self.channel_stride_in_bytes.max(0) as usize).max(samples);
+        let mut hasher = Xxh3::new();
+        hasher.update(&self.sample_rate.to_le_bytes());
+        hasher.update(&self.no_channels.to_le_bytes());
+        hasher.update(&self.no_samples.to_le_bytes());
+        for channel in 0..self.no_channels.max(0) as usize {
+            let start = channel * stride;
+            if let Some(data) = self.data.get(start..start + samples) {
+                hasher.update(data);
+            }
+        }
+        hasher.digest()
+    }
+}
+
+impl AudioFrameRef<'_> {
+    /// As [`AudioFrame::content_hash`], hashing the SDK's buffer in place.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Xxh3::new();
+        hasher.update(&self.sample_rate().to_le_bytes());
+        hasher.update(&self.no_channels().to_le_bytes());
+        hasher.update(&self.no_samples().to_le_bytes());
+        for channel in 0..self.no_channels().max(0) as usize {
+            if let Some(samples) = self.channel(channel) {
+                // The same native-endian bytes an owned frame holds.
+                let bytes = unsafe {
+                    slice::from_raw_parts(samples.as_ptr().cast::<u8>(), samples.len() * 4)
+                };
+                hasher.update(bytes);
+            }
+        }
+        hasher.digest()
+    }
+}
+
+fn video_hash(fourcc: FourCCVideoType, xres: i32, yres: i32, stride: i32, data: &[u8]) -> u64 {
+    let mut hasher = Xxh3::new();
+    hasher.update(&NDIlib_FourCC_video_type_e::from(fourcc).to_le_bytes());
+    hasher.update(&xres.to_le_bytes());
+    hasher.update(&yres.to_le_bytes());
+    for plane in fourcc.info().plane_layout(xres, yres, stride) {
+        for row in 0..plane.rows {
+            let start = plane.offset + row * plane.stride;
+            match data.get(start..start + plane.row_bytes) {
+                Some(bytes) => hasher.update(bytes),
+                // A truncated buffer still hashes what it has.
+                None => hasher.update(data.get(start..).unwrap_or_default()),
+            }
+        }
+    }
+    hasher.digest()
+}
@@ -0,0 +1,79 @@
+use image::RgbaImage;
+
+use crate::{
+    BorrowedVideoFrame, Error, FourCCVideoType, FrameFormatType, ImageFormat, LineStrideOrSize,
+    VideoFrame, VideoFrameRef,
+};
+
+/// Converts an RGBA, RGBX, BGRA or BGRX frame, reordering channels and dropping row padding.
+impl TryFrom<&VideoFrame> for RgbaImage {
+    type Error = Error;
+
+    fn try_from(frame: &VideoFrame) -> Result<Self, Error> {
+        rgba_image(frame.xres, frame.yres, frame.encode(ImageFormat::Rgba)?)
+    }
+}
+
+/// Converts a received RGBA, RGBX, BGRA or BGRX frame without copying it into a [`VideoFrame`]
+/// first.
+impl TryFrom<&VideoFrameRef<'_>> for RgbaImage {
+    type Error = Error;
+
+    fn try_from(frame: &VideoFrameRef<'_>) -> Result<Self, Error> {
+        rgba_image(frame.xres(), frame.yres(), frame.encode(ImageFormat::Rgba)?)
+    }
+}
+
+fn rgba_image(xres: i32, yres: i32, pixels: Vec<u8>) -> Result<RgbaImage, Error> {
+    RgbaImage::from_raw(xres as u32, yres as u32, pixels)
+        .ok_or(Error::InvalidResolution { xres, yres })
+}
+
+/// Wraps the image's pixels in a progressive RGBA frame at 60 fps.
+impl From<RgbaImage> for VideoFrame {
+    fn from(image: RgbaImage) -> Self {
+        let (xres, yres) = (image.width() as i32, image.height() as i32);
+        rgba_frame(xres, yres, image.into_raw())
+    }
+}
+
+/// Copies the image's pixels into a progressive RGBA frame at 60 fps.
+impl From<&RgbaImage> for VideoFrame {
+    fn from(image: &RgbaImage) -> Self {
+        let (xres, yres) = (image.width() as i32, image.height() as i32);
+        rgba_frame(xres, yres, image.as_raw().clone())
+    }
+}
+
+fn rgba_frame(xres: i32, yres: i32, data: Vec<u8>) -> VideoFrame {
+    VideoFrame {
+        xres,
+        yres,
+        fourcc: FourCCVideoType::RGBA,
+        frame_rate_n: 60,
+        frame_rate_d: 1,
+        picture_aspect_ratio: xres as f32 / yres.max(1) as f32,
+        frame_format_type: FrameFormatType::Progressive,
+        timecode: 0,
+        data,
+        line_stride_or_size: LineStrideOrSize {
+            line_stride_in_bytes: FourCCVideoType::RGBA.line_stride(xres),
+        },
+        metadata: None,
+        timestamp: 0,
+    }
+}
+
+/// Borrows the image's pixels as an RGBA frame for sending without a copy.
+impl<'a> TryFrom<&'a RgbaImage> for BorrowedVideoFrame<'a> {
+    type Error = Error;
+
+    fn try_from(image: &'a RgbaImage) -> Result<Self, Error> {
+        BorrowedVideoFrame::try_from_buffer(
+            image.as_raw(),
+            image.width() as i32,
+            image.height() as i32,
+            FourCCVideoType::RGBA,
+        )
+    }
+}
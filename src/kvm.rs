@@ -0,0 +1,296 @@
+use std::{
+    ffi::CString,
+    os::raw::{c_char, c_int},
+    sync::PoisonError,
+};
+
+use crate::{
+    metadata::xml_attribute, ndi_lib::*, Error, KnownMetadata, MetadataFrame, MetadataXml, Recv,
+    Send, SenderCapabilities,
+};
+
+sdk_extern! {
+    fn NDIlib_recv_kvm_is_supported(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_left_mouse_click(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_middle_mouse_click(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_right_mouse_click(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_left_mouse_release(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_middle_mouse_release(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_right_mouse_release(p_instance: NDIlib_recv_instance_t) -> bool;
+    fn NDIlib_recv_kvm_send_vertical_mouse_wheel(
+        p_instance: NDIlib_recv_instance_t,
+        no_units: f32,
+    ) -> bool;
+    fn NDIlib_recv_kvm_send_horizontal_mouse_wheel(
+        p_instance: NDIlib_recv_instance_t,
+        no_units: f32,
+    ) -> bool;
+    fn NDIlib_recv_kvm_send_mouse_position(
+        p_instance: NDIlib_recv_instance_t,
+        posn: *const f32,
+    ) -> bool;
+    fn NDIlib_recv_kvm_send_clipboard_contents(
+        p_instance: NDIlib_recv_instance_t,
+        p_clipboard_contents: *const c_char,
+    ) -> bool;
+    fn NDIlib_recv_kvm_send_keyboard_press(
+        p_instance: NDIlib_recv_instance_t,
+        key_sym_value: c_int,
+    ) -> bool;
+    fn NDIlib_recv_kvm_send_keyboard_release(
+        p_instance: NDIlib_recv_instance_t,
+        key_sym_value: c_int,
+    ) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// A keyboard, mouse or clipboard event sent from a receiver to a KVM-capable source.
+///
+/// This covers the sending half only: the sender gets each event as a [`KvmMessage`], which
+/// is not decoded back into a `KvmEvent`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KvmEvent {
+    /// Pointer position, normalised to `0.0..=1.0` across the source's video.
+    MousePosition {
+        x: f32,
+        y: f32,
+    },
+    MouseDown(MouseButton),
+    MouseUp(MouseButton),
+    VerticalWheel(f32),
+    HorizontalWheel(f32),
+    /// An X11 keysym value.
+    KeyDown(i32),
+    KeyUp(i32),
+    Clipboard(String),
+}
+
+/// A KVM message a sender received from a receiver, as the raw bytes the SDK carried.
+///
+/// **Experimental.** The SDK delivers these to senders as `<ndi_kvm u="..."/>` metadata with a
+/// base64 payload whose layout isn't part of the published SDK documentation, so they aren't
+/// decoded into [`KvmEvent`]s. The bytes are exposed for applications that know the encoding
+/// their receivers use, and may move to a typed API once it is documented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KvmMessage {
+    bytes: Vec<u8>,
+}
+
+impl KvmMessage {
+    /// Reads a KVM message from metadata a sender captured; other metadata yields `None`.
+    pub fn from_metadata(frame: &MetadataFrame) -> Option<Self> {
+        let xml = frame.as_str().ok()?.trim();
+        if !xml.starts_with("<ndi_kvm") {
+            return None;
+        }
+        let bytes = decode_base64(xml_attribute(xml, "u")?)?;
+        Some(KvmMessage { bytes })
+    }
+
+    /// The decoded payload.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Announces whether this sender accepts KVM events from receivers, through the
+    /// `ntk_kvm` flag of its `<ndi_capabilities/>` connection metadata.
+    ///
+    /// The sender's other capabilities and connection metadata are kept; a capabilities entry
+    /// is added if there isn't one yet. Receivers see the change the next time they connect,
+    /// or immediately for those that watch connection metadata.
+    pub fn set_kvm_enabled(&self, enabled: bool) -> Result<(), Error> {
+        let mut entries = self
+            .connection_metadata
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let updated = with_kvm_capability(&entries, enabled)?;
+        unsafe { NDIlib_send_clear_connection_metadata(self.instance) };
+        for entry in &updated {
+            unsafe { NDIlib_send_add_connection_metadata(self.instance, &entry.to_raw()) };
+        }
+        *entries = updated;
+        ndi_debug!(enabled, "kvm capability announced");
+        Ok(())
+    }
+
+    /// Whether the sender's connection metadata announces that it accepts KVM events.
+    pub fn kvm_enabled(&self) -> bool {
+        let entries = self
+            .connection_metadata
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        entries.iter().any(|entry| {
+            matches!(entry.parse_known(), Some(KnownMetadata::Capabilities(capabilities)) if capabilities.kvm)
+        })
+    }
+}
+
+/// Sets the KVM flag of the capabilities among `entries`, adding capabilities if needed.
+fn with_kvm_capability(
+    entries: &[MetadataFrame],
+    enabled: bool,
+) -> Result<Vec<MetadataFrame>, Error> {
+    let mut found = false;
+    let mut updated = entries
+        .iter()
+        .map(|entry| match entry.parse_known() {
+            Some(KnownMetadata::Capabilities(mut capabilities)) => {
+                found = true;
+                capabilities.kvm = enabled;
+                capabilities.to_metadata_frame()
+            }
+            _ => Ok(entry.clone()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !found {
+        let capabilities = SenderCapabilities {
+            kvm: enabled,
+            ..Default::default()
+        };
+        updated.push(capabilities.to_metadata_frame()?);
+    }
+    Ok(updated)
+}
+
+impl<'a> Recv<'a> {
+    /// Whether the connected source accepts KVM events.
+    pub fn kvm_is_supported(&self) -> bool {
+        unsafe { NDIlib_recv_kvm_is_supported(self.instance) }
+    }
+
+    pub fn kvm_send(&self, event: &KvmEvent) -> Result<(), Error> {
+        let sent = unsafe {
+            match event {
+                KvmEvent::MousePosition { x, y } => {
+                    let position = [*x, *y];
+                    NDIlib_recv_kvm_send_mouse_position(self.instance, position.as_ptr())
+                }
+                KvmEvent::MouseDown(MouseButton::Left) => {
+                    NDIlib_recv_kvm_send_left_mouse_click(self.instance)
+                }
+                KvmEvent::MouseDown(MouseButton::Middle) => {
+                    NDIlib_recv_kvm_send_middle_mouse_click(self.instance)
+                }
+                KvmEvent::MouseDown(MouseButton::Right) => {
+                    NDIlib_recv_kvm_send_right_mouse_click(self.instance)
+                }
+                KvmEvent::MouseUp(MouseButton::Left) => {
+                    NDIlib_recv_kvm_send_left_mouse_release(self.instance)
+                }
+                KvmEvent::MouseUp(MouseButton::Middle) => {
+                    NDIlib_recv_kvm_send_middle_mouse_release(self.instance)
+                }
+                KvmEvent::MouseUp(MouseButton::Right) => {
+                    NDIlib_recv_kvm_send_right_mouse_release(self.instance)
+                }
+                KvmEvent::VerticalWheel(units) => {
+                    NDIlib_recv_kvm_send_vertical_mouse_wheel(self.instance, *units)
+                }
+                KvmEvent::HorizontalWheel(units) => {
+                    NDIlib_recv_kvm_send_horizontal_mouse_wheel(self.instance, *units)
+                }
+                KvmEvent::KeyDown(key) => NDIlib_recv_kvm_send_keyboard_press(self.instance, *key),
+                KvmEvent::KeyUp(key) => NDIlib_recv_kvm_send_keyboard_release(self.instance, *key),
+                KvmEvent::Clipboard(contents) => {
+                    let contents =
+                        CString::new(contents.as_str()).map_err(Error::InvalidCString)?;
+                    NDIlib_recv_kvm_send_clipboard_contents(self.instance, contents.as_ptr())
+                }
+            }
+        };
+        if sent {
+            Ok(())
+        } else {
+            Err(Error::KvmCommandFailed(format!("{:?}", event)))
+        }
+    }
+}
+
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in input.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kvm_messages_are_decoded_to_raw_bytes() {
+        let frame = MetadataFrame::from_xml(r#"<ndi_kvm u="AwECAw=="/>"#, 0).unwrap();
+        let message = KvmMessage::from_metadata(&frame).unwrap();
+        assert_eq!(message.bytes(), &[3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn other_metadata_is_not_a_kvm_message() {
+        let frame = MetadataFrame::from_xml(r#"<ndi_tally on_program="true"/>"#, 0).unwrap();
+        assert_eq!(KvmMessage::from_metadata(&frame), None);
+        let frame = MetadataFrame::from_xml(r#"<ndi_kvm u="not base64!"/>"#, 0).unwrap();
+        assert_eq!(KvmMessage::from_metadata(&frame), None);
+    }
+
+    #[test]
+    fn enabling_kvm_keeps_other_capabilities_and_metadata() {
+        let product = MetadataFrame::from_xml(r#"<ndi_product long_name="Cam"/>"#, 0).unwrap();
+        let capabilities = SenderCapabilities {
+            recording: true,
+            ..Default::default()
+        };
+        let entries = vec![product, capabilities.to_metadata_frame().unwrap()];
+
+        let enabled = with_kvm_capability(&entries, true).unwrap();
+        assert_eq!(enabled.len(), 2);
+        assert_eq!(enabled[0].data(), entries[0].data());
+        let Some(KnownMetadata::Capabilities(announced)) = enabled[1].parse_known() else {
+            panic!("capabilities were dropped");
+        };
+        assert!(announced.kvm && announced.recording);
+
+        let disabled = with_kvm_capability(&enabled, false).unwrap();
+        let Some(KnownMetadata::Capabilities(announced)) = disabled[1].parse_known() else {
+            panic!("capabilities were dropped");
+        };
+        assert!(!announced.kvm && announced.recording);
+    }
+
+    #[test]
+    fn enabling_kvm_adds_capabilities_when_missing() {
+        let enabled = with_kvm_capability(&[], true).unwrap();
+        assert_eq!(enabled.len(), 1);
+        assert!(matches!(
+            enabled[0].parse_known(),
+            Some(KnownMetadata::Capabilities(SenderCapabilities {
+                kvm: true,
+                ..
+            }))
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{ndi_lib::*, timecode::TIMESTAMP_UNDEFINED, Error, Recv, VideoFrame, VideoFrameRef};
+
+/// Rolling end-to-end latency over a [`LatencyMeter`]'s window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyEstimate {
+    /// Latency of the most recently observed frame.
+    pub latest: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    /// Standard deviation of the latency.
+    pub jitter: Duration,
+    /// How far the local clock is ahead of the sender's, in 100 ns units; negative if behind.
+    pub clock_offset: i64,
+    /// Number of frames the estimate is over.
+    pub samples: usize,
+}
+
+/// Measures latency from the sender's frame timestamps to local receipt time.
+///
+/// A frame's `timestamp` is the sender's clock when it was sent, so the difference from the
+/// local clock on receipt is the latency plus the offset between the two clocks. If the clocks
+/// are known to agree (e.g. both follow PTP), say so with
+/// [`LatencyMeter::with_synchronized_clocks`] and the latencies are absolute. Otherwise the
+/// offset is estimated by assuming the fastest frame in the window took
+/// [`LatencyMeter::with_min_latency`] (zero by default), which makes the latencies relative to
+/// that floor but still shows jitter, buffering and drift accurately.
+#[derive(Debug, Clone)]
+pub struct LatencyMeter {
+    window: usize,
+    /// Receipt time minus timestamp of each frame in the window, in 100 ns units.
+    deltas: VecDeque<i64>,
+    clock_offset: Option<i64>,
+    min_latency: i64,
+}
+
+impl LatencyMeter {
+    /// Keeps the last `window` frames (at least one) for the rolling estimate.
+    pub fn new(window: usize) -> Self {
+        LatencyMeter {
+            window: window.max(1),
+            deltas: VecDeque::with_capacity(window.max(1)),
+            clock_offset: None,
+            min_latency: 0,
+        }
+    }
+
+    /// Treats the sender's clock as agreeing with the local one, so latencies are absolute.
+    pub fn with_synchronized_clocks(self) -> Self {
+        self.with_clock_offset(0)
+    }
+
+    /// Uses a known clock offset, in 100 ns units, rather than estimating one.
+    pub fn with_clock_offset(mut self, offset: i64) -> Self {
+        self.clock_offset = Some(offset);
+        self
+    }
+
+    /// The latency the fastest frame is assumed to have had when estimating the clock offset.
+    pub fn with_min_latency(mut self, latency: Duration) -> Self {
+        self.min_latency = ticks(latency);
+        self
+    }
+
+    /// Records a frame received now and returns its latency, or `None` if the frame has no
+    /// timestamp.
+    pub fn observe(&mut self, timestamp: i64) -> Option<Duration> {
+        self.observe_at(timestamp, SystemTime::now())
+    }
+
+    /// Records a frame received at `received`, e.g. a time taken straight after capture.
+    pub fn observe_at(&mut self, timestamp: i64, received: SystemTime) -> Option<Duration> {
+        if timestamp == TIMESTAMP_UNDEFINED || timestamp <= 0 {
+            return None;
+        }
+        let received = received
+            .duration_since(UNIX_EPOCH)
+            .map(ticks)
+            .unwrap_or_default();
+        if self.deltas.len() == self.window {
+            self.deltas.pop_front();
+        }
+        let delta = received.saturating_sub(timestamp);
+        self.deltas.push_back(delta);
+        Some(self.latency(delta))
+    }
+
+    /// The clock offset in use, in 100 ns units: the known one, or the current estimate.
+    pub fn clock_offset(&self) -> Option<i64> {
+        self.clock_offset.or_else(|| {
+            let fastest = self.deltas.iter().min()?;
+            Some(fastest.saturating_sub(self.min_latency))
+        })
+    }
+
+    /// Latency statistics over the window, once a frame has been observed.
+    pub fn estimate(&self) -> Option<LatencyEstimate> {
+        let latest = *self.deltas.back()?;
+        let offset = self.clock_offset()?;
+        let latencies = self.deltas.iter().map(|&d| d.saturating_sub(offset) as f64);
+        let n = self.deltas.len() as f64;
+        let mean = latencies.clone().sum::<f64>() / n;
+        let variance = latencies.clone().map(|l| (l - mean).powi(2)).sum::<f64>() / n;
+        let min = latencies.clone().fold(f64::INFINITY, f64::min);
+        let max = latencies.fold(f64::NEG_INFINITY, f64::max);
+        Some(LatencyEstimate {
+            latest: self.latency(latest),
+            mean: duration(mean),
+            min: duration(min),
+            max: duration(max),
+            jitter: duration(variance.sqrt()),
+            clock_offset: offset,
+            samples: self.deltas.len(),
+        })
+    }
+
+    /// Forgets the window, e.g. after reconnecting to a source with a different clock.
+    pub fn reset(&mut self) {
+        self.deltas.clear();
+    }
+
+    fn latency(&self, delta: i64) -> Duration {
+        let offset = self.clock_offset().unwrap_or(delta);
+        duration(delta.saturating_sub(offset) as f64)
+    }
+}
+
+impl<'a> Recv<'a> {
+    /// Waits up to `timeout_ms` for video, then skips ahead to the newest frame already
+    /// queued, so a consumer that fell behind catches up at once instead of working through a
+    /// backlog.
+    ///
+    /// Skipped frames are returned to the SDK unseen. Other frame types arriving meanwhile
+    /// are discarded, as with [`Recv::capture_video_ref`].
+    pub fn capture_latest_video_ref(
+        &self,
+        timeout_ms: u32,
+    ) -> Result<Option<VideoFrameRef<'_>>, Error> {
+        let Some(mut frame) = self.capture_video_ref(timeout_ms)? else {
+            return Ok(None);
+        };
+        while self.queued_video_frames() > 0 {
+            match self.capture_video_ref(0)? {
+                Some(newer) => frame = newer,
+                None => break,
+            }
+        }
+        Ok(Some(frame))
+    }
+
+    /// As [`Recv::capture_latest_video_ref`], copying the newest frame out so it can outlive
+    /// the capture; every stale frame is still returned to the SDK without being copied.
+    pub fn capture_latest_video(&self, timeout: Duration) -> Result<Option<VideoFrame>, Error> {
+        let timeout_ms = timeout.as_millis().min(u32::MAX as u128) as u32;
+        Ok(self
+            .capture_latest_video_ref(timeout_ms)?
+            .map(|frame| frame.to_owned()))
+    }
+
+    /// Discards every video frame waiting in the receive queue, e.g. after a pause in
+    /// processing, and returns how many there were.
+    pub fn drain_video(&self) -> Result<usize, Error> {
+        let mut drained = 0;
+        while self.queued_video_frames() > 0 {
+            if self.capture_video_ref(0)?.is_none() {
+                break;
+            }
+            drained += 1;
+        }
+        if drained > 0 {
+            ndi_debug!(source = %self.source().name, drained, "drained queued video");
+        }
+        Ok(drained)
+    }
+
+    fn queued_video_frames(&self) -> i32 {
+        let mut queue = NDIlib_recv_queue_t::default();
+        unsafe { NDIlib_recv_get_queue(self.instance, &mut queue) };
+        queue.video_frames
+    }
+}
+
+fn ticks(duration: Duration) -> i64 {
+    (duration.as_nanos() / 100).min(i64::MAX as u128) as i64
+}
+
+/// A latency in 100 ns units as a duration; negative latencies, from a clock offset that is
+/// slightly off, count as zero.
+fn duration(ticks: f64) -> Duration {
+    Duration::from_nanos((ticks.max(0.0) * 100.0) as u64)
+}
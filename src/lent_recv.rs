@@ -0,0 +1,167 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+#[cfg(feature = "persistent_stats")]
+use crate::StatsPersistence;
+use crate::{
+    capture_owned, ndi_lib::*, read_connection_stats, ByteCounter, ConnectionMetadata,
+    ConnectionStats, Error, FrameKind, FrameType, KnownMetadata, SenderCapabilities,
+    StatsBaselines, VideoFormatTracker,
+};
+
+/// What a receiver's captures keep track of, shared with the threads capturing for it.
+pub(crate) struct CaptureState {
+    /// The name of the source, for logging.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    pub(crate) source_name: String,
+    pub(crate) bytes: ByteCounter,
+    pub(crate) video_format: VideoFormatTracker,
+    /// The last capabilities the source announced, seen while capturing metadata.
+    pub(crate) capabilities: Mutex<Option<SenderCapabilities>>,
+    /// The last product information the source announced.
+    pub(crate) peer: Mutex<Option<ConnectionMetadata>>,
+    pub(crate) stats_baselines: Mutex<StatsBaselines>,
+    #[cfg(feature = "persistent_stats")]
+    pub(crate) persistent_stats: Mutex<Option<StatsPersistence>>,
+}
+
+impl CaptureState {
+    pub(crate) fn new(source_name: String) -> Self {
+        CaptureState {
+            source_name,
+            bytes: ByteCounter::new(),
+            video_format: VideoFormatTracker::default(),
+            capabilities: Mutex::new(None),
+            peer: Mutex::new(None),
+            stats_baselines: Mutex::new(StatsBaselines::new()),
+            #[cfg(feature = "persistent_stats")]
+            persistent_stats: Mutex::new(None),
+        }
+    }
+}
+
+/// A receiver's instance lent to a thread that captures from or samples it, together with
+/// the state its captures update, so frames captured there are accounted for exactly as
+/// [`Recv::capture`](crate::Recv::capture) accounts for its own.
+#[derive(Clone)]
+pub(crate) struct LentRecv {
+    instance: NDIlib_recv_instance_t,
+    state: Arc<CaptureState>,
+}
+
+// SAFETY: the SDK allows a receiver to be captured from and queried on any thread, and the
+// state is behind atomics and locks. Every thread holding a `LentRecv` is stopped and joined
+// before the instance is destroyed or replaced: `Recv` stops its callback threads and stats
+// monitors on drop and in `replace_instance`, and `MultiReceiver` and `CaptureScheduler` join
+// their workers before dropping their receivers.
+unsafe impl std::marker::Send for LentRecv {}
+
+impl LentRecv {
+    /// # Safety
+    /// `instance` must be a live receiver instance, and must stay live for as long as this or
+    /// any clone of it is used.
+    pub(crate) unsafe fn new(instance: NDIlib_recv_instance_t, state: Arc<CaptureState>) -> Self {
+        LentRecv { instance, state }
+    }
+
+    pub(crate) fn instance(&self) -> NDIlib_recv_instance_t {
+        self.instance
+    }
+
+    pub(crate) fn state(&self) -> &CaptureState {
+        &self.state
+    }
+
+    /// Captures the next frame of any type, recording it as
+    /// [`Recv::capture`](crate::Recv::capture) does.
+    pub(crate) fn capture_frame(&self, timeout_ms: u32) -> Result<FrameType, Error> {
+        let state = &*self.state;
+        let frame = unsafe { capture_owned(self.instance, timeout_ms) };
+        match &frame {
+            Ok(FrameType::None) => {
+                ndi_trace!(source = %state.source_name, timeout_ms, "capture timed out");
+            }
+            Ok(FrameType::StatusChange) => self.on_status_change(),
+            Ok(FrameType::Video(video)) => {
+                state.bytes.record(FrameKind::Video, video.data.len());
+                state.video_format.observe(video.into());
+            }
+            Ok(FrameType::Audio(audio)) => state.bytes.record(FrameKind::Audio, audio.data.len()),
+            Ok(FrameType::Metadata(metadata)) => {
+                state
+                    .bytes
+                    .record(FrameKind::Metadata, metadata.data().to_bytes().len());
+                if let Ok(xml) = metadata.as_str() {
+                    self.observe_metadata(xml);
+                }
+            }
+            Err(Error::ErrorFrame) => {
+                ndi_warn!(source = %state.source_name, "receiver returned an error frame");
+            }
+            _ => {}
+        }
+        frame
+    }
+
+    /// Remembers the source's capabilities and product information when `xml` announces
+    /// them. Other metadata is ignored without allocating.
+    pub(crate) fn observe_metadata(&self, xml: &str) {
+        let element = xml.trim_start();
+        if !element.starts_with("<ndi_capabilities") && !element.starts_with("<ndi_product") {
+            return;
+        }
+        match KnownMetadata::parse(xml) {
+            Some(KnownMetadata::Capabilities(capabilities)) => {
+                ndi_debug!(source = %self.state.source_name, ?capabilities, "source capabilities received");
+                if let Ok(mut slot) = self.state.capabilities.lock() {
+                    *slot = Some(capabilities);
+                }
+            }
+            Some(KnownMetadata::Product(product)) => {
+                ndi_debug!(source = %self.state.source_name, ?product, "source product received");
+                if let Ok(mut slot) = self.state.peer.lock() {
+                    *slot = Some(product);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn on_status_change(&self) {
+        #[cfg(feature = "tracing")]
+        {
+            let stats = self.connection_stats();
+            tracing::debug!(
+                source = %self.state.source_name,
+                connections = stats.connections,
+                video_frames_received = stats.video_frames_received,
+                video_frames_dropped = stats.video_frames_dropped,
+                audio_frames_received = stats.audio_frames_received,
+                audio_frames_dropped = stats.audio_frames_dropped,
+                video_frames_queued = stats.video_frames_queued,
+                "receiver status changed"
+            );
+        }
+        #[cfg(feature = "persistent_stats")]
+        if let Ok(mut stats) = self.state.persistent_stats.lock() {
+            if let Some(persistence) = stats.as_mut() {
+                persistence.observe_connections(self.connections());
+            }
+        }
+    }
+
+    #[cfg(feature = "persistent_stats")]
+    fn connections(&self) -> i32 {
+        unsafe { NDIlib_recv_get_no_connections(self.instance) }
+    }
+
+    /// The SDK's counters, noting any reconnect in the receiver's baselines.
+    pub(crate) fn connection_stats(&self) -> ConnectionStats {
+        let stats = unsafe { read_connection_stats(self.instance) };
+        self.state
+            .stats_baselines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .observe(&stats);
+        stats
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+
+use crate::{AudioFrame, AudioFrameRef};
+
+const BYTES_PER_SAMPLE: usize = 4;
+
+/// Peak and RMS level of one channel over a block of samples, as linear amplitudes where 1.0
+/// is full scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChannelLevel {
+    pub peak: f32,
+    pub rms: f32,
+}
+
+impl ChannelLevel {
+    pub fn from_samples(samples: impl IntoIterator<Item = f32>) -> Self {
+        let (mut peak, mut sum, mut count) = (0.0f32, 0.0f64, 0usize);
+        for sample in samples {
+            peak = peak.max(sample.abs());
+            sum += f64::from(sample) * f64::from(sample);
+            count += 1;
+        }
+        ChannelLevel {
+            peak,
+            rms: if count == 0 {
+                0.0
+            } else {
+                (sum / count as f64).sqrt() as f32
+            },
+        }
+    }
+
+    /// Peak level in dBFS; negative infinity for silence.
+    pub fn peak_dbfs(&self) -> f32 {
+        20.0 * self.peak.log10()
+    }
+
+    /// RMS level in dBFS; negative infinity for silence.
+    pub fn rms_dbfs(&self) -> f32 {
+        20.0 * self.rms.log10()
+    }
+}
+
+/// Levels of each channel of interleaved audio, such as that produced for other libraries.
+pub fn interleaved_levels(samples: &[f32], channels: usize) -> Vec<ChannelLevel> {
+    if channels == 0 {
+        return Vec::new();
+    }
+    (0..channels)
+        .map(|c| ChannelLevel::from_samples(samples.iter().skip(c).step_by(channels).copied()))
+        .collect()
+}
+
+impl AudioFrame {
+    /// Samples of one channel, honouring the channel stride, or `None` if out of range.
+    pub(crate) fn channel_samples(&self, channel: usize) -> Option<impl Iterator<Item = f32> + '_> {
+        if channel >= self.no_channels.max(0) as usize {
+            return None;
+        }
+        let stride = self.channel_stride_in_bytes.max(0) as usize;
+        let samples = (self.no_samples.max(0) as usize).min(stride / BYTES_PER_SAMPLE);
+        let start = channel * stride;
+        let bytes = self
+            .data
+            .get(start..start + samples * BYTES_PER_SAMPLE)
+            .unwrap_or(&[]);
+        Some(
+            bytes
+                .chunks_exact(BYTES_PER_SAMPLE)
+                .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])),
+        )
+    }
+
+    /// Peak and RMS level of each channel in this frame.
+    pub fn levels(&self) -> Vec<ChannelLevel> {
+        (0..self.no_channels.max(0) as usize)
+            .filter_map(|c| self.channel_samples(c).map(ChannelLevel::from_samples))
+            .collect()
+    }
+}
+
+impl AudioFrameRef<'_> {
+    /// Peak and RMS level of each channel in this frame.
+    pub fn levels(&self) -> Vec<ChannelLevel> {
+        (0..self.no_channels().max(0) as usize)
+            .map(|c| ChannelLevel::from_samples(self.channel(c).unwrap_or(&[]).iter().copied()))
+            .collect()
+    }
+}
+
+/// A second-order IIR section in direct form I.
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
+
+/// The two-stage K-weighting filter of ITU-R BS.1770 for `sample_rate`.
+fn k_weighting(sample_rate: f64) -> [Biquad; 2] {
+    // High shelf modelling the acoustic effect of the head.
+    let (f0, gain_db, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+    let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        ..Biquad::default()
+    };
+
+    // High pass removing content below ~38 Hz.
+    let (f0, q) = (38.13547087602444, 0.5003270373238773);
+    let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        ..Biquad::default()
+    };
+    [shelf, high_pass]
+}
+
+/// EBU R128 momentary loudness (K-weighted, over the last 400 ms) of a stream of audio frames.
+///
+/// All channels are weighted equally, so the result is exact for mono and stereo; surround
+/// feeds with an LFE channel read slightly high.
+#[derive(Debug, Clone, Default)]
+pub struct LoudnessMeter {
+    sample_rate: i32,
+    filters: Vec<[Biquad; 2]>,
+    block_len: usize,
+    block_fill: usize,
+    block_sums: Vec<f64>,
+    /// Mean square per channel of the last four 100 ms blocks.
+    blocks: VecDeque<Vec<f64>>,
+}
+
+impl LoudnessMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a frame. A change of sample rate or channel count restarts the measurement.
+    pub fn push(&mut self, frame: &AudioFrame) {
+        let channels = frame.no_channels.max(0) as usize;
+        self.reset_for(frame.sample_rate, channels);
+        let samples: Vec<Vec<f32>> = (0..channels)
+            .filter_map(|c| frame.channel_samples(c).map(Iterator::collect))
+            .collect();
+        self.process(&samples);
+    }
+
+    /// Feeds a received frame without copying it first.
+    pub fn push_ref(&mut self, frame: &AudioFrameRef<'_>) {
+        let channels = frame.no_channels().max(0) as usize;
+        self.reset_for(frame.sample_rate(), channels);
+        let samples: Vec<&[f32]> = (0..channels)
+            .map(|c| frame.channel(c).unwrap_or(&[]))
+            .collect();
+        self.process(&samples);
+    }
+
+    /// Momentary loudness in LUFS, once 400 ms of audio has been fed.
+    pub fn momentary(&self) -> Option<f64> {
+        if self.blocks.len() < 4 {
+            return None;
+        }
+        let power: f64 = (0..self.filters.len())
+            .map(|c| self.blocks.iter().map(|b| b[c]).sum::<f64>() / 4.0)
+            .sum();
+        Some(-0.691 + 10.0 * power.log10())
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn reset_for(&mut self, sample_rate: i32, channels: usize) {
+        if sample_rate == self.sample_rate && channels == self.filters.len() {
+            return;
+        }
+        *self = LoudnessMeter {
+            sample_rate,
+            filters: vec![k_weighting(f64::from(sample_rate.max(1))); channels],
+            block_len: (sample_rate.max(10) / 10) as usize,
+            block_fill: 0,
+            block_sums: vec![0.0; channels],
+            blocks: VecDeque::with_capacity(4),
+        };
+    }
+
+    fn process<S: AsRef<[f32]>>(&mut self, channels: &[S]) {
+        let samples = channels.iter().map(|c| c.as_ref().len()).min().unwrap_or(0);
+        for i in 0..samples {
+            for (c, channel) in channels.iter().enumerate() {
+                let [shelf, high_pass] = &mut self.filters[c];
+                let y = high_pass.process(shelf.process(f64::from(channel.as_ref()[i])));
+                self.block_sums[c] += y * y;
+            }
+            self.block_fill += 1;
+            if self.block_fill == self.block_len {
+                let len = self.block_len as f64;
+                let block = self.block_sums.iter().map(|s| s / len).collect();
+                if self.blocks.len() == 4 {
+                    self.blocks.pop_front();
+                }
+                self.blocks.push_back(block);
+                self.block_sums.iter_mut().for_each(|s| *s = 0.0);
+                self.block_fill = 0;
+            }
+        }
+    }
+}
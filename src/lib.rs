@@ -5,20 +5,286 @@ use std::{
     fmt::{self, Display, Formatter},
     os::raw::c_char,
     ptr,
+    sync::{mpsc, Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
 };
 
+#[macro_use]
+mod sdk_extern;
+
+#[macro_use]
+mod trace;
+
 mod error;
 pub use error::*;
 
 mod ndi_lib;
 use ndi_lib::*;
 
+#[cfg(feature = "dynamic_loading")]
+mod runtime;
+#[cfg(feature = "dynamic_loading")]
+pub use runtime::NDI_REDIST_URL;
+
+mod analysis;
+
+mod async_send;
+pub use async_send::*;
+
+mod audio_slice;
+
+mod av_capture;
+pub use av_capture::*;
+
+mod bitrate;
+pub use bitrate::*;
+
+mod callbacks;
+pub use callbacks::*;
+
+mod capabilities;
+pub use capabilities::*;
+
+mod capture_config;
+pub use capture_config::*;
+
+mod composite;
+pub use composite::*;
+
+mod config;
+pub use config::*;
+
+mod deinterlace;
+pub use deinterlace::*;
+
+mod discovery;
+pub use discovery::*;
+
+mod drift;
+pub use drift::*;
+
+mod failover;
+pub use failover::*;
+
+mod fourcc;
+pub use fourcc::*;
+
+mod frame_channel;
+pub use frame_channel::*;
+
+mod frame_io;
+pub use frame_io::*;
+
+mod frame_ref;
+pub use frame_ref::*;
+
+mod framesync;
+pub use framesync::*;
+
+mod groups;
+pub use groups::*;
+
+mod latency;
+pub use latency::*;
+
+mod levels;
+pub use levels::*;
+mod lent_recv;
+use lent_recv::{CaptureState, LentRecv};
+
+mod metadata;
+pub use metadata::*;
+
+mod metadata_batch;
+pub use metadata_batch::*;
+
+mod metadata_hook;
+pub use metadata_hook::*;
+
+mod multi_recv;
+pub use multi_recv::*;
+
+mod pacer;
+pub use pacer::*;
+
+mod peers;
+pub use peers::*;
+
+mod perf;
+pub use perf::*;
+
+mod pixel_format;
+pub use pixel_format::*;
+
+mod pixel16;
+pub use pixel16::*;
+
+mod player;
+pub use player::*;
+
+mod reconnect;
+pub use reconnect::*;
+
+mod recorder;
+pub use recorder::*;
+
+mod reorder;
+pub use reorder::*;
+
+mod retry;
+pub use retry::*;
+
+mod scheduler;
+pub use scheduler::*;
+
+mod scramble;
+pub use scramble::*;
+
+mod send_stats;
+pub use send_stats::*;
+
+mod session;
+pub use session::*;
+
+mod shared_frame;
+pub use shared_frame::*;
+
+mod shared_memory;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod source_filter;
+use source_filter::NameFilter;
+
+mod stats;
+pub use stats::*;
+
+mod stats_monitor;
+pub use stats_monitor::*;
+
+mod tally;
+pub use tally::*;
+
+mod tally_hub;
+pub use tally_hub::*;
+
+mod test_signal;
+pub use test_signal::*;
+
+mod thread_config;
+pub use thread_config::*;
+
+#[cfg(feature = "thumbnail")]
+mod thumbnail;
+#[cfg(feature = "thumbnail")]
+pub use thumbnail::*;
+
+mod timecode;
+pub use timecode::*;
+
+mod transform;
+pub use transform::*;
+
+mod typed_recv;
+pub use typed_recv::*;
+
+mod version;
+pub use version::*;
+
+mod video_format;
+pub use video_format::*;
+
+mod video_mode;
+pub use video_mode::*;
+
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "python"))]
+mod async_find;
+
+#[cfg(any(
+    feature = "capi",
+    feature = "python",
+    feature = "tokio",
+    feature = "async-std"
+))]
+mod shared_ndi;
+
+#[cfg(feature = "async-std")]
+pub mod async_std;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
+#[cfg(feature = "app")]
+mod app;
+#[cfg(feature = "app")]
+pub use app::*;
+
+#[cfg(feature = "advanced_sdk")]
+mod compressed;
+#[cfg(feature = "advanced_sdk")]
+pub use compressed::*;
+
+#[cfg(feature = "advanced_sdk")]
+mod kvm;
+#[cfg(feature = "advanced_sdk")]
+pub use kvm::*;
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::*;
+
+#[cfg(feature = "ffmpeg")]
+pub mod ffmpeg;
+
+#[cfg(feature = "gamepad")]
+mod gamepad;
+#[cfg(feature = "gamepad")]
+pub use gamepad::*;
+
+#[cfg(feature = "gpu")]
+pub mod gpu;
+
+#[cfg(feature = "gstreamer")]
+pub mod gstreamer;
+
+#[cfg(feature = "hashing")]
+mod hashing;
+
+#[cfg(feature = "http-preview")]
+mod preview;
+#[cfg(feature = "http-preview")]
+pub use preview::*;
+
+#[cfg(feature = "image_interop")]
+mod image_interop;
+
+#[cfg(feature = "proxy")]
+pub mod proxy;
+
+#[cfg(feature = "python")]
+mod python;
+
+#[cfg(feature = "test_util")]
+pub mod testing;
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
 pub struct NDI;
 
 impl NDI {
     pub fn new() -> Result<Self, Error> {
+        #[cfg(feature = "dynamic_loading")]
+        runtime::load()?;
         if Self::initialize() {
             Ok(NDI)
+        } else if !Self::is_supported_cpu() {
+            Err(Error::InitializationFailed(
+                "NDIlib_initialize failed: this CPU lacks the instructions the NDI runtime needs"
+                    .into(),
+            ))
         } else {
             Err(Error::InitializationFailed(
                 "NDIlib_initialize failed".into(),
@@ -26,11 +292,61 @@ impl NDI {
         }
     }
 
+    /// Initializes the runtime with its configuration read from `config_dir` instead of the
+    /// default location.
+    ///
+    /// The SDK looks up `ndi-config.v1.json` through the `NDI_CONFIG_DIR` environment variable,
+    /// which this sets for the current process before initializing.
+    ///
+    /// # Safety
+    ///
+    /// Setting an environment variable races with any other thread reading or writing the
+    /// environment, including inside C libraries. Call this before the process starts any
+    /// other threads. Receivers and senders can instead be configured individually with
+    /// [`Receiver::with_config`] and [`Sender::config`] (Advanced SDK).
+    pub unsafe fn with_config_dir(config_dir: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        std::env::set_var("NDI_CONFIG_DIR", config_dir.as_ref());
+        Self::new()
+    }
+
+    /// Initializes the runtime with `config` in place of any `ndi-config.v1.json`, without
+    /// editing the global configuration file.
+    ///
+    /// The configuration is written to a new directory under the system temporary directory
+    /// and used through [`NDI::with_config_dir`]. The directory has a random name, is created
+    /// afresh (failing if the name is taken) and, on Unix, is readable by this user only, so
+    /// other users can't swap in a configuration of their own. The SDK only reads it when the
+    /// runtime is first initialized in the process, so create this before any other `NDI`.
+    ///
+    /// # Safety
+    ///
+    /// As [`NDI::with_config_dir`]: call this before the process starts any other threads.
+    pub unsafe fn with_config(config: &NdiConfig) -> Result<Self, Error> {
+        let dir = config::create_private_dir(&std::env::temp_dir())?;
+        config.write_to_dir(&dir)?;
+        unsafe { Self::with_config_dir(dir) }
+    }
+
+    /// Initializes the runtime, failing if its version is older than `required`.
+    pub fn with_min_version(required: NdiVersion) -> Result<Self, Error> {
+        let found = Self::sdk_version()?;
+        if found < required {
+            return Err(Error::UnsupportedRuntime { found, required });
+        }
+        Self::new()
+    }
+
     pub fn is_supported_cpu() -> bool {
+        #[cfg(feature = "dynamic_loading")]
+        if runtime::load().is_err() {
+            return false;
+        }
         unsafe { NDIlib_is_supported_CPU() }
     }
 
     pub fn version() -> Result<String, Error> {
+        #[cfg(feature = "dynamic_loading")]
+        runtime::load()?;
         unsafe {
             let version_ptr = NDIlib_version();
             if version_ptr.is_null() {
@@ -44,6 +360,11 @@ impl NDI {
         }
     }
 
+    /// The runtime's version, parsed from [`NDI::version`].
+    pub fn sdk_version() -> Result<NdiVersion, Error> {
+        Self::version()?.parse()
+    }
+
     fn initialize() -> bool {
         unsafe { NDIlib_initialize() }
     }
@@ -55,11 +376,17 @@ impl Drop for NDI {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Finder {
     pub show_local_sources: bool,
     pub groups: Option<String>,
     pub extra_ips: Option<String>,
+    /// Name patterns; see [`Finder::include_pattern`].
+    pub include: Vec<String>,
+    /// Name patterns; see [`Finder::exclude_pattern`].
+    pub exclude: Vec<String>,
 }
 
 impl Finder {
@@ -68,17 +395,36 @@ impl Finder {
             show_local_sources,
             groups: groups.map(|s| s.to_string()),
             extra_ips: extra_ips.map(|s| s.to_string()),
+            include: Vec::new(),
+            exclude: Vec::new(),
         }
     }
+
+    /// Limits discovery to sources in `groups`; an empty list means the default groups.
+    pub fn with_groups(mut self, groups: &Groups) -> Self {
+        self.groups = (!groups.is_empty()).then(|| groups.to_string());
+        self
+    }
 }
 
 pub struct Find<'a> {
     instance: NDIlib_find_instance_t,
+    filter: NameFilter,
     ndi: std::marker::PhantomData<&'a NDI>,
 }
 
 impl<'a> Find<'a> {
     pub fn new(_ndi: &'a NDI, settings: Finder) -> Result<Self, Error> {
+        // SAFETY: the borrow of `_ndi` keeps the runtime alive for as long as the finder.
+        unsafe { Self::new_detached(settings) }
+    }
+
+    /// Creates a finder whose lifetime isn't tied to a borrow of [`NDI`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must keep an `NDI` alive until the finder is dropped.
+    pub(crate) unsafe fn new_detached(settings: Finder) -> Result<Self, Error> {
         let groups_cstr = settings
             .groups
             .as_deref()
@@ -100,18 +446,28 @@ impl<'a> Find<'a> {
 
         let instance = unsafe { NDIlib_find_create_v2(&create_settings) };
         if instance.is_null() {
+            ndi_warn!("NDIlib_find_create_v2 failed");
             return Err(Error::InitializationFailed(
                 "NDIlib_find_create_v2 failed".into(),
             ));
         }
+        ndi_debug!(
+            show_local_sources = settings.show_local_sources,
+            groups = ?settings.groups,
+            extra_ips = ?settings.extra_ips,
+            "finder created"
+        );
         Ok(Find {
             instance,
+            filter: NameFilter::new(&settings),
             ndi: std::marker::PhantomData,
         })
     }
 
     pub fn wait_for_sources(&self, timeout: u32) -> bool {
-        unsafe { NDIlib_find_wait_for_sources(self.instance, timeout) }
+        let changed = unsafe { NDIlib_find_wait_for_sources(self.instance, timeout) };
+        ndi_trace!(timeout_ms = timeout, changed, "waited for sources");
+        changed
     }
 
     pub fn get_sources(&self, timeout: u32) -> Result<Vec<Source>, Error> {
@@ -127,8 +483,10 @@ impl<'a> Find<'a> {
                     let source = &*sources_ptr.add(i as usize);
                     Source::from_raw(source)
                 })
-                .collect()
+                .filter(|source| self.filter.is_empty() || self.filter.matches(&source.name))
+                .collect::<Vec<_>>()
         };
+        ndi_debug!(count = sources.len(), "sources discovered");
         Ok(sources)
     }
 }
@@ -139,7 +497,8 @@ impl<'a> Drop for Find<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Source {
     pub name: String,
     pub url_address: Option<String>,
@@ -147,6 +506,24 @@ pub struct Source {
 }
 
 impl Source {
+    /// The machine part of an NDI name of the form `MACHINE (channel)`, or the whole name if
+    /// it doesn't follow that convention.
+    pub fn machine_name(&self) -> &str {
+        self.split_name().map_or(&self.name, |(machine, _)| machine)
+    }
+
+    /// The channel part of an NDI name of the form `MACHINE (channel)`, if it has one.
+    pub fn channel_name(&self) -> Option<&str> {
+        self.split_name().map(|(_, channel)| channel)
+    }
+
+    /// Splits at the first ` (`: machine names can't contain one, but channel names may.
+    fn split_name(&self) -> Option<(&str, &str)> {
+        let inner = self.name.strip_suffix(')')?;
+        let open = self.name.find(" (")?;
+        Some((&self.name[..open], &inner[open + 2..]))
+    }
+
     fn from_raw(ndi_source: &NDIlib_source_t) -> Self {
         let name = unsafe {
             CStr::from_ptr(ndi_source.p_ndi_name)
@@ -183,33 +560,26 @@ impl Source {
         }
     }
 
-    fn to_raw(&self) -> Result<NDIlib_source_t, Error> {
-        let p_ndi_name = CString::new(self.name.clone()).map_err(Error::InvalidCString)?;
-        let p_url_address = self
+    /// Calls `f` with the SDK's view of this source, whose strings live until `f` returns.
+    fn with_raw<T>(
+        &self,
+        f: impl FnOnce(&NDIlib_source_t) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let name = CString::new(self.name.as_str()).map_err(Error::InvalidCString)?;
+        // The URL and IP address share a field; the URL wins when both are known.
+        let address = self
             .url_address
             .as_deref()
+            .or(self.ip_address.as_deref())
             .map(CString::new)
             .transpose()
-            .map_err(Error::InvalidCString)?
-            .map_or(ptr::null(), |s| s.into_raw());
-
-        let p_ip_address = self
-            .ip_address
-            .as_deref()
-            .map(CString::new)
-            .transpose()
-            .map_err(Error::InvalidCString)?
-            .map_or(ptr::null(), |s| s.into_raw());
-
-        let __bindgen_anon_1 = if !p_url_address.is_null() {
-            NDIlib_source_t__bindgen_ty_1 { p_url_address }
-        } else {
-            NDIlib_source_t__bindgen_ty_1 { p_ip_address }
-        };
+            .map_err(Error::InvalidCString)?;
 
-        Ok(NDIlib_source_t {
-            p_ndi_name: p_ndi_name.into_raw(),
-            __bindgen_anon_1,
+        f(&NDIlib_source_t {
+            p_ndi_name: name.as_ptr(),
+            __bindgen_anon_1: NDIlib_source_t__bindgen_ty_1 {
+                p_url_address: address.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            },
         })
     }
 }
@@ -220,7 +590,7 @@ impl Display for Source {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FourCCVideoType {
     UYVY,
     UYVA,
@@ -274,7 +644,99 @@ impl From<NDIlib_FourCC_video_type_e> for FourCCVideoType {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+impl FourCCVideoType {
+    /// Where each 8-bit plane lives in a buffer with the given first-plane stride, in Y, U, V
+    /// order for the 4:2:0 formats. Packed formats, and those with alpha or 16-bit planes,
+    /// report only their first plane.
+    pub(crate) fn planes(&self, xres: i32, yres: i32, line_stride: i32) -> Vec<Plane> {
+        let (width, height, stride) = (xres as usize, yres as usize, line_stride as usize);
+        let luma = Plane {
+            offset: 0,
+            stride,
+            row_bytes: self.line_stride(xres) as usize,
+            rows: height,
+        };
+        let luma_size = stride * height;
+        let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+        match self {
+            FourCCVideoType::NV12 => vec![
+                luma,
+                Plane {
+                    offset: luma_size,
+                    stride,
+                    row_bytes: (chroma_width * 2).min(stride),
+                    rows: chroma_height,
+                },
+            ],
+            FourCCVideoType::I420 | FourCCVideoType::YV12 => {
+                let chroma_stride = stride / 2;
+                let chroma = |offset| Plane {
+                    offset,
+                    stride: chroma_stride,
+                    row_bytes: chroma_width.min(chroma_stride),
+                    rows: chroma_height,
+                };
+                let (first, second) = (luma_size, luma_size + chroma_stride * chroma_height);
+                // I420 stores U before V; YV12 stores V first.
+                let (u, v) = if matches!(self, FourCCVideoType::I420) {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                vec![luma, chroma(u), chroma(v)]
+            }
+            _ => vec![luma],
+        }
+    }
+
+    /// Validates frame dimensions and stride, returning the buffer size the SDK will read.
+    pub(crate) fn check_layout(
+        &self,
+        xres: i32,
+        yres: i32,
+        line_stride: i32,
+    ) -> Result<usize, Error> {
+        if xres <= 0 || yres <= 0 {
+            return Err(Error::InvalidResolution { xres, yres });
+        }
+        let min_stride = self.line_stride(xres);
+        if line_stride < min_stride {
+            return Err(Error::InvalidStride {
+                fourcc: *self,
+                xres,
+                expected: min_stride,
+                actual: line_stride,
+            });
+        }
+        Ok(self.buffer_size(line_stride, yres))
+    }
+}
+
+/// One plane of a video buffer, as laid out by [`FourCCVideoType::planes`].
+pub(crate) struct Plane {
+    pub offset: usize,
+    pub stride: usize,
+    /// Bytes of pixel data in each row, excluding padding.
+    pub row_bytes: usize,
+    pub rows: usize,
+}
+
+/// Copies `rows` rows of `row_bytes` bytes between buffers with different strides.
+pub(crate) fn copy_rows(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    row_bytes: usize,
+    rows: usize,
+) {
+    for row in 0..rows {
+        let (s, d) = (row * src_stride, row * dst_stride);
+        dst[d..d + row_bytes].copy_from_slice(&src[s..s + row_bytes]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameFormatType {
     Progressive,
     Interlaced,
@@ -367,6 +829,7 @@ impl From<NDIlib_video_frame_v2_t__bindgen_ty_1> for LineStrideOrSize {
     }
 }
 
+#[derive(Clone)]
 pub struct VideoFrame {
     pub xres: i32,
     pub yres: i32,
@@ -425,15 +888,8 @@ impl VideoFrame {
         aspect_ratio: f32,
         format: FrameFormatType,
     ) -> Self {
-        let bpp = match fourcc {
-            FourCCVideoType::BGRA => 32,
-            // Add other formats and their bpp as needed
-            _ => 32, // Default to 32 bpp if unknown
-        };
-
-        let stride = (xres * bpp + 7) / 8;
-        let buffer_size: usize = (yres * stride) as usize;
-        let data = vec![0u8; buffer_size];
+        let stride = fourcc.line_stride(xres);
+        let data = vec![0u8; fourcc.buffer_size(stride, yres)];
 
         VideoFrame {
             xres,
@@ -453,6 +909,23 @@ impl VideoFrame {
         }
     }
 
+    /// Checks that `data` is large enough for the frame's format, resolution and line stride.
+    pub fn validate(&self) -> Result<(), Error> {
+        let stride = unsafe { self.line_stride_or_size.line_stride_in_bytes };
+        let required = self.fourcc.check_layout(self.xres, self.yres, stride)?;
+        if self.data.len() < required {
+            return Err(Error::InvalidDataSize {
+                fourcc: self.fourcc,
+                xres: self.xres,
+                yres: self.yres,
+                line_stride: stride,
+                expected: required,
+                actual: self.data.len(),
+            });
+        }
+        Ok(())
+    }
+
     pub fn to_raw(&self) -> NDIlib_video_frame_v2_t {
         NDIlib_video_frame_v2_t {
             xres: self.xres,
@@ -479,8 +952,7 @@ impl VideoFrame {
     ///
     /// This function assumes the given `NDIlib_video_frame_v2_t` is valid and correctly allocated.
     pub unsafe fn from_raw(c_frame: &NDIlib_video_frame_v2_t) -> Self {
-        let data_size =
-            c_frame.__bindgen_anon_1.data_size_in_bytes as usize * c_frame.yres as usize;
+        let data_size = frame_ref::raw_video_len(c_frame);
         if c_frame.p_data.is_null() || data_size == 0 {
             panic!("Invalid video frame data");
         }
@@ -504,7 +976,7 @@ impl VideoFrame {
             timecode: c_frame.timecode,
             data,
             line_stride_or_size: LineStrideOrSize {
-                data_size_in_bytes: data_size as i32,
+                line_stride_in_bytes: c_frame.__bindgen_anon_1.line_stride_in_bytes,
             },
             metadata,
             timestamp: c_frame.timestamp,
@@ -514,130 +986,463 @@ impl VideoFrame {
 
 impl Drop for VideoFrame {
     fn drop(&mut self) {
-        if let Some(meta) = &self.metadata {
-            unsafe {
-                let _ = CString::from_raw(meta.as_ptr() as *mut c_char);
-            }
-        }
+        // The metadata is an owned `CString` whichever way the frame was made (copied out of
+        // the SDK by `from_raw`, or given to a builder), so it is freed here exactly once.
+        // Rebuilding it with `CString::from_raw` from its own pointer would free it twice.
+        drop(self.metadata.take());
     }
 }
 
-#[derive(Debug)]
-pub struct AudioFrame {
-    pub sample_rate: i32,
-    pub no_channels: i32,
-    pub no_samples: i32,
-    pub timecode: i64,
-    pub fourcc: AudioType,
-    pub data: Vec<u8>,
-    pub channel_stride_in_bytes: i32,
-    pub metadata: Option<CString>,
-    pub timestamp: i64,
+#[derive(Debug, Clone)]
+pub struct VideoFrameBuilder {
+    xres: i32,
+    yres: i32,
+    fourcc: FourCCVideoType,
+    frame_rate_n: i32,
+    frame_rate_d: i32,
+    picture_aspect_ratio: f32,
+    frame_format_type: FrameFormatType,
+    timecode: i64,
+    timecode_smpte: Option<SmpteTimecode>,
+    data: Option<Vec<u8>>,
+    line_stride: Option<i32>,
+    metadata: Option<String>,
+    timestamp: i64,
 }
 
-impl AudioFrame {
-    pub fn new() -> Self {
-        AudioFrame {
-            sample_rate: 0,
-            no_channels: 0,
-            no_samples: 0,
+impl Default for VideoFrameBuilder {
+    fn default() -> Self {
+        VideoFrameBuilder {
+            xres: 1920,
+            yres: 1080,
+            fourcc: FourCCVideoType::BGRA,
+            frame_rate_n: 60,
+            frame_rate_d: 1,
+            picture_aspect_ratio: 16.0 / 9.0,
+            frame_format_type: FrameFormatType::Progressive,
             timecode: 0,
-            fourcc: AudioType::Max, // TODO: Is this the right default?
-            data: vec![],
-            channel_stride_in_bytes: 0,
+            timecode_smpte: None,
+            data: None,
+            line_stride: None,
             metadata: None,
             timestamp: 0,
         }
     }
+}
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn with_data(
-        sample_rate: i32,
-        no_channels: i32,
-        no_samples: i32,
-        timecode: i64,
-        fourcc: AudioType, // TODO: Can this be merged with the fourcc on VideoFrame?
-        data: Vec<u8>,     // TODO: Many of these fields could be combined into a struct
-        metadata: Option<String>,
-        timestamp: i64,
-    ) -> Result<Self, Error> {
-        let metadata_cstring = metadata
-            .map(|m| CString::new(m).map_err(Error::InvalidCString))
-            .transpose()?;
-        Ok(AudioFrame {
-            sample_rate,
-            no_channels,
-            no_samples,
-            timecode,
-            fourcc,
-            data,
-            channel_stride_in_bytes: no_samples * 4,
-            metadata: metadata_cstring,
-            timestamp,
-        })
+impl VideoFrameBuilder {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    pub(crate) fn to_raw(&self) -> NDIlib_audio_frame_v3_t {
-        NDIlib_audio_frame_v3_t {
-            sample_rate: self.sample_rate,
-            no_channels: self.no_channels,
-            no_samples: self.no_samples,
-            timecode: self.timecode,
-            FourCC: self.fourcc.into(),
-            p_data: self.data.as_ptr() as *mut u8,
-            __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
-                channel_stride_in_bytes: self.channel_stride_in_bytes,
-            },
-            p_metadata: self.metadata.as_ref().map_or(ptr::null(), |m| m.as_ptr()),
-            timestamp: self.timestamp,
-        }
+    pub fn resolution(mut self, xres: i32, yres: i32) -> Self {
+        self.xres = xres;
+        self.yres = yres;
+        self
     }
 
-    pub(crate) fn from_raw(raw: NDIlib_audio_frame_v3_t) -> Self {
-        if raw.p_data.is_null() {
-            panic!("Received a null pointer for raw audio frame data");
-        }
+    pub fn fourcc(mut self, fourcc: FourCCVideoType) -> Self {
+        self.fourcc = fourcc;
+        self
+    }
 
-        if raw.sample_rate <= 0 {
-            panic!("Invalid sample rate: {}", raw.sample_rate);
-        }
+    pub fn frame_rate(mut self, numerator: i32, denominator: i32) -> Self {
+        self.frame_rate_n = numerator;
+        self.frame_rate_d = denominator;
+        self
+    }
 
-        if raw.no_channels <= 0 {
-            panic!("Invalid number of channels: {}", raw.no_channels);
-        }
+    pub fn aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+        self.picture_aspect_ratio = aspect_ratio;
+        self
+    }
 
-        if raw.no_samples <= 0 {
-            panic!("Invalid number of samples: {}", raw.no_samples);
-        }
+    pub fn format(mut self, format: FrameFormatType) -> Self {
+        self.frame_format_type = format;
+        self
+    }
 
-        let bytes_per_sample = 4;
-        let data_size = (raw.no_samples * raw.no_channels * bytes_per_sample) as usize;
+    /// Sets the timecode in 100 ns units; pass [`Timecode::SYNTHESIZE`] to have the SDK fill it in.
+    pub fn timecode(mut self, timecode: impl Into<i64>) -> Self {
+        self.timecode = timecode.into();
+        self.timecode_smpte = None;
+        self
+    }
 
-        if data_size == 0 {
-            panic!("Calculated data length is zero");
-        }
+    /// Sets the timecode from hours, minutes, seconds and frames at the frame's frame rate.
+    pub fn timecode_smpte(mut self, timecode: SmpteTimecode) -> Self {
+        self.timecode_smpte = Some(timecode);
+        self
+    }
 
-        let data = unsafe {
-            assert!(!raw.p_data.is_null(), "raw.p_data is null");
-            std::slice::from_raw_parts(raw.p_data, data_size).to_vec()
-        };
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
 
-        if data.len() != data_size {
-            panic!(
-                "Mismatch between data length ({} bytes) and calculated data length ({} bytes)",
-                data.len(),
-                data_size
-            );
-        }
+    pub fn metadata(mut self, metadata: &str) -> Self {
+        self.metadata = Some(metadata.to_string());
+        self
+    }
 
-        let metadata = if raw.p_metadata.is_null() {
-            None
-        } else {
-            Some(unsafe { CString::from_raw(raw.p_metadata as *mut c_char) })
+    /// Uses `data` as the frame buffer. It must be tightly packed for the configured format
+    /// and resolution; [`build`](Self::build) fails with [`Error::InvalidDataSize`] otherwise.
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = Some(data);
+        self.line_stride = None;
+        self
+    }
+
+    /// Uses `data` as the frame buffer with a caller-chosen first-plane stride in bytes.
+    pub fn data_with_stride(mut self, data: Vec<u8>, line_stride: i32) -> Self {
+        self.data = Some(data);
+        self.line_stride = Some(line_stride);
+        self
+    }
+
+    pub fn build(self) -> Result<VideoFrame, Error> {
+        if self.frame_rate_n <= 0 || self.frame_rate_d <= 0 {
+            return Err(Error::InvalidFrameRate {
+                numerator: self.frame_rate_n,
+                denominator: self.frame_rate_d,
+            });
+        }
+        let stride = self
+            .line_stride
+            .unwrap_or_else(|| self.fourcc.line_stride(self.xres));
+        let expected = self.fourcc.check_layout(self.xres, self.yres, stride)?;
+        let data = match self.data {
+            Some(data) if data.len() != expected => {
+                return Err(Error::InvalidDataSize {
+                    fourcc: self.fourcc,
+                    xres: self.xres,
+                    yres: self.yres,
+                    line_stride: stride,
+                    expected,
+                    actual: data.len(),
+                });
+            }
+            Some(data) => data,
+            None => vec![0u8; expected],
         };
 
-        AudioFrame {
-            sample_rate: raw.sample_rate,
+        let metadata = self
+            .metadata
+            .map(CString::new)
+            .transpose()
+            .map_err(Error::InvalidCString)?;
+
+        let timecode = match &self.timecode_smpte {
+            Some(smpte) => Timecode::from_smpte(smpte, self.frame_rate_n, self.frame_rate_d)?.0,
+            None => self.timecode,
+        };
+
+        Ok(VideoFrame {
+            xres: self.xres,
+            yres: self.yres,
+            fourcc: self.fourcc,
+            frame_rate_n: self.frame_rate_n,
+            frame_rate_d: self.frame_rate_d,
+            picture_aspect_ratio: self.picture_aspect_ratio,
+            frame_format_type: self.frame_format_type,
+            timecode,
+            data,
+            line_stride_or_size: LineStrideOrSize {
+                line_stride_in_bytes: stride,
+            },
+            metadata,
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+/// A video frame that borrows its pixel data, for sending without copying into a [`VideoFrame`].
+#[derive(Debug, Clone, Copy)]
+pub struct BorrowedVideoFrame<'buf> {
+    pub xres: i32,
+    pub yres: i32,
+    pub fourcc: FourCCVideoType,
+    pub frame_rate_n: i32,
+    pub frame_rate_d: i32,
+    pub picture_aspect_ratio: f32,
+    pub frame_format_type: FrameFormatType,
+    pub timecode: i64,
+    pub timestamp: i64,
+    data: &'buf [u8],
+    line_stride: i32,
+}
+
+impl<'buf> BorrowedVideoFrame<'buf> {
+    /// Wraps a tightly packed buffer, checking it is large enough for every plane of the format.
+    pub fn try_from_buffer(
+        data: &'buf [u8],
+        xres: i32,
+        yres: i32,
+        fourcc: FourCCVideoType,
+    ) -> Result<Self, Error> {
+        Self::try_from_uncompressed(data, xres, yres, fourcc, fourcc.line_stride(xres))
+    }
+
+    /// Wraps a buffer whose first plane uses `line_stride` bytes per row, checking it is large
+    /// enough for every plane of the format.
+    pub fn try_from_uncompressed(
+        data: &'buf [u8],
+        xres: i32,
+        yres: i32,
+        fourcc: FourCCVideoType,
+        line_stride: i32,
+    ) -> Result<Self, Error> {
+        let required = fourcc.check_layout(xres, yres, line_stride)?;
+        if data.len() < required {
+            return Err(Error::InvalidDataSize {
+                fourcc,
+                xres,
+                yres,
+                line_stride,
+                expected: required,
+                actual: data.len(),
+            });
+        }
+        // Safety: the buffer has just been checked against the layout.
+        Ok(unsafe { Self::from_buffer_unchecked(data, xres, yres, fourcc, line_stride) })
+    }
+
+    /// Wraps a buffer without validating its length.
+    ///
+    /// # Safety
+    ///
+    /// `data` must hold at least as many bytes as the SDK reads for `fourcc` at this resolution
+    /// and stride, including any chroma or alpha planes.
+    pub unsafe fn from_buffer_unchecked(
+        data: &'buf [u8],
+        xres: i32,
+        yres: i32,
+        fourcc: FourCCVideoType,
+        line_stride: i32,
+    ) -> Self {
+        BorrowedVideoFrame {
+            xres,
+            yres,
+            fourcc,
+            frame_rate_n: 60,
+            frame_rate_d: 1,
+            picture_aspect_ratio: xres as f32 / yres.max(1) as f32,
+            frame_format_type: FrameFormatType::Progressive,
+            timecode: 0,
+            timestamp: 0,
+            data,
+            line_stride,
+        }
+    }
+
+    pub fn with_frame_rate(mut self, numerator: i32, denominator: i32) -> Self {
+        self.frame_rate_n = numerator;
+        self.frame_rate_d = denominator;
+        self
+    }
+
+    pub fn with_format(mut self, format: FrameFormatType) -> Self {
+        self.frame_format_type = format;
+        self
+    }
+
+    pub fn with_timecode(mut self, timecode: i64) -> Self {
+        self.timecode = timecode;
+        self
+    }
+
+    pub fn data(&self) -> &'buf [u8] {
+        self.data
+    }
+
+    pub fn line_stride(&self) -> i32 {
+        self.line_stride
+    }
+
+    pub(crate) fn to_raw(self) -> NDIlib_video_frame_v2_t {
+        NDIlib_video_frame_v2_t {
+            xres: self.xres,
+            yres: self.yres,
+            FourCC: self.fourcc.into(),
+            frame_rate_N: self.frame_rate_n,
+            frame_rate_D: self.frame_rate_d,
+            picture_aspect_ratio: self.picture_aspect_ratio,
+            frame_format_type: self.frame_format_type.into(),
+            timecode: self.timecode,
+            p_data: self.data.as_ptr() as *mut u8,
+            __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+                line_stride_in_bytes: self.line_stride,
+            },
+            p_metadata: ptr::null(),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Borrows an owned frame, e.g. one captured from a [`Recv`], for sending on.
+///
+/// Every field carries over except the metadata, which a [`BorrowedVideoFrame`] doesn't hold.
+/// Fails with [`Error::UnknownFourCc`] for a frame in a format [`FourCCVideoType`] can't
+/// name, and as [`BorrowedVideoFrame::try_from_uncompressed`] if the data doesn't cover the
+/// frame's layout.
+impl<'f> TryFrom<&'f VideoFrame> for BorrowedVideoFrame<'f> {
+    type Error = Error;
+
+    fn try_from(frame: &'f VideoFrame) -> Result<Self, Error> {
+        if frame.fourcc == FourCCVideoType::Max {
+            return Err(Error::UnknownFourCc(frame.fourcc.fourcc()));
+        }
+        let borrowed = BorrowedVideoFrame::try_from_uncompressed(
+            &frame.data,
+            frame.xres,
+            frame.yres,
+            frame.fourcc,
+            unsafe { frame.line_stride_or_size.line_stride_in_bytes },
+        )?;
+        Ok(BorrowedVideoFrame {
+            frame_rate_n: frame.frame_rate_n,
+            frame_rate_d: frame.frame_rate_d,
+            picture_aspect_ratio: frame.picture_aspect_ratio,
+            frame_format_type: frame.frame_format_type,
+            timecode: frame.timecode,
+            timestamp: frame.timestamp,
+            ..borrowed
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioFrame {
+    pub sample_rate: i32,
+    pub no_channels: i32,
+    pub no_samples: i32,
+    pub timecode: i64,
+    pub fourcc: AudioType,
+    pub data: Vec<u8>,
+    pub channel_stride_in_bytes: i32,
+    pub metadata: Option<CString>,
+    pub timestamp: i64,
+}
+
+impl AudioFrame {
+    pub fn new() -> Self {
+        AudioFrame {
+            sample_rate: 0,
+            no_channels: 0,
+            no_samples: 0,
+            timecode: 0,
+            fourcc: AudioType::Max, // TODO: Is this the right default?
+            data: vec![],
+            channel_stride_in_bytes: 0,
+            metadata: None,
+            timestamp: 0,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_data(
+        sample_rate: i32,
+        no_channels: i32,
+        no_samples: i32,
+        timecode: i64,
+        fourcc: AudioType, // TODO: Can this be merged with the fourcc on VideoFrame?
+        data: Vec<u8>,     // TODO: Many of these fields could be combined into a struct
+        metadata: Option<String>,
+        timestamp: i64,
+    ) -> Result<Self, Error> {
+        let metadata_cstring = metadata
+            .map(|m| CString::new(m).map_err(Error::InvalidCString))
+            .transpose()?;
+        Ok(AudioFrame {
+            sample_rate,
+            no_channels,
+            no_samples,
+            timecode,
+            fourcc,
+            data,
+            channel_stride_in_bytes: no_samples * 4,
+            metadata: metadata_cstring,
+            timestamp,
+        })
+    }
+
+    /// Checks that `data` holds `no_samples` floats for every channel at the channel stride.
+    pub fn validate(&self) -> Result<(), Error> {
+        let samples = self.no_samples.max(0) as usize;
+        let stride = self.channel_stride_in_bytes.max(0) as usize;
+        let required = match self.no_channels.max(0) as usize {
+            0 => 0,
+            channels => (channels - 1) * stride + samples * 4,
+        };
+        if stride < samples * 4 {
+            return Err(Error::InvalidChannelStride {
+                no_samples: self.no_samples,
+                expected: samples * 4,
+                actual: stride,
+            });
+        }
+        if self.data.len() < required {
+            return Err(Error::InvalidAudioDataSize {
+                no_channels: self.no_channels,
+                no_samples: self.no_samples,
+                channel_stride: self.channel_stride_in_bytes,
+                expected: required,
+                actual: self.data.len(),
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn to_raw(&self) -> NDIlib_audio_frame_v3_t {
+        NDIlib_audio_frame_v3_t {
+            sample_rate: self.sample_rate,
+            no_channels: self.no_channels,
+            no_samples: self.no_samples,
+            timecode: self.timecode,
+            FourCC: self.fourcc.into(),
+            p_data: self.data.as_ptr() as *mut u8,
+            __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
+                channel_stride_in_bytes: self.channel_stride_in_bytes,
+            },
+            p_metadata: self.metadata.as_ref().map_or(ptr::null(), |m| m.as_ptr()),
+            timestamp: self.timestamp,
+        }
+    }
+
+    pub(crate) fn from_raw(raw: NDIlib_audio_frame_v3_t) -> Self {
+        if raw.p_data.is_null() {
+            panic!("Received a null pointer for raw audio frame data");
+        }
+
+        if raw.sample_rate <= 0 {
+            panic!("Invalid sample rate: {}", raw.sample_rate);
+        }
+
+        if raw.no_channels <= 0 {
+            panic!("Invalid number of channels: {}", raw.no_channels);
+        }
+
+        if raw.no_samples <= 0 {
+            panic!("Invalid number of samples: {}", raw.no_samples);
+        }
+
+        let data_size = frame_ref::raw_audio_len(&raw);
+
+        if data_size == 0 {
+            panic!("Calculated data length is zero");
+        }
+
+        let data = unsafe { std::slice::from_raw_parts(raw.p_data, data_size).to_vec() };
+
+        // The SDK owns the metadata string, so copy it rather than taking ownership.
+        let metadata = if raw.p_metadata.is_null() {
+            None
+        } else {
+            Some(CString::from(unsafe { CStr::from_ptr(raw.p_metadata) }))
+        };
+
+        AudioFrame {
+            sample_rate: raw.sample_rate,
             no_channels: raw.no_channels,
             no_samples: raw.no_samples,
             timecode: raw.timecode,
@@ -669,6 +1474,197 @@ impl Drop for AudioFrame {
     }
 }
 
+/// Builds a planar float [`AudioFrame`] with any number of channels, checking the samples
+/// supplied match the channel and sample counts.
+#[derive(Debug, Clone)]
+pub struct AudioFrameBuilder {
+    sample_rate: i32,
+    no_channels: i32,
+    no_samples: i32,
+    timecode: i64,
+    data: Option<Vec<f32>>,
+    metadata: Option<String>,
+    timestamp: i64,
+    /// The first invalid write, reported by [`build`](Self::build).
+    invalid: Option<InvalidChannelWrite>,
+}
+
+/// A [`AudioFrameBuilder::set_channel_data`] call that makes the build fail.
+#[derive(Debug, Clone, Copy)]
+enum InvalidChannelWrite {
+    OutOfRange { channel: usize, channels: usize },
+    Length { expected: usize, actual: usize },
+}
+
+impl From<InvalidChannelWrite> for Error {
+    fn from(write: InvalidChannelWrite) -> Self {
+        match write {
+            InvalidChannelWrite::OutOfRange { channel, channels } => {
+                Error::ChannelOutOfRange { channel, channels }
+            }
+            InvalidChannelWrite::Length { expected, actual } => Error::BufferSizeMismatch {
+                unit: "samples",
+                expected,
+                actual,
+            },
+        }
+    }
+}
+
+impl Default for AudioFrameBuilder {
+    fn default() -> Self {
+        AudioFrameBuilder {
+            sample_rate: 48_000,
+            no_channels: 2,
+            no_samples: 0,
+            timecode: 0,
+            data: None,
+            metadata: None,
+            timestamp: 0,
+            invalid: None,
+        }
+    }
+}
+
+impl AudioFrameBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sample_rate(mut self, sample_rate: i32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Sets the channel count. Channel data already set is kept only if the count is unchanged.
+    pub fn channels(mut self, no_channels: i32) -> Self {
+        if no_channels != self.no_channels {
+            self.data = None;
+        }
+        self.no_channels = no_channels;
+        self
+    }
+
+    /// Sets the samples per channel. Channel data already set is kept only if the count is
+    /// unchanged.
+    pub fn samples(mut self, no_samples: i32) -> Self {
+        if no_samples != self.no_samples {
+            self.data = None;
+        }
+        self.no_samples = no_samples;
+        self
+    }
+
+    /// Sets the timecode in 100 ns units; pass [`Timecode::SYNTHESIZE`] to have the SDK fill it in.
+    pub fn timecode(mut self, timecode: impl Into<i64>) -> Self {
+        self.timecode = timecode.into();
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn metadata(mut self, metadata: &str) -> Self {
+        self.metadata = Some(metadata.to_string());
+        self
+    }
+
+    /// Uses `data` as the samples, one channel after another. It must hold exactly
+    /// `samples * channels` values; [`build`](Self::build) fails otherwise.
+    pub fn data(mut self, data: Vec<f32>) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Uses interleaved samples, one per channel in turn, converting them to planar. Set the
+    /// channel count first.
+    pub fn interleaved(mut self, samples: &[f32]) -> Self {
+        let channels = self.no_channels.max(1) as usize;
+        let mut data = Vec::with_capacity(samples.len());
+        for channel in 0..channels {
+            data.extend(samples.iter().skip(channel).step_by(channels));
+        }
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the samples of one channel, leaving the others as set before (or silent).
+    ///
+    /// `samples` must hold exactly the configured number of samples and `channel` be in
+    /// range; [`build`](Self::build) fails otherwise.
+    pub fn set_channel_data(mut self, channel: usize, samples: &[f32]) -> Self {
+        let (channels, count) = (
+            self.no_channels.max(0) as usize,
+            self.no_samples.max(0) as usize,
+        );
+        let invalid = if channel >= channels {
+            Some(InvalidChannelWrite::OutOfRange { channel, channels })
+        } else if samples.len() != count {
+            Some(InvalidChannelWrite::Length {
+                expected: count,
+                actual: samples.len(),
+            })
+        } else {
+            None
+        };
+        if let Some(invalid) = invalid {
+            self.invalid.get_or_insert(invalid);
+            return self;
+        }
+        let data = self.data.get_or_insert_with(|| vec![0.0; channels * count]);
+        if data.len() == channels * count {
+            data[channel * count..(channel + 1) * count].copy_from_slice(samples);
+        }
+        self
+    }
+
+    pub fn build(self) -> Result<AudioFrame, Error> {
+        if let Some(invalid) = self.invalid {
+            return Err(invalid.into());
+        }
+        let layout = Error::InvalidAudioLayout {
+            no_channels: self.no_channels,
+            no_samples: self.no_samples,
+            sample_rate: self.sample_rate,
+        };
+        if self.sample_rate <= 0 || self.no_channels <= 0 || self.no_samples < 0 {
+            return Err(layout);
+        }
+        let channels = self.no_channels as usize;
+        let samples = self.no_samples as usize;
+        let expected = channels
+            .checked_mul(samples)
+            .filter(|&n| {
+                n.checked_mul(4)
+                    .is_some_and(|bytes| bytes <= i32::MAX as usize)
+            })
+            .ok_or(layout)?;
+        let data = match self.data {
+            Some(data) if data.len() != expected => {
+                return Err(Error::BufferSizeMismatch {
+                    unit: "samples",
+                    expected,
+                    actual: data.len(),
+                });
+            }
+            Some(data) => data,
+            None => vec![0.0; expected],
+        };
+        AudioFrame::with_data(
+            self.sample_rate,
+            self.no_channels,
+            self.no_samples,
+            self.timecode,
+            AudioType::FLTP,
+            data.iter().flat_map(|s| s.to_ne_bytes()).collect(),
+            self.metadata,
+            self.timestamp,
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AudioType {
     FLTP,
@@ -694,22 +1690,60 @@ impl From<AudioType> for u32 {
     }
 }
 
+/// A metadata frame carrying an XML document.
 #[derive(Debug)]
 pub struct MetadataFrame {
+    /// The length of the XML at `p_data`, including its nul terminator.
+    #[deprecated(
+        note = "use `MetadataFrame::data`; this field will be removed in a future release"
+    )]
     pub length: i32,
     pub timecode: i64,
+    /// Points at the frame's own XML unless set by hand, and is what gets sent.
+    #[deprecated(
+        note = "use `MetadataFrame::data` or `MetadataFrame::from_xml`; this field will be removed in a future release"
+    )]
     pub p_data: *mut c_char,
+    data: CString,
 }
 
+// SAFETY: `p_data` points into `data`, which moves with the frame, or at XML the caller lent
+// for sending; the frame never writes through it.
+unsafe impl std::marker::Send for MetadataFrame {}
+unsafe impl Sync for MetadataFrame {}
+
+#[allow(deprecated)]
 impl MetadataFrame {
     pub fn new() -> Self {
+        Self::with_data(CString::default(), 0)
+    }
+
+    pub fn from_xml(xml: &str, timecode: i64) -> Result<Self, Error> {
+        Ok(Self::with_data(
+            CString::new(xml).map_err(Error::InvalidCString)?,
+            timecode,
+        ))
+    }
+
+    fn with_data(data: CString, timecode: i64) -> Self {
         MetadataFrame {
-            length: 0,
-            timecode: 0,
-            p_data: ptr::null_mut(),
+            length: data.as_bytes_with_nul().len() as i32,
+            timecode,
+            p_data: data.as_ptr() as *mut c_char,
+            data,
         }
     }
 
+    pub fn data(&self) -> &CStr {
+        &self.data
+    }
+
+    pub fn as_str(&self) -> Result<&str, Error> {
+        self.data
+            .to_str()
+            .map_err(|e| Error::InvalidUtf8(e.to_string()))
+    }
+
     pub(crate) fn to_raw(&self) -> NDIlib_metadata_frame_t {
         NDIlib_metadata_frame_t {
             length: self.length,
@@ -718,12 +1752,26 @@ impl MetadataFrame {
         }
     }
 
-    pub(crate) fn from_raw(raw: NDIlib_metadata_frame_t) -> Self {
-        MetadataFrame {
-            length: raw.length,
-            timecode: raw.timecode,
-            p_data: raw.p_data,
+    /// Copies a frame owned by the SDK; the caller still has to free `raw`.
+    pub(crate) unsafe fn from_raw(raw: &NDIlib_metadata_frame_t) -> Self {
+        let data = if raw.p_data.is_null() {
+            CString::default()
+        } else {
+            CString::from(CStr::from_ptr(raw.p_data))
+        };
+        Self::with_data(data, raw.timecode)
+    }
+}
+
+#[allow(deprecated)]
+impl Clone for MetadataFrame {
+    fn clone(&self) -> Self {
+        let mut frame = Self::with_data(self.data.clone(), self.timecode);
+        if !ptr::eq(self.p_data, self.data.as_ptr()) {
+            frame.length = self.length;
+            frame.p_data = self.p_data;
         }
+        frame
     }
 }
 
@@ -734,6 +1782,7 @@ impl Default for MetadataFrame {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RecvColorFormat {
     BGRX_BGRA,
     UYVY_BGRA,
@@ -745,6 +1794,41 @@ pub enum RecvColorFormat {
     Max,
 }
 
+impl RecvColorFormat {
+    /// The format for [`Receiver::prefer_alpha`] and [`Receiver::prefer_yuv`].
+    fn from_preferences(alpha: bool, yuv: bool, rgba_order: bool) -> Self {
+        match (yuv, alpha, rgba_order) {
+            (false, _, false) => RecvColorFormat::BGRX_BGRA,
+            (false, _, true) => RecvColorFormat::RGBX_RGBA,
+            (true, true, false) => RecvColorFormat::UYVY_BGRA,
+            (true, true, true) => RecvColorFormat::UYVY_RGBA,
+            // Delivers UYVY, or UYVA for video with alpha, without an RGB conversion.
+            (true, false, _) => RecvColorFormat::Fastest,
+        }
+    }
+
+    fn keeps_alpha(self) -> bool {
+        !matches!(self, RecvColorFormat::Fastest)
+    }
+
+    fn prefers_yuv(self) -> bool {
+        matches!(
+            self,
+            RecvColorFormat::UYVY_BGRA
+                | RecvColorFormat::UYVY_RGBA
+                | RecvColorFormat::Fastest
+                | RecvColorFormat::Best
+        )
+    }
+
+    fn rgba_order(self) -> bool {
+        matches!(
+            self,
+            RecvColorFormat::RGBX_RGBA | RecvColorFormat::UYVY_RGBA
+        )
+    }
+}
+
 impl From<RecvColorFormat> for NDIlib_recv_color_format_e {
     fn from(format: RecvColorFormat) -> Self {
         match format {
@@ -771,6 +1855,7 @@ impl From<RecvColorFormat> for NDIlib_recv_color_format_e {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RecvBandwidth {
     MetadataOnly,
     AudioOnly,
@@ -793,13 +1878,30 @@ impl From<RecvBandwidth> for NDIlib_recv_bandwidth_e {
     }
 }
 
+/// How a receiver delivers interlaced video (`allow_video_fields` in the SDK).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldMode {
+    /// Interlaced video arrives as whole frames with both fields interleaved, marked
+    /// [`FrameFormatType::Interlaced`].
+    Progressive,
+    /// Interlaced video may arrive one field at a time, as [`FrameFormatType::Field0`] and
+    /// [`FrameFormatType::Field1`] frames of half height; a [`FieldPairer`] reassembles them.
+    /// This is the SDK's default and avoids waiting for the second field.
+    Fielded,
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Receiver {
     pub source_to_connect_to: Source,
     pub color_format: RecvColorFormat,
     pub bandwidth: RecvBandwidth,
-    pub allow_video_fields: bool,
+    pub field_mode: FieldMode,
     pub ndi_recv_name: Option<String>,
+    /// Configuration for this receiver only, overriding `ndi-config.v1.json` (Advanced SDK).
+    pub config: Option<NdiConfig>,
 }
 
 impl Default for Receiver {
@@ -812,126 +1914,539 @@ impl Default for Receiver {
             },
             color_format: RecvColorFormat::BGRX_BGRA,
             bandwidth: RecvBandwidth::Highest,
-            allow_video_fields: true,
+            field_mode: FieldMode::Fielded,
             ndi_recv_name: None,
+            config: None,
+        }
+    }
+}
+
+impl Receiver {
+    pub fn new(
+        source_to_connect_to: Source,
+        color_format: RecvColorFormat,
+        bandwidth: RecvBandwidth,
+        field_mode: FieldMode,
+        ndi_recv_name: Option<String>,
+    ) -> Self {
+        Receiver {
+            source_to_connect_to,
+            color_format,
+            bandwidth,
+            field_mode,
+            ndi_recv_name,
+            config: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: NdiConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Sets the name the receiver announces to sources, as listed in their connections.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.ndi_recv_name = Some(name.into());
+        self
+    }
+
+    /// Chooses a `color_format` by whether the alpha channel of sources that send one must
+    /// be kept, leaving the YUV preference and RGB channel order as they are.
+    ///
+    /// Opaque video is never given an alpha channel. With `true`, video with alpha arrives as
+    /// BGRA or RGBA; with `false` and YUV preferred, it may arrive as UYVA, whose first plane
+    /// is plain UYVY. Check [`VideoFrame::has_alpha`] for what a frame actually carries.
+    pub fn prefer_alpha(mut self, alpha: bool) -> Self {
+        self.color_format = RecvColorFormat::from_preferences(
+            alpha,
+            self.color_format.prefers_yuv(),
+            self.color_format.rgba_order(),
+        );
+        self
+    }
+
+    /// Chooses a `color_format` by whether opaque video should arrive as UYVY rather than
+    /// BGRX or RGBX, leaving the alpha preference and RGB channel order as they are.
+    ///
+    /// UYVY is half the size and is what the SDK decodes to natively, so it is the cheaper
+    /// choice unless the frames are going straight to an RGB consumer.
+    pub fn prefer_yuv(mut self, yuv: bool) -> Self {
+        self.color_format = RecvColorFormat::from_preferences(
+            self.color_format.keeps_alpha(),
+            yuv,
+            self.color_format.rgba_order(),
+        );
+        self
+    }
+
+    /// Settings for the least glass-to-glass latency: the SDK's fastest color format, which
+    /// skips any conversion, and whole frames rather than separate fields.
+    ///
+    /// Pair it with [`Recv::capture_latest_video_ref`] so frames never wait in the receive
+    /// queue, and measure the result with a [`LatencyMeter`].
+    pub fn low_latency_preset(mut self) -> Self {
+        self.color_format = RecvColorFormat::Fastest;
+        self.field_mode = FieldMode::Progressive;
+        self
+    }
+
+    /// Calls `f` with the SDK's create settings, whose strings live until `f` returns.
+    fn with_raw<T>(
+        &self,
+        f: impl FnOnce(&NDIlib_recv_create_v3_t) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let name = self
+            .ndi_recv_name
+            .as_deref()
+            .map(CString::new)
+            .transpose()
+            .map_err(Error::InvalidCString)?;
+
+        self.source_to_connect_to.with_raw(|source| {
+            f(&NDIlib_recv_create_v3_t {
+                source_to_connect_to: *source,
+                color_format: self.color_format.into(),
+                bandwidth: self.bandwidth.into(),
+                allow_video_fields: self.field_mode == FieldMode::Fielded,
+                p_ndi_recv_name: name.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            })
+        })
+    }
+
+    /// Creates and connects a receiver instance, returning null if the SDK refused.
+    fn create_instance(&self) -> Result<NDIlib_recv_instance_t, Error> {
+        self.with_raw(|create_t| {
+            let instance = config::create_recv(create_t, self.config.as_ref())?;
+            if !instance.is_null() {
+                unsafe { NDIlib_recv_connect(instance, &create_t.source_to_connect_to) };
+            }
+            Ok(instance)
+        })
+    }
+}
+
+pub struct Recv<'a> {
+    instance: NDIlib_recv_instance_t,
+    source: Source,
+    /// What the instance was created from, for recreating it with different settings.
+    settings: Receiver,
+    /// The bandwidth to resume at while paused.
+    paused_bandwidth: Option<RecvBandwidth>,
+    stats_monitors: Mutex<Vec<Arc<MonitorShared>>>,
+    callback_threads: Mutex<Vec<Arc<CallbackShared>>>,
+    /// Shared with the threads [`Recv::lend`] hands the instance to.
+    state: Arc<CaptureState>,
+    capture_config: CaptureConfig,
+    capture_thread: CaptureThreadConfig,
+    ndi: std::marker::PhantomData<&'a NDI>,
+}
+
+impl<'a> Recv<'a> {
+    pub fn new(_ndi: &'a NDI, create: Receiver) -> Result<Self, Error> {
+        // SAFETY: the borrow of `_ndi` keeps the runtime alive for as long as the receiver.
+        unsafe { Self::new_detached(create) }
+    }
+
+    /// As [`Find::new_detached`]: the caller keeps an `NDI` alive until the receiver is dropped.
+    pub(crate) unsafe fn new_detached(create: Receiver) -> Result<Self, Error> {
+        let instance = create.create_instance()?;
+        if instance.is_null() {
+            ndi_warn!(source = %create.source_to_connect_to.name, "NDIlib_recv_create_v3 failed");
+            Err(Error::InitializationFailed(
+                "Failed to create NDI recv instance".into(),
+            ))
+        } else {
+            ndi_info!(source = %create.source_to_connect_to.name, "receiver connecting");
+            let state = Arc::new(CaptureState::new(create.source_to_connect_to.name.clone()));
+            Ok(Recv {
+                instance,
+                source: create.source_to_connect_to.clone(),
+                settings: create,
+                paused_bandwidth: None,
+                stats_monitors: Mutex::new(Vec::new()),
+                callback_threads: Mutex::new(Vec::new()),
+                state,
+                capture_config: CaptureConfig::default(),
+                capture_thread: CaptureThreadConfig::default(),
+                ndi: std::marker::PhantomData,
+            })
+        }
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// The bandwidth the receiver runs at once resumed, if paused.
+    pub fn bandwidth(&self) -> RecvBandwidth {
+        self.paused_bandwidth.unwrap_or(self.settings.bandwidth)
+    }
+
+    /// Switches the receiver to `bandwidth`, e.g. to bring a multiviewer tile up to full
+    /// quality while it has focus.
+    ///
+    /// The SDK fixes bandwidth when a receiver is created and reconnecting doesn't change it,
+    /// so this creates a new instance connected to the same source and swaps it in. The
+    /// source sees a brief reconnection, frames queued in the old instance are discarded, and
+    /// a tally set with [`Recv::set_tally`] must be set again. On error the receiver is
+    /// left as it was.
+    ///
+    /// While the receiver is paused this only changes the bandwidth it resumes at.
+    pub fn set_bandwidth(&mut self, bandwidth: RecvBandwidth) -> Result<(), Error> {
+        if self.paused_bandwidth.is_some() {
+            self.paused_bandwidth = Some(bandwidth);
+            return Ok(());
+        }
+        self.recreate(bandwidth)
+    }
+
+    /// Stops the source sending video and audio, e.g. for a multiviewer tile that is hidden,
+    /// while staying connected for metadata and tally.
+    ///
+    /// This switches to [`RecvBandwidth::MetadataOnly`] with the same brief reconnection as
+    /// [`Recv::set_bandwidth`], after which nothing is decoded. Does nothing if already paused.
+    pub fn pause(&mut self) -> Result<(), Error> {
+        if self.paused_bandwidth.is_none() {
+            let bandwidth = self.settings.bandwidth;
+            self.recreate(RecvBandwidth::MetadataOnly)?;
+            self.paused_bandwidth = Some(bandwidth);
+        }
+        Ok(())
+    }
+
+    /// Returns to the bandwidth the receiver had before [`Recv::pause`].
+    pub fn resume(&mut self) -> Result<(), Error> {
+        if let Some(bandwidth) = self.paused_bandwidth {
+            self.recreate(bandwidth)?;
+            self.paused_bandwidth = None;
+        }
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_bandwidth.is_some()
+    }
+
+    /// The name this receiver announces to sources, as listed in their connections; `None`
+    /// if it was created without one, in which case the SDK picks one.
+    pub fn name(&self) -> Option<&str> {
+        self.settings.ndi_recv_name.as_deref()
+    }
+
+    /// Changes the name this receiver announces to sources.
+    ///
+    /// The SDK fixes the name when a receiver is created, so this swaps in a new instance
+    /// with the same brief reconnection and caveats as [`Recv::set_bandwidth`]. On error the
+    /// receiver is left as it was.
+    pub fn set_name(&mut self, name: impl Into<String>) -> Result<(), Error> {
+        let mut settings = self.settings.clone();
+        settings.ndi_recv_name = Some(name.into());
+        self.replace_instance(settings)?;
+        ndi_info!(source = %self.source.name, name = ?self.name(), "receiver renamed");
+        Ok(())
+    }
+
+    /// Swaps in a new instance running at `bandwidth`.
+    fn recreate(&mut self, bandwidth: RecvBandwidth) -> Result<(), Error> {
+        let mut settings = self.settings.clone();
+        settings.bandwidth = bandwidth;
+        self.replace_instance(settings)?;
+        ndi_info!(source = %self.source.name, ?bandwidth, "receiver bandwidth changed");
+        Ok(())
+    }
+
+    /// Swaps in a new instance created from `settings`, connected to the same source.
+    fn replace_instance(&mut self, settings: Receiver) -> Result<(), Error> {
+        let instance = settings.create_instance()?;
+        if instance.is_null() {
+            ndi_warn!(source = %self.source.name, "NDIlib_recv_create_v3 failed");
+            return Err(Error::InitializationFailed(
+                "Failed to create NDI recv instance".into(),
+            ));
+        }
+        self.stop_stats_monitors();
+        let stats = self.connection_stats();
+        self.stats_baselines().absorb(&stats);
+        #[cfg(feature = "persistent_stats")]
+        if let Ok(Some(persistence)) = self.state.persistent_stats.lock().as_deref_mut() {
+            persistence.absorb(&stats);
+        }
+        unsafe { NDIlib_recv_destroy(self.instance) };
+        self.instance = instance;
+        self.settings = settings;
+        Ok(())
+    }
+
+    /// Stops any leaked [`StatsMonitor`] or [`CaptureHandle`], which would otherwise keep
+    /// using a destroyed instance.
+    fn stop_stats_monitors(&mut self) {
+        if let Ok(monitors) = self.stats_monitors.get_mut() {
+            for monitor in monitors.drain(..) {
+                monitor.stop();
+            }
+        }
+        if let Ok(threads) = self.callback_threads.get_mut() {
+            for thread in threads.drain(..) {
+                thread.stop();
+            }
+        }
+    }
+
+    /// Accumulates this receiver's statistics into the JSON file at `path`.
+    ///
+    /// Totals already stored there are loaded now and the updated totals are written back when
+    /// the receiver is dropped, so counts survive restarts of the application. Use one file per
+    /// source: a file holding another source's totals is refused with
+    /// [`Error::StatsSourceMismatch`].
+    #[cfg(feature = "persistent_stats")]
+    pub fn with_persistent_stats(self, path: impl Into<std::path::PathBuf>) -> Result<Self, Error> {
+        let mut persistence = StatsPersistence::open(path.into(), &self.source.name)?;
+        persistence.observe_connections(self.connections());
+        if let Ok(mut stats) = self.state.persistent_stats.lock() {
+            *stats = Some(persistence);
         }
+        Ok(self)
     }
-}
 
-impl Receiver {
-    pub fn new(
-        source_to_connect_to: Source,
-        color_format: RecvColorFormat,
-        bandwidth: RecvBandwidth,
-        allow_video_fields: bool,
-        ndi_recv_name: Option<String>,
-    ) -> Self {
-        Receiver {
-            source_to_connect_to,
-            color_format,
-            bandwidth,
-            allow_video_fields,
-            ndi_recv_name,
+    /// Lifetime totals including the current session, if persistence is enabled.
+    #[cfg(feature = "persistent_stats")]
+    pub fn persistent_stats(&self) -> Option<PersistentStats> {
+        let stats = self.connection_stats();
+        let persistence = self.state.persistent_stats.lock().ok()?;
+        persistence.as_ref().map(|p| p.snapshot(&stats))
+    }
+
+    /// Writes the lifetime totals to disk now rather than waiting for the receiver to be dropped.
+    #[cfg(feature = "persistent_stats")]
+    pub fn save_persistent_stats(&self) -> Result<(), Error> {
+        let stats = self.connection_stats();
+        match self.state.persistent_stats.lock().as_deref() {
+            Ok(Some(persistence)) => persistence.save(&stats),
+            _ => Ok(()),
         }
     }
 
-    pub(crate) fn to_raw(&self) -> Result<NDIlib_recv_create_v3_t, Error> {
-        let p_ndi_recv_name = match &self.ndi_recv_name {
-            Some(name) => CString::new(name.clone())
-                .map_err(Error::InvalidCString)?
-                .into_raw(),
-            None => ptr::null(),
-        };
+    pub fn connections(&self) -> i32 {
+        unsafe { NDIlib_recv_get_no_connections(self.instance) }
+    }
 
-        Ok(NDIlib_recv_create_v3_t {
-            source_to_connect_to: self.source_to_connect_to.to_raw()?,
-            color_format: self.color_format.into(),
-            bandwidth: self.bandwidth.into(),
-            allow_video_fields: self.allow_video_fields,
-            p_ndi_recv_name,
-        })
+    /// The SDK's counters, which accumulate for as long as the instance lives, across
+    /// reconnects and [`Recv::reset_stats`].
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.lend().connection_stats()
     }
 
-    // TODO: Does this need a drop impl since it made a CString in to_raw?
-}
+    /// Starts the counts returned by [`Recv::stats_since_reset`] again from zero, e.g. after
+    /// an alert has been handled.
+    pub fn reset_stats(&self) {
+        let stats = self.connection_stats();
+        self.stats_baselines().reset(&stats);
+    }
 
-pub struct Recv<'a> {
-    instance: NDIlib_recv_instance_t,
-    ndi: std::marker::PhantomData<&'a NDI>,
-}
+    /// Frame counts since [`Recv::reset_stats`] was last called, or since the receiver was
+    /// created.
+    pub fn stats_since_reset(&self) -> ConnectionStats {
+        let stats = self.connection_stats();
+        self.stats_baselines().since_reset(&stats)
+    }
 
-impl<'a> Recv<'a> {
-    pub fn new(_ndi: &'a NDI, create: Receiver) -> Result<Self, Error> {
-        let create_t = create.to_raw()?;
-        let instance = unsafe { NDIlib_recv_create_v3(&create_t) };
-        if instance.is_null() {
-            Err(Error::InitializationFailed(
-                "Failed to create NDI recv instance".into(),
-            ))
-        } else {
-            unsafe { NDIlib_recv_connect(instance, &create_t.source_to_connect_to) };
-            Ok(Recv {
-                instance,
-                ndi: std::marker::PhantomData,
-            })
-        }
+    /// Frame counts since the receiver last connected to its source, so drop rates reflect
+    /// the current connection rather than ones that have since been lost.
+    ///
+    /// Reconnects are noticed whenever the receiver's statistics are read, so one that comes
+    /// and goes between two reads starts no new epoch.
+    pub fn epoch_stats(&self) -> EpochStats {
+        let stats = self.connection_stats();
+        self.stats_baselines().epoch(&stats)
+    }
+
+    /// The baselines, recovered from a thread that panicked while holding them; they are
+    /// updated whole, so a poisoned lock still guards consistent counts.
+    fn stats_baselines(&self) -> std::sync::MutexGuard<'_, StatsBaselines> {
+        self.state
+            .stats_baselines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Starts sampling this receiver's statistics every `interval` on a background thread.
+    pub fn monitor_stats(&self, interval: Duration, window: Duration) -> StatsMonitor<'_> {
+        StatsMonitor::new(self, interval, window)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(source = %self.source.name, timeout_ms))
+    )]
     pub fn capture(&mut self, timeout_ms: u32) -> Result<FrameType, Error> {
-        let mut video_frame = NDIlib_video_frame_v2_t::default();
-        let mut audio_frame = NDIlib_audio_frame_v3_t::default();
-        let mut metadata_frame = NDIlib_metadata_frame_t::default();
+        self.capture_frame(timeout_ms)
+    }
+
+    /// [`Recv::capture`] for threads sharing the receiver.
+    pub(crate) fn capture_frame(&self, timeout_ms: u32) -> Result<FrameType, Error> {
+        self.lend().capture_frame(timeout_ms)
+    }
+
+    /// The instance and capture state, for a thread that captures from or samples this
+    /// receiver. The thread must be stopped and joined before the receiver is dropped or its
+    /// instance replaced, as [`Recv::stop_stats_monitors`] does for callbacks and monitors.
+    pub(crate) fn lend(&self) -> LentRecv {
+        // SAFETY: the instance lives until `drop` or `replace_instance`; see `LentRecv`.
+        unsafe { LentRecv::new(self.instance, Arc::clone(&self.state)) }
+    }
 
+    /// Captures a video frame without copying it; the SDK gets the frame back on drop.
+    ///
+    /// Other frame types arriving meanwhile are discarded. Returns `Ok(None)` on timeout or
+    /// status change. Once running, this performs no heap allocations.
+    pub fn capture_video_ref(&self, timeout_ms: u32) -> Result<Option<VideoFrameRef<'_>>, Error> {
+        let mut video_frame = NDIlib_video_frame_v2_t::default();
         let frame_type = unsafe {
             NDIlib_recv_capture_v3(
                 self.instance,
                 &mut video_frame,
-                &mut audio_frame,
-                &mut metadata_frame,
+                ptr::null_mut(),
+                ptr::null_mut(),
                 timeout_ms,
             )
         };
-
         match frame_type {
             NDIlib_frame_type_e_NDIlib_frame_type_video => {
+                let frame = unsafe { VideoFrameRef::from_raw(self.instance, video_frame) };
                 if video_frame.p_data.is_null() {
                     Err(Error::NullPointer("Video frame data is null".into()))
                 } else {
-                    let frame = unsafe { VideoFrame::from_raw(&video_frame) };
-                    unsafe { NDIlib_recv_free_video_v2(self.instance, &video_frame) };
-                    Ok(FrameType::Video(frame))
+                    self.state
+                        .bytes
+                        .record(FrameKind::Video, frame.data().len());
+                    self.state.video_format.observe((&frame).into());
+                    Ok(Some(frame))
                 }
             }
+            other => self.no_frame(other),
+        }
+    }
+
+    /// Captures an audio frame without copying it; the SDK gets the frame back on drop.
+    ///
+    /// Other frame types arriving meanwhile are discarded. Returns `Ok(None)` on timeout or
+    /// status change. Once running, this performs no heap allocations.
+    pub fn capture_audio_ref(&self, timeout_ms: u32) -> Result<Option<AudioFrameRef<'_>>, Error> {
+        let mut audio_frame = NDIlib_audio_frame_v3_t::default();
+        let frame_type = unsafe {
+            NDIlib_recv_capture_v3(
+                self.instance,
+                ptr::null_mut(),
+                &mut audio_frame,
+                ptr::null_mut(),
+                timeout_ms,
+            )
+        };
+        match frame_type {
             NDIlib_frame_type_e_NDIlib_frame_type_audio => {
+                let frame = unsafe { AudioFrameRef::from_raw(self.instance, audio_frame) };
                 if audio_frame.p_data.is_null() {
                     Err(Error::NullPointer("Audio frame data is null".into()))
                 } else {
-                    let frame = AudioFrame::from_raw(audio_frame);
-                    unsafe { NDIlib_recv_free_audio_v3(self.instance, &audio_frame) };
-                    Ok(FrameType::Audio(frame))
+                    self.state
+                        .bytes
+                        .record(FrameKind::Audio, std::mem::size_of_val(frame.data()));
+                    Ok(Some(frame))
                 }
             }
+            other => self.no_frame(other),
+        }
+    }
+
+    /// Captures an audio frame and appends its samples to `samples`, interleaved.
+    ///
+    /// Suited to audio callback APIs that fill their own buffers: reusing `samples` (or
+    /// draining it as a FIFO) avoids allocating per frame. Returns `Ok(None)` on timeout or
+    /// status change, leaving `samples` untouched.
+    pub fn capture_audio_into(
+        &self,
+        samples: &mut Vec<f32>,
+        timeout_ms: u32,
+    ) -> Result<Option<AudioFrameInfo>, Error> {
+        Ok(self.capture_audio_ref(timeout_ms)?.map(|frame| {
+            frame.append_interleaved(samples);
+            frame.info()
+        }))
+    }
+
+    /// Captures a metadata frame without copying it; the SDK gets the frame back on drop.
+    ///
+    /// Other frame types arriving meanwhile are discarded. Returns `Ok(None)` on timeout or
+    /// status change. Once running, this performs no heap allocations.
+    pub fn capture_metadata_ref(
+        &self,
+        timeout_ms: u32,
+    ) -> Result<Option<MetadataFrameRef<'_>>, Error> {
+        let mut metadata_frame = NDIlib_metadata_frame_t::default();
+        let frame_type = unsafe {
+            NDIlib_recv_capture_v3(
+                self.instance,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                &mut metadata_frame,
+                timeout_ms,
+            )
+        };
+        match frame_type {
             NDIlib_frame_type_e_NDIlib_frame_type_metadata => {
+                let frame = unsafe { MetadataFrameRef::from_raw(self.instance, metadata_frame) };
                 if metadata_frame.p_data.is_null() {
                     Err(Error::NullPointer("Metadata frame data is null".into()))
                 } else {
-                    let frame = MetadataFrame::from_raw(metadata_frame);
-                    unsafe { NDIlib_recv_free_metadata(self.instance, &metadata_frame) };
-                    Ok(FrameType::Metadata(frame))
+                    self.state
+                        .bytes
+                        .record(FrameKind::Metadata, frame.data().to_bytes().len());
+                    if let Ok(xml) = frame.as_str() {
+                        self.lend().observe_metadata(xml);
+                    }
+                    Ok(Some(frame))
                 }
             }
-            NDIlib_frame_type_e_NDIlib_frame_type_none => Ok(FrameType::None),
-            NDIlib_frame_type_e_NDIlib_frame_type_status_change => Ok(FrameType::StatusChange),
+            other => self.no_frame(other),
+        }
+    }
+
+    /// Maps a capture result that carries no frame of the requested type.
+    ///
+    /// Error messages are only built on the error path so that the steady state stays
+    /// allocation free.
+    fn no_frame<T>(&self, frame_type: NDIlib_frame_type_e) -> Result<Option<T>, Error> {
+        match frame_type {
+            NDIlib_frame_type_e_NDIlib_frame_type_status_change => {
+                self.lend().on_status_change();
+                Ok(None)
+            }
             NDIlib_frame_type_e_NDIlib_frame_type_error => {
-                Err(Error::CaptureFailed("Received an error frame".into()))
+                ndi_warn!(source = %self.source.name, "receiver returned an error frame");
+                Err(Error::ErrorFrame)
             }
-            _ => Err(Error::CaptureFailed(format!(
-                "Unknown frame type: {}",
-                frame_type
-            ))),
+            NDIlib_frame_type_e_NDIlib_frame_type_none => {
+                ndi_trace!(source = %self.source.name, "capture timed out");
+                Ok(None)
+            }
+            NDIlib_frame_type_e_NDIlib_frame_type_video
+            | NDIlib_frame_type_e_NDIlib_frame_type_audio
+            | NDIlib_frame_type_e_NDIlib_frame_type_metadata => Ok(None),
+            _ => Err(Error::UnknownFrameType(i64::from(frame_type))),
         }
     }
 
+    /// Sends a metadata frame upstream to the source, e.g. a control command for the sender to
+    /// pick up with [`Send::capture_event`]. Returns `false` if the source isn't connected.
+    pub fn send_metadata(&self, metadata_frame: &MetadataFrame) -> bool {
+        unsafe { NDIlib_recv_send_metadata(self.instance, &metadata_frame.to_raw()) }
+    }
+
+    /// Tells the source whether this receiver is showing it on program or preview.
+    pub fn set_tally(&self, tally: &Tally) -> bool {
+        unsafe { NDIlib_recv_set_tally(self.instance, &tally.to_raw()) }
+    }
+
     #[allow(dead_code)]
     pub fn free_string(&self, string: &str) {
         let c_string = CString::new(string).expect("Failed to create CString");
@@ -944,6 +2459,38 @@ impl<'a> Recv<'a> {
         unsafe { NDIlib_recv_ptz_is_supported(self.instance) }
     }
 
+    /// What the connected sender reports about itself, once its connection metadata has been
+    /// seen by [`Recv::capture`], [`Recv::capture_metadata_ref`] or [`Recv::start_callbacks`].
+    pub fn peer_info(&self) -> Option<PeerInfo> {
+        self.state.peer.lock().ok()?.as_ref().map(PeerInfo::from)
+    }
+
+    /// The sender's full product metadata, as summarized by [`Recv::peer_info`].
+    pub fn peer_metadata(&self) -> Option<ConnectionMetadata> {
+        self.state.peer.lock().ok()?.clone()
+    }
+
+    /// The capabilities the source announced, once its connection metadata has been seen by
+    /// [`Recv::capture`], [`Recv::capture_metadata_ref`] or [`Recv::start_callbacks`].
+    pub fn sender_capabilities(&self) -> Option<SenderCapabilities> {
+        self.state.capabilities.lock().ok()?.clone()
+    }
+
+    /// Which PTZ controls the source accepts, so a UI can disable the others rather than
+    /// issue commands that fail.
+    ///
+    /// `None` until the source's capabilities have been captured, as for
+    /// [`Recv::sender_capabilities`]; empty if it takes no PTZ commands.
+    pub fn ptz_capabilities(&self) -> Option<PtzCapabilities> {
+        self.sender_capabilities().map(|capabilities| {
+            if capabilities.ptz {
+                capabilities.ptz_controls
+            } else {
+                PtzCapabilities::default()
+            }
+        })
+    }
+
     pub fn ptz_recall_preset(&self, preset: u32, speed: f32) -> bool {
         unsafe { NDIlib_recv_ptz_recall_preset(self.instance, preset as i32, speed) }
     }
@@ -1013,8 +2560,90 @@ impl<'a> Recv<'a> {
     }
 }
 
+/// # Safety
+/// `instance` must be a live receiver instance.
+pub(crate) unsafe fn read_connection_stats(instance: NDIlib_recv_instance_t) -> ConnectionStats {
+    let mut total = NDIlib_recv_performance_t::default();
+    let mut dropped = NDIlib_recv_performance_t::default();
+    let mut queue = NDIlib_recv_queue_t::default();
+    NDIlib_recv_get_performance(instance, &mut total, &mut dropped);
+    NDIlib_recv_get_queue(instance, &mut queue);
+    ConnectionStats {
+        video_frames_received: total.video_frames,
+        audio_frames_received: total.audio_frames,
+        metadata_frames_received: total.metadata_frames,
+        video_frames_dropped: dropped.video_frames,
+        audio_frames_dropped: dropped.audio_frames,
+        metadata_frames_dropped: dropped.metadata_frames,
+        video_frames_queued: queue.video_frames,
+        audio_frames_queued: queue.audio_frames,
+        metadata_frames_queued: queue.metadata_frames,
+        connections: NDIlib_recv_get_no_connections(instance),
+    }
+}
+
+/// Captures the next frame of any type from `instance`, copying it out of the SDK's buffers.
+///
+/// # Safety
+/// `instance` must be a live receiver instance.
+pub(crate) unsafe fn capture_owned(
+    instance: NDIlib_recv_instance_t,
+    timeout_ms: u32,
+) -> Result<FrameType, Error> {
+    let mut video_frame = NDIlib_video_frame_v2_t::default();
+    let mut audio_frame = NDIlib_audio_frame_v3_t::default();
+    let mut metadata_frame = NDIlib_metadata_frame_t::default();
+
+    let frame_type = unsafe {
+        NDIlib_recv_capture_v3(
+            instance,
+            &mut video_frame,
+            &mut audio_frame,
+            &mut metadata_frame,
+            timeout_ms,
+        )
+    };
+
+    match frame_type {
+        NDIlib_frame_type_e_NDIlib_frame_type_video => {
+            let frame = unsafe { VideoFrameRef::from_raw(instance, video_frame) };
+            if video_frame.p_data.is_null() {
+                Err(Error::NullPointer("Video frame data is null".into()))
+            } else {
+                Ok(FrameType::Video(frame.to_owned()))
+            }
+        }
+        NDIlib_frame_type_e_NDIlib_frame_type_audio => {
+            let frame = unsafe { AudioFrameRef::from_raw(instance, audio_frame) };
+            if audio_frame.p_data.is_null() {
+                Err(Error::NullPointer("Audio frame data is null".into()))
+            } else {
+                Ok(FrameType::Audio(frame.to_owned()))
+            }
+        }
+        NDIlib_frame_type_e_NDIlib_frame_type_metadata => {
+            let frame = unsafe { MetadataFrameRef::from_raw(instance, metadata_frame) };
+            if metadata_frame.p_data.is_null() {
+                Err(Error::NullPointer("Metadata frame data is null".into()))
+            } else {
+                Ok(FrameType::Metadata(frame.to_owned()))
+            }
+        }
+        NDIlib_frame_type_e_NDIlib_frame_type_none => Ok(FrameType::None),
+        NDIlib_frame_type_e_NDIlib_frame_type_status_change => Ok(FrameType::StatusChange),
+        NDIlib_frame_type_e_NDIlib_frame_type_error => Err(Error::ErrorFrame),
+        _ => Err(Error::UnknownFrameType(i64::from(frame_type))),
+    }
+}
+
 impl<'a> Drop for Recv<'a> {
     fn drop(&mut self) {
+        self.stop_stats_monitors();
+        #[cfg(feature = "persistent_stats")]
+        {
+            // Errors can't be reported from drop; call `save_persistent_stats` to observe them.
+            let _ = self.save_persistent_stats();
+        }
         unsafe {
             NDIlib_recv_destroy(self.instance);
         }
@@ -1030,7 +2659,8 @@ pub enum FrameType {
     StatusChange,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tally {
     pub on_program: bool,
     pub on_preview: bool,
@@ -1052,14 +2682,107 @@ impl Tally {
     }
 }
 
+/// A message from the receivers connected to a sender, returned by [`Send::capture_event`].
+#[derive(Debug, Clone)]
+pub enum SendEvent {
+    Metadata(MetadataFrame),
+    /// The combined tally of every connected receiver changed.
+    Tally(Tally),
+}
+
+/// Identifies a frame handed to [`Send::send_video_async_owned`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OwnedAsyncToken {
+    sequence: u64,
+}
+
+impl OwnedAsyncToken {
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+}
+
+/// A borrowed frame in flight after [`Send::try_send_video_async`].
+///
+/// The frame stays borrowed for as long as the permit lives, since the SDK may still be
+/// reading it. Dropping the permit waits for the SDK to release the frame; chaining with
+/// [`AsyncVideoPermit::send_next`] hands over the next frame without waiting.
+#[derive(Debug)]
+#[must_use = "dropping the permit immediately waits for the frame to be sent"]
+pub struct AsyncVideoPermit<'s, 'f> {
+    send: &'s Send<'s>,
+    frame: std::marker::PhantomData<&'f VideoFrame>,
+}
+
+impl<'s> AsyncVideoPermit<'s, '_> {
+    /// Sends `frame` asynchronously in place of the one in flight, which the SDK releases.
+    pub fn send_next<'g>(self, frame: &'g VideoFrame) -> Result<AsyncVideoPermit<'s, 'g>, Error> {
+        frame.validate()?;
+        let send = self.send;
+        // Hand the permit over without the flush its drop would do.
+        std::mem::forget(self);
+        send.send_video_async_raw(&frame.to_raw());
+        Ok(AsyncVideoPermit {
+            send,
+            frame: std::marker::PhantomData,
+        })
+    }
+}
+
+impl Drop for AsyncVideoPermit<'_, '_> {
+    fn drop(&mut self) {
+        let mut state = self.send.video_state();
+        unsafe { NDIlib_send_send_video_async_v2(self.send.instance, ptr::null()) };
+        state.permit_held = false;
+        Send::release_in_flight(&mut state);
+    }
+}
+
+#[derive(Debug, Default)]
+struct OwnedAsyncState {
+    next_sequence: u64,
+    in_flight: Option<(u64, VideoFrame)>,
+    recycle: Option<mpsc::Sender<VideoFrame>>,
+    /// Whether an [`AsyncVideoPermit`] is outstanding.
+    permit_held: bool,
+    stats: SendCounters,
+}
+
 #[derive(Debug)]
 pub struct Send<'a> {
     instance: NDIlib_send_instance_t,
     ndi: std::marker::PhantomData<&'a NDI>,
+    /// Also serializes every video send, so a frame is never released by one thread while
+    /// another is handing the SDK its replacement.
+    owned_async: Mutex<OwnedAsyncState>,
+    tally_watchers: Mutex<Vec<Arc<TallyShared>>>,
+    /// Product announcements from connected receivers, oldest first.
+    peers: Mutex<Vec<ConnectionMetadata>>,
+    metadata_hook: Mutex<Option<HookState>>,
+    /// Copies of the connection metadata added since it was last cleared, so it can be
+    /// re-announced when one entry changes.
+    connection_metadata: Mutex<Vec<MetadataFrame>>,
+    /// The tally last reported by [`Send::capture_event`].
+    event_tally: Mutex<Tally>,
+    #[cfg(feature = "advanced_sdk")]
+    video_completions: CompletionDispatcher,
+    #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+    audio_completions: CompletionDispatcher,
 }
 
+// The SDK allows a sender's video, audio and metadata functions to be called from different
+// threads; video calls, which release earlier async frames, are serialized by `owned_async`.
+unsafe impl std::marker::Send for Send<'_> {}
+unsafe impl Sync for Send<'_> {}
+
 impl<'a> Send<'a> {
     pub fn new(_ndi: &'a NDI, create_settings: Sender) -> Result<Self, Error> {
+        // SAFETY: the borrow of `_ndi` keeps the runtime alive for as long as the sender.
+        unsafe { Self::new_detached(create_settings) }
+    }
+
+    /// As [`Find::new_detached`]: the caller keeps an `NDI` alive until the sender is dropped.
+    pub(crate) unsafe fn new_detached(create_settings: Sender) -> Result<Self, Error> {
         let p_ndi_name = CString::new(create_settings.name).map_err(Error::InvalidCString)?;
         let p_groups = match create_settings.groups {
             Some(ref groups) => CString::new(groups.clone())
@@ -1075,28 +2798,189 @@ impl<'a> Send<'a> {
             clock_audio: create_settings.clock_audio,
         };
 
-        let instance = unsafe { NDIlib_send_create(&c_settings) };
+        let instance = config::create_send(&c_settings, create_settings.config.as_ref())?;
         if instance.is_null() {
+            ndi_warn!("NDIlib_send_create failed");
             Err(Error::InitializationFailed(
                 "Failed to create NDI send instance".into(),
             ))
         } else {
+            ndi_info!(
+                clock_video = create_settings.clock_video,
+                clock_audio = create_settings.clock_audio,
+                "sender created"
+            );
             Ok(Send {
                 instance,
                 ndi: std::marker::PhantomData,
+                owned_async: Mutex::new(OwnedAsyncState::default()),
+                tally_watchers: Mutex::new(Vec::new()),
+                peers: Mutex::new(Vec::new()),
+                metadata_hook: Mutex::new(None),
+                connection_metadata: Mutex::new(Vec::new()),
+                event_tally: Mutex::new(Tally::new(false, false)),
+                #[cfg(feature = "advanced_sdk")]
+                video_completions: CompletionDispatcher::default(),
+                #[cfg(all(feature = "advanced_sdk", has_async_audio_completion))]
+                audio_completions: CompletionDispatcher::default(),
             })
         }
     }
 
     pub fn send_video(&self, video_frame: &VideoFrame) {
-        unsafe {
-            NDIlib_send_send_video_v2(self.instance, &video_frame.to_raw());
-        }
+        self.send_video_raw(&video_frame.to_raw());
+    }
+
+    pub fn send_video_borrowed(&self, video_frame: &BorrowedVideoFrame) {
+        self.send_video_raw(&video_frame.to_raw());
     }
 
+    /// Sends `video_frame` asynchronously without keeping it borrowed.
+    ///
+    /// The SDK keeps reading the frame until the next video send or
+    /// [`flush_async`](Self::flush_async), so the frame must outlive that and stay unchanged;
+    /// nothing here enforces it, and another thread may send in between.
+    #[deprecated(
+        note = "the frame isn't kept borrowed while the SDK reads it; use `try_send_video_async` or `send_video_async_owned`"
+    )]
     pub fn send_video_async(&self, video_frame: &VideoFrame) {
+        self.send_video_async_raw(&video_frame.to_raw());
+    }
+
+    /// Sends `video_frame` asynchronously, borrowing it until the SDK is done with it.
+    ///
+    /// Only one permit can be outstanding per sender; while one is, this fails with
+    /// [`Error::AsyncVideoInFlight`]. Synchronous and owned video sends from other threads
+    /// remain possible and simply release the borrowed frame early.
+    pub fn try_send_video_async<'f>(
+        &self,
+        video_frame: &'f VideoFrame,
+    ) -> Result<AsyncVideoPermit<'_, 'f>, Error> {
+        video_frame.validate()?;
+        let mut state = self.video_state();
+        if state.permit_held {
+            return Err(Error::AsyncVideoInFlight);
+        }
+        state.permit_held = true;
+        self.call_send_video(&mut state, &video_frame.to_raw(), true);
+        Self::release_in_flight(&mut state);
+        Ok(AsyncVideoPermit {
+            send: self,
+            frame: std::marker::PhantomData,
+        })
+    }
+
+    /// Sends a frame asynchronously, taking ownership of its buffer until the SDK is done with it.
+    ///
+    /// The SDK releases a frame when the next video frame is sent or on
+    /// [`flush_async`](Self::flush_async); released frames are passed to the channel returned by
+    /// [`recycle_channel`](Self::recycle_channel), or dropped if there is none.
+    pub fn send_video_async_owned(
+        &self,
+        mut video_frame: VideoFrame,
+    ) -> Result<OwnedAsyncToken, Error> {
+        video_frame.validate()?;
+        let mut state = self.video_state();
+        if let Some(metadata) = self.stamp_metadata(&video_frame.to_raw()) {
+            video_frame.metadata = Some(metadata);
+        }
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+
+        let previous = state.in_flight.replace((sequence, video_frame));
+        if let Some(raw) = state.in_flight.as_ref().map(|(_, frame)| frame.to_raw()) {
+            self.call_send_video(&mut state, &raw, true);
+        }
+        if let Some((_, frame)) = previous {
+            Self::recycle(&mut state, frame);
+        }
+        Ok(OwnedAsyncToken { sequence })
+    }
+
+    /// Returns a channel that receives frames from
+    /// [`send_video_async_owned`](Self::send_video_async_owned) once the SDK has released them.
+    pub fn recycle_channel(&self) -> mpsc::Receiver<VideoFrame> {
+        let (tx, rx) = mpsc::channel();
+        if let Ok(mut state) = self.owned_async.lock() {
+            state.recycle = Some(tx);
+        }
+        rx
+    }
+
+    pub fn is_async_complete(&self, token: OwnedAsyncToken) -> bool {
+        self.owned_async.lock().map_or(
+            true,
+            |state| !matches!(state.in_flight, Some((sequence, _)) if sequence == token.sequence),
+        )
+    }
+
+    /// Blocks until the SDK has released any asynchronously sent frame.
+    pub fn flush_async(&self) {
+        ndi_trace!("flushing async video");
+        self.send_video_async_raw(ptr::null());
+    }
+
+    /// Sends a video frame synchronously, releasing any frame still in flight.
+    pub(crate) fn send_video_raw(&self, raw: &NDIlib_video_frame_v2_t) {
+        let mut state = self.video_state();
+        match self.stamp_metadata(raw) {
+            Some(metadata) => {
+                let stamped = NDIlib_video_frame_v2_t {
+                    p_metadata: metadata.as_ptr(),
+                    ..*raw
+                };
+                self.call_send_video(&mut state, &stamped, false);
+            }
+            None => self.call_send_video(&mut state, raw, false),
+        }
+        Self::release_in_flight(&mut state);
+    }
+
+    /// Sends a video frame asynchronously, or flushes when `raw` is null, releasing the frame
+    /// it replaces.
+    pub(crate) fn send_video_async_raw(&self, raw: *const NDIlib_video_frame_v2_t) {
+        let mut state = self.video_state();
+        self.call_send_video(&mut state, raw, true);
+        Self::release_in_flight(&mut state);
+    }
+
+    /// Hands `raw` to the SDK, or flushes when it is null, counting it in [`Send::stats`].
+    fn call_send_video(
+        &self,
+        state: &mut OwnedAsyncState,
+        raw: *const NDIlib_video_frame_v2_t,
+        asynchronous: bool,
+    ) {
+        let started = Instant::now();
         unsafe {
-            NDIlib_send_send_video_async_v2(self.instance, &video_frame.to_raw());
+            if asynchronous {
+                NDIlib_send_send_video_async_v2(self.instance, raw);
+            } else {
+                NDIlib_send_send_video_v2(self.instance, raw);
+            }
+        }
+        if let Some(raw) = unsafe { raw.as_ref() } {
+            state.stats.record(raw, started);
+        }
+    }
+
+    fn video_state(&self) -> std::sync::MutexGuard<'_, OwnedAsyncState> {
+        self.owned_async
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn release_in_flight(state: &mut OwnedAsyncState) {
+        if let Some((_, frame)) = state.in_flight.take() {
+            Self::recycle(state, frame);
+        }
+    }
+
+    fn recycle(state: &mut OwnedAsyncState, frame: VideoFrame) {
+        if let Some(recycle) = &state.recycle {
+            if recycle.send(frame).is_err() {
+                state.recycle = None;
+            }
         }
     }
 
@@ -1106,33 +2990,129 @@ impl<'a> Send<'a> {
         }
     }
 
+    /// Sends an audio frame after checking its data covers every channel, so a frame whose
+    /// counts don't match its buffer is refused rather than read out of bounds.
+    pub fn try_send_audio(&self, audio_frame: &AudioFrame) -> Result<(), Error> {
+        if audio_frame.sample_rate <= 0 || audio_frame.no_channels <= 0 {
+            return Err(Error::InvalidAudioLayout {
+                no_channels: audio_frame.no_channels,
+                no_samples: audio_frame.no_samples,
+                sample_rate: audio_frame.sample_rate,
+            });
+        }
+        audio_frame.validate()?;
+        self.send_audio(audio_frame);
+        Ok(())
+    }
+
     pub fn send_metadata(&self, metadata_frame: &MetadataFrame) {
         unsafe {
             NDIlib_send_send_metadata(self.instance, &metadata_frame.to_raw());
         }
     }
 
+    pub fn send_metadata_xml(&self, xml: &str, timecode: i64) -> Result<(), Error> {
+        self.send_metadata(&MetadataFrame::from_xml(xml, timecode)?);
+        Ok(())
+    }
+
+    /// Sends several metadata fragments, coalescing them into as few SDK calls as the default
+    /// [`MetadataBatchOptions`] allow. Returns the number of frames actually sent.
+    pub fn send_metadata_batch(&self, metadata_frames: &[MetadataFrame]) -> Result<usize, Error> {
+        let mut batcher = MetadataBatcher::new(MetadataBatchOptions::default());
+        for frame in metadata_frames {
+            batcher.push(frame.data().to_string_lossy(), frame.timecode);
+        }
+        batcher.flush(self)
+    }
+
+    /// Waits up to `timeout_ms` for a metadata frame from a connected receiver. Returns
+    /// [`FrameType::StatusChange`] if the sender's status changed and [`Error::Timeout`] if
+    /// nothing arrived in time.
     pub fn capture(&self, timeout_ms: u32) -> Result<FrameType, Error> {
-        let metadata_frame = MetadataFrame::new();
+        match self.capture_frame(timeout_ms)? {
+            FrameType::None => Err(Error::Timeout {
+                operation: "metadata from a receiver",
+                timeout_ms,
+            }),
+            frame => Ok(frame),
+        }
+    }
+
+    /// Waits up to `timeout_ms` for a metadata frame sent back by a connected receiver, such as
+    /// a control command or PTZ request. Returns `Ok(None)` on timeout or status change.
+    pub fn capture_metadata(&self, timeout_ms: u32) -> Result<Option<MetadataFrame>, Error> {
+        match self.capture_frame(timeout_ms)? {
+            FrameType::Metadata(frame) => Ok(Some(frame)),
+            _ => Ok(None),
+        }
+    }
+
+    fn capture_frame(&self, timeout_ms: u32) -> Result<FrameType, Error> {
+        let mut metadata_frame = NDIlib_metadata_frame_t::default();
         let frame_type =
-            unsafe { NDIlib_send_capture(self.instance, &mut metadata_frame.to_raw(), timeout_ms) };
+            unsafe { NDIlib_send_capture(self.instance, &mut metadata_frame, timeout_ms) };
 
         match frame_type {
-            NDIlib_frame_type_e_NDIlib_frame_type_metadata => Ok(FrameType::Metadata(
-                MetadataFrame::from_raw(metadata_frame.to_raw()),
-            )),
-            _ => Err(Error::CaptureFailed("Failed to capture frame".into())),
+            NDIlib_frame_type_e_NDIlib_frame_type_metadata => {
+                let frame = unsafe { MetadataFrame::from_raw(&metadata_frame) };
+                unsafe { NDIlib_send_free_metadata(self.instance, &metadata_frame) };
+                if let Ok(xml) = frame.as_str() {
+                    self.observe_metadata(xml);
+                }
+                Ok(FrameType::Metadata(frame))
+            }
+            NDIlib_frame_type_e_NDIlib_frame_type_none => Ok(FrameType::None),
+            NDIlib_frame_type_e_NDIlib_frame_type_status_change => Ok(FrameType::StatusChange),
+            other => Err(Error::UnknownFrameType(i64::from(other))),
         }
     }
 
-    pub fn free_metadata(&self, metadata_frame: &MetadataFrame) {
-        unsafe {
-            NDIlib_send_free_metadata(self.instance, &metadata_frame.to_raw());
+    /// Waits up to `timeout_ms` for the next message from the connected receivers: a metadata
+    /// frame or a change of tally.
+    ///
+    /// This is the sender's half of a bidirectional control channel; receivers reply with
+    /// [`Recv::send_metadata`]. Returns `Ok(None)` if nothing arrived in time.
+    pub fn capture_event(&self, timeout_ms: u32) -> Result<Option<SendEvent>, Error> {
+        if let Some(tally) = self.tally_event() {
+            return Ok(Some(tally));
+        }
+        if let Some(frame) = self.capture_metadata(timeout_ms)? {
+            return Ok(Some(SendEvent::Metadata(frame)));
         }
+        Ok(self.tally_event())
+    }
+
+    // Compares against the tally last reported here rather than trusting the SDK's "changed"
+    // flag, which is shared with every other caller of `get_tally`, including a `TallyWatcher`.
+    fn tally_event(&self) -> Option<SendEvent> {
+        let mut last = self
+            .event_tally
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut tally = last.clone();
+        self.get_tally(&mut tally, 0);
+        if tally == *last {
+            return None;
+        }
+        *last = tally.clone();
+        Some(SendEvent::Tally(tally))
     }
 
+    /// Does nothing: captured metadata frames are copied and the SDK's copy freed on capture.
+    #[deprecated(
+        note = "captured metadata frames are copied and the SDK's copy freed on capture; this will be removed in a future release"
+    )]
+    pub fn free_metadata(&self, _metadata_frame: &MetadataFrame) {}
+
+    /// Fills in `tally`, waiting up to `timeout_ms` for it to change; returns whether it did.
+    ///
+    /// Use [`Send::watch_tally`] to be notified of changes instead of polling.
     pub fn get_tally(&self, tally: &mut Tally, timeout_ms: u32) -> bool {
-        unsafe { NDIlib_send_get_tally(self.instance, &mut tally.to_raw(), timeout_ms) }
+        let mut raw = tally.to_raw();
+        let changed = unsafe { NDIlib_send_get_tally(self.instance, &mut raw, timeout_ms) };
+        *tally = Tally::new(raw.on_program, raw.on_preview);
+        changed
     }
 
     pub fn get_no_connections(&self, timeout_ms: u32) -> i32 {
@@ -1140,19 +3120,39 @@ impl<'a> Send<'a> {
     }
 
     pub fn clear_connection_metadata(&self) {
+        let entries = self.connection_metadata.lock();
         unsafe { NDIlib_send_clear_connection_metadata(self.instance) }
+        if let Ok(mut entries) = entries {
+            entries.clear();
+        }
     }
 
     pub fn add_connection_metadata(&self, metadata_frame: &MetadataFrame) {
-        unsafe { NDIlib_send_add_connection_metadata(self.instance, &metadata_frame.to_raw()) }
+        let raw = metadata_frame.to_raw();
+        let entries = self.connection_metadata.lock();
+        unsafe { NDIlib_send_add_connection_metadata(self.instance, &raw) }
+        if let Ok(mut entries) = entries {
+            // Copies what was sent, which is `p_data` for frames that still set it by hand.
+            entries.push(unsafe { MetadataFrame::from_raw(&raw) });
+        }
     }
 
-    pub fn set_failover(&self, source: &Source) -> Result<(), Error> {
-        let raw_source = source.to_raw()?;
-        unsafe { NDIlib_send_set_failover(self.instance, &raw_source) }
+    /// Advertises `capabilities` to every current and future receiver.
+    ///
+    /// This adds to the existing connection metadata; call [`Send::clear_connection_metadata`]
+    /// first when replacing previously advertised capabilities.
+    pub fn set_capabilities(&self, capabilities: &SenderCapabilities) -> Result<(), Error> {
+        self.add_connection_metadata(&capabilities.to_metadata_frame()?);
         Ok(())
     }
 
+    pub fn set_failover(&self, source: &Source) -> Result<(), Error> {
+        source.with_raw(|raw_source| {
+            unsafe { NDIlib_send_set_failover(self.instance, raw_source) };
+            Ok(())
+        })
+    }
+
     pub fn get_source_name(&self) -> Source {
         let source_ptr = unsafe { NDIlib_send_get_source_name(self.instance) };
         Source::from_raw(unsafe { &*source_ptr })
@@ -1161,6 +3161,12 @@ impl<'a> Send<'a> {
 
 impl<'a> Drop for Send<'a> {
     fn drop(&mut self) {
+        // A leaked `TallyWatcher` would otherwise keep polling a destroyed instance.
+        if let Ok(watchers) = self.tally_watchers.get_mut() {
+            for watcher in watchers.drain(..) {
+                watcher.stop();
+            }
+        }
         unsafe {
             NDIlib_send_destroy(self.instance);
         }
@@ -1168,9 +3174,234 @@ impl<'a> Drop for Send<'a> {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sender {
     pub name: String,
     pub groups: Option<String>,
     pub clock_video: bool,
     pub clock_audio: bool,
+    /// Configuration for this sender only, overriding `ndi-config.v1.json` (Advanced SDK).
+    pub config: Option<NdiConfig>,
+}
+
+impl Sender {
+    /// Publishes the source in `groups`; an empty list means the default groups.
+    pub fn with_groups(mut self, groups: &Groups) -> Self {
+        self.groups = (!groups.is_empty()).then(|| groups.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_metadata_is_owned_once() {
+        let frame = VideoFrameBuilder::new()
+            .resolution(4, 2)
+            .fourcc(FourCCVideoType::BGRA)
+            .metadata("<tag/>")
+            .build()
+            .unwrap();
+        let copy = frame.clone();
+        drop(frame);
+        assert_eq!(copy.metadata.as_deref(), Some(c"<tag/>"));
+    }
+
+    #[test]
+    fn metadata_survives_raw_round_trip() {
+        let frame = VideoFrameBuilder::new()
+            .resolution(4, 2)
+            .fourcc(FourCCVideoType::BGRA)
+            .metadata("<tag/>")
+            .build()
+            .unwrap();
+        let copy = unsafe { VideoFrame::from_raw(&frame.to_raw()) };
+        drop(frame);
+        assert_eq!(copy.metadata.as_deref(), Some(c"<tag/>"));
+        assert_eq!(copy.data.len(), 4 * 2 * 4);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn metadata_frame_fields_follow_owned_xml() {
+        let frame = MetadataFrame::from_xml("<tag/>", 7).unwrap();
+        let copy = frame.clone();
+        drop(frame);
+        assert_eq!(copy.p_data as *const c_char, copy.data().as_ptr());
+        assert_eq!(copy.length, 7);
+        let raw = copy.to_raw();
+        assert_eq!(unsafe { CStr::from_ptr(raw.p_data) }, c"<tag/>");
+        assert_eq!(raw.timecode, 7);
+    }
+
+    fn samples(frame: &AudioFrame) -> Vec<f32> {
+        frame
+            .data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn audio_builder_defaults_to_silence() {
+        let frame = AudioFrameBuilder::new().samples(3).build().unwrap();
+        assert_eq!((frame.sample_rate, frame.no_channels), (48_000, 2));
+        assert_eq!(samples(&frame), [0.0; 6]);
+        assert_eq!(frame.channel_stride_in_bytes, 3 * 4);
+    }
+
+    #[test]
+    fn audio_builder_deinterleaves() {
+        let frame = AudioFrameBuilder::new()
+            .channels(2)
+            .samples(3)
+            .interleaved(&[1.0, -1.0, 2.0, -2.0, 3.0, -3.0])
+            .build()
+            .unwrap();
+        assert_eq!(samples(&frame), [1.0, 2.0, 3.0, -1.0, -2.0, -3.0]);
+    }
+
+    #[test]
+    fn audio_builder_rejects_ragged_interleaved_samples() {
+        let result = AudioFrameBuilder::new()
+            .channels(2)
+            .samples(2)
+            .interleaved(&[1.0, 2.0, 3.0])
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::BufferSizeMismatch {
+                expected: 4,
+                actual: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn audio_builder_sets_one_channel_at_a_time() {
+        let frame = AudioFrameBuilder::new()
+            .channels(3)
+            .samples(2)
+            .set_channel_data(1, &[0.5, 0.25])
+            .build()
+            .unwrap();
+        assert_eq!(samples(&frame), [0.0, 0.0, 0.5, 0.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn audio_builder_rejects_bad_channel_writes() {
+        let out_of_range = AudioFrameBuilder::new()
+            .channels(2)
+            .samples(2)
+            .set_channel_data(2, &[0.0, 0.0])
+            .build();
+        assert!(matches!(
+            out_of_range,
+            Err(Error::ChannelOutOfRange {
+                channel: 2,
+                channels: 2
+            })
+        ));
+        let wrong_length = AudioFrameBuilder::new()
+            .channels(2)
+            .samples(2)
+            .set_channel_data(0, &[0.0])
+            .build();
+        assert!(matches!(
+            wrong_length,
+            Err(Error::BufferSizeMismatch {
+                expected: 2,
+                actual: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn audio_builder_drops_data_when_the_layout_changes() {
+        let frame = AudioFrameBuilder::new()
+            .samples(2)
+            .data(vec![1.0; 4])
+            .channels(1)
+            .build()
+            .unwrap();
+        assert_eq!(samples(&frame), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn audio_builder_rejects_mismatched_data_and_bad_counts() {
+        let short = AudioFrameBuilder::new()
+            .samples(2)
+            .data(vec![1.0; 3])
+            .build();
+        assert!(matches!(
+            short,
+            Err(Error::BufferSizeMismatch {
+                expected: 4,
+                actual: 3,
+                ..
+            })
+        ));
+        let no_channels = AudioFrameBuilder::new().channels(0).build();
+        assert!(matches!(
+            no_channels,
+            Err(Error::InvalidAudioLayout { no_channels: 0, .. })
+        ));
+        let no_rate = AudioFrameBuilder::new().sample_rate(0).build();
+        assert!(matches!(
+            no_rate,
+            Err(Error::InvalidAudioLayout { sample_rate: 0, .. })
+        ));
+        let too_large = AudioFrameBuilder::new()
+            .channels(i32::MAX)
+            .samples(i32::MAX)
+            .build();
+        assert!(matches!(too_large, Err(Error::InvalidAudioLayout { .. })));
+    }
+
+    #[test]
+    fn owned_frames_borrow_with_their_layout_and_timing() {
+        let frame = VideoFrameBuilder::new()
+            .resolution(4, 2)
+            .fourcc(FourCCVideoType::UYVY)
+            .frame_rate(30_000, 1001)
+            .format(FrameFormatType::Interlaced)
+            .timestamp(42)
+            .build()
+            .unwrap();
+        let borrowed = BorrowedVideoFrame::try_from(&frame).unwrap();
+        let (raw, borrowed_raw) = (frame.to_raw(), borrowed.to_raw());
+        assert_eq!(borrowed_raw.p_data, raw.p_data);
+        assert_eq!(borrowed.line_stride(), 4 * 2);
+        assert_eq!(borrowed.data().len(), frame.data.len());
+        assert_eq!(
+            (borrowed.frame_rate_n, borrowed.frame_rate_d),
+            (30_000, 1001)
+        );
+        assert_eq!(borrowed.frame_format_type, FrameFormatType::Interlaced);
+        assert_eq!(borrowed.timestamp, 42);
+    }
+
+    #[test]
+    fn owned_frames_in_unknown_formats_are_not_borrowed() {
+        let mut frame = VideoFrameBuilder::new()
+            .resolution(4, 2)
+            .fourcc(FourCCVideoType::BGRA)
+            .build()
+            .unwrap();
+        frame.fourcc = FourCCVideoType::Max;
+        assert!(matches!(
+            BorrowedVideoFrame::try_from(&frame),
+            Err(Error::UnknownFourCc(_))
+        ));
+        frame.fourcc = FourCCVideoType::BGRA;
+        frame.data.truncate(4);
+        assert!(matches!(
+            BorrowedVideoFrame::try_from(&frame),
+            Err(Error::InvalidDataSize { .. })
+        ));
+    }
 }
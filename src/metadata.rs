@@ -0,0 +1,452 @@
+use std::{ffi::CString, fmt::Write};
+
+use crate::{
+    AudioFrame, Error, MetadataFrame, PtzCapabilities, SenderCapabilities, SmpteTimecode, Tally,
+    VideoFrame,
+};
+
+/// A value with a well-known NDI metadata XML representation.
+pub trait MetadataXml {
+    fn to_xml(&self) -> String;
+
+    /// Wraps the XML in a metadata frame with a zero timecode, ready to send or to add as
+    /// connection metadata.
+    fn to_metadata_frame(&self) -> Result<MetadataFrame, Error> {
+        MetadataFrame::from_xml(&self.to_xml(), 0)
+    }
+}
+
+/// Product information announced by senders and receivers as connection metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionMetadata {
+    pub long_name: String,
+    pub short_name: String,
+    pub manufacturer: Option<String>,
+    pub version: String,
+    pub model_name: Option<String>,
+    pub serial: Option<String>,
+    pub session: Option<String>,
+}
+
+impl ConnectionMetadata {
+    pub fn product(name: &str, version: &str) -> Self {
+        ConnectionMetadata {
+            long_name: name.to_string(),
+            short_name: name.to_string(),
+            version: version.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_short_name(mut self, short_name: &str) -> Self {
+        self.short_name = short_name.to_string();
+        self
+    }
+
+    pub fn with_manufacturer(mut self, manufacturer: &str) -> Self {
+        self.manufacturer = Some(manufacturer.to_string());
+        self
+    }
+
+    pub fn with_model_name(mut self, model_name: &str) -> Self {
+        self.model_name = Some(model_name.to_string());
+        self
+    }
+
+    pub fn with_serial(mut self, serial: &str) -> Self {
+        self.serial = Some(serial.to_string());
+        self
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        self.session = Some(session.to_string());
+        self
+    }
+}
+
+/// The product, version and manufacturer a connected sender reports about itself, as
+/// returned by [`Recv::peer_info`](crate::Recv::peer_info).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerInfo {
+    pub product: String,
+    pub version: String,
+    pub manufacturer: Option<String>,
+}
+
+impl From<&ConnectionMetadata> for PeerInfo {
+    fn from(metadata: &ConnectionMetadata) -> Self {
+        let product = if metadata.long_name.is_empty() {
+            &metadata.short_name
+        } else {
+            &metadata.long_name
+        };
+        PeerInfo {
+            product: product.clone(),
+            version: metadata.version.clone(),
+            manufacturer: metadata.manufacturer.clone(),
+        }
+    }
+}
+
+impl MetadataXml for ConnectionMetadata {
+    fn to_xml(&self) -> String {
+        let mut xml = String::from("<ndi_product");
+        push_attribute(&mut xml, "long_name", &self.long_name);
+        push_attribute(&mut xml, "short_name", &self.short_name);
+        if let Some(manufacturer) = &self.manufacturer {
+            push_attribute(&mut xml, "manufacturer", manufacturer);
+        }
+        push_attribute(&mut xml, "version", &self.version);
+        if let Some(model_name) = &self.model_name {
+            push_attribute(&mut xml, "model_name", model_name);
+        }
+        if let Some(serial) = &self.serial {
+            push_attribute(&mut xml, "serial", serial);
+        }
+        if let Some(session) = &self.session {
+            push_attribute(&mut xml, "session", session);
+        }
+        xml.push_str("/>");
+        xml
+    }
+}
+
+impl MetadataXml for SenderCapabilities {
+    fn to_xml(&self) -> String {
+        let mut xml = String::from("<ndi_capabilities");
+        if let Some(url) = &self.web_control {
+            push_attribute(&mut xml, "web_control", url);
+        }
+        if self.ptz {
+            let controls = if self.ptz_controls.any() {
+                self.ptz_controls
+            } else {
+                PtzCapabilities::all()
+            };
+            push_attribute(&mut xml, "ntk_ptz", "true");
+            for (attribute, supported) in ptz_control_attributes(&controls) {
+                if supported {
+                    push_attribute(&mut xml, attribute, "true");
+                }
+            }
+        }
+        if self.recording {
+            push_attribute(&mut xml, "ntk_record", "true");
+        }
+        if self.kvm {
+            push_attribute(&mut xml, "ntk_kvm", "true");
+        }
+        xml.push_str("/>");
+        xml
+    }
+}
+
+/// The `<ndi_capabilities/>` attribute for each PTZ control.
+fn ptz_control_attributes(controls: &PtzCapabilities) -> [(&'static str, bool); 6] {
+    [
+        ("ntk_pan_tilt", controls.pan_tilt),
+        ("ntk_zoom", controls.zoom),
+        ("ntk_iris", controls.iris),
+        ("ntk_white_balance", controls.white_balance),
+        ("ntk_focus", controls.focus),
+        ("ntk_exposure_v2", controls.exposure),
+    ]
+}
+
+/// Sent by a receiver to tell the source whether it is on program or preview.
+impl MetadataXml for Tally {
+    fn to_xml(&self) -> String {
+        tally_xml("ndi_tally", self)
+    }
+}
+
+fn tally_xml(element: &str, tally: &Tally) -> String {
+    format!(
+        "<{} on_program=\"{}\" on_preview=\"{}\"/>",
+        element, tally.on_program, tally.on_preview
+    )
+}
+
+/// PTZ commands as carried in metadata, using the `ntk_ptz_*` elements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PtzCommand {
+    Zoom(f32),
+    ZoomSpeed(f32),
+    PanTilt { pan: f32, tilt: f32 },
+    PanTiltSpeed { pan_speed: f32, tilt_speed: f32 },
+    StorePreset(u32),
+    RecallPreset { index: u32, speed: f32 },
+    AutoFocus,
+    Focus(f32),
+    FocusSpeed(f32),
+}
+
+impl MetadataXml for PtzCommand {
+    fn to_xml(&self) -> String {
+        match self {
+            PtzCommand::Zoom(zoom) => format!("<ntk_ptz_zoom zoom=\"{}\"/>", zoom),
+            PtzCommand::ZoomSpeed(speed) => {
+                format!("<ntk_ptz_zoom_speed zoom_speed=\"{}\"/>", speed)
+            }
+            PtzCommand::PanTilt { pan, tilt } => {
+                format!("<ntk_ptz_pan_tilt pan=\"{}\" tilt=\"{}\"/>", pan, tilt)
+            }
+            PtzCommand::PanTiltSpeed {
+                pan_speed,
+                tilt_speed,
+            } => format!(
+                "<ntk_ptz_pan_tilt_speed pan_speed=\"{}\" tilt_speed=\"{}\"/>",
+                pan_speed, tilt_speed
+            ),
+            PtzCommand::StorePreset(index) => {
+                format!("<ntk_ptz_store_preset index=\"{}\"/>", index)
+            }
+            PtzCommand::RecallPreset { index, speed } => format!(
+                "<ntk_ptz_recall_preset index=\"{}\" speed=\"{}\"/>",
+                index, speed
+            ),
+            PtzCommand::AutoFocus => "<ntk_ptz_focus mode=\"auto\"/>".to_string(),
+            PtzCommand::Focus(distance) => {
+                format!("<ntk_ptz_focus mode=\"manual\" distance=\"{}\"/>", distance)
+            }
+            PtzCommand::FocusSpeed(speed) => {
+                format!("<ntk_ptz_focus_speed speed=\"{}\"/>", speed)
+            }
+        }
+    }
+}
+
+/// Written as `<ndi_timecode timecode="HH:MM:SS:FF"/>`.
+impl MetadataXml for SmpteTimecode {
+    fn to_xml(&self) -> String {
+        format!("<ndi_timecode timecode=\"{}\"/>", self)
+    }
+}
+
+/// A metadata message recognised by [`MetadataFrame::parse_known`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum KnownMetadata {
+    Product(ConnectionMetadata),
+    Capabilities(SenderCapabilities),
+    Tally(Tally),
+    /// The tally state a sender echoes back to its receivers.
+    TallyEcho(Tally),
+    Ptz(PtzCommand),
+    Timecode(SmpteTimecode),
+}
+
+impl MetadataXml for KnownMetadata {
+    fn to_xml(&self) -> String {
+        match self {
+            KnownMetadata::Product(product) => product.to_xml(),
+            KnownMetadata::Capabilities(capabilities) => capabilities.to_xml(),
+            KnownMetadata::Tally(tally) => tally.to_xml(),
+            KnownMetadata::TallyEcho(tally) => tally_xml("ndi_tally_echo", tally),
+            KnownMetadata::Ptz(command) => command.to_xml(),
+            KnownMetadata::Timecode(timecode) => timecode.to_xml(),
+        }
+    }
+}
+
+impl KnownMetadata {
+    /// Parses a single well-known element; anything else yields `None`.
+    ///
+    /// This is not a general XML parser: it reads the element name and double-quoted
+    /// attributes the way the NDI SDK and this crate write them, so single-quoted attributes,
+    /// comments, CDATA and namespace prefixes aren't understood. Use an XML parser for
+    /// metadata from other sources.
+    pub fn parse(xml: &str) -> Option<Self> {
+        let xml = xml.trim();
+        let name = element_name(xml)?;
+        let attr = |name| xml_attribute(xml, name).map(unescape_xml);
+        let number = |name| attr(name)?.parse::<f32>().ok();
+        let flag = |name| attr(name).is_some_and(|v| v == "true");
+
+        let known = match name {
+            "ndi_product" => KnownMetadata::Product(ConnectionMetadata {
+                long_name: attr("long_name").unwrap_or_default(),
+                short_name: attr("short_name").unwrap_or_default(),
+                manufacturer: attr("manufacturer"),
+                version: attr("version").unwrap_or_default(),
+                model_name: attr("model_name"),
+                serial: attr("serial"),
+                session: attr("session"),
+            }),
+            "ndi_capabilities" => KnownMetadata::Capabilities(SenderCapabilities {
+                ptz: flag("ntk_ptz"),
+                ptz_controls: PtzCapabilities {
+                    pan_tilt: flag("ntk_pan_tilt"),
+                    zoom: flag("ntk_zoom"),
+                    iris: flag("ntk_iris"),
+                    white_balance: flag("ntk_white_balance"),
+                    focus: flag("ntk_focus"),
+                    // Older senders only announce the original exposure control.
+                    exposure: flag("ntk_exposure_v2") || flag("ntk_exposure"),
+                },
+                recording: flag("ntk_record"),
+                kvm: flag("ntk_kvm"),
+                web_control: attr("web_control"),
+            }),
+            "ndi_tally" => KnownMetadata::Tally(Tally::new(flag("on_program"), flag("on_preview"))),
+            "ndi_tally_echo" => {
+                KnownMetadata::TallyEcho(Tally::new(flag("on_program"), flag("on_preview")))
+            }
+            "ndi_timecode" => KnownMetadata::Timecode(attr("timecode")?.parse().ok()?),
+            "ntk_ptz_zoom" => KnownMetadata::Ptz(PtzCommand::Zoom(number("zoom")?)),
+            "ntk_ptz_zoom_speed" => {
+                KnownMetadata::Ptz(PtzCommand::ZoomSpeed(number("zoom_speed")?))
+            }
+            "ntk_ptz_pan_tilt" => KnownMetadata::Ptz(PtzCommand::PanTilt {
+                pan: number("pan")?,
+                tilt: number("tilt")?,
+            }),
+            "ntk_ptz_pan_tilt_speed" => KnownMetadata::Ptz(PtzCommand::PanTiltSpeed {
+                pan_speed: number("pan_speed")?,
+                tilt_speed: number("tilt_speed")?,
+            }),
+            "ntk_ptz_store_preset" => {
+                KnownMetadata::Ptz(PtzCommand::StorePreset(attr("index")?.parse().ok()?))
+            }
+            "ntk_ptz_recall_preset" => KnownMetadata::Ptz(PtzCommand::RecallPreset {
+                index: attr("index")?.parse().ok()?,
+                speed: number("speed").unwrap_or(1.0),
+            }),
+            "ntk_ptz_focus" => match attr("mode").as_deref() {
+                Some("auto") => KnownMetadata::Ptz(PtzCommand::AutoFocus),
+                _ => KnownMetadata::Ptz(PtzCommand::Focus(number("distance")?)),
+            },
+            "ntk_ptz_focus_speed" => KnownMetadata::Ptz(PtzCommand::FocusSpeed(number("speed")?)),
+            _ => return None,
+        };
+        Some(known)
+    }
+}
+
+impl MetadataFrame {
+    /// Interprets the frame as one of the well-known NDI metadata messages.
+    pub fn parse_known(&self) -> Option<KnownMetadata> {
+        KnownMetadata::parse(self.as_str().ok()?)
+    }
+}
+
+/// Serializes key/value tags as `<ndi_frame_tags><tag key=".." value=".."/>..</ndi_frame_tags>`
+/// for attaching to individual frames.
+pub fn frame_tags_xml<'t>(tags: impl IntoIterator<Item = (&'t str, &'t str)>) -> String {
+    let mut xml = String::from("<ndi_frame_tags>");
+    for (key, value) in tags {
+        xml.push_str("<tag");
+        push_attribute(&mut xml, "key", key);
+        push_attribute(&mut xml, "value", value);
+        xml.push_str("/>");
+    }
+    xml.push_str("</ndi_frame_tags>");
+    xml
+}
+
+/// Reads the tags written by [`frame_tags_xml`], in order; other XML yields no tags.
+///
+/// Like [`KnownMetadata::parse`] this only understands the shape [`frame_tags_xml`] writes,
+/// not arbitrary XML.
+pub fn parse_frame_tags(xml: &str) -> Vec<(String, String)> {
+    let Some(start) = xml.find("<ndi_frame_tags") else {
+        return Vec::new();
+    };
+    let body = &xml[start..];
+    let body = &body[..body.find("</ndi_frame_tags>").unwrap_or(body.len())];
+    body.match_indices("<tag ")
+        .filter_map(|(i, _)| {
+            let element = &body[i..body[i..].find('>').map_or(body.len(), |end| i + end)];
+            let key = xml_attribute(element, "key")?;
+            let value = xml_attribute(element, "value").unwrap_or_default();
+            Some((unescape_xml(key), unescape_xml(value)))
+        })
+        .collect()
+}
+
+macro_rules! frame_metadata_accessors {
+    ($frame:ty) => {
+        impl $frame {
+            /// The frame's metadata, if it has any and it is valid UTF-8.
+            pub fn metadata_str(&self) -> Option<&str> {
+                self.metadata.as_deref()?.to_str().ok()
+            }
+
+            /// Replaces the frame's metadata with `xml`.
+            pub fn set_metadata_xml(&mut self, xml: &str) -> Result<(), Error> {
+                self.metadata = Some(CString::new(xml)?);
+                Ok(())
+            }
+
+            /// Replaces the frame's metadata with key/value tags, such as scene IDs or
+            /// annotations, serialized by [`frame_tags_xml`].
+            pub fn set_metadata_tags<'t>(
+                &mut self,
+                tags: impl IntoIterator<Item = (&'t str, &'t str)>,
+            ) -> Result<(), Error> {
+                self.set_metadata_xml(&frame_tags_xml(tags))
+            }
+
+            /// The tags set with `set_metadata_tags`, or an empty list if the metadata holds
+            /// something else.
+            pub fn metadata_tags(&self) -> Vec<(String, String)> {
+                self.metadata_str()
+                    .map(parse_frame_tags)
+                    .unwrap_or_default()
+            }
+        }
+    };
+}
+
+frame_metadata_accessors!(VideoFrame);
+frame_metadata_accessors!(AudioFrame);
+
+fn element_name(xml: &str) -> Option<&str> {
+    let rest = xml.strip_prefix('<')?;
+    let end = rest
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|name| !name.is_empty())
+}
+
+fn push_attribute(xml: &mut String, name: &str, value: &str) {
+    let _ = write!(xml, " {}=\"{}\"", name, escape_xml_attribute(value));
+}
+
+/// Returns the raw (still escaped) value of attribute `name` in the first element of `xml`.
+///
+/// A substring search, not an XML parser: it expects double-quoted attributes and no
+/// attribute named `name` in comments or nested elements before the first element ends.
+pub(crate) fn xml_attribute<'x>(xml: &'x str, name: &str) -> Option<&'x str> {
+    let pattern = format!("{}=\"", name);
+    let start = xml
+        .match_indices(&pattern)
+        .find(|(i, _)| *i == 0 || xml.as_bytes()[i - 1].is_ascii_whitespace())?
+        .0
+        + pattern.len();
+    let end = xml[start..].find('"')? + start;
+    Some(&xml[start..end])
+}
+
+pub(crate) fn escape_xml_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub(crate) fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
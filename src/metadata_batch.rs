@@ -0,0 +1,202 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Send};
+
+#[derive(Debug, Clone)]
+pub struct MetadataBatchOptions {
+    /// Upper bound on the XML size of one coalesced frame, in bytes.
+    pub max_frame_bytes: usize,
+    /// Element that wraps coalesced fragments so each sent frame stays a single XML document.
+    /// Fragments are sent one per frame when this is `None`.
+    pub group_element: Option<String>,
+    /// Minimum time between flushes; fragments pushed in the meantime wait for the next flush.
+    pub min_interval: Option<Duration>,
+    /// Oldest fragments are discarded once this many are waiting to be sent.
+    pub max_pending: usize,
+}
+
+impl Default for MetadataBatchOptions {
+    fn default() -> Self {
+        MetadataBatchOptions {
+            max_frame_bytes: 16 * 1024,
+            group_element: Some("ndi_metadata_group".to_string()),
+            min_interval: None,
+            max_pending: 1024,
+        }
+    }
+}
+
+/// Queues small metadata fragments and sends them in coalesced, rate-limited frames.
+#[derive(Debug)]
+pub struct MetadataBatcher {
+    options: MetadataBatchOptions,
+    pending: VecDeque<(String, i64)>,
+    last_flush: Option<Instant>,
+    dropped: u64,
+}
+
+impl MetadataBatcher {
+    pub fn new(options: MetadataBatchOptions) -> Self {
+        MetadataBatcher {
+            options,
+            pending: VecDeque::new(),
+            last_flush: None,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, xml: impl Into<String>, timecode: i64) {
+        if self.options.max_pending > 0 && self.pending.len() >= self.options.max_pending {
+            self.pending.pop_front();
+            self.dropped += 1;
+        }
+        self.pending.push_back((xml.into(), timecode));
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of fragments discarded because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Sends pending fragments unless the rate limit says to wait.
+    ///
+    /// Returns the number of metadata frames handed to the SDK.
+    pub fn flush(&mut self, send: &Send) -> Result<usize, Error> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        if let (Some(interval), Some(last)) = (self.options.min_interval, self.last_flush) {
+            if last.elapsed() < interval {
+                return Ok(0);
+            }
+        }
+
+        let frames = self.coalesce();
+        for (xml, timecode) in &frames {
+            send.send_metadata_xml(xml, *timecode)?;
+        }
+        self.pending.clear();
+        self.last_flush = Some(Instant::now());
+        Ok(frames.len())
+    }
+
+    fn coalesce(&self) -> Vec<(String, i64)> {
+        let Some(element) = &self.options.group_element else {
+            return self.pending.iter().cloned().collect();
+        };
+
+        let open = format!("<{}>", element);
+        let close = format!("</{}>", element);
+        let mut frames = Vec::new();
+        let mut current: Option<(String, i64, usize)> = None;
+
+        for (xml, timecode) in &self.pending {
+            if let Some((body, _, count)) = &mut current {
+                if body.len() + xml.len() + close.len() <= self.options.max_frame_bytes {
+                    body.push_str(xml);
+                    *count += 1;
+                    continue;
+                }
+            }
+            if let Some(done) = current.take() {
+                frames.push(Self::finish(done, &close));
+            }
+            current = Some((format!("{}{}", open, xml), *timecode, 1));
+        }
+        if let Some(done) = current {
+            frames.push(Self::finish(done, &close));
+        }
+        frames
+    }
+
+    fn finish((body, timecode, count): (String, i64, usize), close: &str) -> (String, i64) {
+        if count == 1 {
+            // A lone fragment is sent as-is rather than wrapped.
+            let start = body.find('>').map_or(0, |i| i + 1);
+            (body[start..].to_string(), timecode)
+        } else {
+            (body + close, timecode)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batcher(options: MetadataBatchOptions) -> MetadataBatcher {
+        MetadataBatcher::new(options)
+    }
+
+    #[test]
+    fn coalesces_fragments_into_one_group() {
+        let mut batcher = batcher(MetadataBatchOptions::default());
+        batcher.push("<a/>", 1);
+        batcher.push("<b/>", 2);
+        assert_eq!(
+            batcher.coalesce(),
+            [(
+                "<ndi_metadata_group><a/><b/></ndi_metadata_group>".to_string(),
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn lone_fragment_is_not_wrapped() {
+        let mut batcher = batcher(MetadataBatchOptions::default());
+        batcher.push("<a x=\"1\"/>", 7);
+        assert_eq!(batcher.coalesce(), [("<a x=\"1\"/>".to_string(), 7)]);
+    }
+
+    #[test]
+    fn splits_groups_at_the_size_limit() {
+        let mut batcher = batcher(MetadataBatchOptions {
+            max_frame_bytes: 50,
+            ..Default::default()
+        });
+        for (i, xml) in ["<a/>", "<b/>", "<c/>"].into_iter().enumerate() {
+            batcher.push(xml, i as i64);
+        }
+        let frames = batcher.coalesce();
+        assert_eq!(frames.len(), 2);
+        assert!(frames.iter().all(|(xml, _)| xml.len() <= 50));
+        assert_eq!(frames[1], ("<c/>".to_string(), 2));
+    }
+
+    #[test]
+    fn without_group_element_sends_one_per_frame() {
+        let mut batcher = batcher(MetadataBatchOptions {
+            group_element: None,
+            ..Default::default()
+        });
+        batcher.push("<a/>", 1);
+        batcher.push("<b/>", 2);
+        assert_eq!(
+            batcher.coalesce(),
+            [("<a/>".to_string(), 1), ("<b/>".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn drops_oldest_when_full() {
+        let mut batcher = batcher(MetadataBatchOptions {
+            group_element: None,
+            max_pending: 2,
+            ..Default::default()
+        });
+        batcher.push("<a/>", 1);
+        batcher.push("<b/>", 2);
+        batcher.push("<c/>", 3);
+        assert_eq!(batcher.pending(), 2);
+        assert_eq!(batcher.dropped(), 1);
+        assert_eq!(batcher.coalesce()[0].0, "<b/>");
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    ffi::{CStr, CString},
+    fmt,
+};
+
+use crate::{ndi_lib::*, FourCCVideoType, Send};
+
+type MetadataHook = Box<dyn FnMut(&FrameStamp<'_>) -> Option<String> + std::marker::Send>;
+
+/// What a hook installed with [`Send::set_frame_metadata_hook`] knows about the frame being
+/// sent.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameStamp<'f> {
+    /// Number of video frames the hook has seen before this one.
+    pub sequence: u64,
+    pub xres: i32,
+    pub yres: i32,
+    pub fourcc: FourCCVideoType,
+    /// In 100 ns units, possibly [`Timecode::SYNTHESIZE`](crate::Timecode::SYNTHESIZE).
+    pub timecode: i64,
+    pub timestamp: i64,
+    /// The metadata the frame itself carries.
+    pub metadata: Option<&'f str>,
+}
+
+/// A sender's installed hook and how many frames it has stamped.
+pub(crate) struct HookState {
+    hook: MetadataHook,
+    sequence: u64,
+}
+
+impl fmt::Debug for HookState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookState")
+            .field("sequence", &self.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Calls `hook` for every video frame this sender sends, sending the XML it returns as the
+    /// frame's metadata in place of the frame's own; `None` leaves the frame as it is.
+    ///
+    /// This stamps instrumentation such as timecodes or scene markers in one place instead of
+    /// at every call site. The hook sees frames from [`Send::send_video`],
+    /// [`Send::send_video_borrowed`] and [`Send::send_video_async_owned`], including those
+    /// sent by the paced and test-pattern helpers. Frames sent with the borrowing async
+    /// methods can't carry a stamp beyond the call, so the hook doesn't see them. It runs on
+    /// the sending thread so keep it quick. Replaces any
+    /// previous hook.
+    pub fn set_frame_metadata_hook(
+        &self,
+        hook: impl FnMut(&FrameStamp<'_>) -> Option<String> + std::marker::Send + 'static,
+    ) {
+        if let Ok(mut slot) = self.metadata_hook.lock() {
+            *slot = Some(HookState {
+                hook: Box::new(hook),
+                sequence: 0,
+            });
+        }
+    }
+
+    pub fn clear_frame_metadata_hook(&self) {
+        if let Ok(mut slot) = self.metadata_hook.lock() {
+            *slot = None;
+        }
+    }
+
+    /// Runs the hook, if any, on a frame about to be sent, returning the metadata to send
+    /// instead of the frame's own.
+    pub(crate) fn stamp_metadata(&self, raw: &NDIlib_video_frame_v2_t) -> Option<CString> {
+        let mut slot = self.metadata_hook.lock().ok()?;
+        let state = slot.as_mut()?;
+        let metadata = (!raw.p_metadata.is_null())
+            .then(|| unsafe { CStr::from_ptr(raw.p_metadata) }.to_str().ok())
+            .flatten();
+        let stamp = FrameStamp {
+            sequence: state.sequence,
+            xres: raw.xres,
+            yres: raw.yres,
+            fourcc: raw.FourCC.into(),
+            timecode: raw.timecode,
+            timestamp: raw.timestamp,
+            metadata,
+        };
+        state.sequence += 1;
+        let xml = (state.hook)(&stamp)?;
+        CString::new(xml)
+            .inspect_err(|_e| {
+                ndi_warn!(error = %_e, "frame metadata hook returned XML with a NUL byte; ignored");
+            })
+            .ok()
+    }
+}
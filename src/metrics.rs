@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use metrics::{counter, gauge};
+
+use crate::{ConnectionStats, Recv, Send, StatsMonitor};
+
+/// Publishes a receiver's counters through the `metrics` facade, labelled with `source`.
+///
+/// Totals become counters (`ndi_video_frames_received_total`, `ndi_video_frames_dropped_total`,
+/// ...) and queue depths and connection counts become gauges (`ndi_video_frames_queued`,
+/// `ndi_receiver_connections`).
+pub fn record_connection_stats(source: &str, stats: &ConnectionStats) {
+    let total = |name: &'static str, value: i64| {
+        counter!(name, "source" => source.to_string()).absolute(value.max(0) as u64);
+    };
+    total(
+        "ndi_video_frames_received_total",
+        stats.video_frames_received,
+    );
+    total(
+        "ndi_audio_frames_received_total",
+        stats.audio_frames_received,
+    );
+    total(
+        "ndi_metadata_frames_received_total",
+        stats.metadata_frames_received,
+    );
+    total("ndi_video_frames_dropped_total", stats.video_frames_dropped);
+    total("ndi_audio_frames_dropped_total", stats.audio_frames_dropped);
+    total(
+        "ndi_metadata_frames_dropped_total",
+        stats.metadata_frames_dropped,
+    );
+
+    let level = |name: &'static str, value: i32| {
+        gauge!(name, "source" => source.to_string()).set(value as f64);
+    };
+    level("ndi_video_frames_queued", stats.video_frames_queued);
+    level("ndi_audio_frames_queued", stats.audio_frames_queued);
+    level("ndi_metadata_frames_queued", stats.metadata_frames_queued);
+    level("ndi_receiver_connections", stats.connections);
+}
+
+impl<'a> Recv<'a> {
+    /// Publishes the current statistics through the `metrics` facade.
+    pub fn record_metrics(&self) {
+        record_connection_stats(&self.source.name, &self.connection_stats());
+    }
+
+    /// Publishes the statistics every `interval` from a background thread until the returned
+    /// monitor is dropped.
+    pub fn export_metrics(&self, interval: Duration) -> StatsMonitor<'_> {
+        let source = self.source.name.clone();
+        StatsMonitor::with_callback(self, interval, interval, move |snapshot| {
+            record_connection_stats(&source, &snapshot.stats)
+        })
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Publishes the number of connected receivers as the `ndi_sender_connections` gauge,
+    /// labelled with the sender's source name.
+    pub fn record_metrics(&self) {
+        let connections = self.get_no_connections(0);
+        gauge!("ndi_sender_connections", "sender" => self.get_source_name().name)
+            .set(connections as f64);
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver as ChannelReceiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{Error, FrameType, LentRecv, Receiver, Recv, Source, NDI};
+
+/// Frames buffered for a consumer that isn't keeping up; capture threads wait beyond this.
+pub(crate) const FRAME_BUFFER: usize = 16;
+/// Longest a capture thread blocks in the SDK before checking whether it should stop.
+const POLL_TIMEOUT_MS: u32 = 100;
+const RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Identifies one of the sources of a [`MultiReceiver`], in the order they were given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(pub usize);
+
+/// Receives from several sources at once, delivering every frame through one channel.
+///
+/// Each source gets its own receiver and capture thread, which captures through the receiver
+/// as [`Recv::capture`] does, so statistics, format tracking and status-change handling work
+/// as for a single receiver. Frames arrive tagged with the [`SourceId`] of the source they
+/// came from; timeouts are not forwarded, status changes are. A thread waits while the channel
+/// is full, so a consumer that falls behind holds back capture rather than losing frames.
+/// Retryable capture errors are retried; any other error stops that source's thread and is kept
+/// for [`MultiReceiver::take_error`]. Dropping the receiver stops and joins every thread.
+pub struct MultiReceiver<'a> {
+    receivers: Vec<Recv<'a>>,
+    workers: Vec<Worker>,
+    stopped: Arc<AtomicBool>,
+    frames: ChannelReceiver<(SourceId, FrameType)>,
+}
+
+struct Worker {
+    thread: Option<JoinHandle<()>>,
+    error: Arc<Mutex<Option<Error>>>,
+}
+
+impl<'a> MultiReceiver<'a> {
+    /// Creates a receiver for each of `receivers` and starts capturing from all of them.
+    pub fn new(ndi: &'a NDI, receivers: impl IntoIterator<Item = Receiver>) -> Result<Self, Error> {
+        let receivers = receivers
+            .into_iter()
+            .map(|settings| Recv::new(ndi, settings))
+            .collect::<Result<Vec<_>, _>>()?;
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (sender, frames) = mpsc::sync_channel(FRAME_BUFFER);
+        let workers = receivers
+            .iter()
+            .enumerate()
+            .map(|(index, recv)| {
+                let recv = recv.lend();
+                let id = SourceId(index);
+                let error = Arc::new(Mutex::new(None));
+                let thread_error = Arc::clone(&error);
+                let thread_stopped = Arc::clone(&stopped);
+                let sender = sender.clone();
+                let thread = thread::spawn(move || {
+                    if let Err(e) = capture_loop(&recv, id, &thread_stopped, &sender) {
+                        if let Ok(mut slot) = thread_error.lock() {
+                            *slot = Some(e);
+                        }
+                    }
+                });
+                Worker {
+                    thread: Some(thread),
+                    error,
+                }
+            })
+            .collect();
+        Ok(MultiReceiver {
+            receivers,
+            workers,
+            stopped,
+            frames,
+        })
+    }
+
+    /// The channel every captured frame is delivered through.
+    pub fn frames(&self) -> &ChannelReceiver<(SourceId, FrameType)> {
+        &self.frames
+    }
+
+    /// Waits up to `timeout` for the next frame from any source.
+    ///
+    /// Returns `None` on timeout, or once every capture thread has stopped and the channel is
+    /// drained.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<(SourceId, FrameType)> {
+        self.frames.recv_timeout(timeout).ok()
+    }
+
+    /// The next buffered frame, if one is waiting.
+    pub fn try_recv(&self) -> Option<(SourceId, FrameType)> {
+        self.frames.try_recv().ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.receivers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.receivers.is_empty()
+    }
+
+    pub fn source(&self, id: SourceId) -> Option<&Source> {
+        self.recv(id).map(Recv::source)
+    }
+
+    /// The receiver for `id`, for tally, PTZ and statistics; capture happens on its thread.
+    pub fn recv(&self, id: SourceId) -> Option<&Recv<'a>> {
+        self.receivers.get(id.0)
+    }
+
+    /// Whether the capture thread for `id` is still running.
+    pub fn is_running(&self, id: SourceId) -> bool {
+        self.workers
+            .get(id.0)
+            .and_then(|w| w.thread.as_ref())
+            .is_some_and(|t| !t.is_finished())
+    }
+
+    /// The error that stopped the capture thread for `id`, if any.
+    pub fn take_error(&self, id: SourceId) -> Option<Error> {
+        self.workers
+            .get(id.0)
+            .and_then(|w| w.error.lock().ok()?.take())
+    }
+}
+
+impl Drop for MultiReceiver<'_> {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                join_draining(thread, &self.frames);
+            }
+        }
+    }
+}
+
+fn capture_loop(
+    recv: &LentRecv,
+    id: SourceId,
+    stopped: &AtomicBool,
+    sender: &SyncSender<(SourceId, FrameType)>,
+) -> Result<(), Error> {
+    while !stopped.load(Ordering::Relaxed) {
+        let frame = match recv.capture_frame(POLL_TIMEOUT_MS) {
+            Ok(FrameType::None) => continue,
+            Ok(frame) => frame,
+            Err(e) if e.is_retryable() => {
+                thread::sleep(RETRY_DELAY);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if !deliver(sender, (id, frame), stopped) {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+/// Sends `item`, blocking while the channel is full; returns `false` once the consumer is
+/// gone or `stopped` is set. [`join_draining`] frees a sender blocked when stopping.
+pub(crate) fn deliver(
+    sender: &SyncSender<(SourceId, FrameType)>,
+    item: (SourceId, FrameType),
+    stopped: &AtomicBool,
+) -> bool {
+    !stopped.load(Ordering::Relaxed) && sender.send(item).is_ok()
+}
+
+/// Joins a capture thread that has been told to stop, discarding frames so it can't stay
+/// blocked delivering into a full channel.
+pub(crate) fn join_draining(
+    thread: JoinHandle<()>,
+    frames: &ChannelReceiver<(SourceId, FrameType)>,
+) {
+    while !thread.is_finished() {
+        let _ = frames.recv_timeout(Duration::from_millis(u64::from(POLL_TIMEOUT_MS)));
+    }
+    let _ = thread.join();
+}
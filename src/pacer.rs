@@ -0,0 +1,132 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Send, VideoFrame};
+
+/// How long before a deadline to stop sleeping and start spinning, to absorb scheduler jitter.
+const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+/// Paces a generated source at an exact rational frame rate such as 60000/1001.
+///
+/// Deadlines are computed from the frame count rather than accumulated, so rounding never
+/// builds up into drift. Timecodes are in the SDK's 100 ns units and advance by exactly one
+/// frame duration per frame.
+#[derive(Debug)]
+pub struct FramePacer {
+    frame_rate_n: u64,
+    frame_rate_d: u64,
+    start: Instant,
+    frame: u64,
+    timecode_base: i64,
+    skipped: u64,
+}
+
+impl FramePacer {
+    pub fn new(frame_rate_n: u32, frame_rate_d: u32) -> Self {
+        FramePacer {
+            frame_rate_n: frame_rate_n.max(1) as u64,
+            frame_rate_d: frame_rate_d.max(1) as u64,
+            start: Instant::now(),
+            frame: 0,
+            timecode_base: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Starts timecodes at `timecode` instead of zero.
+    pub fn with_timecode_base(mut self, timecode: i64) -> Self {
+        self.timecode_base = timecode;
+        self
+    }
+
+    /// Number of frames paced so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Frame slots skipped because the caller fell more than a frame behind.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Timecode of the next frame.
+    pub fn timecode(&self) -> i64 {
+        let ticks =
+            self.frame as u128 * self.frame_rate_d as u128 * 10_000_000 / self.frame_rate_n as u128;
+        self.timecode_base.saturating_add(ticks as i64)
+    }
+
+    /// Blocks until the next frame is due and returns its timecode.
+    ///
+    /// If the caller is running more than one frame late, the missed slots are skipped rather
+    /// than sent in a burst.
+    pub fn wait(&mut self) -> i64 {
+        let now = Instant::now();
+        let deadline = self.deadline(self.frame);
+        if now > deadline + self.frame_duration() {
+            let behind = self
+                .frame_at(now.duration_since(self.start))
+                .saturating_sub(self.frame);
+            self.frame += behind;
+            self.skipped += behind;
+        } else {
+            sleep_until(deadline);
+        }
+        let timecode = self.timecode();
+        self.frame += 1;
+        timecode
+    }
+
+    /// Restarts pacing from now, keeping the timecode running.
+    pub fn reset(&mut self) {
+        self.timecode_base = self.timecode();
+        self.start = Instant::now();
+        self.frame = 0;
+    }
+
+    fn frame_duration(&self) -> Duration {
+        Duration::from_nanos(self.frame_rate_d * 1_000_000_000 / self.frame_rate_n)
+    }
+
+    fn deadline(&self, frame: u64) -> Instant {
+        let nanos =
+            frame as u128 * self.frame_rate_d as u128 * 1_000_000_000 / self.frame_rate_n as u128;
+        self.start + Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+    }
+
+    fn frame_at(&self, elapsed: Duration) -> u64 {
+        (elapsed.as_nanos() * self.frame_rate_n as u128
+            / (self.frame_rate_d as u128 * 1_000_000_000)) as u64
+    }
+}
+
+fn sleep_until(deadline: Instant) {
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        let remaining = deadline - now;
+        if remaining > SPIN_MARGIN {
+            thread::sleep(remaining - SPIN_MARGIN);
+        } else {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Waits for `pacer`'s next slot, stamps `frame` with its timecode and sends it.
+    pub fn send_video_paced(
+        &self,
+        pacer: &mut FramePacer,
+        frame: &mut VideoFrame,
+    ) -> Result<(), Error> {
+        frame.validate()?;
+        frame.timecode = pacer.wait();
+        self.send_video(frame);
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{ConnectionMetadata, KnownMetadata, Send};
+
+/// Who is connected to a sender, as returned by [`Send::connected_receivers`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderConnections {
+    /// Receivers connected right now, as counted by the SDK.
+    pub count: i32,
+    /// Product information announced by connected receivers, oldest first.
+    pub receivers: Vec<ConnectionMetadata>,
+}
+
+impl SenderConnections {
+    /// Connections whose receiver hasn't announced itself (yet).
+    pub fn anonymous(&self) -> usize {
+        (self.count.max(0) as usize).saturating_sub(self.receivers.len())
+    }
+}
+
+impl<'a> Send<'a> {
+    /// The number of connected receivers, with the product information those that announce
+    /// themselves sent on connecting, for dashboards showing who is pulling the feed.
+    ///
+    /// Receivers announce themselves through metadata, so this only knows about announcements
+    /// already read by [`Send::capture_metadata`] or [`Send::capture`]; keep capturing for it to
+    /// stay current. The SDK doesn't say which connection went away, so when there are fewer
+    /// connections than announcements the oldest ones are dropped.
+    pub fn connected_receivers(&self) -> SenderConnections {
+        let count = self.get_no_connections(0);
+        let Ok(mut peers) = self.peers.lock() else {
+            return SenderConnections {
+                count,
+                receivers: Vec::new(),
+            };
+        };
+        let connected = count.max(0) as usize;
+        if peers.len() > connected {
+            let stale = peers.len() - connected;
+            peers.drain(..stale);
+        }
+        SenderConnections {
+            count,
+            receivers: peers.clone(),
+        }
+    }
+
+    /// Records a receiver's product announcement. Other metadata is ignored without
+    /// allocating.
+    pub(crate) fn observe_metadata(&self, xml: &str) {
+        if !xml.trim_start().starts_with("<ndi_product") {
+            return;
+        }
+        if let Some(KnownMetadata::Product(product)) = KnownMetadata::parse(xml) {
+            ndi_debug!(?product, "receiver announced itself");
+            if let Ok(mut peers) = self.peers.lock() {
+                // A receiver reconnecting announces itself again.
+                peers.retain(|p| *p != product);
+                peers.push(product);
+            }
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    process,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::RecvTimeoutError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Error, FieldMode, Find, Finder, Pattern, Receiver, Recv, RecvBandwidth, RecvColorFormat, Send,
+    Sender, VideoFrame, NDI,
+};
+
+/// How long each capture call in the loop waits before checking the deadline again.
+const POLL_MS: u32 = 100;
+
+/// Tells concurrent loopback runs in one process apart.
+static NEXT_RUN: AtomicU64 = AtomicU64::new(0);
+
+/// How [`run_loopback`] takes frames from the receiver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// Copies every frame out of the SDK with [`VideoFrameRef::to_owned`](crate::VideoFrameRef::to_owned).
+    Owned,
+    /// Reads every frame in place through [`Recv::capture_video_ref`].
+    Ref,
+}
+
+/// How [`run_loopback`] hands frames to the sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendMode {
+    /// [`Send::send_video`], which blocks until the SDK has taken the frame.
+    Sync,
+    /// [`Send::send_video_async_owned`] with `buffers` frames in rotation, each reused once the
+    /// SDK releases it through [`Send::recycle_channel`].
+    Async { buffers: usize },
+}
+
+/// What [`run_loopback`] sends and for how long.
+#[derive(Debug, Clone)]
+pub struct LoopbackConfig {
+    pub width: i32,
+    pub height: i32,
+    /// Frames to receive once the first has arrived.
+    pub frames: u32,
+    pub capture: CaptureMode,
+    pub send: SendMode,
+    /// Gives up this long after the sender starts, whether or not all frames have arrived.
+    pub timeout: Duration,
+}
+
+impl LoopbackConfig {
+    pub fn new(width: i32, height: i32) -> Self {
+        LoopbackConfig {
+            width,
+            height,
+            frames: 300,
+            capture: CaptureMode::Ref,
+            send: SendMode::Sync,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_frames(mut self, frames: u32) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    pub fn with_capture(mut self, capture: CaptureMode) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    pub fn with_send(mut self, send: SendMode) -> Self {
+        self.send = send;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// What a [`run_loopback`] measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThroughputReport {
+    pub frames_sent: u64,
+    pub frames_received: u64,
+    pub bytes_received: u64,
+    /// From the first frame received to the last.
+    pub elapsed: Duration,
+    /// Time spent copying frames out of the SDK; zero for [`CaptureMode::Ref`].
+    pub copy_time: Duration,
+    /// Time the sender spent waiting for the SDK to release a buffer, with
+    /// [`SendMode::Async`].
+    pub send_stall_time: Duration,
+}
+
+impl ThroughputReport {
+    /// Frames received per second, counting the intervals between them.
+    pub fn fps(&self) -> f64 {
+        per_second(self.frames_received.saturating_sub(1) as f64, self.elapsed)
+    }
+
+    /// Megabytes per second of decoded video delivered by the receiver.
+    pub fn receive_mb_per_sec(&self) -> f64 {
+        per_second(self.bytes_received as f64, self.elapsed) / 1_000_000.0
+    }
+
+    /// Megabytes per second the owned capture copies at, or `None` if nothing was copied.
+    pub fn copy_mb_per_sec(&self) -> Option<f64> {
+        (!self.copy_time.is_zero())
+            .then(|| per_second(self.bytes_received as f64, self.copy_time) / 1_000_000.0)
+    }
+
+    /// Frames sent that the receiver never saw, e.g. because it fell behind and the SDK
+    /// dropped them, or because they were sent before it connected.
+    pub fn frames_dropped(&self) -> u64 {
+        self.frames_sent.saturating_sub(self.frames_received)
+    }
+}
+
+fn per_second(amount: f64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        0.0
+    } else {
+        amount / elapsed.as_secs_f64()
+    }
+}
+
+/// Sends colour bars from a sender to a receiver in this process, over localhost, as fast as
+/// they will go, and measures what arrives.
+///
+/// This is the harness behind the crate's benchmarks, public so that throughput can be
+/// checked on the machine and SDK version actually deployed. The sender isn't clocked, so
+/// the frame rate is bound by encoding, decoding and copying rather than by a nominal rate;
+/// compare runs against each other, not against a camera's frame rate.
+pub fn run_loopback(ndi: &NDI, config: &LoopbackConfig) -> Result<ThroughputReport, Error> {
+    let frame = Pattern::ColorBars.frame(config.width, config.height, 60)?;
+    let name = format!(
+        "grafton-ndi loopback {}-{}",
+        process::id(),
+        NEXT_RUN.fetch_add(1, Ordering::Relaxed)
+    );
+    let send = Send::new(
+        ndi,
+        Sender {
+            name: name.clone(),
+            groups: None,
+            clock_video: false,
+            clock_audio: false,
+            config: None,
+        },
+    )?;
+    let find = Find::new(ndi, Finder::new(true, None, None))?;
+    let source = find
+        .sources_matching(&format!("*({name})"), config.timeout)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::SourceNotFound {
+            name: name.clone(),
+            timeout: config.timeout,
+            attempts: 1,
+            seen: Vec::new(),
+        })?;
+    let recv = Recv::new(
+        ndi,
+        Receiver::new(
+            source,
+            RecvColorFormat::BGRX_BGRA,
+            RecvBandwidth::Highest,
+            FieldMode::Progressive,
+            Some(format!("{name} receiver")),
+        ),
+    )?;
+
+    let stop = AtomicBool::new(false);
+    let deadline = Instant::now() + config.timeout;
+    thread::scope(|scope| {
+        let sender = scope.spawn(|| send_until(&send, frame, config.send, &stop));
+        let received = receive(&recv, config, deadline);
+        stop.store(true, Ordering::Relaxed);
+        let (frames_sent, send_stall_time) = sender.join().unwrap_or_default();
+        let mut report = received?;
+        report.frames_sent = frames_sent;
+        report.send_stall_time = send_stall_time;
+        ndi_debug!(
+            fps = report.fps(),
+            frames_dropped = report.frames_dropped(),
+            "loopback run finished"
+        );
+        Ok(report)
+    })
+}
+
+/// Sends until `stop` is set, returning the frames sent and the time spent waiting for
+/// buffers.
+fn send_until(
+    send: &Send,
+    frame: VideoFrame,
+    mode: SendMode,
+    stop: &AtomicBool,
+) -> (u64, Duration) {
+    let mut sent = 0;
+    let mut stalled = Duration::ZERO;
+    match mode {
+        SendMode::Sync => {
+            while !stop.load(Ordering::Relaxed) {
+                send.send_video(&frame);
+                sent += 1;
+            }
+        }
+        SendMode::Async { buffers } => {
+            let recycled = send.recycle_channel();
+            let mut pool = vec![frame.clone(); buffers.max(1) - 1];
+            pool.push(frame);
+            while !stop.load(Ordering::Relaxed) {
+                let frame = match pool.pop() {
+                    Some(frame) => frame,
+                    None => {
+                        let waited = Instant::now();
+                        let frame =
+                            recycled.recv_timeout(Duration::from_millis(u64::from(POLL_MS)));
+                        stalled += waited.elapsed();
+                        match frame {
+                            Ok(frame) => frame,
+                            Err(RecvTimeoutError::Timeout) => continue,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        }
+                    }
+                };
+                if send.send_video_async_owned(frame).is_err() {
+                    break;
+                }
+                sent += 1;
+            }
+            send.flush_async();
+        }
+    }
+    (sent, stalled)
+}
+
+/// Captures `config.frames` frames, timing from the first.
+fn receive(
+    recv: &Recv,
+    config: &LoopbackConfig,
+    deadline: Instant,
+) -> Result<ThroughputReport, Error> {
+    let mut report = ThroughputReport::default();
+    let mut first = None;
+    while report.frames_received < u64::from(config.frames) && Instant::now() < deadline {
+        let Some(frame) = recv.capture_video_ref(POLL_MS)? else {
+            continue;
+        };
+        let started = *first.get_or_insert_with(Instant::now);
+        match config.capture {
+            CaptureMode::Owned => {
+                let copying = Instant::now();
+                let owned = frame.to_owned();
+                report.copy_time += copying.elapsed();
+                report.bytes_received += owned.data.len() as u64;
+            }
+            CaptureMode::Ref => report.bytes_received += frame.data().len() as u64,
+        }
+        report.frames_received += 1;
+        report.elapsed = started.elapsed();
+    }
+    if report.frames_received == 0 {
+        return Err(Error::Timeout {
+            operation: "the first loopback frame",
+            timeout_ms: config.timeout.as_millis().min(u32::MAX as u128) as u32,
+        });
+    }
+    Ok(report)
+}
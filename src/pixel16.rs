@@ -0,0 +1,105 @@
+use crate::{Error, FourCCVideoType, VideoFrame, VideoFrameRef};
+
+/// The planes of a P216 or PA16 frame as 16-bit samples.
+///
+/// Each plane has `yres` rows of `stride` samples. The UV plane holds interleaved U and V
+/// samples at half the horizontal resolution of Y (4:2:2).
+#[derive(Debug, Clone, Copy)]
+pub struct Planes16<'f> {
+    pub y: &'f [u16],
+    pub uv: &'f [u16],
+    /// Only present for PA16.
+    pub alpha: Option<&'f [u16]>,
+    /// Row length in samples, including any padding.
+    pub stride: usize,
+}
+
+impl VideoFrame {
+    /// The data of a P216 or PA16 frame as 16-bit samples, in the machine's byte order.
+    ///
+    /// Fails if the buffer isn't 2-byte aligned, which can only happen for data placed in the
+    /// frame by hand.
+    pub fn data_u16(&self) -> Result<&[u16], Error> {
+        let stride = unsafe { self.line_stride_or_size.line_stride_in_bytes };
+        samples(self.fourcc, self.xres, self.yres, stride, &self.data)
+    }
+
+    /// The Y, UV and (for PA16) alpha planes of a P216 or PA16 frame.
+    pub fn planes_u16(&self) -> Result<Planes16<'_>, Error> {
+        let stride = unsafe { self.line_stride_or_size.line_stride_in_bytes };
+        let data = samples(self.fourcc, self.xres, self.yres, stride, &self.data)?;
+        Ok(planes(self.fourcc, self.yres, stride, data))
+    }
+}
+
+impl VideoFrameRef<'_> {
+    /// The data of a P216 or PA16 frame as 16-bit samples, in the machine's byte order.
+    pub fn data_u16(&self) -> Result<&[u16], Error> {
+        let stride = self.stride();
+        samples(self.fourcc(), self.xres(), self.yres(), stride, self.data())
+    }
+
+    /// The Y, UV and (for PA16) alpha planes of a P216 or PA16 frame.
+    pub fn planes_u16(&self) -> Result<Planes16<'_>, Error> {
+        let stride = self.stride();
+        let data = samples(self.fourcc(), self.xres(), self.yres(), stride, self.data())?;
+        Ok(planes(self.fourcc(), self.yres(), stride, data))
+    }
+
+    fn stride(&self) -> i32 {
+        self.line_stride()
+            .unwrap_or_else(|| self.fourcc().line_stride(self.xres()))
+    }
+}
+
+/// Checks the layout and reinterprets `data` as 16-bit samples.
+fn samples(
+    fourcc: FourCCVideoType,
+    xres: i32,
+    yres: i32,
+    line_stride: i32,
+    data: &[u8],
+) -> Result<&[u16], Error> {
+    if !matches!(fourcc, FourCCVideoType::P216 | FourCCVideoType::PA16) {
+        return Err(Error::UnsupportedFormat {
+            fourcc,
+            operation: "view as 16-bit samples",
+        });
+    }
+    let required = fourcc.check_layout(xres, yres, line_stride)?;
+    if line_stride % 2 != 0 {
+        return Err(Error::InvalidFrame(format!(
+            "line stride of {} bytes is not a whole number of 16-bit samples",
+            line_stride
+        )));
+    }
+    if data.len() < required {
+        return Err(Error::InvalidDataSize {
+            fourcc,
+            xres,
+            yres,
+            line_stride,
+            expected: required,
+            actual: data.len(),
+        });
+    }
+    // SAFETY: every bit pattern is a valid u16; `align_to` only yields the aligned middle.
+    let (prefix, samples, _) = unsafe { data[..required].align_to::<u16>() };
+    if !prefix.is_empty() {
+        return Err(Error::InvalidFrame(
+            "16-bit frame data is not 2-byte aligned".into(),
+        ));
+    }
+    Ok(samples)
+}
+
+fn planes(fourcc: FourCCVideoType, yres: i32, line_stride: i32, data: &[u16]) -> Planes16<'_> {
+    let stride = line_stride as usize / 2;
+    let plane = stride * yres as usize;
+    Planes16 {
+        y: &data[..plane],
+        uv: &data[plane..plane * 2],
+        alpha: matches!(fourcc, FourCCVideoType::PA16).then(|| &data[plane * 2..plane * 3]),
+        stride,
+    }
+}
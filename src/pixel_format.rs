@@ -0,0 +1,213 @@
+#[cfg(feature = "hashing")]
+use crate::Plane;
+use crate::{FourCCVideoType, VideoFrame, VideoFrameRef};
+
+/// How much the chroma of a format is subsampled relative to luma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChromaSubsampling {
+    /// Every pixel has its own colour, as in the RGB formats.
+    None,
+    /// Half horizontal chroma resolution.
+    Yuv422,
+    /// Half horizontal and half vertical chroma resolution.
+    Yuv420,
+}
+
+/// Size of one plane relative to the first plane's stride and height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PlaneLayout {
+    /// Divides the first plane's stride.
+    stride_divisor: usize,
+    /// Divides the frame height, rounding up.
+    rows_divisor: usize,
+}
+
+const FULL: PlaneLayout = PlaneLayout {
+    stride_divisor: 1,
+    rows_divisor: 1,
+};
+const HALF_STRIDE: PlaneLayout = PlaneLayout {
+    stride_divisor: 2,
+    rows_divisor: 1,
+};
+const HALF_HEIGHT: PlaneLayout = PlaneLayout {
+    stride_divisor: 1,
+    rows_divisor: 2,
+};
+const QUARTER: PlaneLayout = PlaneLayout {
+    stride_divisor: 2,
+    rows_divisor: 2,
+};
+
+/// Layout facts about an uncompressed video format, from [`FourCCVideoType::info`].
+///
+/// This is the single description the crate sizes and validates buffers from, so a frame
+/// accepted by a builder is one the SDK reads the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelFormatInfo {
+    pub fourcc: FourCCVideoType,
+    /// Average storage per pixel across all planes.
+    pub bits_per_pixel: u32,
+    /// Storage per colour or alpha sample.
+    pub bits_per_component: u32,
+    pub subsampling: ChromaSubsampling,
+    pub has_alpha: bool,
+    /// Bytes per pixel in the first plane, which the default stride is based on.
+    first_plane_bytes: i32,
+    planes: &'static [PlaneLayout],
+}
+
+impl PixelFormatInfo {
+    /// Number of separately stored planes; 1 for the packed formats.
+    pub fn plane_count(&self) -> usize {
+        self.planes.len()
+    }
+
+    /// Stride in bytes of the first plane of a tightly packed frame `xres` pixels wide.
+    pub fn line_stride(&self, xres: i32) -> i32 {
+        xres * self.first_plane_bytes
+    }
+
+    /// Total buffer size in bytes, across all planes, for the given first-plane stride.
+    pub fn buffer_size(&self, line_stride: i32, yres: i32) -> usize {
+        let stride = line_stride.max(0) as usize;
+        let rows = yres.max(0) as usize;
+        self.planes
+            .iter()
+            .map(|p| (stride / p.stride_divisor) * rows.div_ceil(p.rows_divisor))
+            .sum()
+    }
+
+    /// Where every plane, alpha and 16-bit ones included, lives in a buffer with the given
+    /// first-plane stride.
+    #[cfg(feature = "hashing")]
+    pub(crate) fn plane_layout(&self, xres: i32, yres: i32, line_stride: i32) -> Vec<Plane> {
+        let stride = line_stride.max(0) as usize;
+        let rows = yres.max(0) as usize;
+        let row_bytes = self.line_stride(xres).max(0) as usize;
+        let mut offset = 0;
+        self.planes
+            .iter()
+            .map(|p| {
+                let plane_stride = stride / p.stride_divisor;
+                let plane = Plane {
+                    offset,
+                    stride: plane_stride,
+                    row_bytes: row_bytes.div_ceil(p.stride_divisor).min(plane_stride),
+                    rows: rows.div_ceil(p.rows_divisor),
+                };
+                offset += plane.stride * plane.rows;
+                plane
+            })
+            .collect()
+    }
+
+    /// Buffer size in bytes of a tightly packed `xres` x `yres` frame.
+    pub fn frame_size(&self, xres: i32, yres: i32) -> usize {
+        self.buffer_size(self.line_stride(xres), yres)
+    }
+}
+
+impl FourCCVideoType {
+    pub fn info(&self) -> PixelFormatInfo {
+        let (bits_per_pixel, bits_per_component, subsampling, has_alpha, first_plane_bytes, planes) =
+            match self {
+                // 4:2:2 packed as U0 Y0 V0 Y1
+                FourCCVideoType::UYVY => (16, 8, ChromaSubsampling::Yuv422, false, 2, &[FULL][..]),
+                // As UYVY, followed by an 8-bit alpha plane at half the stride
+                FourCCVideoType::UYVA => (
+                    24,
+                    8,
+                    ChromaSubsampling::Yuv422,
+                    true,
+                    2,
+                    &[FULL, HALF_STRIDE][..],
+                ),
+                // 16-bit Y plane followed by an interleaved 16-bit UV plane of the same size
+                FourCCVideoType::P216 => (
+                    32,
+                    16,
+                    ChromaSubsampling::Yuv422,
+                    false,
+                    2,
+                    &[FULL, FULL][..],
+                ),
+                // As P216, followed by a 16-bit alpha plane
+                FourCCVideoType::PA16 => (
+                    48,
+                    16,
+                    ChromaSubsampling::Yuv422,
+                    true,
+                    2,
+                    &[FULL, FULL, FULL][..],
+                ),
+                // 4:2:0 planar, two chroma planes at half stride and half height
+                FourCCVideoType::YV12 | FourCCVideoType::I420 => (
+                    12,
+                    8,
+                    ChromaSubsampling::Yuv420,
+                    false,
+                    1,
+                    &[FULL, QUARTER, QUARTER][..],
+                ),
+                // 4:2:0 with an interleaved UV plane at full stride and half height
+                FourCCVideoType::NV12 => (
+                    12,
+                    8,
+                    ChromaSubsampling::Yuv420,
+                    false,
+                    1,
+                    &[FULL, HALF_HEIGHT][..],
+                ),
+                FourCCVideoType::BGRA | FourCCVideoType::RGBA => {
+                    (32, 8, ChromaSubsampling::None, true, 4, &[FULL][..])
+                }
+                FourCCVideoType::BGRX | FourCCVideoType::RGBX | FourCCVideoType::Max => {
+                    (32, 8, ChromaSubsampling::None, false, 4, &[FULL][..])
+                }
+            };
+        PixelFormatInfo {
+            fourcc: *self,
+            bits_per_pixel,
+            bits_per_component,
+            subsampling,
+            has_alpha,
+            first_plane_bytes,
+            planes,
+        }
+    }
+
+    /// Stride in bytes of the first plane of a tightly packed frame `xres` pixels wide.
+    pub(crate) fn line_stride(&self, xres: i32) -> i32 {
+        self.info().line_stride(xres)
+    }
+
+    /// Total buffer size in bytes, across all planes, for the given first-plane stride.
+    pub(crate) fn buffer_size(&self, line_stride: i32, yres: i32) -> usize {
+        self.info().buffer_size(line_stride, yres)
+    }
+}
+
+impl VideoFrame {
+    /// Layout of the format this frame was actually delivered in.
+    pub fn pixel_format(&self) -> PixelFormatInfo {
+        self.fourcc.info()
+    }
+
+    /// Whether the frame carries an alpha channel (BGRA, RGBA, UYVA or PA16).
+    pub fn has_alpha(&self) -> bool {
+        self.fourcc.info().has_alpha
+    }
+}
+
+impl VideoFrameRef<'_> {
+    /// Layout of the format this frame was actually delivered in.
+    pub fn pixel_format(&self) -> PixelFormatInfo {
+        self.fourcc().info()
+    }
+
+    /// Whether the frame carries an alpha channel (BGRA, RGBA, UYVA or PA16).
+    pub fn has_alpha(&self) -> bool {
+        self.fourcc().info().has_alpha
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{Error, FourCCVideoType, FramePacer, Send, VideoFrame, VideoFrameBuilder};
+
+const DEFAULT_FRAME_RATE_N: u32 = 30;
+const DEFAULT_FRAME_RATE_D: u32 = 1;
+
+/// Plays a sequence of frames from disk out through a sender, for test signals and signage.
+///
+/// Frames come from a directory of PNG/JPEG images (with the `image_interop` feature), a
+/// directory of raw frame files, or one file of raw frames back to back, such as the output
+/// of `ffmpeg -f rawvideo`. Files in a directory play in name order. Frames are read from disk
+/// as they are sent unless [`Player::preload`] has been called.
+#[derive(Debug)]
+pub struct Player {
+    frames: Frames,
+    frame_rate_n: u32,
+    frame_rate_d: u32,
+    looping: bool,
+}
+
+#[derive(Debug)]
+enum Frames {
+    Raw {
+        files: Vec<PathBuf>,
+        format: RawFormat,
+    },
+    RawFile {
+        path: PathBuf,
+        count: usize,
+        format: RawFormat,
+    },
+    #[cfg(feature = "image_interop")]
+    Images(Vec<PathBuf>),
+    Loaded(Vec<VideoFrame>),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RawFormat {
+    fourcc: FourCCVideoType,
+    xres: i32,
+    yres: i32,
+}
+
+impl RawFormat {
+    fn frame_size(&self) -> usize {
+        self.fourcc.info().frame_size(self.xres, self.yres)
+    }
+
+    fn frame(&self, data: Vec<u8>) -> Result<VideoFrame, Error> {
+        VideoFrameBuilder::new()
+            .resolution(self.xres, self.yres)
+            .fourcc(self.fourcc)
+            .aspect_ratio(self.xres as f32 / self.yres as f32)
+            .data(data)
+            .build()
+    }
+}
+
+impl Player {
+    /// Plays tightly packed `fourcc` frames of `xres` x `yres` from `path`: either a
+    /// directory holding one frame per file, or a single file of frames back to back.
+    pub fn raw(
+        path: impl AsRef<Path>,
+        fourcc: FourCCVideoType,
+        xres: i32,
+        yres: i32,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let format = RawFormat { fourcc, xres, yres };
+        fourcc.check_layout(xres, yres, fourcc.line_stride(xres))?;
+        let frames = if path.is_dir() {
+            Frames::Raw {
+                files: sorted_files(path, |_| true)?,
+                format,
+            }
+        } else {
+            let len = fs::metadata(path)?.len() as usize;
+            let frame_size = format.frame_size();
+            if !len.is_multiple_of(frame_size) {
+                return Err(Error::InvalidFrame(format!(
+                    "{} is {} bytes, not a whole number of {} byte frames",
+                    path.display(),
+                    len,
+                    frame_size
+                )));
+            }
+            Frames::RawFile {
+                path: path.to_path_buf(),
+                count: len / frame_size,
+                format,
+            }
+        };
+        Self::with_frames(frames, path)
+    }
+
+    /// Plays the PNG and JPEG images in the directory `dir`, as RGBA frames.
+    #[cfg(feature = "image_interop")]
+    pub fn images(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        let dir = dir.as_ref();
+        let files = sorted_files(dir, |path| {
+            path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+                ["png", "jpg", "jpeg"]
+                    .iter()
+                    .any(|ext| e.eq_ignore_ascii_case(ext))
+            })
+        })?;
+        Self::with_frames(Frames::Images(files), dir)
+    }
+
+    fn with_frames(frames: Frames, path: &Path) -> Result<Self, Error> {
+        let player = Player {
+            frames,
+            frame_rate_n: DEFAULT_FRAME_RATE_N,
+            frame_rate_d: DEFAULT_FRAME_RATE_D,
+            looping: true,
+        };
+        if player.is_empty() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no frames to play in {}", path.display()),
+            )));
+        }
+        Ok(player)
+    }
+
+    /// Sets the frame rate frames are sent at; defaults to 30 fps.
+    pub fn frame_rate(mut self, numerator: u32, denominator: u32) -> Self {
+        self.frame_rate_n = numerator.max(1);
+        self.frame_rate_d = denominator.max(1);
+        self
+    }
+
+    /// Whether to start over after the last frame; defaults to `true`.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Reads and decodes every frame now, so playback never waits on the disk.
+    ///
+    /// Worth it for short loops; a long sequence may not fit in memory.
+    pub fn preload(mut self) -> Result<Self, Error> {
+        let frames = (0..self.len())
+            .map(|index| self.frame(index))
+            .collect::<Result<_, _>>()?;
+        self.frames = Frames::Loaded(frames);
+        Ok(self)
+    }
+
+    /// Number of frames in one pass of the sequence.
+    pub fn len(&self) -> usize {
+        match &self.frames {
+            Frames::Raw { files, .. } => files.len(),
+            Frames::RawFile { count, .. } => *count,
+            #[cfg(feature = "image_interop")]
+            Frames::Images(files) => files.len(),
+            Frames::Loaded(frames) => frames.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Loads frame `index` of the sequence, stamped with the player's frame rate.
+    ///
+    /// Panics if `index` is not less than [`Player::len`].
+    pub fn frame(&self, index: usize) -> Result<VideoFrame, Error> {
+        let mut frame = match &self.frames {
+            Frames::Raw { files, format } => format.frame(fs::read(&files[index])?)?,
+            Frames::RawFile { path, format, .. } => {
+                let size = format.frame_size();
+                let mut file = File::open(path)?;
+                file.seek(SeekFrom::Start((index * size) as u64))?;
+                let mut data = vec![0; size];
+                file.read_exact(&mut data)?;
+                format.frame(data)?
+            }
+            #[cfg(feature = "image_interop")]
+            Frames::Images(files) => {
+                let image = image::open(&files[index]).map_err(|e| {
+                    Error::InvalidFrame(format!("{}: {}", files[index].display(), e))
+                })?;
+                VideoFrame::from(image.to_rgba8())
+            }
+            Frames::Loaded(frames) => frames[index].clone(),
+        };
+        frame.frame_rate_n = self.frame_rate_n.min(i32::MAX as u32) as i32;
+        frame.frame_rate_d = self.frame_rate_d.min(i32::MAX as u32) as i32;
+        Ok(frame)
+    }
+
+    /// Sends the sequence through `send` in real time, returning the number of frames sent.
+    ///
+    /// Stops after `duration`, or, with no duration, at the end of the sequence; a looping
+    /// player given no duration plays until an error occurs.
+    pub fn play(&self, send: &Send<'_>, duration: Option<Duration>) -> Result<u64, Error> {
+        let deadline = duration.map(|d| Instant::now() + d);
+        let mut pacer = FramePacer::new(self.frame_rate_n, self.frame_rate_d);
+        let mut sent = 0;
+        loop {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+            // Index by the pacer's slot so the sequence keeps to real time when slots are skipped.
+            let position = pacer.frame() as usize;
+            if !self.looping && position >= self.len() {
+                break;
+            }
+            let mut frame = self.frame(position % self.len())?;
+            send.send_video_paced(&mut pacer, &mut frame)?;
+            sent += 1;
+        }
+        ndi_debug!(sent, skipped = pacer.skipped(), "player stopped");
+        Ok(sent)
+    }
+}
+
+/// The regular files in `dir` that `keep` accepts, sorted by name.
+fn sorted_files(dir: &Path, keep: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && keep(&path) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,186 @@
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Recv};
+
+const DEFAULT_QUALITY: u8 = 75;
+/// How long a capture waits before new connections are accepted again.
+const POLL_TIMEOUT_MS: u32 = 100;
+/// How long a connecting client may take to send its request, and a client to take a frame.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+/// Requests with longer headers are refused.
+const MAX_REQUEST_LEN: usize = 8 * 1024;
+const BOUNDARY: &str = "ndiframe";
+
+/// Serves a receiver's video over HTTP as an MJPEG (`multipart/x-mixed-replace`) stream, for
+/// a quick browser preview of an NDI source.
+///
+/// Any `GET` path streams the video; open `http://<addr>/` in a browser or point an `<img>`
+/// at it. Frames are only encoded while a client is connected. A client that blocks a frame
+/// for more than a second or disconnects is dropped; the others carry on.
+pub struct PreviewServer {
+    listener: TcpListener,
+    clients: Mutex<Vec<TcpStream>>,
+    quality: u8,
+    frame_interval: Duration,
+    stopped: AtomicBool,
+}
+
+impl PreviewServer {
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        ndi_info!(addr = ?listener.local_addr().ok(), "preview server listening");
+        Ok(PreviewServer {
+            listener,
+            clients: Mutex::new(Vec::new()),
+            quality: DEFAULT_QUALITY,
+            frame_interval: Duration::ZERO,
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    /// Binds to `addr` and serves `recv` until capturing fails.
+    pub fn serve(recv: &Recv<'_>, addr: impl ToSocketAddrs) -> Result<(), Error> {
+        PreviewServer::bind(addr)?.run(recv)
+    }
+
+    /// JPEG quality from 1 to 100; defaults to 75.
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.quality = quality.clamp(1, 100);
+        self
+    }
+
+    /// Streams at most `fps` frames per second, skipping the rest; unlimited by default.
+    pub fn with_max_fps(mut self, fps: f64) -> Self {
+        self.frame_interval = if fps > 0.0 {
+            Duration::from_secs_f64(1.0 / fps)
+        } else {
+            Duration::ZERO
+        };
+        self
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn clients(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    /// Captures from `recv` and streams each frame to the connected clients until
+    /// [`PreviewServer::stop`] is called or capturing fails.
+    pub fn run(&self, recv: &Recv<'_>) -> Result<(), Error> {
+        let mut last_sent: Option<Instant> = None;
+        while !self.stopped.load(Ordering::Acquire) {
+            self.accept_pending()?;
+            let Some(frame) = recv.capture_video_ref(POLL_TIMEOUT_MS)? else {
+                continue;
+            };
+            if self.clients() == 0 || last_sent.is_some_and(|at| at.elapsed() < self.frame_interval)
+            {
+                continue;
+            }
+            last_sent = Some(Instant::now());
+            let jpeg = frame.encode_jpeg(self.quality)?;
+            drop(frame);
+            self.broadcast(&jpeg);
+        }
+        Ok(())
+    }
+
+    /// Makes [`PreviewServer::run`] return after its current capture.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Release);
+    }
+
+    fn accept_pending(&self) -> Result<(), Error> {
+        loop {
+            let (stream, _addr) = match self.listener.accept() {
+                Ok(accepted) => accepted,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e.into()),
+            };
+            match start_stream(stream) {
+                Ok(Some(stream)) => {
+                    ndi_debug!(addr = %_addr, "preview client connected");
+                    self.clients.lock().unwrap().push(stream);
+                }
+                Ok(None) => {}
+                Err(_e) => {
+                    ndi_debug!(addr = %_addr, error = %_e, "preview request failed");
+                }
+            }
+        }
+    }
+
+    fn broadcast(&self, jpeg: &[u8]) {
+        let header = format!(
+            "--{}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+            BOUNDARY,
+            jpeg.len()
+        );
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain_mut(|stream| {
+            let sent = stream
+                .write_all(header.as_bytes())
+                .and_then(|()| stream.write_all(jpeg))
+                .and_then(|()| stream.write_all(b"\r\n"));
+            match sent {
+                Ok(()) => true,
+                Err(_e) => {
+                    ndi_debug!(error = %_e, "dropping preview client");
+                    false
+                }
+            }
+        });
+    }
+}
+
+/// Reads a request from a new connection and answers it: `GET` starts the stream, anything
+/// else is refused and the connection dropped.
+fn start_stream(mut stream: TcpStream) -> io::Result<Option<TcpStream>> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        if request.len() > MAX_REQUEST_LEN {
+            stream.write_all(
+                b"HTTP/1.1 431 Request Header Fields Too Large\r\nConnection: close\r\n\r\n",
+            )?;
+            return Ok(None);
+        }
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        request.extend_from_slice(&chunk[..read]);
+    }
+
+    if !request.starts_with(b"GET ") {
+        stream.write_all(
+            b"HTTP/1.1 405 Method Not Allowed\r\nAllow: GET\r\nConnection: close\r\n\r\n",
+        )?;
+        return Ok(None);
+    }
+    let response = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: multipart/x-mixed-replace; boundary={}\r\n\
+         Cache-Control: no-cache, no-store\r\n\
+         Connection: close\r\n\r\n",
+        BOUNDARY
+    );
+    stream.write_all(response.as_bytes())?;
+    Ok(Some(stream))
+}
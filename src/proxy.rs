@@ -0,0 +1,226 @@
+use std::{
+    io,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tungstenite::{Message, WebSocket};
+
+use crate::{Error, Recv, VideoFrameRef};
+
+/// Identifies a [`ProxyFrame`] message, followed by the format version.
+const MAGIC: &[u8; 4] = b"NDIJ";
+const VERSION: u8 = 1;
+/// Magic, version, resolution, timecode and timestamp.
+const HEADER_LEN: usize = 4 + 1 + 4 + 4 + 8 + 8;
+
+const DEFAULT_QUALITY: u8 = 75;
+/// How long a capture waits before new connections are accepted again.
+const POLL_TIMEOUT_MS: u32 = 100;
+/// How long a connecting client may take over its handshake, and a client over each frame.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A video frame as pushed to proxy clients: its JPEG encoding plus the frame's properties.
+///
+/// This is the whole wire format, one binary WebSocket message per frame: `NDIJ`, a version
+/// byte, then `xres`, `yres` (little-endian `u32`), `timecode` and `timestamp`
+/// (little-endian `i64`) and the JPEG data. Decoding needs nothing from the NDI SDK, so a
+/// browser client, e.g. one built for `wasm32`, can reimplement or reuse it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyFrame {
+    xres: i32,
+    yres: i32,
+    timecode: i64,
+    timestamp: i64,
+    jpeg: Vec<u8>,
+}
+
+impl ProxyFrame {
+    /// Encodes an RGBA, RGBX, BGRA or BGRX frame as a JPEG of the given `quality` (1-100).
+    pub fn from_video(frame: &VideoFrameRef<'_>, quality: u8) -> Result<Self, Error> {
+        Ok(ProxyFrame {
+            xres: frame.xres(),
+            yres: frame.yres(),
+            timecode: frame.timecode(),
+            timestamp: frame.timestamp(),
+            jpeg: frame.encode_jpeg(quality)?,
+        })
+    }
+
+    /// Parses a message received from a [`ProxyServer`].
+    pub fn decode(message: &[u8]) -> Result<Self, Error> {
+        if message.len() < HEADER_LEN || &message[..4] != MAGIC {
+            return Err(Error::InvalidFrame("not an NDI proxy frame".into()));
+        }
+        if message[4] != VERSION {
+            return Err(Error::InvalidFrame(format!(
+                "unsupported proxy frame version {}",
+                message[4]
+            )));
+        }
+        let u32_at = |at: usize| u32::from_le_bytes(message[at..at + 4].try_into().unwrap());
+        let i64_at = |at: usize| i64::from_le_bytes(message[at..at + 8].try_into().unwrap());
+        Ok(ProxyFrame {
+            xres: u32_at(5) as i32,
+            yres: u32_at(9) as i32,
+            timecode: i64_at(13),
+            timestamp: i64_at(21),
+            jpeg: message[HEADER_LEN..].to_vec(),
+        })
+    }
+
+    /// The message sent to clients.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(HEADER_LEN + self.jpeg.len());
+        message.extend_from_slice(MAGIC);
+        message.push(VERSION);
+        message.extend_from_slice(&(self.xres as u32).to_le_bytes());
+        message.extend_from_slice(&(self.yres as u32).to_le_bytes());
+        message.extend_from_slice(&self.timecode.to_le_bytes());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(&self.jpeg);
+        message
+    }
+
+    pub fn xres(&self) -> i32 {
+        self.xres
+    }
+
+    pub fn yres(&self) -> i32 {
+        self.yres
+    }
+
+    pub fn timecode(&self) -> i64 {
+        self.timecode
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// The frame as a JPEG image, ready for an `<img>` or `createImageBitmap`.
+    pub fn jpeg(&self) -> &[u8] {
+        &self.jpeg
+    }
+
+    pub fn into_jpeg(self) -> Vec<u8> {
+        self.jpeg
+    }
+}
+
+/// Pushes a receiver's video to WebSocket clients as JPEG [`ProxyFrame`]s, for previewing NDI
+/// sources in browser dashboards.
+///
+/// Frames are only encoded while a client is connected. A client that blocks a frame for more
+/// than a second or disconnects is dropped; the others carry on.
+pub struct ProxyServer {
+    listener: TcpListener,
+    clients: Mutex<Vec<WebSocket<TcpStream>>>,
+    quality: u8,
+    frame_interval: Duration,
+    stopped: AtomicBool,
+}
+
+impl ProxyServer {
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        ndi_info!(addr = ?listener.local_addr().ok(), "proxy server listening");
+        Ok(ProxyServer {
+            listener,
+            clients: Mutex::new(Vec::new()),
+            quality: DEFAULT_QUALITY,
+            frame_interval: Duration::ZERO,
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    /// JPEG quality from 1 to 100; defaults to 75.
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.quality = quality.clamp(1, 100);
+        self
+    }
+
+    /// Pushes at most `fps` frames per second, skipping the rest; unlimited by default.
+    pub fn with_max_fps(mut self, fps: f64) -> Self {
+        self.frame_interval = if fps > 0.0 {
+            Duration::from_secs_f64(1.0 / fps)
+        } else {
+            Duration::ZERO
+        };
+        self
+    }
+
+    pub fn local_addr(&self) -> Result<std::net::SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn clients(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    /// Captures from `recv` and pushes each frame to the connected clients until
+    /// [`ProxyServer::stop`] is called or capturing fails.
+    pub fn serve(&self, recv: &Recv<'_>) -> Result<(), Error> {
+        let mut last_sent: Option<Instant> = None;
+        while !self.stopped.load(Ordering::Acquire) {
+            self.accept_pending()?;
+            let Some(frame) = recv.capture_video_ref(POLL_TIMEOUT_MS)? else {
+                continue;
+            };
+            if self.clients() == 0 || last_sent.is_some_and(|at| at.elapsed() < self.frame_interval)
+            {
+                continue;
+            }
+            last_sent = Some(Instant::now());
+            let message = ProxyFrame::from_video(&frame, self.quality)?.encode();
+            drop(frame);
+            self.broadcast(message);
+        }
+        Ok(())
+    }
+
+    /// Makes [`ProxyServer::serve`] return after its current capture.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Release);
+    }
+
+    fn accept_pending(&self) -> Result<(), Error> {
+        loop {
+            let (stream, _addr) = match self.listener.accept() {
+                Ok(accepted) => accepted,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e.into()),
+            };
+            stream.set_nonblocking(false)?;
+            stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+            stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+            match tungstenite::accept(stream) {
+                Ok(socket) => {
+                    ndi_debug!(addr = %_addr, "proxy client connected");
+                    self.clients.lock().unwrap().push(socket);
+                }
+                Err(_e) => {
+                    ndi_warn!(addr = %_addr, error = %_e, "proxy handshake failed");
+                }
+            }
+        }
+    }
+
+    fn broadcast(&self, message: Vec<u8>) {
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain_mut(
+            |socket| match socket.send(Message::Binary(message.clone())) {
+                Ok(()) => true,
+                Err(_e) => {
+                    ndi_debug!(error = %_e, "dropping proxy client");
+                    false
+                }
+            },
+        );
+    }
+}
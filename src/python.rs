@@ -0,0 +1,616 @@
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_int,
+    ptr,
+    sync::Arc,
+    time::Duration,
+};
+
+use pyo3::{
+    buffer::PyBuffer,
+    create_exception,
+    exceptions::{PyBufferError, PyException, PyTimeoutError, PyValueError},
+    ffi,
+    prelude::*,
+};
+
+use crate::{
+    async_find::SharedFind, shared_ndi::SharedNdi, AudioFrame, AudioType, BorrowedVideoFrame,
+    Error, FieldMode, Finder, FourCCVideoType, Receiver, Recv, RecvBandwidth, RecvColorFormat,
+    Send, Sender, Source, VideoFrame,
+};
+
+create_exception!(grafton_ndi, NdiError, PyException);
+
+impl From<Error> for PyErr {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Timeout { .. } => PyTimeoutError::new_err(e.to_string()),
+            _ => NdiError::new_err(e.to_string()),
+        }
+    }
+}
+
+/// The `grafton_ndi` Python extension module.
+#[pymodule]
+fn grafton_ndi(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("NdiError", m.py().get_type::<NdiError>())?;
+    m.add_class::<PySource>()?;
+    m.add_class::<PyFinder>()?;
+    m.add_class::<PyReceiver>()?;
+    m.add_class::<PySender>()?;
+    m.add_class::<PyVideoFrame>()?;
+    m.add_class::<PyAudioFrame>()?;
+    Ok(())
+}
+
+/// A discovered NDI source.
+#[pyclass(name = "Source", frozen)]
+#[derive(Clone)]
+pub struct PySource {
+    source: Source,
+}
+
+#[pymethods]
+impl PySource {
+    #[new]
+    #[pyo3(signature = (name, url_address=None))]
+    fn new(name: String, url_address: Option<String>) -> Self {
+        PySource {
+            source: Source {
+                name,
+                url_address,
+                ip_address: None,
+            },
+        }
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.source.name
+    }
+
+    #[getter]
+    fn url_address(&self) -> Option<&str> {
+        self.source.url_address.as_deref()
+    }
+
+    #[getter]
+    fn ip_address(&self) -> Option<&str> {
+        self.source.ip_address.as_deref()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Source({:?})", self.source.name)
+    }
+}
+
+/// Source discovery. Waits release the GIL.
+#[pyclass(name = "Finder", frozen)]
+pub struct PyFinder {
+    find: Arc<SharedFind>,
+}
+
+#[pymethods]
+impl PyFinder {
+    #[new]
+    #[pyo3(signature = (show_local_sources=false, groups=None, extra_ips=None))]
+    fn new(
+        show_local_sources: bool,
+        groups: Option<&str>,
+        extra_ips: Option<&str>,
+    ) -> PyResult<Self> {
+        Ok(PyFinder {
+            find: SharedFind::new(Finder::new(show_local_sources, groups, extra_ips))?,
+        })
+    }
+
+    /// The sources found so far, waiting up to `timeout` seconds for the first one.
+    #[pyo3(signature = (timeout=1.0))]
+    fn find_sources(&self, py: Python<'_>, timeout: f64) -> PyResult<Vec<PySource>> {
+        let timeout = seconds(timeout)?;
+        let sources = py.allow_threads(|| self.find.find_sources(timeout))?;
+        Ok(sources
+            .into_iter()
+            .map(|source| PySource { source })
+            .collect())
+    }
+
+    /// Waits up to `timeout` seconds for the source list to change.
+    #[pyo3(signature = (timeout=1.0))]
+    fn wait_for_sources(&self, py: Python<'_>, timeout: f64) -> PyResult<bool> {
+        let timeout = seconds(timeout)?;
+        Ok(py.allow_threads(|| self.find.wait_for_sources(timeout)))
+    }
+}
+
+/// A receiver that can be used from any Python thread.
+struct SharedRecv {
+    // Declared first so it is destroyed before the runtime reference below is released.
+    recv: Recv<'static>,
+    _ndi: SharedNdi,
+}
+
+// Only the receiver's `&self` methods are called, which use the SDK's thread-safe capture
+// and query calls.
+unsafe impl std::marker::Send for SharedRecv {}
+unsafe impl Sync for SharedRecv {}
+
+/// Receives frames from one source. Captures release the GIL while waiting.
+#[pyclass(name = "Receiver")]
+pub struct PyReceiver {
+    shared: SharedRecv,
+}
+
+#[pymethods]
+impl PyReceiver {
+    /// `source` is a `Source` or a source name. `color_format` is one of `"bgra"`, `"rgba"`,
+    /// `"uyvy"` or `"fastest"`, and `bandwidth` one of `"highest"`, `"lowest"`,
+    /// `"audio_only"` or `"metadata_only"`.
+    #[new]
+    #[pyo3(signature = (source, color_format="bgra", bandwidth="highest", name=None))]
+    fn new(
+        source: &Bound<'_, PyAny>,
+        color_format: &str,
+        bandwidth: &str,
+        name: Option<String>,
+    ) -> PyResult<Self> {
+        let source = match source.downcast::<PySource>() {
+            Ok(source) => source.get().source.clone(),
+            Err(_) => PySource::new(source.extract()?, None).source,
+        };
+        let settings = Receiver::new(
+            source,
+            color_format_from(color_format)?,
+            bandwidth_from(bandwidth)?,
+            FieldMode::Fielded,
+            name,
+        );
+        let ndi = SharedNdi::acquire()?;
+        // SAFETY: `_ndi` is dropped after `recv`.
+        let recv = unsafe { Recv::new_detached(settings)? };
+        Ok(PyReceiver {
+            shared: SharedRecv { recv, _ndi: ndi },
+        })
+    }
+
+    #[getter]
+    fn source(&self) -> PySource {
+        PySource {
+            source: self.shared.recv.source().clone(),
+        }
+    }
+
+    #[getter]
+    fn connections(&self) -> i32 {
+        self.shared.recv.connections()
+    }
+
+    /// Waits up to `timeout` seconds for a video frame, retrying per the receiver's policy.
+    /// Raises `TimeoutError` if none arrives.
+    #[pyo3(signature = (timeout=5.0))]
+    fn capture_video(&self, py: Python<'_>, timeout: f64) -> PyResult<PyVideoFrame> {
+        let timeout = seconds(timeout)?;
+        let shared = &self.shared;
+        let frame = py.allow_threads(|| shared.recv.capture_video(timeout))?;
+        Ok(PyVideoFrame::new(frame))
+    }
+
+    /// Waits up to `timeout` seconds for an audio frame.
+    #[pyo3(signature = (timeout=5.0))]
+    fn capture_audio(&self, py: Python<'_>, timeout: f64) -> PyResult<PyAudioFrame> {
+        let timeout = seconds(timeout)?;
+        let shared = &self.shared;
+        let frame = py.allow_threads(|| shared.recv.capture_audio(timeout))?;
+        Ok(PyAudioFrame::new(frame))
+    }
+
+    /// Waits up to `timeout` seconds for a metadata frame and returns its XML.
+    #[pyo3(signature = (timeout=5.0))]
+    fn capture_metadata(&self, py: Python<'_>, timeout: f64) -> PyResult<String> {
+        let timeout = seconds(timeout)?;
+        let shared = &self.shared;
+        let frame = py.allow_threads(|| shared.recv.capture_metadata(timeout))?;
+        Ok(frame.data().to_string_lossy().into_owned())
+    }
+}
+
+/// Publishes a source. Sends release the GIL, so clocked sends don't stall other threads.
+#[pyclass(name = "Sender", frozen)]
+pub struct PySender {
+    // Declared first so it is destroyed before the runtime reference below is released.
+    send: Send<'static>,
+    _ndi: SharedNdi,
+}
+
+#[pymethods]
+impl PySender {
+    #[new]
+    #[pyo3(signature = (name, groups=None, clock_video=true, clock_audio=true))]
+    fn new(
+        name: String,
+        groups: Option<String>,
+        clock_video: bool,
+        clock_audio: bool,
+    ) -> PyResult<Self> {
+        let settings = Sender {
+            name,
+            groups,
+            clock_video,
+            clock_audio,
+            config: None,
+        };
+        let ndi = SharedNdi::acquire()?;
+        // SAFETY: `_ndi` is dropped after `send`.
+        let send = unsafe { Send::new_detached(settings)? };
+        Ok(PySender { send, _ndi: ndi })
+    }
+
+    /// The number of receivers connected, waiting up to `timeout` seconds for one.
+    #[pyo3(signature = (timeout=0.0))]
+    fn connections(&self, py: Python<'_>, timeout: f64) -> PyResult<i32> {
+        let timeout = seconds(timeout)?;
+        Ok(py.allow_threads(|| self.send.get_no_connections(timeout_ms(timeout))))
+    }
+
+    /// Sends pixels from any C-contiguous buffer, such as a `(yres, xres, 4)` uint8 numpy
+    /// array, without copying. `line_stride` defaults to the format's packed stride.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (data, xres, yres, fourcc="BGRA", frame_rate=(30, 1), line_stride=None))]
+    fn send_video(
+        &self,
+        py: Python<'_>,
+        data: &Bound<'_, PyAny>,
+        xres: i32,
+        yres: i32,
+        fourcc: &str,
+        frame_rate: (i32, i32),
+        line_stride: Option<i32>,
+    ) -> PyResult<()> {
+        let fourcc = fourcc_from(fourcc)?;
+        let buffer = PyBuffer::<u8>::get(data)?;
+        if !buffer.is_c_contiguous() {
+            return Err(PyBufferError::new_err("video data must be C-contiguous"));
+        }
+        // SAFETY: the buffer is held, and so kept alive and unresized, until the send returns.
+        let data = unsafe {
+            std::slice::from_raw_parts(buffer.buf_ptr() as *const u8, buffer.len_bytes())
+        };
+        let line_stride = line_stride.unwrap_or_else(|| fourcc.line_stride(xres));
+        let frame =
+            BorrowedVideoFrame::try_from_uncompressed(data, xres, yres, fourcc, line_stride)?
+                .with_frame_rate(frame_rate.0, frame_rate.1);
+        py.allow_threads(|| self.send.send_video_borrowed(&frame));
+        Ok(())
+    }
+
+    /// Sends planar float32 audio laid out as `(channels, samples)`.
+    fn send_audio(
+        &self,
+        py: Python<'_>,
+        samples: &Bound<'_, PyAny>,
+        sample_rate: i32,
+    ) -> PyResult<()> {
+        let buffer = PyBuffer::<f32>::get(samples)?;
+        let (channels, count) = match buffer.shape() {
+            [channels, count] => (*channels, *count),
+            [count] => (1, *count),
+            shape => {
+                return Err(PyValueError::new_err(format!(
+                    "audio must be (channels, samples), not {:?}",
+                    shape
+                )))
+            }
+        };
+        let samples = buffer.to_vec(py)?;
+        let data = samples.iter().flat_map(|s| s.to_ne_bytes()).collect();
+        let frame = AudioFrame::with_data(
+            sample_rate,
+            channels as i32,
+            count as i32,
+            0,
+            AudioType::FLTP,
+            data,
+            None,
+            0,
+        )?;
+        frame.validate()?;
+        py.allow_threads(|| self.send.send_audio(&frame));
+        Ok(())
+    }
+
+    /// Sends an XML metadata frame.
+    #[pyo3(signature = (xml, timecode=0))]
+    fn send_metadata(&self, xml: &str, timecode: i64) -> PyResult<()> {
+        Ok(self.send.send_metadata_xml(xml, timecode)?)
+    }
+}
+
+/// A captured video frame. Supports the buffer protocol, so `numpy.asarray(frame)` views the
+/// pixels without copying: packed 8-bit formats as `(yres, xres, channels)`, others as raw
+/// bytes.
+#[pyclass(name = "VideoFrame", frozen)]
+pub struct PyVideoFrame {
+    frame: VideoFrame,
+    shape: [ffi::Py_ssize_t; 3],
+    strides: [ffi::Py_ssize_t; 3],
+    ndim: c_int,
+}
+
+impl PyVideoFrame {
+    fn new(frame: VideoFrame) -> Self {
+        let stride = unsafe { frame.line_stride_or_size.line_stride_in_bytes } as ffi::Py_ssize_t;
+        let channels = match frame.fourcc {
+            FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX
+            | FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX => Some(4),
+            FourCCVideoType::UYVY => Some(2),
+            _ => None,
+        };
+        let (shape, strides, ndim) = match channels {
+            Some(channels) if stride > 0 => (
+                [frame.yres as _, frame.xres as _, channels],
+                [stride, channels, 1],
+                3,
+            ),
+            _ => ([frame.data.len() as _, 0, 0], [1, 0, 0], 1),
+        };
+        PyVideoFrame {
+            frame,
+            shape,
+            strides,
+            ndim,
+        }
+    }
+}
+
+#[pymethods]
+impl PyVideoFrame {
+    #[getter]
+    fn xres(&self) -> i32 {
+        self.frame.xres
+    }
+
+    #[getter]
+    fn yres(&self) -> i32 {
+        self.frame.yres
+    }
+
+    #[getter]
+    fn fourcc(&self) -> String {
+        format!("{:?}", self.frame.fourcc)
+    }
+
+    #[getter]
+    fn line_stride(&self) -> i32 {
+        unsafe { self.frame.line_stride_or_size.line_stride_in_bytes }
+    }
+
+    #[getter]
+    fn frame_rate(&self) -> (i32, i32) {
+        (self.frame.frame_rate_n, self.frame.frame_rate_d)
+    }
+
+    #[getter]
+    fn timecode(&self) -> i64 {
+        self.frame.timecode
+    }
+
+    #[getter]
+    fn timestamp(&self) -> i64 {
+        self.frame.timestamp
+    }
+
+    /// A copy of the pixel data as `bytes`.
+    fn to_bytes(&self) -> &[u8] {
+        &self.frame.data
+    }
+
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        let this = slf.get();
+        fill_buffer(
+            slf.as_any(),
+            view,
+            flags,
+            &this.frame.data,
+            c"B",
+            1,
+            &this.shape[..this.ndim as usize],
+            &this.strides[..this.ndim as usize],
+        )
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {}
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VideoFrame({}x{} {:?} at {}/{})",
+            self.frame.xres,
+            self.frame.yres,
+            self.frame.fourcc,
+            self.frame.frame_rate_n,
+            self.frame.frame_rate_d
+        )
+    }
+}
+
+/// A captured audio frame. Supports the buffer protocol, so `numpy.asarray(frame)` views the
+/// planar float32 samples as `(channels, samples)` without copying.
+#[pyclass(name = "AudioFrame", frozen)]
+pub struct PyAudioFrame {
+    frame: AudioFrame,
+    shape: [ffi::Py_ssize_t; 2],
+    strides: [ffi::Py_ssize_t; 2],
+}
+
+impl PyAudioFrame {
+    fn new(frame: AudioFrame) -> Self {
+        PyAudioFrame {
+            shape: [frame.no_channels.max(0) as _, frame.no_samples.max(0) as _],
+            strides: [frame.channel_stride_in_bytes as _, 4],
+            frame,
+        }
+    }
+}
+
+#[pymethods]
+impl PyAudioFrame {
+    #[getter]
+    fn sample_rate(&self) -> i32 {
+        self.frame.sample_rate
+    }
+
+    #[getter]
+    fn no_channels(&self) -> i32 {
+        self.frame.no_channels
+    }
+
+    #[getter]
+    fn no_samples(&self) -> i32 {
+        self.frame.no_samples
+    }
+
+    #[getter]
+    fn timecode(&self) -> i64 {
+        self.frame.timecode
+    }
+
+    #[getter]
+    fn timestamp(&self) -> i64 {
+        self.frame.timestamp
+    }
+
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        let this = slf.get();
+        fill_buffer(
+            slf.as_any(),
+            view,
+            flags,
+            &this.frame.data,
+            c"f",
+            4,
+            &this.shape,
+            &this.strides,
+        )
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {}
+
+    fn __repr__(&self) -> String {
+        format!(
+            "AudioFrame({} channels of {} samples at {} Hz)",
+            self.frame.no_channels, self.frame.no_samples, self.frame.sample_rate
+        )
+    }
+}
+
+/// Exposes `data` read-only through `view`. `shape` and `strides` must live as long as
+/// `owner`, which the view keeps alive.
+#[allow(clippy::too_many_arguments)]
+unsafe fn fill_buffer(
+    owner: &Bound<'_, PyAny>,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+    data: &[u8],
+    format: &'static CStr,
+    itemsize: ffi::Py_ssize_t,
+    shape: &[ffi::Py_ssize_t],
+    strides: &[ffi::Py_ssize_t],
+) -> PyResult<()> {
+    if view.is_null() {
+        return Err(PyBufferError::new_err("view is null"));
+    }
+    if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+        return Err(PyBufferError::new_err("frames are read-only"));
+    }
+    let contiguous = strides
+        .iter()
+        .rev()
+        .zip(shape.iter().rev())
+        .try_fold(itemsize, |expected, (&stride, &len)| {
+            (stride == expected).then_some(expected * len)
+        });
+    if flags & ffi::PyBUF_STRIDES != ffi::PyBUF_STRIDES && contiguous.is_none() {
+        return Err(PyBufferError::new_err(
+            "frame rows are padded; request a strided buffer",
+        ));
+    }
+    (*view).obj = owner.clone().into_ptr();
+    (*view).buf = data.as_ptr() as *mut c_void;
+    (*view).len = data.len() as ffi::Py_ssize_t;
+    (*view).readonly = 1;
+    (*view).itemsize = itemsize;
+    (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+        format.as_ptr() as *mut _
+    } else {
+        ptr::null_mut()
+    };
+    (*view).ndim = shape.len() as c_int;
+    (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+        shape.as_ptr() as *mut _
+    } else {
+        ptr::null_mut()
+    };
+    (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+        strides.as_ptr() as *mut _
+    } else {
+        ptr::null_mut()
+    };
+    (*view).suboffsets = ptr::null_mut();
+    (*view).internal = ptr::null_mut();
+    Ok(())
+}
+
+fn seconds(timeout: f64) -> PyResult<Duration> {
+    Duration::try_from_secs_f64(timeout)
+        .map_err(|_| PyValueError::new_err(format!("invalid timeout {}", timeout)))
+}
+
+fn timeout_ms(timeout: Duration) -> u32 {
+    crate::async_find::timeout_ms(timeout)
+}
+
+fn color_format_from(name: &str) -> PyResult<RecvColorFormat> {
+    match name {
+        "bgra" => Ok(RecvColorFormat::BGRX_BGRA),
+        "rgba" => Ok(RecvColorFormat::RGBX_RGBA),
+        "uyvy" => Ok(RecvColorFormat::UYVY_BGRA),
+        "fastest" => Ok(RecvColorFormat::Fastest),
+        other => Err(PyValueError::new_err(format!(
+            "unknown color format {:?}",
+            other
+        ))),
+    }
+}
+
+fn bandwidth_from(name: &str) -> PyResult<RecvBandwidth> {
+    match name {
+        "metadata_only" => Ok(RecvBandwidth::MetadataOnly),
+        "audio_only" => Ok(RecvBandwidth::AudioOnly),
+        "lowest" => Ok(RecvBandwidth::Lowest),
+        "highest" => Ok(RecvBandwidth::Highest),
+        other => Err(PyValueError::new_err(format!(
+            "unknown bandwidth {:?}",
+            other
+        ))),
+    }
+}
+
+fn fourcc_from(name: &str) -> PyResult<FourCCVideoType> {
+    use FourCCVideoType::*;
+    [
+        UYVY, UYVA, P216, PA16, YV12, I420, NV12, BGRA, BGRX, RGBA, RGBX,
+    ]
+    .into_iter()
+    .find(|fourcc| format!("{:?}", fourcc).eq_ignore_ascii_case(name))
+    .ok_or_else(|| PyValueError::new_err(format!("unknown FourCC {:?}", name)))
+}
@@ -0,0 +1,257 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Find, Finder, FrameType, Receiver, Recv, RetryPolicy, Source, NDI};
+
+const DEFAULT_LOSS_GRACE: Duration = Duration::from_secs(2);
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Connection state reported by a [`ReconnectingReceiver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// A receiver exists but the source hasn't connected to it yet.
+    Connecting,
+    Connected,
+    /// The source disconnected; the SDK is given the grace period to recover on its own.
+    Lost,
+    /// The receiver was torn down and attempt number `attempt` to re-create it is pending.
+    Reconnecting {
+        attempt: u32,
+    },
+}
+
+type StateListener = Box<dyn FnMut(&Source, ConnectionState)>;
+
+/// A receiver that re-resolves its source and re-creates itself after the connection is lost.
+///
+/// The SDK reconnects on its own when a source briefly drops out, so the receiver is only torn
+/// down once it has had no connections for the loss grace period. The source is then looked up
+/// again by name, which picks up a new address if it moved machines or ports, and a fresh
+/// receiver is created. Failed attempts are spaced using the delays and backoff of the
+/// [`RetryPolicy`].
+///
+/// Retryable capture errors are treated as a sign of a lost connection and reported as
+/// [`FrameType::None`] rather than returned.
+pub struct ReconnectingReceiver<'a> {
+    ndi: &'a NDI,
+    settings: Receiver,
+    finder: Finder,
+    find: Option<Find<'a>>,
+    recv: Option<Recv<'a>>,
+    state: ConnectionState,
+    listener: Option<StateListener>,
+    policy: RetryPolicy,
+    loss_grace: Duration,
+    /// When the receiver was created or last seen without connections.
+    unconnected_since: Instant,
+    next_attempt: Instant,
+    delay: Duration,
+}
+
+impl<'a> ReconnectingReceiver<'a> {
+    pub fn new(ndi: &'a NDI, settings: Receiver) -> Result<Self, Error> {
+        let recv = Recv::new(ndi, settings.clone())?;
+        let now = Instant::now();
+        let policy = RetryPolicy::fixed(DEFAULT_POLL_INTERVAL, Duration::from_millis(500))
+            .with_backoff(2.0, Duration::from_secs(30));
+        Ok(ReconnectingReceiver {
+            ndi,
+            settings,
+            finder: Finder::new(true, None, None),
+            find: None,
+            recv: Some(recv),
+            state: ConnectionState::Connecting,
+            listener: None,
+            policy,
+            loss_grace: DEFAULT_LOSS_GRACE,
+            unconnected_since: now,
+            next_attempt: now,
+            delay: policy.retry_delay,
+        })
+    }
+
+    /// Discovery settings used to look the source up again; defaults to all groups, including
+    /// local sources.
+    pub fn with_finder(mut self, finder: Finder) -> Self {
+        self.finder = finder;
+        self.find = None;
+        self
+    }
+
+    /// Delays between reconnection attempts, taken from the policy's `retry_delay`, `backoff`
+    /// and `max_retry_delay`; defaults to 500 ms doubling up to 30 s. With `max_retries` set,
+    /// [`ReconnectingReceiver::capture`] returns the last error once that many consecutive
+    /// attempts have failed.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self.delay = policy.retry_delay;
+        self
+    }
+
+    /// How long the source may stay disconnected before the receiver is re-created; defaults to
+    /// two seconds.
+    pub fn with_loss_grace(mut self, grace: Duration) -> Self {
+        self.loss_grace = grace;
+        self
+    }
+
+    /// Calls `listener` with the source and its new state on every state change.
+    pub fn on_state_change(
+        mut self,
+        listener: impl FnMut(&Source, ConnectionState) + 'static,
+    ) -> Self {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.settings.source_to_connect_to
+    }
+
+    /// The current receiver, if one exists; it is replaced on reconnection.
+    pub fn recv(&self) -> Option<&Recv<'a>> {
+        self.recv.as_ref()
+    }
+
+    /// Captures the next frame, first reconnecting if the connection has been lost.
+    ///
+    /// While no receiver exists this waits up to `timeout_ms` for the next reconnection
+    /// attempt and returns [`FrameType::None`].
+    pub fn capture(&mut self, timeout_ms: u32) -> Result<FrameType, Error> {
+        let deadline = Instant::now() + Duration::from_millis(u64::from(timeout_ms));
+        self.check_connection();
+        if self.recv.is_none() {
+            let now = Instant::now();
+            if self.next_attempt > now {
+                thread::sleep(
+                    (self.next_attempt - now).min(deadline.saturating_duration_since(now)),
+                );
+            }
+            if Instant::now() >= self.next_attempt {
+                self.reconnect()?;
+            }
+        }
+
+        let Some(recv) = self.recv.as_mut() else {
+            return Ok(FrameType::None);
+        };
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match recv.capture(remaining.as_millis() as u32) {
+            Ok(FrameType::StatusChange) => {
+                self.check_connection();
+                Ok(FrameType::StatusChange)
+            }
+            Ok(frame) => {
+                if !matches!(frame, FrameType::None) {
+                    self.set_state(ConnectionState::Connected);
+                }
+                Ok(frame)
+            }
+            Err(e) if e.is_retryable() => {
+                if self.state == ConnectionState::Connected {
+                    self.unconnected_since = Instant::now();
+                    self.set_state(ConnectionState::Lost);
+                }
+                Ok(FrameType::None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Updates the state from the receiver's connection count, tearing the receiver down once
+    /// it has been unconnected for longer than the grace period.
+    fn check_connection(&mut self) {
+        let Some(recv) = &self.recv else {
+            return;
+        };
+        if recv.connections() > 0 {
+            self.delay = self.policy.retry_delay;
+            self.set_state(ConnectionState::Connected);
+            return;
+        }
+        if self.state == ConnectionState::Connected {
+            self.unconnected_since = Instant::now();
+            self.set_state(ConnectionState::Lost);
+        } else if self.unconnected_since.elapsed() >= self.loss_grace {
+            self.recv = None;
+            let attempt = match self.state {
+                ConnectionState::Reconnecting { attempt } => attempt + 1,
+                _ => 1,
+            };
+            self.schedule(attempt);
+        }
+    }
+
+    /// Looks the source up again and creates a new receiver for it, failing only once the
+    /// policy's retries are exhausted.
+    fn reconnect(&mut self) -> Result<(), Error> {
+        let attempt = match self.state {
+            ConnectionState::Reconnecting { attempt } => attempt,
+            _ => 1,
+        };
+        match self.resolve().and_then(|source| {
+            let mut settings = self.settings.clone();
+            settings.source_to_connect_to = source;
+            Recv::new(self.ndi, settings.clone()).map(|recv| (settings, recv))
+        }) {
+            Ok((settings, recv)) => {
+                ndi_info!(source = %settings.source_to_connect_to.name, attempt, "receiver re-created");
+                self.settings = settings;
+                self.recv = Some(recv);
+                self.unconnected_since = Instant::now();
+                Ok(())
+            }
+            Err(e) if self.policy.max_retries.is_some_and(|max| attempt > max) => Err(e),
+            Err(_e) => {
+                ndi_debug!(source = %self.settings.source_to_connect_to.name, attempt, error = %_e, "reconnection failed");
+                self.schedule(attempt + 1);
+                Ok(())
+            }
+        }
+    }
+
+    fn resolve(&mut self) -> Result<Source, Error> {
+        if self.find.is_none() {
+            self.find = Some(Find::new(self.ndi, self.finder.clone())?);
+        }
+        let name = &self.settings.source_to_connect_to.name;
+        let mut sources = self
+            .find
+            .as_ref()
+            .map_or(Ok(Vec::new()), |find| find.get_sources(0))?;
+        match sources.iter().position(|source| source.name == *name) {
+            Some(index) => Ok(sources.swap_remove(index)),
+            None => Err(Error::SourceNotFound {
+                name: name.clone(),
+                timeout: Duration::ZERO,
+                attempts: 1,
+                seen: sources.into_iter().map(|source| source.name).collect(),
+            }),
+        }
+    }
+
+    fn schedule(&mut self, attempt: u32) {
+        self.next_attempt = Instant::now() + self.delay;
+        self.delay = self
+            .delay
+            .mul_f64(self.policy.backoff)
+            .min(self.policy.max_retry_delay);
+        self.set_state(ConnectionState::Reconnecting { attempt });
+    }
+
+    fn set_state(&mut self, state: ConnectionState) {
+        if self.state == state {
+            return;
+        }
+        self.state = state;
+        if let Some(listener) = self.listener.as_mut() {
+            listener(&self.settings.source_to_connect_to, state);
+        }
+    }
+}
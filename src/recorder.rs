@@ -0,0 +1,711 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    copy_rows, timecode::TIMESTAMP_UNDEFINED, AudioFrame, Error, FourCCVideoType, FrameFormatType,
+    FrameType, Recv, VideoFrame,
+};
+
+// Matroska element IDs, with their length marker bits included.
+const EBML: u32 = 0x1A45DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+const SEGMENT: u32 = 0x18538067;
+const INFO: u32 = 0x1549A966;
+const TIMESTAMP_SCALE: u32 = 0x2AD7B1;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+const DURATION: u32 = 0x4489;
+const TRACKS: u32 = 0x1654AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const FLAG_LACING: u32 = 0x9C;
+const CODEC_ID: u32 = 0x86;
+const DEFAULT_DURATION: u32 = 0x23E383;
+const VIDEO: u32 = 0xE0;
+const FLAG_INTERLACED: u32 = 0x9A;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+const COLOUR_SPACE: u32 = 0x2EB524;
+const AUDIO: u32 = 0xE1;
+const SAMPLING_FREQUENCY: u32 = 0xB5;
+const CHANNELS: u32 = 0x9F;
+const BIT_DEPTH: u32 = 0x6264;
+const CLUSTER: u32 = 0x1F43B675;
+const CLUSTER_TIMESTAMP: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+/// An 8-byte EBML size meaning "unknown", left in place until the element is finished so an
+/// interrupted recording stays readable.
+const UNKNOWN_SIZE: [u8; 8] = [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+/// Block times are in milliseconds.
+const TIMESTAMP_SCALE_NS: u64 = 1_000_000;
+/// NDI timestamps and timecodes count 100 ns ticks.
+const TICKS_PER_MS: i64 = 10_000;
+/// A new cluster is started at least this often, in milliseconds, to keep files seekable.
+const CLUSTER_SPAN_MS: i64 = 1_000;
+/// How much stream time, in milliseconds, is held back waiting to see the first frame of
+/// every enabled track before recording starts with the tracks seen so far.
+const MAX_PENDING_MS: i64 = 1_000;
+/// The most frame data held back while waiting, whatever its duration.
+const MAX_PENDING_BYTES: usize = 64 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VideoTrack {
+    xres: i32,
+    yres: i32,
+    fourcc: FourCCVideoType,
+    frame_rate_n: i32,
+    frame_rate_d: i32,
+    interlaced: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AudioTrack {
+    sample_rate: i32,
+    channels: i32,
+}
+
+enum Pending {
+    Video(VideoFrame),
+    Audio(AudioFrame),
+}
+
+/// Which of a frame's times a track is placed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeBase {
+    Timestamp,
+    Timecode,
+}
+
+/// Places a track's blocks on one time base, chosen from its first frame.
+#[derive(Debug, Clone, Copy)]
+struct TrackClock {
+    base: TimeBase,
+    /// Where the next block goes if a frame lacks the track's time.
+    next_ms: i64,
+}
+
+impl TrackClock {
+    fn new(timestamp: i64) -> Self {
+        TrackClock {
+            base: if timestamp != TIMESTAMP_UNDEFINED {
+                TimeBase::Timestamp
+            } else {
+                TimeBase::Timecode
+            },
+            next_ms: 0,
+        }
+    }
+}
+
+struct Cluster {
+    size_at: u64,
+    time_ms: i64,
+}
+
+/// Writes received video and audio to a Matroska (`.mkv`) file, uncompressed, with the
+/// sender's timestamps preserved to the millisecond.
+///
+/// Video is stored as `V_UNCOMPRESSED` tagged with its FourCC and audio as interleaved
+/// 32-bit float PCM, both of which FFmpeg-based tools read directly. Tracks are declared from
+/// the first frame of each type, so frames are held back until every enabled track has been
+/// seen, for up to a second of stream time (or 64 MiB of frames); after that recording starts
+/// with the tracks seen so far, and frames of a track that arrives later are dropped. Call
+/// [`finish`](Self::finish) to write the final sizes and duration; a recording that is cut
+/// short is still readable, just without a duration.
+///
+/// Each track is placed by the sender timestamps if its first frame has one, and by timecodes
+/// otherwise, so a track never mixes the two. Tracks on the same base share its origin; a
+/// frame missing its track's time follows straight on from the one before.
+pub struct Recorder<W: Write + Seek> {
+    out: W,
+    record_video: bool,
+    record_audio: bool,
+    video: Option<VideoTrack>,
+    audio: Option<AudioTrack>,
+    video_number: u8,
+    audio_number: u8,
+    pending: Vec<Pending>,
+    pending_bytes: usize,
+    pending_ms: i64,
+    started: bool,
+    segment_size_at: u64,
+    duration_at: u64,
+    cluster: Option<Cluster>,
+    video_clock: Option<TrackClock>,
+    audio_clock: Option<TrackClock>,
+    /// The first sender timestamp and timecode seen, in 100 ns ticks.
+    timestamp_origin: Option<i64>,
+    timecode_origin: Option<i64>,
+    end_ms: i64,
+}
+
+impl Recorder<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Recorder::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Seek> Recorder<W> {
+    pub fn new(out: W) -> Self {
+        Recorder {
+            out,
+            record_video: true,
+            record_audio: true,
+            video: None,
+            audio: None,
+            video_number: 0,
+            audio_number: 0,
+            pending: Vec::new(),
+            pending_bytes: 0,
+            pending_ms: 0,
+            started: false,
+            segment_size_at: 0,
+            duration_at: 0,
+            cluster: None,
+            video_clock: None,
+            audio_clock: None,
+            timestamp_origin: None,
+            timecode_origin: None,
+            end_ms: 0,
+        }
+    }
+
+    /// Records only video, ignoring audio frames.
+    pub fn video_only(mut self) -> Self {
+        self.record_audio = false;
+        self
+    }
+
+    /// Records only audio, ignoring video frames.
+    pub fn audio_only(mut self) -> Self {
+        self.record_video = false;
+        self
+    }
+
+    /// Captures from `recv` for `duration`, writing every video and audio frame.
+    pub fn record(&mut self, recv: &mut Recv<'_>, duration: Duration) -> Result<(), Error> {
+        let deadline = Instant::now() + duration;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(());
+            }
+            match recv.capture(remaining.as_millis().min(100) as u32)? {
+                FrameType::Video(frame) => self.write_video(&frame)?,
+                FrameType::Audio(frame) => self.write_audio(&frame)?,
+                _ => {}
+            }
+        }
+    }
+
+    /// Appends a video frame. The resolution and format must match the first frame recorded.
+    pub fn write_video(&mut self, frame: &VideoFrame) -> Result<(), Error> {
+        if !self.record_video {
+            return Ok(());
+        }
+        let track = VideoTrack {
+            xres: frame.xres,
+            yres: frame.yres,
+            fourcc: frame.fourcc,
+            frame_rate_n: frame.frame_rate_n,
+            frame_rate_d: frame.frame_rate_d,
+            interlaced: !matches!(frame.frame_format_type, FrameFormatType::Progressive),
+        };
+        match self.video {
+            None if !self.started => {
+                codec_fourcc(frame.fourcc)?;
+                self.video = Some(track);
+            }
+            // Started without a video track; nothing to write it to.
+            None => return Ok(()),
+            Some(expected) if expected != track => {
+                return Err(Error::InvalidFrame(format!(
+                "Video changed from {}x{} {:?} to {}x{} {:?} mid-recording; start a new recording",
+                expected.xres, expected.yres, expected.fourcc, track.xres, track.yres, track.fourcc
+            )))
+            }
+            Some(_) => {}
+        }
+        frame.validate()?;
+        self.video_clock
+            .get_or_insert_with(|| TrackClock::new(frame.timestamp));
+        self.start_clock(frame.timestamp, frame.timecode);
+        if self.started {
+            self.write_video_block(frame)
+        } else {
+            self.pending_bytes += frame.data.len();
+            self.pending_ms += video_duration_ms(&track);
+            self.pending.push(Pending::Video(frame.clone()));
+            self.start_if_ready()
+        }
+    }
+
+    /// Appends an audio frame. The sample rate and channel count must match the first frame
+    /// recorded.
+    pub fn write_audio(&mut self, frame: &AudioFrame) -> Result<(), Error> {
+        if !self.record_audio {
+            return Ok(());
+        }
+        let track = AudioTrack {
+            sample_rate: frame.sample_rate,
+            channels: frame.no_channels,
+        };
+        match self.audio {
+            None if !self.started => self.audio = Some(track),
+            None => return Ok(()),
+            Some(expected) if expected != track => {
+                return Err(Error::InvalidFrame(format!(
+                "Audio changed from {} Hz x{} to {} Hz x{} mid-recording; start a new recording",
+                expected.sample_rate, expected.channels, track.sample_rate, track.channels
+            )))
+            }
+            Some(_) => {}
+        }
+        frame.validate()?;
+        self.audio_clock
+            .get_or_insert_with(|| TrackClock::new(frame.timestamp));
+        self.start_clock(frame.timestamp, frame.timecode);
+        if self.started {
+            self.write_audio_block(frame)
+        } else {
+            self.pending_bytes += frame.data.len();
+            self.pending_ms += audio_duration_ms(frame);
+            self.pending.push(Pending::Audio(frame.clone()));
+            self.start_if_ready()
+        }
+    }
+
+    /// Writes the final element sizes and duration and returns the writer.
+    pub fn finish(mut self) -> Result<W, Error> {
+        if !self.started {
+            self.start()?;
+        }
+        self.close_cluster()?;
+        let end = self.out.stream_position()?;
+        self.out.seek(SeekFrom::Start(self.segment_size_at))?;
+        self.out
+            .write_all(&fixed_size(end - self.segment_size_at - 8))?;
+        self.out.seek(SeekFrom::Start(self.duration_at))?;
+        self.out.write_all(&(self.end_ms as f64).to_be_bytes())?;
+        self.out.seek(SeekFrom::Start(end))?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn start_clock(&mut self, timestamp: i64, timecode: i64) {
+        if timestamp != TIMESTAMP_UNDEFINED {
+            self.timestamp_origin.get_or_insert(timestamp);
+        }
+        self.timecode_origin.get_or_insert(timecode);
+    }
+
+    /// Milliseconds since the origin of the track's time base, at which to place a block
+    /// lasting `duration_ms`.
+    fn time_ms(&mut self, video: bool, timestamp: i64, timecode: i64, duration_ms: i64) -> i64 {
+        let clock = if video {
+            &mut self.video_clock
+        } else {
+            &mut self.audio_clock
+        };
+        let Some(clock) = clock else {
+            return 0;
+        };
+        let time = match clock.base {
+            TimeBase::Timestamp if timestamp != TIMESTAMP_UNDEFINED => {
+                self.timestamp_origin.map(|origin| (timestamp, origin))
+            }
+            TimeBase::Timestamp => None,
+            TimeBase::Timecode => self.timecode_origin.map(|origin| (timecode, origin)),
+        };
+        let time = time.map_or(clock.next_ms, |(time, origin)| {
+            time.saturating_sub(origin).max(0) / TICKS_PER_MS
+        });
+        clock.next_ms = time + duration_ms;
+        time
+    }
+
+    fn start_if_ready(&mut self) -> Result<(), Error> {
+        let have_video = !self.record_video || self.video.is_some();
+        let have_audio = !self.record_audio || self.audio.is_some();
+        let waited = self.pending_ms >= MAX_PENDING_MS || self.pending_bytes >= MAX_PENDING_BYTES;
+        if (have_video && have_audio) || waited {
+            if waited {
+                ndi_debug!(
+                    video = self.video.is_some(),
+                    audio = self.audio.is_some(),
+                    "recording started without every track"
+                );
+            }
+            self.start()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the file header and track list, then everything held back so far.
+    fn start(&mut self) -> Result<(), Error> {
+        let mut header = Vec::new();
+        let mut ebml = Vec::new();
+        uint(&mut ebml, EBML_VERSION, 1);
+        uint(&mut ebml, EBML_READ_VERSION, 1);
+        uint(&mut ebml, EBML_MAX_ID_LENGTH, 4);
+        uint(&mut ebml, EBML_MAX_SIZE_LENGTH, 8);
+        string(&mut ebml, DOC_TYPE, "matroska");
+        uint(&mut ebml, DOC_TYPE_VERSION, 4);
+        uint(&mut ebml, DOC_TYPE_READ_VERSION, 2);
+        element(&mut header, EBML, &ebml);
+        self.out.write_all(&header)?;
+
+        id(&mut self.out, SEGMENT)?;
+        self.segment_size_at = self.out.stream_position()?;
+        self.out.write_all(&UNKNOWN_SIZE)?;
+
+        let mut info = Vec::new();
+        uint(&mut info, TIMESTAMP_SCALE, TIMESTAMP_SCALE_NS);
+        string(&mut info, MUXING_APP, "grafton-ndi");
+        string(&mut info, WRITING_APP, "grafton-ndi");
+        let duration_offset = info.len();
+        float(&mut info, DURATION, 0.0);
+        let mut bytes = Vec::new();
+        element(&mut bytes, INFO, &info);
+        // The duration's value is the last 8 bytes of its element.
+        let info_header = bytes.len() - info.len();
+        self.duration_at = self.out.stream_position()?
+            + (info_header + duration_offset + element_header_len(DURATION, 8)) as u64;
+        self.out.write_all(&bytes)?;
+
+        let mut tracks = Vec::new();
+        let mut number = 0;
+        if let Some(video) = self.video {
+            number += 1;
+            self.video_number = number;
+            tracks.extend(video_entry(number, &video)?);
+        }
+        if let Some(audio) = self.audio {
+            number += 1;
+            self.audio_number = number;
+            tracks.extend(audio_entry(number, &audio));
+        }
+        let mut bytes = Vec::new();
+        element(&mut bytes, TRACKS, &tracks);
+        self.out.write_all(&bytes)?;
+        self.started = true;
+
+        self.pending_bytes = 0;
+        self.pending_ms = 0;
+        for pending in std::mem::take(&mut self.pending) {
+            match pending {
+                Pending::Video(frame) => self.write_video_block(&frame)?,
+                Pending::Audio(frame) => self.write_audio_block(&frame)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_video_block(&mut self, frame: &VideoFrame) -> Result<(), Error> {
+        let Some(track) = self.video else {
+            return Ok(());
+        };
+        let stride = unsafe { frame.line_stride_or_size.line_stride_in_bytes };
+        let planes = frame.fourcc.planes(frame.xres, frame.yres, stride);
+        let mut payload = Vec::with_capacity(planes.iter().map(|p| p.row_bytes * p.rows).sum());
+        for plane in &planes {
+            let start = payload.len();
+            payload.resize(start + plane.row_bytes * plane.rows, 0);
+            copy_rows(
+                &frame.data[plane.offset..],
+                plane.stride,
+                &mut payload[start..],
+                plane.row_bytes,
+                plane.row_bytes,
+                plane.rows,
+            );
+        }
+
+        let duration = video_duration_ms(&track);
+        let time = self.time_ms(true, frame.timestamp, frame.timecode, duration);
+        self.write_block(self.video_number, time, duration, &payload)
+    }
+
+    fn write_audio_block(&mut self, frame: &AudioFrame) -> Result<(), Error> {
+        if self.audio.is_none() {
+            return Ok(());
+        }
+        let channels = frame.no_channels.max(0) as usize;
+        let samples = frame.no_samples.max(0) as usize;
+        let stride = frame.channel_stride_in_bytes.max(0) as usize;
+        let mut payload = Vec::with_capacity(channels * samples * 4);
+        for sample in 0..samples {
+            for channel in 0..channels {
+                let offset = channel * stride + sample * 4;
+                let b = &frame.data[offset..offset + 4];
+                let value = f32::from_ne_bytes([b[0], b[1], b[2], b[3]]);
+                payload.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        let duration = audio_duration_ms(frame);
+        let time = self.time_ms(false, frame.timestamp, frame.timecode, duration);
+        self.write_block(self.audio_number, time, duration, &payload)
+    }
+
+    fn write_block(
+        &mut self,
+        track: u8,
+        time_ms: i64,
+        duration_ms: i64,
+        payload: &[u8],
+    ) -> Result<(), Error> {
+        let relative = self
+            .cluster
+            .as_ref()
+            .map(|c| time_ms - c.time_ms)
+            .filter(|r| *r < CLUSTER_SPAN_MS && *r >= i64::from(i16::MIN));
+        let relative = match relative {
+            Some(relative) => relative as i16,
+            None => {
+                self.close_cluster()?;
+                id(&mut self.out, CLUSTER)?;
+                let size_at = self.out.stream_position()?;
+                self.out.write_all(&UNKNOWN_SIZE)?;
+                let mut timestamp = Vec::new();
+                uint(&mut timestamp, CLUSTER_TIMESTAMP, time_ms as u64);
+                self.out.write_all(&timestamp)?;
+                self.cluster = Some(Cluster { size_at, time_ms });
+                0
+            }
+        };
+
+        id(&mut self.out, SIMPLE_BLOCK)?;
+        self.out.write_all(&size(4 + payload.len() as u64))?;
+        // Track number as a one-byte vint, relative time, then the keyframe flag.
+        self.out.write_all(&[0x80 | track])?;
+        self.out.write_all(&relative.to_be_bytes())?;
+        self.out.write_all(&[0x80])?;
+        self.out.write_all(payload)?;
+        self.end_ms = self.end_ms.max(time_ms + duration_ms);
+        Ok(())
+    }
+
+    fn close_cluster(&mut self) -> io::Result<()> {
+        if let Some(cluster) = self.cluster.take() {
+            let end = self.out.stream_position()?;
+            self.out.seek(SeekFrom::Start(cluster.size_at))?;
+            self.out.write_all(&fixed_size(end - cluster.size_at - 8))?;
+            self.out.seek(SeekFrom::Start(end))?;
+        }
+        Ok(())
+    }
+}
+
+fn video_duration_ms(track: &VideoTrack) -> i64 {
+    if track.frame_rate_n > 0 {
+        1000 * i64::from(track.frame_rate_d) / i64::from(track.frame_rate_n)
+    } else {
+        0
+    }
+}
+
+fn audio_duration_ms(frame: &AudioFrame) -> i64 {
+    if frame.sample_rate > 0 {
+        1000 * i64::from(frame.no_samples.max(0)) / i64::from(frame.sample_rate)
+    } else {
+        0
+    }
+}
+
+/// The FourCC stored for raw video. Planes are written tightly packed in Y, U, V order, so
+/// YV12 is recorded as I420.
+fn codec_fourcc(fourcc: FourCCVideoType) -> Result<[u8; 4], Error> {
+    match fourcc {
+        FourCCVideoType::UYVY => Ok(*b"UYVY"),
+        FourCCVideoType::BGRA => Ok(*b"BGRA"),
+        FourCCVideoType::BGRX => Ok(*b"BGR\0"),
+        FourCCVideoType::RGBA => Ok(*b"RGBA"),
+        FourCCVideoType::RGBX => Ok(*b"RGB\0"),
+        FourCCVideoType::NV12 => Ok(*b"NV12"),
+        FourCCVideoType::I420 | FourCCVideoType::YV12 => Ok(*b"I420"),
+        fourcc => Err(Error::UnsupportedFormat {
+            fourcc,
+            operation: "record",
+        }),
+    }
+}
+
+fn video_entry(number: u8, track: &VideoTrack) -> Result<Vec<u8>, Error> {
+    let mut video = Vec::new();
+    uint(
+        &mut video,
+        FLAG_INTERLACED,
+        if track.interlaced { 1 } else { 2 },
+    );
+    uint(&mut video, PIXEL_WIDTH, track.xres as u64);
+    uint(&mut video, PIXEL_HEIGHT, track.yres as u64);
+    element(&mut video, COLOUR_SPACE, &codec_fourcc(track.fourcc)?);
+
+    let mut entry = Vec::new();
+    uint(&mut entry, TRACK_NUMBER, u64::from(number));
+    uint(&mut entry, TRACK_UID, u64::from(number));
+    uint(&mut entry, TRACK_TYPE, 1);
+    uint(&mut entry, FLAG_LACING, 0);
+    string(&mut entry, CODEC_ID, "V_UNCOMPRESSED");
+    if track.frame_rate_n > 0 && track.frame_rate_d > 0 {
+        let frame_ns = 1_000_000_000 * track.frame_rate_d as u64 / track.frame_rate_n as u64;
+        uint(&mut entry, DEFAULT_DURATION, frame_ns);
+    }
+    element(&mut entry, VIDEO, &video);
+
+    let mut out = Vec::new();
+    element(&mut out, TRACK_ENTRY, &entry);
+    Ok(out)
+}
+
+fn audio_entry(number: u8, track: &AudioTrack) -> Vec<u8> {
+    let mut audio = Vec::new();
+    float(&mut audio, SAMPLING_FREQUENCY, f64::from(track.sample_rate));
+    uint(&mut audio, CHANNELS, track.channels.max(0) as u64);
+    uint(&mut audio, BIT_DEPTH, 32);
+
+    let mut entry = Vec::new();
+    uint(&mut entry, TRACK_NUMBER, u64::from(number));
+    uint(&mut entry, TRACK_UID, u64::from(number));
+    uint(&mut entry, TRACK_TYPE, 2);
+    uint(&mut entry, FLAG_LACING, 0);
+    string(&mut entry, CODEC_ID, "A_PCM/FLOAT/IEEE");
+    element(&mut entry, AUDIO, &audio);
+
+    let mut out = Vec::new();
+    element(&mut out, TRACK_ENTRY, &entry);
+    out
+}
+
+fn id_bytes(id: u32) -> Vec<u8> {
+    let bytes = id.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count();
+    bytes[skip..].to_vec()
+}
+
+fn id(out: &mut impl Write, id: u32) -> io::Result<()> {
+    out.write_all(&id_bytes(id))
+}
+
+/// The shortest EBML variable-length encoding of `value`.
+fn size(value: u64) -> Vec<u8> {
+    let len = (1..=8)
+        .find(|&len| value < (1u64 << (7 * len)) - 1)
+        .unwrap_or(8);
+    let marked = value | (1u64 << (7 * len));
+    marked.to_be_bytes()[8 - len..].to_vec()
+}
+
+/// `value` as an 8-byte EBML size, to overwrite an [`UNKNOWN_SIZE`] placeholder.
+fn fixed_size(value: u64) -> [u8; 8] {
+    (value | (1u64 << 56)).to_be_bytes()
+}
+
+fn element_header_len(id: u32, len: usize) -> usize {
+    id_bytes(id).len() + size(len as u64).len()
+}
+
+fn element(out: &mut Vec<u8>, id: u32, data: &[u8]) {
+    out.extend(id_bytes(id));
+    out.extend(size(data.len() as u64));
+    out.extend_from_slice(data);
+}
+
+fn uint(out: &mut Vec<u8>, id: u32, value: u64) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    element(out, id, &bytes[skip..]);
+}
+
+fn float(out: &mut Vec<u8>, id: u32, value: f64) {
+    element(out, id, &value.to_be_bytes());
+}
+
+fn string(out: &mut Vec<u8>, id: u32, value: &str) {
+    element(out, id, value.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{AudioFrameBuilder, VideoFrameBuilder};
+
+    fn video(timestamp: i64) -> VideoFrame {
+        VideoFrameBuilder::new()
+            .resolution(4, 2)
+            .fourcc(FourCCVideoType::BGRA)
+            .frame_rate(25, 1)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    fn audio(timestamp: i64) -> AudioFrame {
+        AudioFrameBuilder::new()
+            .sample_rate(48_000)
+            .channels(2)
+            .samples(1920)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn video_is_recorded_alone_once_the_audio_wait_expires() {
+        let mut recorder = Recorder::new(Cursor::new(Vec::new()));
+        // 24 frames of 40 ms are still within the wait; the 25th reaches a second.
+        for i in 0..24 {
+            recorder.write_video(&video(i * 400_000)).unwrap();
+        }
+        assert!(!recorder.started);
+        recorder.write_video(&video(24 * 400_000)).unwrap();
+        assert!(recorder.started);
+        assert!(recorder.pending.is_empty());
+
+        // Audio arriving after the start has no track to go to.
+        recorder.write_audio(&audio(0)).unwrap();
+        assert!(recorder.audio.is_none());
+        let written = recorder.finish().unwrap().into_inner();
+        assert!(written.len() > 25 * 4 * 2 * 4);
+    }
+
+    #[test]
+    fn recording_starts_once_every_track_is_seen() {
+        let mut recorder = Recorder::new(Cursor::new(Vec::new()));
+        recorder.write_video(&video(0)).unwrap();
+        assert!(!recorder.started);
+        recorder.write_audio(&audio(0)).unwrap();
+        assert!(recorder.started);
+        assert_eq!((recorder.video_number, recorder.audio_number), (1, 2));
+    }
+
+    #[test]
+    fn each_track_keeps_its_time_base() {
+        let mut recorder = Recorder::new(Cursor::new(Vec::new()));
+        recorder.video_clock = Some(TrackClock::new(1_000));
+        recorder.audio_clock = Some(TrackClock::new(TIMESTAMP_UNDEFINED));
+        recorder.start_clock(1_000, 5_000_000);
+
+        assert_eq!(recorder.time_ms(true, 401_000, 0, 40), 40);
+        // A video frame without a timestamp follows on rather than using its timecode.
+        assert_eq!(recorder.time_ms(true, TIMESTAMP_UNDEFINED, 0, 40), 80);
+        // Audio is placed by timecode from the first timecode seen, whatever its timestamps.
+        assert_eq!(recorder.time_ms(false, 123, 5_200_000, 20), 20);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    time::Duration,
+};
+
+use crate::{timecode::TIMESTAMP_UNDEFINED, AudioFrame, FrameType, VideoFrame};
+
+/// Something that can be ordered by its NDI timestamp (100 ns units).
+pub trait Timestamped {
+    /// Returns `None` when the item has no meaningful timestamp.
+    fn timestamp(&self) -> Option<i64>;
+}
+
+impl Timestamped for VideoFrame {
+    fn timestamp(&self) -> Option<i64> {
+        Some(self.timestamp).filter(|&t| t != TIMESTAMP_UNDEFINED)
+    }
+}
+
+impl Timestamped for AudioFrame {
+    fn timestamp(&self) -> Option<i64> {
+        Some(self.timestamp).filter(|&t| t != TIMESTAMP_UNDEFINED)
+    }
+}
+
+impl Timestamped for FrameType {
+    fn timestamp(&self) -> Option<i64> {
+        match self {
+            FrameType::Video(frame) => frame.timestamp(),
+            FrameType::Audio(frame) => frame.timestamp(),
+            FrameType::Metadata(_) | FrameType::None | FrameType::StatusChange => None,
+        }
+    }
+}
+
+struct Entry<T> {
+    timestamp: i64,
+    sequence: u64,
+    item: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.timestamp, self.sequence).cmp(&(other.timestamp, other.sequence))
+    }
+}
+
+/// Holds frames back for a bounded time so they can be released in timestamp order.
+///
+/// A frame is released once a frame at least `max_delay` newer has arrived, or when more than
+/// `max_frames` are waiting. Items without a timestamp keep their place relative to the frames
+/// received before them. Frames older than one already released are dropped and counted in
+/// [`ReorderBuffer::late`].
+pub struct ReorderBuffer<T: Timestamped> {
+    max_delay: i64,
+    max_frames: usize,
+    pending: BinaryHeap<Reverse<Entry<T>>>,
+    newest: Option<i64>,
+    released: Option<i64>,
+    sequence: u64,
+    late: u64,
+}
+
+impl<T: Timestamped> ReorderBuffer<T> {
+    pub fn new(max_delay: Duration, max_frames: usize) -> Self {
+        ReorderBuffer {
+            max_delay: (max_delay.as_nanos() / 100).min(i64::MAX as u128) as i64,
+            max_frames: max_frames.max(1),
+            pending: BinaryHeap::new(),
+            newest: None,
+            released: None,
+            sequence: 0,
+            late: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        let timestamp = match item.timestamp() {
+            Some(timestamp) => {
+                if self.released.is_some_and(|released| timestamp < released) {
+                    self.late += 1;
+                    return;
+                }
+                self.newest = Some(self.newest.map_or(timestamp, |n| n.max(timestamp)));
+                timestamp
+            }
+            None => self.newest.or(self.released).unwrap_or(i64::MIN),
+        };
+        self.pending.push(Reverse(Entry {
+            timestamp,
+            sequence: self.sequence,
+            item,
+        }));
+        self.sequence += 1;
+    }
+
+    /// Takes the next frame that is ready for delivery.
+    pub fn pop(&mut self) -> Option<T> {
+        let Reverse(oldest) = self.pending.peek()?;
+        let due = self
+            .newest
+            .is_none_or(|newest| newest.saturating_sub(oldest.timestamp) >= self.max_delay);
+        if due || self.pending.len() > self.max_frames {
+            self.take()
+        } else {
+            None
+        }
+    }
+
+    /// Releases every waiting frame in order, regardless of delay.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.take())
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Number of frames dropped because they arrived after a newer frame was released.
+    pub fn late(&self) -> u64 {
+        self.late
+    }
+
+    fn take(&mut self) -> Option<T> {
+        let Reverse(entry) = self.pending.pop()?;
+        self.released = Some(
+            self.released
+                .map_or(entry.timestamp, |r| r.max(entry.timestamp)),
+        );
+        Some(entry.item)
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{AudioFrame, Error, MetadataFrame, Recv, VideoFrame};
+
+/// How the `capture_*` methods that take a total timeout poll the SDK and recover from
+/// transient errors.
+///
+/// The SDK is asked for a frame in slices of at most `poll_interval`, so a shorter interval
+/// notices a deadline sooner while a longer one makes fewer calls on slow links. After a
+/// retryable error the receiver waits `retry_delay`, multiplied by `backoff` after each
+/// consecutive failure up to `max_retry_delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub poll_interval: Duration,
+    pub retry_delay: Duration,
+    pub backoff: f64,
+    pub max_retry_delay: Duration,
+    /// Consecutive retryable errors tolerated before the last one is returned.
+    pub max_retries: Option<u32>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            poll_interval: Duration::from_millis(100),
+            retry_delay: Duration::from_millis(10),
+            backoff: 1.0,
+            max_retry_delay: Duration::from_millis(10),
+            max_retries: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Polls every `poll_interval` and waits a constant `retry_delay` after errors.
+    pub fn fixed(poll_interval: Duration, retry_delay: Duration) -> Self {
+        RetryPolicy {
+            poll_interval,
+            retry_delay,
+            backoff: 1.0,
+            max_retry_delay: retry_delay,
+            max_retries: None,
+        }
+    }
+
+    /// Multiplies the retry delay by `backoff` after each consecutive error, up to `max_delay`.
+    pub fn with_backoff(mut self, backoff: f64, max_delay: Duration) -> Self {
+        self.backoff = backoff.max(1.0);
+        self.max_retry_delay = max_delay.max(self.retry_delay);
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Calls `attempt` with a per-call timeout in milliseconds until it yields a value, the
+    /// deadline passes or a non-retryable error occurs.
+    pub(crate) fn run<T>(
+        &self,
+        timeout: Duration,
+        operation: &'static str,
+        mut attempt: impl FnMut(u32) -> Result<Option<T>, Error>,
+    ) -> Result<T, Error> {
+        let deadline = Instant::now() + timeout;
+        let mut delay = self.retry_delay;
+        let mut failures = 0;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let poll = remaining.min(self.poll_interval);
+            match attempt(poll.as_millis() as u32) {
+                Ok(Some(value)) => return Ok(value),
+                Ok(None) => {
+                    failures = 0;
+                    delay = self.retry_delay;
+                }
+                Err(e) if e.is_retryable() => {
+                    failures += 1;
+                    if self.max_retries.is_some_and(|max| failures > max) {
+                        return Err(e);
+                    }
+                    ndi_debug!(operation, failures, delay = ?delay, error = %e, "retrying capture");
+                    thread::sleep(delay.min(deadline.saturating_duration_since(Instant::now())));
+                    delay = delay.mul_f64(self.backoff).min(self.max_retry_delay);
+                }
+                Err(e) => return Err(e),
+            }
+            if Instant::now() >= deadline {
+                return Err(timeout_error(operation, timeout));
+            }
+        }
+    }
+}
+
+fn timeout_error(operation: &'static str, timeout: Duration) -> Error {
+    Error::Timeout {
+        operation,
+        timeout_ms: timeout.as_millis().min(u32::MAX as u128) as u32,
+    }
+}
+
+impl<'a> Recv<'a> {
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.capture_config.retry_policy
+    }
+
+    /// Sets the policy used by [`capture_video`](Self::capture_video) and its siblings.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.capture_config.retry_policy = policy;
+    }
+
+    /// Waits up to `timeout` for a video frame, polling and retrying per the receiver's policy.
+    pub fn capture_video(&self, timeout: Duration) -> Result<VideoFrame, Error> {
+        self.capture_video_with_policy(timeout, &self.capture_config.retry_policy)
+    }
+
+    pub fn capture_video_with_policy(
+        &self,
+        timeout: Duration,
+        policy: &RetryPolicy,
+    ) -> Result<VideoFrame, Error> {
+        policy.run(timeout, "a video frame", |timeout_ms| {
+            Ok(self.capture_video_ref(timeout_ms)?.map(|f| f.to_owned()))
+        })
+    }
+
+    /// Waits up to `timeout` for an audio frame, polling and retrying per the receiver's policy.
+    pub fn capture_audio(&self, timeout: Duration) -> Result<AudioFrame, Error> {
+        self.capture_audio_with_policy(timeout, &self.capture_config.retry_policy)
+    }
+
+    pub fn capture_audio_with_policy(
+        &self,
+        timeout: Duration,
+        policy: &RetryPolicy,
+    ) -> Result<AudioFrame, Error> {
+        policy.run(timeout, "an audio frame", |timeout_ms| {
+            Ok(self.capture_audio_ref(timeout_ms)?.map(|f| f.to_owned()))
+        })
+    }
+
+    /// Waits up to `timeout` for a metadata frame, polling and retrying per the receiver's policy.
+    pub fn capture_metadata(&self, timeout: Duration) -> Result<MetadataFrame, Error> {
+        self.capture_metadata_with_policy(timeout, &self.capture_config.retry_policy)
+    }
+
+    pub fn capture_metadata_with_policy(
+        &self,
+        timeout: Duration,
+        policy: &RetryPolicy,
+    ) -> Result<MetadataFrame, Error> {
+        policy.run(timeout, "a metadata frame", |timeout_ms| {
+            Ok(self.capture_metadata_ref(timeout_ms)?.map(|f| f.to_owned()))
+        })
+    }
+}
@@ -0,0 +1,111 @@
+use std::{env, path::PathBuf, sync::OnceLock};
+
+use libloading::Library;
+
+use crate::{ndi_lib::RuntimeFunctions, Error};
+
+/// Where the NDI runtime can be downloaded (`NDILIB_REDIST_URL`).
+pub const NDI_REDIST_URL: &str = "http://ndi.link/NDIRedistV6";
+
+/// Environment variable the runtime installer sets to its install directory.
+const RUNTIME_DIR_VAR: &str = "NDI_RUNTIME_DIR_V6";
+
+#[cfg(all(
+    target_os = "windows",
+    target_pointer_width = "64",
+    feature = "advanced_sdk"
+))]
+const LIBRARY_NAMES: &[&str] = &["Processing.NDI.Lib.Advanced.x64.dll"];
+#[cfg(all(
+    target_os = "windows",
+    target_pointer_width = "64",
+    not(feature = "advanced_sdk")
+))]
+const LIBRARY_NAMES: &[&str] = &["Processing.NDI.Lib.x64.dll"];
+#[cfg(all(
+    target_os = "windows",
+    target_pointer_width = "32",
+    feature = "advanced_sdk"
+))]
+const LIBRARY_NAMES: &[&str] = &["Processing.NDI.Lib.Advanced.x86.dll"];
+#[cfg(all(
+    target_os = "windows",
+    target_pointer_width = "32",
+    not(feature = "advanced_sdk")
+))]
+const LIBRARY_NAMES: &[&str] = &["Processing.NDI.Lib.x86.dll"];
+#[cfg(target_os = "macos")]
+const LIBRARY_NAMES: &[&str] = &["libndi.dylib"];
+#[cfg(all(
+    not(any(target_os = "windows", target_os = "macos")),
+    feature = "advanced_sdk"
+))]
+const LIBRARY_NAMES: &[&str] = &["libndi_advanced.so.6", "libndi_advanced.so"];
+#[cfg(all(
+    not(any(target_os = "windows", target_os = "macos")),
+    not(feature = "advanced_sdk")
+))]
+const LIBRARY_NAMES: &[&str] = &["libndi.so.6", "libndi.so"];
+
+struct Runtime {
+    // Kept mapped for as long as the function pointers are in use, i.e. the whole process.
+    #[cfg_attr(not(feature = "advanced_sdk"), allow(dead_code))]
+    library: Library,
+    functions: RuntimeFunctions,
+}
+
+static RUNTIME: OnceLock<Result<Runtime, String>> = OnceLock::new();
+
+/// Opens the NDI runtime if that hasn't happened yet, reporting where it was looked for if it
+/// can't be found.
+pub(crate) fn load() -> Result<(), Error> {
+    match RUNTIME.get_or_init(open) {
+        Ok(_) => Ok(()),
+        Err(searched) => Err(Error::RuntimeNotFound {
+            searched: searched.clone(),
+            download_url: NDI_REDIST_URL,
+        }),
+    }
+}
+
+/// The resolved SDK functions. The crate only calls into the SDK after [`load`] has succeeded.
+pub(crate) fn functions() -> &'static RuntimeFunctions {
+    match RUNTIME.get() {
+        Some(Ok(runtime)) => &runtime.functions,
+        _ => panic!("the NDI runtime is not loaded; create an NDI instance first"),
+    }
+}
+
+/// Looks up a symbol the generated bindings don't cover, such as Advanced SDK extensions.
+///
+/// # Safety
+/// `T` must be the function pointer type matching the symbol's C signature.
+#[cfg_attr(not(feature = "advanced_sdk"), allow(dead_code))]
+pub(crate) unsafe fn symbol<T: Copy>(name: &str) -> Option<T> {
+    match RUNTIME.get() {
+        Some(Ok(runtime)) => runtime.library.get::<T>(name.as_bytes()).ok().map(|f| *f),
+        _ => None,
+    }
+}
+
+fn open() -> Result<Runtime, String> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env::var_os(RUNTIME_DIR_VAR) {
+        let dir = PathBuf::from(dir);
+        candidates.extend(LIBRARY_NAMES.iter().map(|name| dir.join(name)));
+    }
+    // Bare names go through the platform's usual library search path.
+    candidates.extend(LIBRARY_NAMES.iter().map(PathBuf::from));
+
+    let mut searched = Vec::new();
+    for candidate in candidates {
+        match unsafe { Library::new(&candidate) } {
+            Ok(library) => {
+                let functions = unsafe { RuntimeFunctions::load(&library) };
+                return Ok(Runtime { library, functions });
+            }
+            Err(e) => searched.push(format!("{} ({})", candidate.display(), e)),
+        }
+    }
+    Err(searched.join(", "))
+}
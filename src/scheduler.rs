@@ -0,0 +1,220 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc::{self, Receiver as ChannelReceiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    multi_recv::{deliver, join_draining, FRAME_BUFFER},
+    Error, FrameType, LentRecv, Receiver, Recv, Source, SourceId, NDI,
+};
+
+const DEFAULT_POLL_TIMEOUT_MS: u32 = 1;
+/// How long a worker rests when every source is being polled by another worker.
+const IDLE_SLEEP: Duration = Duration::from_millis(1);
+/// Pass distance covered by one poll of a priority-1 source; divided by the priority.
+const STRIDE: u64 = 1 << 20;
+
+/// Captures from many sources on a fixed pool of worker threads.
+///
+/// Rather than one blocking thread per receiver, each worker repeatedly picks a source, polls
+/// it with a short timeout and moves on, so a few threads can serve dozens of sources. Sources
+/// are picked by stride scheduling: every source is polled regularly, and one with priority
+/// `n` is polled `n` times as often as a priority-1 source. A source is never polled by two
+/// workers at once, which keeps its frames in order.
+///
+/// Workers capture through the receivers as [`Recv::capture`] does, so statistics, format
+/// tracking and status-change handling work as for a single receiver. Frames are delivered
+/// through one channel tagged with their [`SourceId`], as with
+/// [`MultiReceiver`](crate::MultiReceiver). Dropping the scheduler stops and joins the workers.
+pub struct CaptureScheduler<'a> {
+    ndi: &'a NDI,
+    receivers: Vec<Recv<'a>>,
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+    frames: ChannelReceiver<(SourceId, FrameType)>,
+}
+
+struct Shared {
+    slots: Mutex<Vec<Slot>>,
+    stopped: AtomicBool,
+    poll_timeout_ms: AtomicU32,
+}
+
+struct Slot {
+    recv: LentRecv,
+    priority: u32,
+    /// Virtual time of the next poll; the idle source with the lowest pass goes next.
+    pass: u64,
+    busy: bool,
+    failed: bool,
+    error: Option<Error>,
+}
+
+impl<'a> CaptureScheduler<'a> {
+    /// Starts `workers` threads (at least one) with no sources yet.
+    pub fn new(ndi: &'a NDI, workers: usize) -> Self {
+        let shared = Arc::new(Shared {
+            slots: Mutex::new(Vec::new()),
+            stopped: AtomicBool::new(false),
+            poll_timeout_ms: AtomicU32::new(DEFAULT_POLL_TIMEOUT_MS),
+        });
+        let (sender, frames) = mpsc::sync_channel(FRAME_BUFFER);
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                let sender = sender.clone();
+                thread::spawn(move || work(&shared, &sender))
+            })
+            .collect();
+        CaptureScheduler {
+            ndi,
+            receivers: Vec::new(),
+            shared,
+            workers,
+            frames,
+        }
+    }
+
+    /// How long each poll may block in the SDK; defaults to 1 ms.
+    ///
+    /// Longer polls use less CPU when sources are idle but let a busy source wait longer for
+    /// its turn.
+    pub fn with_poll_timeout(self, timeout: Duration) -> Self {
+        self.shared
+            .poll_timeout_ms
+            .store(timeout.as_millis() as u32, Ordering::Relaxed);
+        self
+    }
+
+    /// Creates a receiver and schedules it with `priority` (at least 1).
+    pub fn add_source(&mut self, settings: Receiver, priority: u32) -> Result<SourceId, Error> {
+        let recv = Recv::new(self.ndi, settings)?;
+        let mut slots = self.lock_slots();
+        // Start level with the others so a new source neither starves nor hogs the workers.
+        let pass = slots.iter().map(|s| s.pass).min().unwrap_or(0);
+        slots.push(Slot {
+            recv: recv.lend(),
+            priority: priority.max(1),
+            pass,
+            busy: false,
+            failed: false,
+            error: None,
+        });
+        drop(slots);
+        self.receivers.push(recv);
+        Ok(SourceId(self.receivers.len() - 1))
+    }
+
+    /// Changes how often `id` is polled relative to the other sources.
+    pub fn set_priority(&self, id: SourceId, priority: u32) {
+        if let Some(slot) = self.lock_slots().get_mut(id.0) {
+            slot.priority = priority.max(1);
+        }
+    }
+
+    /// The channel every captured frame is delivered through.
+    pub fn frames(&self) -> &ChannelReceiver<(SourceId, FrameType)> {
+        &self.frames
+    }
+
+    /// Waits up to `timeout` for the next frame from any source.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<(SourceId, FrameType)> {
+        self.frames.recv_timeout(timeout).ok()
+    }
+
+    /// The next buffered frame, if one is waiting.
+    pub fn try_recv(&self) -> Option<(SourceId, FrameType)> {
+        self.frames.try_recv().ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.receivers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.receivers.is_empty()
+    }
+
+    pub fn source(&self, id: SourceId) -> Option<&Source> {
+        self.receivers.get(id.0).map(Recv::source)
+    }
+
+    /// The receiver for `id`, for tally, PTZ and statistics; capture happens on the workers.
+    pub fn recv(&self, id: SourceId) -> Option<&Recv<'a>> {
+        self.receivers.get(id.0)
+    }
+
+    /// The error that took `id` out of the schedule, if any.
+    ///
+    /// Retryable errors are ignored; any other error stops the source being polled.
+    pub fn take_error(&self, id: SourceId) -> Option<Error> {
+        self.lock_slots().get_mut(id.0)?.error.take()
+    }
+
+    fn lock_slots(&self) -> std::sync::MutexGuard<'_, Vec<Slot>> {
+        self.shared
+            .slots
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for CaptureScheduler<'_> {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            join_draining(worker, &self.frames);
+        }
+    }
+}
+
+fn work(shared: &Shared, sender: &SyncSender<(SourceId, FrameType)>) {
+    while !shared.stopped.load(Ordering::Relaxed) {
+        let Some((index, recv)) = pick(shared) else {
+            thread::sleep(IDLE_SLEEP);
+            continue;
+        };
+        let timeout_ms = shared.poll_timeout_ms.load(Ordering::Relaxed);
+        let (frame, error) = match recv.capture_frame(timeout_ms) {
+            Ok(FrameType::None) => (None, None),
+            Ok(frame) => (Some(frame), None),
+            Err(e) if e.is_retryable() => (None, None),
+            Err(e) => {
+                ndi_warn!(index, error = %e, "source removed from the capture schedule");
+                (None, Some(e))
+            }
+        };
+        // Deliver before releasing the source so its frames can't be reordered by another worker.
+        let delivered =
+            frame.is_none_or(|frame| deliver(sender, (SourceId(index), frame), &shared.stopped));
+        if let Ok(mut slots) = shared.slots.lock() {
+            let slot = &mut slots[index];
+            slot.busy = false;
+            if error.is_some() {
+                slot.failed = true;
+                slot.error = error;
+            }
+        }
+        if !delivered {
+            return;
+        }
+    }
+}
+
+/// Claims the idle, healthy source with the lowest pass and advances its pass.
+fn pick(shared: &Shared) -> Option<(usize, LentRecv)> {
+    let mut slots = shared.slots.lock().ok()?;
+    let (index, slot) = slots
+        .iter_mut()
+        .enumerate()
+        .filter(|(_, s)| !s.busy && !s.failed)
+        .min_by_key(|(_, s)| s.pass)?;
+    slot.busy = true;
+    slot.pass += STRIDE / u64::from(slot.priority);
+    Some((index, slot.recv.clone()))
+}
@@ -0,0 +1,416 @@
+use std::{
+    collections::hash_map::RandomState,
+    ffi::CString,
+    hash::{BuildHasher, Hasher},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    metadata::{escape_xml_attribute, unescape_xml, xml_attribute},
+    AudioFrame, Error, MetadataFrame, Send, VideoFrame,
+};
+
+const SCRAMBLE_ELEMENT: &str = "ndi_scramble";
+
+/// A symmetric transform applied to a payload before sending and reversed after receiving.
+///
+/// NDI itself does not encrypt media. Implement this trait to plug in a real cipher; the
+/// crate only handles nonces and the metadata that tells the receiving side how to undo it.
+///
+/// The transform must see exactly the bytes it produced, so it only works on payloads the
+/// link carries unchanged, such as metadata. Video is re-encoded with lossy SpeedHQ and may
+/// be converted to the receiver's color format, and audio is converted too, so scrambled
+/// pixels or samples come out the other end as noise.
+pub trait FrameScrambler: std::marker::Send + Sync {
+    /// Short identifier of the algorithm, advertised to receivers.
+    fn scheme(&self) -> &str;
+    /// Identifies the key so receivers holding several keys can pick the right one.
+    fn key_id(&self) -> &str;
+    fn scramble(&self, data: &mut [u8], nonce: u64);
+    fn unscramble(&self, data: &mut [u8], nonce: u64);
+}
+
+/// XORs data with a keystream derived from a shared key and a per-frame nonce.
+///
+/// This keeps casual listeners on a shared LAN from reading the payload, but it is not
+/// authenticated encryption; use a vetted cipher through [`FrameScrambler`] where that matters.
+/// As for any scrambler, only payloads that cross the link unchanged can be recovered.
+#[derive(Debug, Clone)]
+pub struct XorScrambler {
+    key_id: String,
+    seed: u64,
+}
+
+impl XorScrambler {
+    pub fn new(key_id: &str, key: &[u8]) -> Self {
+        // FNV-1a over the key gives a stable 64-bit seed for the keystream.
+        let seed = key.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        XorScrambler {
+            key_id: key_id.to_string(),
+            seed,
+        }
+    }
+
+    fn apply(&self, data: &mut [u8], nonce: u64) {
+        let mut state = self.seed ^ nonce.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        for chunk in data.chunks_mut(8) {
+            // SplitMix64
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            for (byte, key) in chunk.iter_mut().zip(z.to_le_bytes()) {
+                *byte ^= key;
+            }
+        }
+    }
+}
+
+impl FrameScrambler for XorScrambler {
+    fn scheme(&self) -> &str {
+        "xor-splitmix64"
+    }
+
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn scramble(&self, data: &mut [u8], nonce: u64) {
+        self.apply(data, nonce)
+    }
+
+    fn unscramble(&self, data: &mut [u8], nonce: u64) {
+        self.apply(data, nonce)
+    }
+}
+
+/// Scrambles outgoing metadata and tags it with what is needed to reverse the transform.
+///
+/// Use [`scramble_metadata`](Self::scramble_metadata) and
+/// [`unscramble_metadata`](Self::unscramble_metadata). The video and audio methods only
+/// round-trip frames that never cross an NDI link, since the SDK re-encodes media; see
+/// [`FrameScrambler`].
+///
+/// Nonces count up from a random starting point chosen for each set of hooks, so senders
+/// sharing a key, or one sender restarted, don't reuse each other's keystreams. Each frame
+/// carries its nonce, so receivers need nothing more than the key.
+pub struct ScrambleHooks<S: FrameScrambler> {
+    scrambler: S,
+    next_nonce: AtomicU64,
+}
+
+impl<S: FrameScrambler> ScrambleHooks<S> {
+    pub fn new(scrambler: S) -> Self {
+        ScrambleHooks {
+            scrambler,
+            next_nonce: AtomicU64::new(session_salt()),
+        }
+    }
+
+    pub fn scrambler(&self) -> &S {
+        &self.scrambler
+    }
+
+    /// Connection metadata announcing that frames from this sender are scrambled.
+    pub fn capability_xml(&self) -> String {
+        format!(
+            "<{} scheme=\"{}\" key_id=\"{}\"/>",
+            SCRAMBLE_ELEMENT,
+            escape_xml_attribute(self.scrambler.scheme()),
+            escape_xml_attribute(self.scrambler.key_id())
+        )
+    }
+
+    /// Registers the scrambling capability as connection metadata on `send`, so receivers
+    /// learn about it as soon as they connect.
+    pub fn advertise(&self, send: &Send) -> Result<(), Error> {
+        send.add_connection_metadata(&MetadataFrame::from_xml(&self.capability_xml(), 0)?);
+        Ok(())
+    }
+
+    /// Scrambles `xml` into a metadata frame that carries it hex-encoded, with the nonce and
+    /// key needed to undo it.
+    pub fn scramble_metadata(&self, xml: &str, timecode: i64) -> Result<MetadataFrame, Error> {
+        let nonce = self.next_nonce.fetch_add(1, Ordering::Relaxed);
+        let mut data = xml.as_bytes().to_vec();
+        self.scrambler.scramble(&mut data, nonce);
+        let hex: String = data.iter().map(|byte| format!("{:02x}", byte)).collect();
+        MetadataFrame::from_xml(
+            &format!(
+                "<{} scheme=\"{}\" key_id=\"{}\" nonce=\"{}\" data=\"{}\"/>",
+                SCRAMBLE_ELEMENT,
+                escape_xml_attribute(self.scrambler.scheme()),
+                escape_xml_attribute(self.scrambler.key_id()),
+                nonce,
+                hex
+            ),
+            timecode,
+        )
+    }
+
+    /// Reverses [`scramble_metadata`](Self::scramble_metadata), returning the original XML.
+    ///
+    /// Returns `Ok(None)` when the frame was not scrambled.
+    pub fn unscramble_metadata(&self, frame: &MetadataFrame) -> Result<Option<String>, Error> {
+        let Ok(xml) = frame.as_str() else {
+            return Ok(None);
+        };
+        let Some(nonce) = self.received_nonce(Some(xml))? else {
+            return Ok(None);
+        };
+        let hex = xml_attribute(xml, "data").unwrap_or_default();
+        let mut data = decode_hex(hex).ok_or_else(|| {
+            Error::InvalidFrame("Scrambled metadata carries malformed data".into())
+        })?;
+        self.scrambler.unscramble(&mut data, nonce);
+        String::from_utf8(data)
+            .map(Some)
+            .map_err(|e| Error::InvalidUtf8(e.to_string()))
+    }
+
+    #[deprecated(
+        note = "the SDK re-encodes video, so scrambled pixels don't survive an NDI link; use `scramble_metadata`"
+    )]
+    pub fn scramble_video(&self, frame: &mut VideoFrame) -> Result<(), Error> {
+        let nonce = self.tag(&frame.metadata)?;
+        self.scrambler.scramble(&mut frame.data, nonce);
+        frame.metadata = Some(self.frame_tag(nonce)?);
+        Ok(())
+    }
+
+    #[deprecated(
+        note = "the SDK converts audio, so scrambled samples don't survive an NDI link; use `scramble_metadata`"
+    )]
+    pub fn scramble_audio(&self, frame: &mut AudioFrame) -> Result<(), Error> {
+        let nonce = self.tag(&frame.metadata)?;
+        self.scrambler.scramble(&mut frame.data, nonce);
+        frame.metadata = Some(self.frame_tag(nonce)?);
+        Ok(())
+    }
+
+    /// Reverses [`scramble_video`](Self::scramble_video) on a received frame.
+    ///
+    /// Returns `Ok(false)` when the frame was not scrambled.
+    #[deprecated(
+        note = "the SDK re-encodes video, so scrambled pixels don't survive an NDI link; use `unscramble_metadata`"
+    )]
+    pub fn unscramble_video(&self, frame: &mut VideoFrame) -> Result<bool, Error> {
+        let Some(nonce) = self.received_nonce(received_xml(&frame.metadata))? else {
+            return Ok(false);
+        };
+        self.scrambler.unscramble(&mut frame.data, nonce);
+        frame.metadata = None;
+        Ok(true)
+    }
+
+    /// Reverses [`scramble_audio`](Self::scramble_audio) on a received frame.
+    ///
+    /// Returns `Ok(false)` when the frame was not scrambled.
+    #[deprecated(
+        note = "the SDK converts audio, so scrambled samples don't survive an NDI link; use `unscramble_metadata`"
+    )]
+    pub fn unscramble_audio(&self, frame: &mut AudioFrame) -> Result<bool, Error> {
+        let Some(nonce) = self.received_nonce(received_xml(&frame.metadata))? else {
+            return Ok(false);
+        };
+        self.scrambler.unscramble(&mut frame.data, nonce);
+        frame.metadata = None;
+        Ok(true)
+    }
+
+    fn tag(&self, metadata: &Option<CString>) -> Result<u64, Error> {
+        if metadata.is_some() {
+            return Err(Error::InvalidFrame(
+                "Frame already carries metadata; scrambled frames use it for the nonce".into(),
+            ));
+        }
+        Ok(self.next_nonce.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn frame_tag(&self, nonce: u64) -> Result<CString, Error> {
+        CString::new(format!(
+            "<{} scheme=\"{}\" key_id=\"{}\" nonce=\"{}\"/>",
+            SCRAMBLE_ELEMENT,
+            escape_xml_attribute(self.scrambler.scheme()),
+            escape_xml_attribute(self.scrambler.key_id()),
+            nonce
+        ))
+        .map_err(Error::InvalidCString)
+    }
+
+    fn received_nonce(&self, xml: Option<&str>) -> Result<Option<u64>, Error> {
+        let Some(xml) = xml else {
+            return Ok(None);
+        };
+        if !xml
+            .trim_start()
+            .starts_with(&format!("<{}", SCRAMBLE_ELEMENT))
+        {
+            return Ok(None);
+        }
+
+        let scheme = xml_attribute(xml, "scheme").map(unescape_xml);
+        let key_id = xml_attribute(xml, "key_id").map(unescape_xml);
+        if scheme.as_deref() != Some(self.scrambler.scheme())
+            || key_id.as_deref() != Some(self.scrambler.key_id())
+        {
+            return Err(Error::InvalidFrame(format!(
+                "Frame was scrambled with scheme {:?} and key {:?}, which this receiver cannot undo",
+                scheme.unwrap_or_default(),
+                key_id.unwrap_or_default()
+            )));
+        }
+        xml_attribute(xml, "nonce")
+            .and_then(|n| n.parse().ok())
+            .map(Some)
+            .ok_or_else(|| Error::InvalidFrame("Scrambled frame is missing its nonce".into()))
+    }
+}
+
+fn received_xml(metadata: &Option<CString>) -> Option<&str> {
+    metadata.as_ref().and_then(|m| m.to_str().ok())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// A random starting nonce for a session.
+///
+/// `RandomState` is keyed from the operating system's random source; mixing in the time and
+/// process id keeps two sessions started together in one process apart as well.
+fn session_salt() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    hasher.write_u128(now.as_nanos());
+    hasher.write_u32(process::id());
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_data() -> Vec<u8> {
+        (0..64).collect()
+    }
+
+    fn frame() -> VideoFrame {
+        let mut frame = VideoFrame::default();
+        frame.data = frame_data();
+        frame
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn video_round_trips() {
+        let hooks = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let mut frame = frame();
+        hooks.scramble_video(&mut frame).unwrap();
+        assert_ne!(frame.data, frame_data());
+        assert!(hooks.unscramble_video(&mut frame).unwrap());
+        assert_eq!(frame.data, frame_data());
+        assert!(frame.metadata.is_none());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn sessions_start_from_different_nonces() {
+        let first = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let second = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let (mut a, mut b) = (frame(), frame());
+        first.scramble_video(&mut a).unwrap();
+        second.scramble_video(&mut b).unwrap();
+        assert_ne!(a.metadata, b.metadata);
+        assert_ne!(a.data, b.data);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn nonces_are_unique_within_a_session() {
+        let hooks = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let (mut a, mut b) = (frame(), frame());
+        hooks.scramble_video(&mut a).unwrap();
+        hooks.scramble_video(&mut b).unwrap();
+        assert_ne!(a.data, b.data);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn other_keys_are_refused() {
+        let sender = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let receiver = ScrambleHooks::new(XorScrambler::new("k2", b"other"));
+        let mut frame = frame();
+        sender.scramble_video(&mut frame).unwrap();
+        assert!(receiver.unscramble_video(&mut frame).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn unscrambled_frames_pass_through() {
+        let hooks = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let mut frame = frame();
+        assert!(!hooks.unscramble_video(&mut frame).unwrap());
+        assert_eq!(frame.data, frame_data());
+    }
+
+    #[test]
+    fn metadata_round_trips() {
+        let hooks = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let frame = hooks
+            .scramble_metadata("<ptz_zoom zoom=\"0.5\"/>", 7)
+            .unwrap();
+        assert!(!frame.as_str().unwrap().contains("ptz_zoom"));
+        assert_eq!(frame.timecode, 7);
+        assert_eq!(
+            hooks.unscramble_metadata(&frame).unwrap().as_deref(),
+            Some("<ptz_zoom zoom=\"0.5\"/>")
+        );
+    }
+
+    #[test]
+    fn metadata_with_other_keys_or_bad_data_is_refused() {
+        let sender = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let receiver = ScrambleHooks::new(XorScrambler::new("k2", b"other"));
+        let frame = sender.scramble_metadata("<tag/>", 0).unwrap();
+        assert!(receiver.unscramble_metadata(&frame).is_err());
+
+        let xml = r#"<ndi_scramble scheme="xor-splitmix64" key_id="k1" nonce="1" data="+f"/>"#;
+        let frame = MetadataFrame::from_xml(xml, 0).unwrap();
+        assert!(sender.unscramble_metadata(&frame).is_err());
+    }
+
+    #[test]
+    fn plain_metadata_passes_through() {
+        let hooks = ScrambleHooks::new(XorScrambler::new("k1", b"secret"));
+        let frame = MetadataFrame::from_xml("<tag/>", 0).unwrap();
+        assert_eq!(hooks.unscramble_metadata(&frame).unwrap(), None);
+    }
+
+    #[test]
+    fn key_ids_are_escaped() {
+        let hooks = ScrambleHooks::new(XorScrambler::new("a\"b<&>", b"secret"));
+        assert_eq!(
+            hooks.capability_xml(),
+            r#"<ndi_scramble scheme="xor-splitmix64" key_id="a&quot;b&lt;&amp;&gt;"/>"#
+        );
+        let frame = hooks.scramble_metadata("<tag/>", 0).unwrap();
+        assert_eq!(
+            hooks.unscramble_metadata(&frame).unwrap().as_deref(),
+            Some("<tag/>")
+        );
+    }
+}
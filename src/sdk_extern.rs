@@ -0,0 +1,28 @@
+// Declares SDK functions that the generated bindings don't include. They are linked normally,
+// or looked up in the runtime opened at `NDI::new` when the `dynamic_loading` feature is on.
+
+#[allow(unused_macros)]
+macro_rules! sdk_extern {
+    ($(fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        #[cfg(not(feature = "dynamic_loading"))]
+        extern "C" {
+            $(fn $name($($arg: $ty),*) $(-> $ret)?;)*
+        }
+
+        $(
+            #[cfg(feature = "dynamic_loading")]
+            #[allow(non_snake_case)]
+            unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                type Function = unsafe extern "C" fn($($ty),*) $(-> $ret)?;
+                static FUNCTION: std::sync::OnceLock<Option<Function>> = std::sync::OnceLock::new();
+                let function = FUNCTION
+                    .get_or_init(|| crate::runtime::symbol(concat!(stringify!($name), "\0")))
+                    .expect(concat!(
+                        stringify!($name),
+                        " is not exported by the loaded NDI runtime"
+                    ));
+                function($($arg),*)
+            }
+        )*
+    };
+}
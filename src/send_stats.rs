@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+use crate::{ndi_lib::*, Send};
+
+/// How far past its due time a frame has to be sent to count as late, in frame durations.
+const LATE_TOLERANCE: f64 = 1.5;
+
+/// How a sender's video sends are keeping up, from [`Send::stats`].
+///
+/// With `clock_video` set the SDK paces sends by blocking in them, so a sender that keeps up
+/// spends most of each frame inside the send call and sends at exactly the frame rate. A
+/// frame is counted as late when it is handed over more than one and a half frame durations
+/// after the previous one, i.e. when whatever produces the frames fell behind; receivers then
+/// see a repeated or dropped frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SendStats {
+    pub frames_sent: u64,
+    pub late_frames: u64,
+    /// Average time spent in the SDK's send call, including any time blocked for pacing.
+    pub avg_send_duration: Duration,
+    pub max_send_duration: Duration,
+}
+
+impl SendStats {
+    /// Share of the frames sent that were late, from 0 to 1.
+    pub fn late_ratio(&self) -> f64 {
+        if self.frames_sent == 0 {
+            0.0
+        } else {
+            self.late_frames as f64 / self.frames_sent as f64
+        }
+    }
+}
+
+/// Running totals behind [`SendStats`], kept with the sender's video state.
+#[derive(Debug, Default)]
+pub(crate) struct SendCounters {
+    frames_sent: u64,
+    late_frames: u64,
+    total_send: Duration,
+    max_send: Duration,
+    last_start: Option<Instant>,
+}
+
+impl SendCounters {
+    /// Records a send of `raw` that started at `started` and has just returned.
+    pub(crate) fn record(&mut self, raw: &NDIlib_video_frame_v2_t, started: Instant) {
+        let took = started.elapsed();
+        if let Some(last) = self.last_start {
+            if raw.frame_rate_N > 0 && raw.frame_rate_D > 0 {
+                let frame = Duration::from_secs_f64(
+                    f64::from(raw.frame_rate_D) / f64::from(raw.frame_rate_N),
+                );
+                if started.duration_since(last) > frame.mul_f64(LATE_TOLERANCE) {
+                    self.late_frames += 1;
+                    ndi_trace!(
+                        gap = ?started.duration_since(last),
+                        "video frame sent late"
+                    );
+                }
+            }
+        }
+        self.last_start = Some(started);
+        self.frames_sent += 1;
+        self.total_send += took;
+        self.max_send = self.max_send.max(took);
+    }
+
+    fn snapshot(&self) -> SendStats {
+        SendStats {
+            frames_sent: self.frames_sent,
+            late_frames: self.late_frames,
+            avg_send_duration: if self.frames_sent == 0 {
+                Duration::ZERO
+            } else {
+                self.total_send.div_f64(self.frames_sent as f64)
+            },
+            max_send_duration: self.max_send,
+        }
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Counts of the video frames sent so far and how long sending them took.
+    pub fn stats(&self) -> SendStats {
+        self.video_state().stats.snapshot()
+    }
+
+    /// Starts the counts behind [`Send::stats`] over, e.g. after a deliberate pause that
+    /// would otherwise count as a late frame.
+    pub fn reset_stats(&self) {
+        self.video_state().stats = SendCounters::default();
+    }
+}
@@ -0,0 +1,144 @@
+use std::time::{Duration, Instant};
+
+use crate::{Error, Find, Finder, FrameType, Receiver, Recv, Send, Sender, NDI};
+
+/// How long dropping a session that wasn't shut down may spend draining receivers.
+const DROP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Identifies a sender owned by an [`NdiSession`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SenderId(usize);
+
+/// Identifies a receiver owned by an [`NdiSession`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReceiverId(usize);
+
+/// Identifies a finder owned by an [`NdiSession`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FinderId(usize);
+
+/// The NDI runtime together with the finders, receivers and senders created from it, torn
+/// down in a safe order.
+///
+/// [`NdiSession::shutdown`] (or dropping the session) first flushes every sender's async
+/// video so the SDK lets go of frame buffers, destroys the senders, drains frames still queued
+/// in the receivers and destroys them, then the finders, and releases the runtime last.
+pub struct NdiSession {
+    // Field order is drop order: everything is destroyed before the runtime.
+    senders: Vec<Send<'static>>,
+    receivers: Vec<Recv<'static>>,
+    finders: Vec<Find<'static>>,
+    ndi: NDI,
+}
+
+impl NdiSession {
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self::with_runtime(NDI::new()?))
+    }
+
+    /// Takes over an already initialized runtime, e.g. one from [`NDI::with_config_dir`].
+    pub fn with_runtime(ndi: NDI) -> Self {
+        NdiSession {
+            senders: Vec::new(),
+            receivers: Vec::new(),
+            finders: Vec::new(),
+            ndi,
+        }
+    }
+
+    pub fn ndi(&self) -> &NDI {
+        &self.ndi
+    }
+
+    pub fn add_sender(&mut self, settings: Sender) -> Result<SenderId, Error> {
+        // SAFETY: `ndi` is dropped after `senders`.
+        self.senders.push(unsafe { Send::new_detached(settings)? });
+        Ok(SenderId(self.senders.len() - 1))
+    }
+
+    pub fn add_receiver(&mut self, settings: Receiver) -> Result<ReceiverId, Error> {
+        // SAFETY: `ndi` is dropped after `receivers`.
+        self.receivers
+            .push(unsafe { Recv::new_detached(settings)? });
+        Ok(ReceiverId(self.receivers.len() - 1))
+    }
+
+    pub fn add_finder(&mut self, settings: Finder) -> Result<FinderId, Error> {
+        // SAFETY: `ndi` is dropped after `finders`.
+        self.finders.push(unsafe { Find::new_detached(settings)? });
+        Ok(FinderId(self.finders.len() - 1))
+    }
+
+    pub fn sender(&self, id: SenderId) -> &Send<'_> {
+        &self.senders[id.0]
+    }
+
+    pub fn receiver(&self, id: ReceiverId) -> &Recv<'_> {
+        &self.receivers[id.0]
+    }
+
+    /// The receiver for calls that need exclusive access, such as [`Recv::capture`].
+    pub fn receiver_mut(&mut self, id: ReceiverId) -> &mut Recv<'static> {
+        &mut self.receivers[id.0]
+    }
+
+    pub fn finder(&self, id: FinderId) -> &Find<'_> {
+        &self.finders[id.0]
+    }
+
+    /// Tears everything down, spending at most `timeout` draining receivers.
+    ///
+    /// Every object is destroyed either way; a receiver that still had frames queued when the
+    /// time ran out is reported as [`Error::Timeout`].
+    pub fn shutdown(mut self, timeout: Duration) -> Result<(), Error> {
+        self.close(timeout)
+    }
+
+    fn close(&mut self, timeout: Duration) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+        for send in &self.senders {
+            send.flush_async();
+        }
+        ndi_debug!(senders = self.senders.len(), "destroying senders");
+        self.senders.clear();
+
+        let mut drained = true;
+        for recv in &mut self.receivers {
+            drained &= drain(recv, deadline);
+        }
+        ndi_debug!(
+            receivers = self.receivers.len(),
+            drained,
+            "destroying receivers"
+        );
+        self.receivers.clear();
+        self.finders.clear();
+
+        if drained {
+            Ok(())
+        } else {
+            Err(Error::Timeout {
+                operation: "draining receivers",
+                timeout_ms: timeout.as_millis().min(u32::MAX as u128) as u32,
+            })
+        }
+    }
+}
+
+impl Drop for NdiSession {
+    fn drop(&mut self) {
+        // Errors can't be reported from drop; call `shutdown` to observe them.
+        let _ = self.close(DROP_TIMEOUT);
+    }
+}
+
+/// Discards queued frames until the receiver is empty; `false` if `deadline` came first.
+fn drain(recv: &mut Recv<'_>, deadline: Instant) -> bool {
+    while Instant::now() < deadline {
+        match recv.capture(0) {
+            Ok(FrameType::None) | Err(_) => return true,
+            Ok(_) => {}
+        }
+    }
+    false
+}
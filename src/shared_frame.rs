@@ -0,0 +1,204 @@
+use std::{
+    ffi::{CStr, CString},
+    fmt, ptr,
+    sync::Arc,
+};
+
+use crate::{
+    ndi_lib::*, FourCCVideoType, FrameFormatType, LineStrideOrSize, Send, VideoFrame, VideoFrameRef,
+};
+
+/// An immutable video frame whose pixel data is reference counted, so one captured frame can
+/// be handed to several consumers, e.g. an encoder, a preview and an analytics thread,
+/// without copying it for each.
+///
+/// Cloning is cheap and the frame can be moved between threads. Converting from a
+/// [`VideoFrame`] or [`VideoFrameRef`] copies the data once; [`Send::send_video_shared`]
+/// sends it without copying.
+#[derive(Clone)]
+pub struct SharedVideoFrame {
+    xres: i32,
+    yres: i32,
+    fourcc: FourCCVideoType,
+    frame_rate_n: i32,
+    frame_rate_d: i32,
+    picture_aspect_ratio: f32,
+    frame_format_type: FrameFormatType,
+    timecode: i64,
+    timestamp: i64,
+    line_stride_or_size: LineStrideOrSize,
+    data: Arc<[u8]>,
+    metadata: Option<Arc<CStr>>,
+}
+
+impl SharedVideoFrame {
+    pub fn xres(&self) -> i32 {
+        self.xres
+    }
+
+    pub fn yres(&self) -> i32 {
+        self.yres
+    }
+
+    pub fn fourcc(&self) -> FourCCVideoType {
+        self.fourcc
+    }
+
+    pub fn frame_rate(&self) -> (i32, i32) {
+        (self.frame_rate_n, self.frame_rate_d)
+    }
+
+    pub fn picture_aspect_ratio(&self) -> f32 {
+        self.picture_aspect_ratio
+    }
+
+    pub fn frame_format_type(&self) -> FrameFormatType {
+        self.frame_format_type
+    }
+
+    pub fn timecode(&self) -> i64 {
+        self.timecode
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Stride of the first plane, or `None` for formats whose layout is not known.
+    pub fn line_stride(&self) -> Option<i32> {
+        match self.fourcc {
+            FourCCVideoType::Max => None,
+            _ => Some(unsafe { self.line_stride_or_size.line_stride_in_bytes }),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The shared pixel data itself, for consumers that keep it beyond the frame.
+    pub fn data_arc(&self) -> Arc<[u8]> {
+        Arc::clone(&self.data)
+    }
+
+    pub fn metadata(&self) -> Option<&CStr> {
+        self.metadata.as_deref()
+    }
+
+    /// Number of clones of this frame's data alive, including this one.
+    pub fn share_count(&self) -> usize {
+        Arc::strong_count(&self.data)
+    }
+
+    /// Copies the frame into an owned, mutable [`VideoFrame`].
+    pub fn to_owned(&self) -> VideoFrame {
+        VideoFrame {
+            xres: self.xres,
+            yres: self.yres,
+            fourcc: self.fourcc,
+            frame_rate_n: self.frame_rate_n,
+            frame_rate_d: self.frame_rate_d,
+            picture_aspect_ratio: self.picture_aspect_ratio,
+            frame_format_type: self.frame_format_type,
+            timecode: self.timecode,
+            data: self.data.to_vec(),
+            line_stride_or_size: self.line_stride_or_size,
+            metadata: self.metadata.as_deref().map(CString::from),
+            timestamp: self.timestamp,
+        }
+    }
+
+    fn to_raw(&self) -> NDIlib_video_frame_v2_t {
+        NDIlib_video_frame_v2_t {
+            xres: self.xres,
+            yres: self.yres,
+            FourCC: self.fourcc.into(),
+            frame_rate_N: self.frame_rate_n,
+            frame_rate_D: self.frame_rate_d,
+            picture_aspect_ratio: self.picture_aspect_ratio,
+            frame_format_type: self.frame_format_type.into(),
+            timecode: self.timecode,
+            p_data: self.data.as_ptr() as *mut u8,
+            __bindgen_anon_1: self.line_stride_or_size.into(),
+            p_metadata: self.metadata.as_ref().map_or(ptr::null(), |m| m.as_ptr()),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+impl fmt::Debug for SharedVideoFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedVideoFrame")
+            .field("xres", &self.xres)
+            .field("yres", &self.yres)
+            .field("fourcc", &self.fourcc)
+            .field("frame_rate_n", &self.frame_rate_n)
+            .field("frame_rate_d", &self.frame_rate_d)
+            .field("picture_aspect_ratio", &self.picture_aspect_ratio)
+            .field("frame_format_type", &self.frame_format_type)
+            .field("timecode", &self.timecode)
+            .field("data (bytes)", &self.data.len())
+            .field("line_stride_or_size", &self.line_stride_or_size)
+            .field("metadata", &self.metadata)
+            .field("timestamp", &self.timestamp)
+            .field("share_count", &self.share_count())
+            .finish()
+    }
+}
+
+impl From<VideoFrame> for SharedVideoFrame {
+    fn from(mut frame: VideoFrame) -> Self {
+        SharedVideoFrame {
+            xres: frame.xres,
+            yres: frame.yres,
+            fourcc: frame.fourcc,
+            frame_rate_n: frame.frame_rate_n,
+            frame_rate_d: frame.frame_rate_d,
+            picture_aspect_ratio: frame.picture_aspect_ratio,
+            frame_format_type: frame.frame_format_type,
+            timecode: frame.timecode,
+            timestamp: frame.timestamp,
+            line_stride_or_size: frame.line_stride_or_size,
+            data: Arc::from(std::mem::take(&mut frame.data)),
+            metadata: frame.metadata.take().map(Arc::from),
+        }
+    }
+}
+
+impl From<&VideoFrameRef<'_>> for SharedVideoFrame {
+    /// Copies the SDK's buffer straight into shared storage, with no intermediate
+    /// [`VideoFrame`].
+    fn from(frame: &VideoFrameRef<'_>) -> Self {
+        let (frame_rate_n, frame_rate_d) = frame.frame_rate();
+        let data: Arc<[u8]> = Arc::from(frame.data());
+        let line_stride_or_size = match frame.line_stride() {
+            Some(line_stride_in_bytes) => LineStrideOrSize {
+                line_stride_in_bytes,
+            },
+            None => LineStrideOrSize {
+                data_size_in_bytes: data.len() as i32,
+            },
+        };
+        SharedVideoFrame {
+            xres: frame.xres(),
+            yres: frame.yres(),
+            fourcc: frame.fourcc(),
+            frame_rate_n,
+            frame_rate_d,
+            picture_aspect_ratio: frame.picture_aspect_ratio(),
+            frame_format_type: frame.frame_format_type(),
+            timecode: frame.timecode(),
+            timestamp: frame.timestamp(),
+            line_stride_or_size,
+            data,
+            metadata: frame.metadata().map(Arc::from),
+        }
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Sends a shared frame synchronously, reading its data in place.
+    pub fn send_video_shared(&self, video_frame: &SharedVideoFrame) {
+        self.send_video_raw(&video_frame.to_raw());
+    }
+}
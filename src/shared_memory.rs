@@ -0,0 +1,169 @@
+use std::slice;
+
+use crate::{AsyncVideoPermit, BorrowedVideoFrame, Error, FourCCVideoType, Send};
+
+impl FourCCVideoType {
+    /// Alignment the SDK expects of a buffer in this format, for both its start address and
+    /// its line stride: the size of the unit it reads the pixels in.
+    pub fn buffer_alignment(self) -> usize {
+        match self {
+            FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX
+            | FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX => 4,
+            FourCCVideoType::P216 | FourCCVideoType::PA16 => 2,
+            FourCCVideoType::UYVY
+            | FourCCVideoType::UYVA
+            | FourCCVideoType::YV12
+            | FourCCVideoType::I420
+            | FourCCVideoType::NV12
+            | FourCCVideoType::Max => 1,
+        }
+    }
+}
+
+impl<'buf> BorrowedVideoFrame<'buf> {
+    /// Wraps memory the process doesn't allocate itself, such as a memory-mapped file
+    /// (e.g. a `memmap2::Mmap`, which derefs to `&[u8]`) or a capture card's DMA buffer,
+    /// checking its size and its alignment for `fourcc`.
+    ///
+    /// The frame borrows `data`, so the mapping can't be unmapped while the frame or an
+    /// [`AsyncVideoPermit`] for it is alive. The borrow can't stop another process writing
+    /// into shared memory, though: the producer must leave the region alone until the frame
+    /// has been sent, i.e. until the permit is dropped or replaced.
+    pub fn try_from_shared(
+        data: &'buf [u8],
+        xres: i32,
+        yres: i32,
+        fourcc: FourCCVideoType,
+        line_stride: i32,
+    ) -> Result<Self, Error> {
+        let required = fourcc.buffer_alignment();
+        let aligned = |n: usize| n.is_multiple_of(required);
+        if !aligned(data.as_ptr() as usize) || !aligned(line_stride.max(0) as usize) {
+            return Err(Error::MisalignedBuffer { fourcc, required });
+        }
+        Self::try_from_uncompressed(data, xres, yres, fourcc, line_stride)
+    }
+
+    /// As [`BorrowedVideoFrame::try_from_shared`], for memory only known by address, such as
+    /// an IPC segment mapped by a C library.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null and valid for reads of `len` bytes for all of `'buf`, and the
+    /// memory must not be freed or unmapped during `'buf`.
+    pub unsafe fn from_raw_parts(
+        ptr: *const u8,
+        len: usize,
+        xres: i32,
+        yres: i32,
+        fourcc: FourCCVideoType,
+        line_stride: i32,
+    ) -> Result<Self, Error> {
+        if ptr.is_null() {
+            return Err(Error::NullPointer(
+                "BorrowedVideoFrame::from_raw_parts".into(),
+            ));
+        }
+        let data = unsafe { slice::from_raw_parts(ptr, len) };
+        Self::try_from_shared(data, xres, yres, fourcc, line_stride)
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Sends a borrowed frame asynchronously, e.g. straight out of shared memory, keeping it
+    /// borrowed until the SDK is done with it.
+    ///
+    /// This is [`Send::try_send_video_async`] for [`BorrowedVideoFrame`]; the same
+    /// one-permit-per-sender rule applies.
+    pub fn try_send_video_async_borrowed<'f>(
+        &self,
+        video_frame: &BorrowedVideoFrame<'f>,
+    ) -> Result<AsyncVideoPermit<'_, 'f>, Error> {
+        let mut state = self.video_state();
+        if state.permit_held {
+            return Err(Error::AsyncVideoInFlight);
+        }
+        state.permit_held = true;
+        self.call_send_video(&mut state, &video_frame.to_raw(), true);
+        Self::release_in_flight(&mut state);
+        Ok(AsyncVideoPermit {
+            send: self,
+            frame: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<'s> AsyncVideoPermit<'s, '_> {
+    /// As [`AsyncVideoPermit::send_next`], for a borrowed frame.
+    pub fn send_next_borrowed<'g>(
+        self,
+        frame: &BorrowedVideoFrame<'g>,
+    ) -> AsyncVideoPermit<'s, 'g> {
+        let send = self.send;
+        // Hand the permit over without the flush its drop would do.
+        std::mem::forget(self);
+        send.send_video_async_raw(&frame.to_raw());
+        AsyncVideoPermit {
+            send,
+            frame: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A zeroed buffer of `len` bytes whose start is 4-byte aligned.
+    fn aligned(storage: &mut Vec<u8>, len: usize) -> &[u8] {
+        storage.resize(len + 4, 0);
+        let offset = storage.as_ptr().align_offset(4);
+        &storage[offset..offset + len]
+    }
+
+    #[test]
+    fn aligned_shared_memory_is_accepted() {
+        let mut storage = Vec::new();
+        let data = aligned(&mut storage, 16);
+        let frame =
+            BorrowedVideoFrame::try_from_shared(data, 2, 2, FourCCVideoType::BGRA, 8).unwrap();
+        assert_eq!(frame.data().len(), 16);
+    }
+
+    #[test]
+    fn misaligned_start_is_rejected() {
+        let mut storage = Vec::new();
+        let data = &aligned(&mut storage, 17)[1..];
+        let result = BorrowedVideoFrame::try_from_shared(data, 2, 2, FourCCVideoType::BGRA, 8);
+        assert!(matches!(
+            result,
+            Err(Error::MisalignedBuffer {
+                fourcc: FourCCVideoType::BGRA,
+                required: 4
+            })
+        ));
+        // Formats read a byte at a time accept any start.
+        assert!(BorrowedVideoFrame::try_from_shared(data, 2, 2, FourCCVideoType::UYVY, 4).is_ok());
+    }
+
+    #[test]
+    fn stride_off_the_pixel_size_is_rejected() {
+        let mut storage = Vec::new();
+        let data = aligned(&mut storage, 64);
+        let result = BorrowedVideoFrame::try_from_shared(data, 2, 2, FourCCVideoType::BGRA, 10);
+        assert!(matches!(
+            result,
+            Err(Error::MisalignedBuffer {
+                fourcc: FourCCVideoType::BGRA,
+                required: 4
+            })
+        ));
+        let result = BorrowedVideoFrame::try_from_shared(data, 2, 2, FourCCVideoType::P216, 5);
+        assert!(matches!(
+            result,
+            Err(Error::MisalignedBuffer { required: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::{Mutex, PoisonError};
+
+use crate::{Error, NDI};
+
+/// The runtime shared by every [`SharedNdi`], and how many there are.
+static SHARED: Mutex<(usize, Option<NDI>)> = Mutex::new((0, None));
+
+/// A counted reference to one process-wide [`NDI`], for handles that can't borrow one.
+///
+/// The runtime is initialized when the first reference is taken and destroyed when the last
+/// is dropped, so handles created and freed independently never tear it down under each
+/// other.
+pub(crate) struct SharedNdi(());
+
+impl SharedNdi {
+    pub(crate) fn acquire() -> Result<Self, Error> {
+        let mut shared = SHARED.lock().unwrap_or_else(PoisonError::into_inner);
+        if shared.1.is_none() {
+            shared.1 = Some(NDI::new()?);
+        }
+        shared.0 += 1;
+        Ok(SharedNdi(()))
+    }
+}
+
+impl Drop for SharedNdi {
+    fn drop(&mut self) {
+        let mut shared = SHARED.lock().unwrap_or_else(PoisonError::into_inner);
+        shared.0 -= 1;
+        if shared.0 == 0 {
+            shared.1 = None;
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Error, FieldMode, Find, Finder, FourCCVideoType, Receiver, Recv, RecvBandwidth,
+    RecvColorFormat, RetryPolicy, Source, VideoFrame, VideoFrameRef, NDI,
+};
+
+/// Output encoding for [`snapshot`] and the frame `encode` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    /// Baseline JPEG; `quality` runs from 1 to 100 and the alpha channel is dropped. Needs
+    /// the `thumbnail` feature.
+    #[cfg(feature = "thumbnail")]
+    Jpeg {
+        quality: u8,
+    },
+    /// Tightly packed 8-bit RGBA pixels, row by row.
+    Rgba,
+}
+
+impl Receiver {
+    /// Settings for grabbing stills: the source's low-bandwidth preview stream, delivered as
+    /// progressive RGBX/RGBA so frames can be encoded without conversion.
+    pub fn snapshot_preset(source: Source) -> Self {
+        Receiver::new(
+            source,
+            RecvColorFormat::RGBX_RGBA,
+            RecvBandwidth::Lowest,
+            FieldMode::Progressive,
+            None,
+        )
+    }
+}
+
+/// Captures one video frame from a source and returns it encoded as `format`.
+///
+/// `source` is a source name (or part of one) to discover, the IP address of a machine to take
+/// the first source from, or an `ip:port` to connect to directly. `timeout` covers discovery
+/// and capture together.
+pub fn snapshot(source: &str, timeout: Duration, format: ImageFormat) -> Result<Vec<u8>, Error> {
+    let deadline = Instant::now() + timeout;
+    let ndi = NDI::new()?;
+    let source = resolve_source(&ndi, source, timeout)?;
+    let receiver = Receiver::new(
+        source,
+        RecvColorFormat::RGBX_RGBA,
+        RecvBandwidth::Highest,
+        FieldMode::Progressive,
+        None,
+    );
+    let recv = Recv::new(&ndi, receiver)?;
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    RetryPolicy::default().run(remaining, "a video frame", |timeout_ms| {
+        match recv.capture_video_ref(timeout_ms)? {
+            Some(frame) => frame.encode(format).map(Some),
+            None => Ok(None),
+        }
+    })
+}
+
+fn resolve_source(ndi: &NDI, query: &str, timeout: Duration) -> Result<Source, Error> {
+    if let Ok(address) = query.parse::<SocketAddr>() {
+        return Ok(Source {
+            name: query.to_string(),
+            url_address: Some(address.to_string()),
+            ip_address: None,
+        });
+    }
+    let host = query.parse::<IpAddr>().is_ok().then_some(query);
+    Find::new(ndi, Finder::new(true, None, host))?.find_source(query, Instant::now() + timeout)
+}
+
+impl VideoFrame {
+    /// Encodes an RGBA, RGBX, BGRA or BGRX frame as an image.
+    pub fn encode(&self, format: ImageFormat) -> Result<Vec<u8>, Error> {
+        let stride = unsafe { self.line_stride_or_size.line_stride_in_bytes };
+        encode(
+            self.xres,
+            self.yres,
+            self.fourcc,
+            stride,
+            &self.data,
+            format,
+        )
+    }
+
+    /// Encodes an RGBA, RGBX, BGRA or BGRX frame as a JPEG of the given `quality` (1-100).
+    #[cfg(feature = "thumbnail")]
+    pub fn encode_jpeg(&self, quality: u8) -> Result<Vec<u8>, Error> {
+        self.encode(ImageFormat::Jpeg { quality })
+    }
+}
+
+impl VideoFrameRef<'_> {
+    /// Encodes an RGBA, RGBX, BGRA or BGRX frame as an image without copying it first.
+    pub fn encode(&self, format: ImageFormat) -> Result<Vec<u8>, Error> {
+        let stride = self
+            .line_stride()
+            .unwrap_or_else(|| self.fourcc().line_stride(self.xres()));
+        encode(
+            self.xres(),
+            self.yres(),
+            self.fourcc(),
+            stride,
+            self.data(),
+            format,
+        )
+    }
+
+    /// Encodes an RGBA, RGBX, BGRA or BGRX frame as a JPEG of the given `quality` (1-100).
+    #[cfg(feature = "thumbnail")]
+    pub fn encode_jpeg(&self, quality: u8) -> Result<Vec<u8>, Error> {
+        self.encode(ImageFormat::Jpeg { quality })
+    }
+}
+
+fn encode(
+    xres: i32,
+    yres: i32,
+    fourcc: FourCCVideoType,
+    line_stride: i32,
+    data: &[u8],
+    format: ImageFormat,
+) -> Result<Vec<u8>, Error> {
+    let (swap, opaque) = match fourcc {
+        FourCCVideoType::RGBA => (false, false),
+        FourCCVideoType::RGBX => (false, true),
+        FourCCVideoType::BGRA => (true, false),
+        FourCCVideoType::BGRX => (true, true),
+        fourcc => {
+            return Err(Error::UnsupportedFormat {
+                fourcc,
+                operation: "encode",
+            })
+        }
+    };
+    let required = fourcc.check_layout(xres, yres, line_stride)?;
+    if data.len() < required {
+        return Err(Error::InvalidDataSize {
+            fourcc,
+            xres,
+            yres,
+            line_stride,
+            expected: required,
+            actual: data.len(),
+        });
+    }
+
+    let (width, height, stride) = (xres as usize, yres as usize, line_stride as usize);
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in data.chunks(stride).take(height) {
+        for pixel in row[..width * 4].chunks_exact(4) {
+            let (r, b) = if swap {
+                (pixel[2], pixel[0])
+            } else {
+                (pixel[0], pixel[2])
+            };
+            rgba.extend_from_slice(&[r, pixel[1], b, if opaque { 255 } else { pixel[3] }]);
+        }
+    }
+
+    match format {
+        ImageFormat::Rgba => Ok(rgba),
+        ImageFormat::Png => {
+            let mut png_data = Vec::new();
+            let mut encoder = png::Encoder::new(&mut png_data, xres as u32, yres as u32);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .write_header()
+                .and_then(|mut writer| writer.write_image_data(&rgba))
+                .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+            Ok(png_data)
+        }
+        #[cfg(feature = "thumbnail")]
+        ImageFormat::Jpeg { quality } => {
+            let (width, height) = match (u16::try_from(xres), u16::try_from(yres)) {
+                (Ok(width), Ok(height)) => (width, height),
+                _ => {
+                    return Err(Error::ImageEncoding(format!(
+                        "{}x{} exceeds the JPEG size limit of 65535x65535",
+                        xres, yres
+                    )))
+                }
+            };
+            let mut jpeg_data = Vec::new();
+            jpeg_encoder::Encoder::new(&mut jpeg_data, quality.clamp(1, 100))
+                .encode(&rgba, width, height, jpeg_encoder::ColorType::Rgba)
+                .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+            Ok(jpeg_data)
+        }
+    }
+}
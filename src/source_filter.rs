@@ -0,0 +1,255 @@
+use std::time::{Duration, Instant};
+
+use crate::{Error, Find, Finder, Source};
+
+/// The include and exclude patterns of a [`Finder`], kept by the [`Find`] created from it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NameFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl NameFilter {
+    pub(crate) fn new(finder: &Finder) -> Self {
+        NameFilter {
+            include: finder.include.clone(),
+            exclude: finder.exclude.clone(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub(crate) fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| glob_match(p, name)))
+            && !self.exclude.iter().any(|p| glob_match(p, name))
+    }
+}
+
+impl Finder {
+    /// Only reports sources whose full name, e.g. `"STUDIO-PC (Camera 1)"`, matches one of the
+    /// include patterns given. Patterns are case-insensitive globs: `*` matches any run of
+    /// characters and `?` any single one, so `"*(Camera*"` finds every camera channel.
+    pub fn include_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Never reports sources whose full name matches `pattern`, even if an include pattern
+    /// matches too.
+    pub fn exclude_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Whether `source` passes the include and exclude patterns.
+    pub fn matches(&self, source: &Source) -> bool {
+        NameFilter::new(self).matches(&source.name)
+    }
+}
+
+impl<'a> Find<'a> {
+    /// Waits up to `timeout` for sources whose name matches the glob `pattern`, as well as
+    /// the finder's own patterns, returning them as soon as there is at least one, or an
+    /// empty list once the time is up.
+    pub fn sources_matching(&self, pattern: &str, timeout: Duration) -> Result<Vec<Source>, Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let sources = self
+                .get_sources(0)?
+                .into_iter()
+                .filter(|s| glob_match(pattern, &s.name))
+                .collect::<Vec<_>>();
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !sources.is_empty() || remaining.is_zero() {
+                return Ok(sources);
+            }
+            self.wait_for_sources(remaining.as_millis().min(u32::MAX as u128) as u32);
+        }
+    }
+
+    /// Waits until `deadline` for the source `name_or_host` names, failing with
+    /// [`Error::SourceNotFound`], which lists the sources that were seen, if it doesn't appear.
+    ///
+    /// The query is compared, ignoring case, with each source's full name, then its machine
+    /// name and the host of its address, and then searched for within its name; the closest
+    /// match wins. So `"STUDIO-PC (Camera 1)"`, `"studio-pc"`, `"192.168.1.20"` and
+    /// `"Camera 1"` all find the same source, the last two as long as nothing else matches
+    /// them as well.
+    pub fn find_source(&self, name_or_host: &str, deadline: Instant) -> Result<Source, Error> {
+        let started = Instant::now();
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            ndi_trace!(
+                name = name_or_host,
+                attempt = attempts,
+                "looking for source"
+            );
+            let sources = self.get_sources(0)?;
+            if let Some(source) = sources
+                .iter()
+                .filter_map(|s| match_rank(s, name_or_host).map(|rank| (rank, s)))
+                .min_by_key(|&(rank, _)| rank)
+                .map(|(_, s)| s.clone())
+            {
+                return Ok(source);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::SourceNotFound {
+                    name: name_or_host.to_string(),
+                    timeout: deadline.saturating_duration_since(started),
+                    attempts,
+                    seen: sources.into_iter().map(|s| s.name).collect(),
+                });
+            }
+            self.wait_for_sources(remaining.as_millis().min(u32::MAX as u128) as u32);
+        }
+    }
+}
+
+/// How closely `query` names `source`, lower being closer, or `None` if it doesn't.
+fn match_rank(source: &Source, query: &str) -> Option<u8> {
+    let host = source
+        .url_address
+        .as_deref()
+        .or(source.ip_address.as_deref())
+        .map(|address| {
+            address
+                .rsplit_once(':')
+                .filter(|(host, _)| !host.is_empty())
+                .map_or(address, |(host, _)| host)
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+        });
+    if source.name.eq_ignore_ascii_case(query) {
+        Some(0)
+    } else if source.machine_name().eq_ignore_ascii_case(query)
+        || host.is_some_and(|host| host.eq_ignore_ascii_case(query))
+    {
+        Some(1)
+    } else if source.name.to_lowercase().contains(&query.to_lowercase()) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Matches `text` against a glob of `*` and `?` wildcards, ignoring case.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    let text = text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was and the text position it is currently matched up to.
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character and try again.
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    t = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str) -> Source {
+        Source {
+            name: name.to_string(),
+            url_address: None,
+            ip_address: None,
+        }
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*(Camera*", "STUDIO-PC (Camera 1)"));
+        assert!(glob_match("studio-pc (camera ?)", "STUDIO-PC (Camera 2)"));
+        assert!(!glob_match("studio-pc (camera ?)", "STUDIO-PC (Camera 10)"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+        assert!(!glob_match("Camera", "STUDIO-PC (Camera 1)"));
+    }
+
+    #[test]
+    fn glob_backtracks_past_early_matches() {
+        assert!(glob_match("*ab", "aab"));
+        assert!(glob_match("*a*ab", "xaxaab"));
+        assert!(!glob_match("*a?", "ba"));
+    }
+
+    #[test]
+    fn glob_ignores_case_beyond_ascii() {
+        assert!(glob_match("ÉCRAN*", "écran 1"));
+    }
+
+    #[test]
+    fn excludes_win_over_includes() {
+        let finder = Finder::default()
+            .include_pattern("*(Camera*")
+            .exclude_pattern("*Camera 2*");
+        assert!(finder.matches(&source("STUDIO-PC (Camera 1)")));
+        assert!(!finder.matches(&source("STUDIO-PC (Camera 2)")));
+        assert!(!finder.matches(&source("STUDIO-PC (Playout)")));
+    }
+
+    #[test]
+    fn no_patterns_match_everything() {
+        let finder = Finder::default();
+        assert!(NameFilter::new(&finder).is_empty());
+        assert!(finder.matches(&source("STUDIO-PC (Playout)")));
+    }
+
+    #[test]
+    fn exact_names_rank_before_hosts_and_substrings() {
+        let camera = Source {
+            url_address: Some("192.168.1.20:5961".to_string()),
+            ..source("STUDIO-PC (Camera 1)")
+        };
+        assert_eq!(match_rank(&camera, "studio-pc (camera 1)"), Some(0));
+        assert_eq!(match_rank(&camera, "STUDIO-PC"), Some(1));
+        assert_eq!(match_rank(&camera, "192.168.1.20"), Some(1));
+        assert_eq!(match_rank(&camera, "camera 1"), Some(2));
+        assert_eq!(match_rank(&camera, "Camera 2"), None);
+    }
+
+    #[test]
+    fn hosts_are_read_from_either_address() {
+        let v6 = Source {
+            url_address: Some("[fe80::1]:5961".to_string()),
+            ..source("STUDIO-PC (Camera 1)")
+        };
+        assert_eq!(match_rank(&v6, "fe80::1"), Some(1));
+        let legacy = Source {
+            ip_address: Some("10.0.0.5".to_string()),
+            ..source("STUDIO-PC (Camera 1)")
+        };
+        assert_eq!(match_rank(&legacy, "10.0.0.5"), Some(1));
+    }
+}
@@ -0,0 +1,302 @@
+use std::time::Instant;
+#[cfg(feature = "persistent_stats")]
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "persistent_stats")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "persistent_stats")]
+use crate::Error;
+
+/// Point-in-time counters reported by the SDK for a receiver.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionStats {
+    pub video_frames_received: i64,
+    pub audio_frames_received: i64,
+    pub metadata_frames_received: i64,
+    pub video_frames_dropped: i64,
+    pub audio_frames_dropped: i64,
+    pub metadata_frames_dropped: i64,
+    pub video_frames_queued: i32,
+    pub audio_frames_queued: i32,
+    pub metadata_frames_queued: i32,
+    pub connections: i32,
+}
+
+impl ConnectionStats {
+    /// Frame counts since `base` was read from the same receiver. Queue depths and the
+    /// connection count are the current ones from `self`.
+    pub fn since(&self, base: &ConnectionStats) -> ConnectionStats {
+        ConnectionStats {
+            video_frames_received: self.video_frames_received - base.video_frames_received,
+            audio_frames_received: self.audio_frames_received - base.audio_frames_received,
+            metadata_frames_received: self.metadata_frames_received - base.metadata_frames_received,
+            video_frames_dropped: self.video_frames_dropped - base.video_frames_dropped,
+            audio_frames_dropped: self.audio_frames_dropped - base.audio_frames_dropped,
+            metadata_frames_dropped: self.metadata_frames_dropped - base.metadata_frames_dropped,
+            ..*self
+        }
+    }
+}
+
+/// A receiver's counters scoped to its current connection epoch: the time since it last
+/// went from having no connection to its source to having one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochStats {
+    /// How many times the receiver has connected, so 1 during its first connection and 0
+    /// before it has ever connected.
+    pub epoch: u64,
+    /// When the epoch started, or when the receiver was created for epoch 0.
+    pub started: Instant,
+    pub stats: ConnectionStats,
+}
+
+/// Baselines that scope a receiver's cumulative SDK counters to the time since
+/// [`Recv::reset_stats`](crate::Recv::reset_stats) and since the last reconnect.
+#[derive(Debug)]
+pub(crate) struct StatsBaselines {
+    reset: ConnectionStats,
+    epoch_base: ConnectionStats,
+    epoch: u64,
+    epoch_started: Instant,
+    connected: bool,
+}
+
+impl StatsBaselines {
+    pub(crate) fn new() -> Self {
+        StatsBaselines {
+            reset: ConnectionStats::default(),
+            epoch_base: ConnectionStats::default(),
+            epoch: 0,
+            epoch_started: Instant::now(),
+            connected: false,
+        }
+    }
+
+    /// Starts a new epoch if the receiver has connected since it was last observed.
+    pub(crate) fn observe(&mut self, current: &ConnectionStats) {
+        let connected = current.connections > 0;
+        if connected && !self.connected {
+            self.epoch += 1;
+            self.epoch_base = *current;
+            self.epoch_started = Instant::now();
+            ndi_debug!(epoch = self.epoch, "receiver connection epoch started");
+        }
+        self.connected = connected;
+    }
+
+    pub(crate) fn reset(&mut self, current: &ConnectionStats) {
+        self.reset = *current;
+    }
+
+    pub(crate) fn since_reset(&self, current: &ConnectionStats) -> ConnectionStats {
+        current.since(&self.reset)
+    }
+
+    pub(crate) fn epoch(&self, current: &ConnectionStats) -> EpochStats {
+        EpochStats {
+            epoch: self.epoch,
+            started: self.epoch_started,
+            stats: current.since(&self.epoch_base),
+        }
+    }
+
+    /// Carries the counts of an instance about to be replaced over to the new one, whose
+    /// counters start from zero.
+    pub(crate) fn absorb(&mut self, retiring: &ConnectionStats) {
+        self.reset = self.reset.since(retiring);
+        self.epoch_base = self.epoch_base.since(retiring);
+    }
+}
+
+/// Lifetime statistics for one source, accumulated across process restarts.
+#[cfg(feature = "persistent_stats")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersistentStats {
+    pub source: String,
+    pub video_frames: u64,
+    pub audio_frames: u64,
+    pub metadata_frames: u64,
+    pub video_frames_dropped: u64,
+    pub audio_frames_dropped: u64,
+    pub metadata_frames_dropped: u64,
+    pub uptime_secs: f64,
+    pub reconnects: u64,
+    pub sessions: u64,
+}
+
+#[cfg(feature = "persistent_stats")]
+impl PersistentStats {
+    /// Loads stats from `path`, starting from zero if the file does not exist yet.
+    pub fn load_or_default(path: &Path, source: &str) -> Result<Self, Error> {
+        match fs::read(path) {
+            Ok(bytes) => {
+                serde_json::from_slice(&bytes).map_err(|e| Error::Serialization(e.to_string()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(PersistentStats {
+                source: source.to_string(),
+                ..Default::default()
+            }),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    /// Writes the stats to `path`, replacing the previous file atomically.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let json =
+            serde_json::to_vec_pretty(self).map_err(|e| Error::Serialization(e.to_string()))?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Tracks one receiver session on top of the stats loaded at start-up.
+#[cfg(feature = "persistent_stats")]
+#[derive(Debug)]
+pub(crate) struct StatsPersistence {
+    path: PathBuf,
+    base: PersistentStats,
+    started: Instant,
+    reconnects: u64,
+    connected: bool,
+    ever_connected: bool,
+}
+
+#[cfg(feature = "persistent_stats")]
+impl StatsPersistence {
+    /// Fails with [`Error::StatsSourceMismatch`] if the file holds another source's stats,
+    /// rather than mixing the two.
+    pub(crate) fn open(path: PathBuf, source: &str) -> Result<Self, Error> {
+        let mut base = PersistentStats::load_or_default(&path, source)?;
+        if base.source != source {
+            return Err(Error::StatsSourceMismatch {
+                path,
+                expected: source.to_string(),
+                found: base.source,
+            });
+        }
+        base.sessions += 1;
+        Ok(StatsPersistence {
+            path,
+            base,
+            started: Instant::now(),
+            reconnects: 0,
+            connected: false,
+            ever_connected: false,
+        })
+    }
+
+    pub(crate) fn observe_connections(&mut self, connections: i32) {
+        let connected = connections > 0;
+        if connected && !self.connected && self.ever_connected {
+            self.reconnects += 1;
+        }
+        self.ever_connected |= connected;
+        self.connected = connected;
+    }
+
+    pub(crate) fn snapshot(&self, session: &ConnectionStats) -> PersistentStats {
+        let base = &self.base;
+        PersistentStats {
+            source: base.source.clone(),
+            video_frames: base.video_frames + session.video_frames_received.max(0) as u64,
+            audio_frames: base.audio_frames + session.audio_frames_received.max(0) as u64,
+            metadata_frames: base.metadata_frames + session.metadata_frames_received.max(0) as u64,
+            video_frames_dropped: base.video_frames_dropped
+                + session.video_frames_dropped.max(0) as u64,
+            audio_frames_dropped: base.audio_frames_dropped
+                + session.audio_frames_dropped.max(0) as u64,
+            metadata_frames_dropped: base.metadata_frames_dropped
+                + session.metadata_frames_dropped.max(0) as u64,
+            uptime_secs: base.uptime_secs + self.started.elapsed().as_secs_f64(),
+            reconnects: base.reconnects + self.reconnects,
+            sessions: base.sessions,
+        }
+    }
+
+    /// Folds the frame counts of a session whose instance is about to be replaced into the
+    /// base, since the new instance's counters start from zero.
+    pub(crate) fn absorb(&mut self, session: &ConnectionStats) {
+        let totals = self.snapshot(session);
+        let base = &mut self.base;
+        base.video_frames = totals.video_frames;
+        base.audio_frames = totals.audio_frames;
+        base.metadata_frames = totals.metadata_frames;
+        base.video_frames_dropped = totals.video_frames_dropped;
+        base.audio_frames_dropped = totals.audio_frames_dropped;
+        base.metadata_frames_dropped = totals.metadata_frames_dropped;
+    }
+
+    pub(crate) fn save(&self, session: &ConnectionStats) -> Result<(), Error> {
+        self.snapshot(session).save(&self.path)
+    }
+}
+
+#[cfg(all(test, feature = "persistent_stats"))]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("grafton-ndi-{}-{name}.json", std::process::id()))
+    }
+
+    #[test]
+    fn totals_accumulate_across_sessions() {
+        let path = temp_path("accumulate");
+        let session = ConnectionStats {
+            video_frames_received: 10,
+            video_frames_dropped: 1,
+            ..Default::default()
+        };
+        StatsPersistence::open(path.clone(), "CAM (1)")
+            .unwrap()
+            .save(&session)
+            .unwrap();
+        let reopened = StatsPersistence::open(path.clone(), "CAM (1)").unwrap();
+        let totals = reopened.snapshot(&session);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(totals.video_frames, 20);
+        assert_eq!(totals.video_frames_dropped, 2);
+        assert_eq!(totals.sessions, 2);
+    }
+
+    #[test]
+    fn another_sources_file_is_refused() {
+        let path = temp_path("mismatch");
+        StatsPersistence::open(path.clone(), "CAM (1)")
+            .unwrap()
+            .save(&ConnectionStats::default())
+            .unwrap();
+        let result = StatsPersistence::open(path.clone(), "CAM (2)");
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(Error::StatsSourceMismatch { .. })));
+    }
+
+    #[test]
+    fn malformed_file_is_a_serialization_error() {
+        let path = temp_path("malformed");
+        fs::write(&path, b"not json").unwrap();
+        let result = PersistentStats::load_or_default(&path, "CAM (1)");
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(Error::Serialization(_))));
+    }
+
+    #[test]
+    fn reconnects_are_counted() {
+        let mut persistence = StatsPersistence::open(temp_path("reconnects"), "CAM (1)").unwrap();
+        for connections in [0, 1, 0, 1, 1, 0, 1] {
+            persistence.observe_connections(connections);
+        }
+        assert_eq!(
+            persistence.snapshot(&ConnectionStats::default()).reconnects,
+            2
+        );
+    }
+}
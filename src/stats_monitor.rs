@@ -0,0 +1,228 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{ConnectionStats, LentRecv, Recv};
+
+/// Snapshots buffered for a consumer that isn't keeping up; newer ones are dropped beyond this.
+const SNAPSHOT_BUFFER: usize = 64;
+
+type SnapshotCallback = Box<dyn FnMut(&StatsSnapshot) + std::marker::Send>;
+
+/// Receiver statistics sampled by a [`StatsMonitor`], with rates over its rolling window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatsSnapshot {
+    /// The raw counters at the time of the sample.
+    pub stats: ConnectionStats,
+    /// Time since the monitor started.
+    pub elapsed: Duration,
+    pub video_fps: f64,
+    pub audio_fps: f64,
+    pub metadata_fps: f64,
+    pub video_drops_per_sec: f64,
+    pub audio_drops_per_sec: f64,
+    pub metadata_drops_per_sec: f64,
+    /// Change in queued video frames per second; positive when the consumer is falling behind.
+    pub video_queue_trend: f64,
+    pub audio_queue_trend: f64,
+}
+
+/// Samples a receiver's statistics on a background thread.
+///
+/// Every snapshot is delivered through [`StatsMonitor::try_recv`] / [`StatsMonitor::recv_timeout`],
+/// and to the callback given to [`StatsMonitor::with_callback`], if any. Dropping the monitor
+/// stops the thread.
+pub struct StatsMonitor<'r> {
+    recv: &'r Recv<'r>,
+    shared: Arc<MonitorShared>,
+    snapshots: Receiver<StatsSnapshot>,
+}
+
+pub(crate) struct MonitorShared {
+    stopped: Mutex<bool>,
+    wake: Condvar,
+    thread: Mutex<Option<JoinHandle<()>>>,
+    latest: Mutex<Option<StatsSnapshot>>,
+}
+
+impl MonitorShared {
+    /// Stops the sampling thread and waits for it, so the receiver can safely be destroyed.
+    pub(crate) fn stop(&self) {
+        if let Ok(mut stopped) = self.stopped.lock() {
+            *stopped = true;
+        }
+        self.wake.notify_all();
+        let thread = self.thread.lock().ok().and_then(|mut t| t.take());
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<'r> StatsMonitor<'r> {
+    /// Samples `recv` every `interval`, computing rates over the last `window`.
+    pub fn new(recv: &'r Recv<'r>, interval: Duration, window: Duration) -> Self {
+        Self::start(recv, interval, window, None)
+    }
+
+    /// Like [`StatsMonitor::new`], also calling `callback` on the sampling thread for each snapshot.
+    pub fn with_callback(
+        recv: &'r Recv<'r>,
+        interval: Duration,
+        window: Duration,
+        callback: impl FnMut(&StatsSnapshot) + std::marker::Send + 'static,
+    ) -> Self {
+        Self::start(recv, interval, window, Some(Box::new(callback)))
+    }
+
+    fn start(
+        recv: &'r Recv<'r>,
+        interval: Duration,
+        window: Duration,
+        callback: Option<SnapshotCallback>,
+    ) -> Self {
+        let shared = Arc::new(MonitorShared {
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+            thread: Mutex::new(None),
+            latest: Mutex::new(None),
+        });
+        let (sender, snapshots) = mpsc::sync_channel(SNAPSHOT_BUFFER);
+        let lent = recv.lend();
+        let thread_shared = Arc::clone(&shared);
+        let thread = thread::spawn(move || {
+            sample(
+                &lent,
+                interval.max(Duration::from_millis(1)),
+                window,
+                &thread_shared,
+                sender,
+                callback,
+            )
+        });
+        if let Ok(mut slot) = shared.thread.lock() {
+            *slot = Some(thread);
+        }
+        if let Ok(mut monitors) = recv.stats_monitors.lock() {
+            monitors.push(Arc::clone(&shared));
+        }
+        StatsMonitor {
+            recv,
+            shared,
+            snapshots,
+        }
+    }
+
+    /// The most recent snapshot, regardless of what has been read from the channel.
+    pub fn latest(&self) -> Option<StatsSnapshot> {
+        self.shared.latest.lock().ok().and_then(|latest| *latest)
+    }
+
+    /// The next buffered snapshot, if one is waiting.
+    pub fn try_recv(&self) -> Option<StatsSnapshot> {
+        self.snapshots.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next snapshot.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<StatsSnapshot> {
+        self.snapshots.recv_timeout(timeout).ok()
+    }
+}
+
+impl Drop for StatsMonitor<'_> {
+    fn drop(&mut self) {
+        self.shared.stop();
+        if let Ok(mut monitors) = self.recv.stats_monitors.lock() {
+            monitors.retain(|m| !Arc::ptr_eq(m, &self.shared));
+        }
+    }
+}
+
+fn sample(
+    recv: &LentRecv,
+    interval: Duration,
+    window: Duration,
+    shared: &MonitorShared,
+    sender: SyncSender<StatsSnapshot>,
+    mut callback: Option<SnapshotCallback>,
+) {
+    let start = Instant::now();
+    let mut history: VecDeque<(Instant, ConnectionStats)> = VecDeque::new();
+    let mut next = start;
+    loop {
+        {
+            let Ok(mut stopped) = shared.stopped.lock() else {
+                return;
+            };
+            while !*stopped {
+                let now = Instant::now();
+                if now >= next {
+                    break;
+                }
+                stopped = match shared.wake.wait_timeout(stopped, next - now) {
+                    Ok((guard, _)) => guard,
+                    Err(_) => return,
+                };
+            }
+            if *stopped {
+                return;
+            }
+        }
+        next += interval;
+
+        let now = Instant::now();
+        let stats = recv.connection_stats();
+        history.push_back((now, stats));
+        while history.len() > 2 && now - history[1].0 >= window {
+            history.pop_front();
+        }
+        let snapshot = snapshot(start, &history);
+
+        if let Ok(mut latest) = shared.latest.lock() {
+            *latest = Some(snapshot);
+        }
+        if let Some(callback) = callback.as_mut() {
+            callback(&snapshot);
+        }
+        // A full buffer means nobody is reading the channel; the snapshot stays in `latest`.
+        let _ = sender.try_send(snapshot);
+    }
+}
+
+fn snapshot(start: Instant, history: &VecDeque<(Instant, ConnectionStats)>) -> StatsSnapshot {
+    let (now, stats) = history[history.len() - 1];
+    let (then, old) = history[0];
+    let secs = (now - then).as_secs_f64();
+    let rate = |new: i64, old: i64| {
+        if secs > 0.0 {
+            (new - old).max(0) as f64 / secs
+        } else {
+            0.0
+        }
+    };
+    let trend = |new: i32, old: i32| {
+        if secs > 0.0 {
+            (new - old) as f64 / secs
+        } else {
+            0.0
+        }
+    };
+    StatsSnapshot {
+        stats,
+        elapsed: now - start,
+        video_fps: rate(stats.video_frames_received, old.video_frames_received),
+        audio_fps: rate(stats.audio_frames_received, old.audio_frames_received),
+        metadata_fps: rate(stats.metadata_frames_received, old.metadata_frames_received),
+        video_drops_per_sec: rate(stats.video_frames_dropped, old.video_frames_dropped),
+        audio_drops_per_sec: rate(stats.audio_frames_dropped, old.audio_frames_dropped),
+        metadata_drops_per_sec: rate(stats.metadata_frames_dropped, old.metadata_frames_dropped),
+        video_queue_trend: trend(stats.video_frames_queued, old.video_frames_queued),
+        audio_queue_trend: trend(stats.audio_frames_queued, old.audio_frames_queued),
+    }
+}
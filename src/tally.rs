@@ -0,0 +1,154 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{ndi_lib::*, Send, Tally};
+
+/// Changes buffered for a consumer that isn't keeping up; newer ones are dropped beyond this.
+const CHANGE_BUFFER: usize = 16;
+/// Longest the watcher thread waits in the SDK before checking whether it should stop.
+const POLL_TIMEOUT_MS: u32 = 100;
+
+type TallyCallback = Box<dyn FnMut(&Tally) + std::marker::Send>;
+
+/// Watches a sender's tally on a background thread, reporting each change.
+///
+/// Every change is delivered through [`TallyWatcher::try_recv`] / [`TallyWatcher::recv_timeout`],
+/// and to the callback given to [`Send::on_tally_change`], if any. Dropping the watcher stops
+/// the thread.
+pub struct TallyWatcher<'s> {
+    send: &'s Send<'s>,
+    shared: Arc<TallyShared>,
+    changes: Receiver<Tally>,
+}
+
+#[derive(Debug)]
+pub(crate) struct TallyShared {
+    stopped: AtomicBool,
+    thread: Mutex<Option<JoinHandle<()>>>,
+    latest: Mutex<Tally>,
+}
+
+impl TallyShared {
+    /// Stops the watcher thread and waits for it, so the sender can safely be destroyed.
+    pub(crate) fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        let thread = self.thread.lock().ok().and_then(|mut t| t.take());
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+struct Instance(NDIlib_send_instance_t);
+
+// The SDK's tally query is thread-safe, and the sender joins the watcher thread before
+// destroying the instance.
+unsafe impl std::marker::Send for Instance {}
+
+impl<'a> Send<'a> {
+    /// Starts watching for tally changes on a background thread.
+    pub fn watch_tally(&self) -> TallyWatcher<'_> {
+        TallyWatcher::start(self, None)
+    }
+
+    /// Like [`Send::watch_tally`], also calling `callback` on the watcher thread for each change.
+    pub fn on_tally_change(
+        &self,
+        callback: impl FnMut(&Tally) + std::marker::Send + 'static,
+    ) -> TallyWatcher<'_> {
+        TallyWatcher::start(self, Some(Box::new(callback)))
+    }
+}
+
+impl<'s> TallyWatcher<'s> {
+    fn start(send: &'s Send<'s>, callback: Option<TallyCallback>) -> Self {
+        let shared = Arc::new(TallyShared {
+            stopped: AtomicBool::new(false),
+            thread: Mutex::new(None),
+            latest: Mutex::new(Tally::new(false, false)),
+        });
+        let (sender, changes) = mpsc::sync_channel(CHANGE_BUFFER);
+        let instance = Instance(send.instance);
+        let thread_shared = Arc::clone(&shared);
+        let thread = thread::spawn(move || watch(instance, &thread_shared, sender, callback));
+        if let Ok(mut slot) = shared.thread.lock() {
+            *slot = Some(thread);
+        }
+        if let Ok(mut watchers) = send.tally_watchers.lock() {
+            watchers.push(Arc::clone(&shared));
+        }
+        TallyWatcher {
+            send,
+            shared,
+            changes,
+        }
+    }
+
+    /// The current tally, regardless of what has been read from the channel.
+    pub fn latest(&self) -> Tally {
+        self.shared
+            .latest
+            .lock()
+            .map(|latest| latest.clone())
+            .unwrap_or_else(|_| Tally::new(false, false))
+    }
+
+    /// The next buffered change, if one is waiting.
+    pub fn try_recv(&self) -> Option<Tally> {
+        self.changes.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next change.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Tally> {
+        self.changes.recv_timeout(timeout).ok()
+    }
+}
+
+impl Drop for TallyWatcher<'_> {
+    fn drop(&mut self) {
+        self.shared.stop();
+        if let Ok(mut watchers) = self.send.tally_watchers.lock() {
+            watchers.retain(|w| !Arc::ptr_eq(w, &self.shared));
+        }
+    }
+}
+
+fn watch(
+    instance: Instance,
+    shared: &TallyShared,
+    sender: SyncSender<Tally>,
+    mut callback: Option<TallyCallback>,
+) {
+    let mut current = Tally::new(false, false);
+    while !shared.stopped.load(Ordering::Relaxed) {
+        // Compare against the last value rather than trusting the SDK's "changed" flag, which
+        // is shared with every other caller of `get_tally` on this sender.
+        let mut raw = current.to_raw();
+        unsafe { NDIlib_send_get_tally(instance.0, &mut raw, POLL_TIMEOUT_MS) };
+        let tally = Tally::new(raw.on_program, raw.on_preview);
+        if tally == current {
+            continue;
+        }
+        ndi_debug!(
+            on_program = tally.on_program,
+            on_preview = tally.on_preview,
+            "tally changed"
+        );
+        current = tally.clone();
+        if let Ok(mut latest) = shared.latest.lock() {
+            *latest = tally.clone();
+        }
+        if let Some(callback) = callback.as_mut() {
+            callback(&tally);
+        }
+        // A full buffer means nobody is reading the channel; the change stays in `latest`.
+        let _ = sender.try_send(tally);
+    }
+}
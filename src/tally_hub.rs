@@ -0,0 +1,191 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    Error, FieldMode, KnownMetadata, Receiver, Recv, RecvBandwidth, RecvColorFormat, Source, Tally,
+    NDI,
+};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Metadata frames read from one receiver per poll, so a chatty source can't starve the rest.
+const MAX_FRAMES_PER_POLL: usize = 64;
+
+/// A source's tally changing, as noticed by a [`TallyHub`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TallyChange {
+    pub source: Source,
+    pub from: Tally,
+    pub to: Tally,
+}
+
+type ChangeListener = Box<dyn FnMut(&TallyChange)>;
+
+/// Tracks the tally of many sources at once, e.g. for a control room's multiviewer borders.
+///
+/// Each source is watched by a metadata-only receiver, which costs next to no bandwidth, and
+/// its tally is taken from the `<ndi_tally_echo/>` messages the source sends its receivers:
+/// whether anyone, anywhere, has it on program or preview. A source that loses its
+/// connection is reported as off air until it comes back and echoes its tally again.
+///
+/// Call [`TallyHub::poll`] regularly, or [`TallyHub::run`] on a dedicated thread.
+pub struct TallyHub<'a> {
+    ndi: &'a NDI,
+    monitors: Vec<TallyMonitor<'a>>,
+    listener: Option<ChangeListener>,
+}
+
+/// A metadata-only receiver and the last tally its source echoed.
+struct TallyMonitor<'a> {
+    recv: Recv<'a>,
+    tally: Tally,
+}
+
+impl<'a> TallyHub<'a> {
+    pub fn new(ndi: &'a NDI) -> Self {
+        TallyHub {
+            ndi,
+            monitors: Vec::new(),
+            listener: None,
+        }
+    }
+
+    /// Starts watching `source`; sources already watched are left as they are.
+    pub fn add_source(&mut self, source: Source) -> Result<(), Error> {
+        if self.monitor(&source).is_some() {
+            return Ok(());
+        }
+        let settings = Receiver::new(
+            source,
+            RecvColorFormat::UYVY_BGRA,
+            RecvBandwidth::MetadataOnly,
+            FieldMode::Progressive,
+            Some("tally hub".into()),
+        );
+        ndi_debug!(source = %settings.source_to_connect_to.name, "tally hub watching source");
+        self.monitors.push(TallyMonitor {
+            recv: Recv::new(self.ndi, settings)?,
+            tally: Tally::new(false, false),
+        });
+        Ok(())
+    }
+
+    pub fn with_sources(
+        mut self,
+        sources: impl IntoIterator<Item = Source>,
+    ) -> Result<Self, Error> {
+        for source in sources {
+            self.add_source(source)?;
+        }
+        Ok(self)
+    }
+
+    /// Stops watching `source`, dropping its receiver. Returns whether it was watched.
+    pub fn remove_source(&mut self, source: &Source) -> bool {
+        let before = self.monitors.len();
+        self.monitors.retain(|m| m.recv.source() != source);
+        self.monitors.len() != before
+    }
+
+    /// Calls `listener` with every change noticed by [`TallyHub::poll`].
+    pub fn on_change(mut self, listener: impl FnMut(&TallyChange) + 'static) -> Self {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &Source> {
+        self.monitors.iter().map(|m| m.recv.source())
+    }
+
+    /// The last tally known for `source`, or `None` if it isn't watched.
+    pub fn tally(&self, source: &Source) -> Option<Tally> {
+        self.monitor(source).map(|m| m.tally.clone())
+    }
+
+    /// The last tally known for every watched source.
+    pub fn tallies(&self) -> HashMap<Source, Tally> {
+        self.monitors
+            .iter()
+            .map(|m| (m.recv.source().clone(), m.tally.clone()))
+            .collect()
+    }
+
+    /// Tells `source` whether this application has it on program or preview, through the
+    /// hub's receiver; the source's echo then includes it. Returns `false` if the source
+    /// isn't watched or isn't connected.
+    pub fn set_tally(&self, source: &Source, tally: &Tally) -> bool {
+        self.monitor(source)
+            .is_some_and(|m| m.recv.set_tally(tally))
+    }
+
+    /// Reads the metadata waiting on every receiver and updates the tallies. Returns the
+    /// changes noticed, which also go to the listener.
+    pub fn poll(&mut self) -> Result<Vec<TallyChange>, Error> {
+        let mut changes = Vec::new();
+        for monitor in &mut self.monitors {
+            if let Some(tally) = monitor.update()? {
+                let source = monitor.recv.source().clone();
+                ndi_debug!(
+                    source = %source.name,
+                    on_program = tally.on_program,
+                    on_preview = tally.on_preview,
+                    "source tally changed"
+                );
+                let from = std::mem::replace(&mut monitor.tally, tally.clone());
+                changes.push(TallyChange {
+                    source,
+                    from,
+                    to: tally,
+                });
+            }
+        }
+        if let Some(listener) = self.listener.as_mut() {
+            for change in &changes {
+                listener(change);
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Polls every 100 ms until `stopped` is set or polling fails.
+    pub fn run(&mut self, stopped: &AtomicBool) -> Result<(), Error> {
+        while !stopped.load(Ordering::Relaxed) {
+            self.poll()?;
+            thread::sleep(DEFAULT_POLL_INTERVAL);
+        }
+        Ok(())
+    }
+
+    fn monitor(&self, source: &Source) -> Option<&TallyMonitor<'a>> {
+        self.monitors.iter().find(|m| m.recv.source() == source)
+    }
+}
+
+impl TallyMonitor<'_> {
+    /// Drains the receiver's metadata; returns the new tally if it differs from the last.
+    fn update(&mut self) -> Result<Option<Tally>, Error> {
+        let mut tally = self.tally.clone();
+        for _ in 0..MAX_FRAMES_PER_POLL {
+            match self.recv.capture_metadata_ref(0) {
+                Ok(Some(frame)) => {
+                    if let Some(KnownMetadata::TallyEcho(echo)) =
+                        frame.as_str().ok().and_then(KnownMetadata::parse)
+                    {
+                        tally = echo;
+                    }
+                }
+                Ok(None) => break,
+                // A dropped connection shows up in the connection count below.
+                Err(e) if e.is_retryable() => break,
+                Err(e) => return Err(e),
+            }
+        }
+        if self.recv.connections() == 0 {
+            tally = Tally::new(false, false);
+        }
+        Ok((tally != self.tally).then_some(tally))
+    }
+}
@@ -0,0 +1,190 @@
+use std::{f64::consts::TAU, time::Duration};
+
+use crate::{
+    AudioFrame, AudioType, Error, FourCCVideoType, FramePacer, Send, VideoFrame, VideoFrameBuilder,
+};
+
+/// Sample rate of [`Send::send_test_tone`].
+pub const TEST_TONE_SAMPLE_RATE: i32 = 48_000;
+/// Channels of [`Send::send_test_tone`], all carrying the same tone.
+pub const TEST_TONE_CHANNELS: i32 = 2;
+/// Peak level of the test tone: -20 dBFS, the usual line-up level.
+const TONE_AMPLITUDE: f64 = 0.1;
+/// Audio frames sent per second of tone.
+const TONE_FRAMES_PER_SECOND: u32 = 50;
+
+/// 75% colour bars, left to right, as RGB.
+const BARS: [[u8; 3]; 7] = [
+    [191, 191, 191],
+    [191, 191, 0],
+    [0, 191, 191],
+    [0, 191, 0],
+    [191, 0, 191],
+    [191, 0, 0],
+    [0, 0, 191],
+];
+const BLACK: [u8; 3] = [0, 0, 0];
+const WHITE: [u8; 3] = [255, 255, 255];
+
+/// A synthetic picture for [`Send::send_test_pattern`] and [`Pattern::frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Seven full-height 75% bars: white, yellow, cyan, green, magenta, red, blue.
+    ColorBars,
+    /// SMPTE-style bars: the seven bars over a strip of reversed blue bars, with -I, white,
+    /// +Q and a PLUGE along the bottom.
+    Smpte,
+    /// A black and white checkerboard of eight squares per picture height.
+    Checker,
+}
+
+impl Pattern {
+    /// Renders the pattern as a progressive BGRA frame.
+    pub fn frame(self, width: i32, height: i32, fps: u32) -> Result<VideoFrame, Error> {
+        if width <= 0 || height <= 0 {
+            return Err(Error::InvalidResolution {
+                xres: width,
+                yres: height,
+            });
+        }
+        let (w, h) = (width as usize, height as usize);
+        let mut data = Vec::with_capacity(w * h * 4);
+        for y in 0..h {
+            for x in 0..w {
+                let [r, g, b] = self.pixel(x, y, w, h);
+                data.extend_from_slice(&[b, g, r, 255]);
+            }
+        }
+        VideoFrameBuilder::new()
+            .resolution(width, height)
+            .fourcc(FourCCVideoType::BGRA)
+            .frame_rate(fps.min(i32::MAX as u32) as i32, 1)
+            .aspect_ratio(width as f32 / height as f32)
+            .data(data)
+            .build()
+    }
+
+    fn pixel(self, x: usize, y: usize, w: usize, h: usize) -> [u8; 3] {
+        match self {
+            Pattern::ColorBars => BARS[x * 7 / w],
+            Pattern::Checker => {
+                let square = (h / 8).max(1);
+                if (x / square + y / square).is_multiple_of(2) {
+                    WHITE
+                } else {
+                    BLACK
+                }
+            }
+            Pattern::Smpte if y < h * 2 / 3 => BARS[x * 7 / w],
+            Pattern::Smpte if y < h * 3 / 4 => {
+                let bar = x * 7 / w;
+                if bar.is_multiple_of(2) {
+                    BARS[6 - bar]
+                } else {
+                    BLACK
+                }
+            }
+            Pattern::Smpte => {
+                // -I, white and +Q a sixth of the width each, then black with the PLUGE (black
+                // next to a just-visible dark grey) under the red bar.
+                let sixth = x * 6 / w;
+                let bar = x * 7 / w;
+                match (sixth, bar) {
+                    (0, _) => [0, 33, 76],
+                    (1, _) => WHITE,
+                    (2, _) => [50, 0, 106],
+                    (_, 5) => {
+                        let third = (x * 7 - 5 * w) * 3 / w;
+                        [[0; 3], [0; 3], [9; 3]][third.min(2)]
+                    }
+                    _ => BLACK,
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Send<'a> {
+    /// Sends `pattern` at `fps` frames per second for `duration`, blocking until done.
+    ///
+    /// The picture is rendered once; each frame is stamped with a timecode that advances by
+    /// one frame duration.
+    pub fn send_test_pattern(
+        &self,
+        pattern: Pattern,
+        width: i32,
+        height: i32,
+        fps: u32,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        if fps == 0 {
+            return Err(Error::InvalidFrameRate {
+                numerator: 0,
+                denominator: 1,
+            });
+        }
+        let mut frame = pattern.frame(width, height, fps)?;
+        let total = (duration.as_secs_f64() * f64::from(fps)).ceil() as u64;
+        let mut pacer = FramePacer::new(fps, 1);
+        while pacer.frame() < total {
+            self.send_video_paced(&mut pacer, &mut frame)?;
+        }
+        Ok(())
+    }
+
+    /// Sends a `frequency` Hz sine at -20 dBFS for `duration`, blocking until done.
+    ///
+    /// The tone is 48 kHz stereo, sent in real time as 20 ms frames.
+    pub fn send_test_tone(&self, frequency: f32, duration: Duration) -> Result<(), Error> {
+        let nyquist = TEST_TONE_SAMPLE_RATE as f32 / 2.0;
+        if !(frequency > 0.0 && frequency < nyquist) {
+            return Err(Error::InvalidFrame(format!(
+                "Test tone frequency {} Hz is outside 0-{} Hz",
+                frequency, nyquist
+            )));
+        }
+        let total = (duration.as_secs_f64() * f64::from(TEST_TONE_SAMPLE_RATE)).round() as u64;
+        let chunk = (TEST_TONE_SAMPLE_RATE as u32 / TONE_FRAMES_PER_SECOND) as u64;
+        let cycles_per_sample = f64::from(frequency) / f64::from(TEST_TONE_SAMPLE_RATE);
+        let mut pacer = FramePacer::new(TONE_FRAMES_PER_SECOND, 1);
+        // Phase in cycles at sample `next`, kept in 0..1 so precision holds up over soak tests.
+        let (mut next, mut phase) = (0, 0.0);
+        while next < total {
+            let timecode = pacer.wait();
+            // Frames the pacer skipped still advance the tone, keeping it in step with time.
+            let start = (pacer.frame() - 1) * chunk;
+            if start >= total {
+                break;
+            }
+            phase = (phase + (start - next) as f64 * cycles_per_sample).fract();
+            let samples = chunk.min(total - start);
+            let mut frame = tone_frame(phase, cycles_per_sample, samples as usize)?;
+            frame.timecode = timecode;
+            self.send_audio(&frame);
+            phase = (phase + samples as f64 * cycles_per_sample).fract();
+            next = start + samples;
+        }
+        Ok(())
+    }
+}
+
+/// `samples` samples of a sine at -20 dBFS on every channel, starting at `phase` cycles.
+fn tone_frame(phase: f64, cycles_per_sample: f64, samples: usize) -> Result<AudioFrame, Error> {
+    let channel: Vec<u8> = (0..samples)
+        .flat_map(|i| {
+            let cycles = phase + i as f64 * cycles_per_sample;
+            ((TONE_AMPLITUDE * (TAU * cycles).sin()) as f32).to_ne_bytes()
+        })
+        .collect();
+    let data = channel.repeat(TEST_TONE_CHANNELS as usize);
+    AudioFrame::with_data(
+        TEST_TONE_SAMPLE_RATE,
+        TEST_TONE_CHANNELS,
+        samples as i32,
+        0,
+        AudioType::FLTP,
+        data,
+        None,
+        0,
+    )
+}
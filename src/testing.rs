@@ -0,0 +1,290 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{AudioFrame, Error, FrameSink, FrameSource, MetadataFrame, VideoFrame};
+
+struct Scripted<T> {
+    at: Duration,
+    frame: Result<T, Error>,
+}
+
+type Script<T> = Mutex<VecDeque<Scripted<T>>>;
+
+/// A [`FrameSource`] that replays scripted frames and errors.
+///
+/// Each entry is due `at` a time after the mock was created. By default entries are returned
+/// as soon as they are asked for and an empty script times out immediately, keeping tests
+/// fast; [`realtime`](Self::realtime) makes captures wait for each entry's time and for the
+/// full timeout as a real receiver would.
+pub struct MockReceiver {
+    started: Instant,
+    realtime: bool,
+    video: Script<VideoFrame>,
+    audio: Script<AudioFrame>,
+    metadata: Script<MetadataFrame>,
+}
+
+impl Default for MockReceiver {
+    fn default() -> Self {
+        MockReceiver {
+            started: Instant::now(),
+            realtime: false,
+            video: Mutex::default(),
+            audio: Mutex::default(),
+            metadata: Mutex::default(),
+        }
+    }
+}
+
+impl MockReceiver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Honours the scripted times and capture timeouts.
+    pub fn realtime(mut self) -> Self {
+        self.realtime = true;
+        self
+    }
+
+    pub fn push_video(&self, frame: VideoFrame, at: Duration) {
+        push(&self.video, at, Ok(frame));
+    }
+
+    pub fn push_audio(&self, frame: AudioFrame, at: Duration) {
+        push(&self.audio, at, Ok(frame));
+    }
+
+    pub fn push_metadata(&self, frame: MetadataFrame, at: Duration) {
+        push(&self.metadata, at, Ok(frame));
+    }
+
+    /// Makes the video capture due at `at` fail with `error`.
+    pub fn push_video_error(&self, error: Error, at: Duration) {
+        push(&self.video, at, Err(error));
+    }
+
+    pub fn push_audio_error(&self, error: Error, at: Duration) {
+        push(&self.audio, at, Err(error));
+    }
+
+    pub fn push_metadata_error(&self, error: Error, at: Duration) {
+        push(&self.metadata, at, Err(error));
+    }
+
+    /// Scripted entries, of all frame types, not yet captured.
+    pub fn remaining(&self) -> usize {
+        self.video.lock().unwrap().len()
+            + self.audio.lock().unwrap().len()
+            + self.metadata.lock().unwrap().len()
+    }
+
+    fn next<T>(
+        &self,
+        script: &Script<T>,
+        timeout: Duration,
+        operation: &'static str,
+    ) -> Result<T, Error> {
+        let now = Instant::now();
+        let due = script
+            .lock()
+            .unwrap()
+            .front()
+            .map(|entry| self.started + entry.at);
+        match due {
+            Some(due) if !self.realtime || due <= now + timeout => {
+                if self.realtime {
+                    thread::sleep(due.saturating_duration_since(now));
+                }
+                if let Some(entry) = script.lock().unwrap().pop_front() {
+                    return entry.frame;
+                }
+            }
+            _ => {
+                if self.realtime {
+                    thread::sleep(timeout);
+                }
+            }
+        }
+        Err(Error::Timeout {
+            operation,
+            timeout_ms: timeout.as_millis().min(u32::MAX as u128) as u32,
+        })
+    }
+}
+
+/// Inserts keeping the script ordered by time, after entries due at the same time.
+fn push<T>(script: &Script<T>, at: Duration, frame: Result<T, Error>) {
+    let mut script = script.lock().unwrap();
+    let index = script.partition_point(|entry| entry.at <= at);
+    script.insert(index, Scripted { at, frame });
+}
+
+impl FrameSource for MockReceiver {
+    fn capture_video(&self, timeout: Duration) -> Result<VideoFrame, Error> {
+        self.next(&self.video, timeout, "a video frame")
+    }
+
+    fn capture_audio(&self, timeout: Duration) -> Result<AudioFrame, Error> {
+        self.next(&self.audio, timeout, "an audio frame")
+    }
+
+    fn capture_metadata(&self, timeout: Duration) -> Result<MetadataFrame, Error> {
+        self.next(&self.metadata, timeout, "a metadata frame")
+    }
+}
+
+/// A [`FrameSink`] that records every frame sent to it.
+#[derive(Default)]
+pub struct MockSender {
+    video: Mutex<Vec<VideoFrame>>,
+    audio: Mutex<Vec<AudioFrame>>,
+    metadata: Mutex<Vec<MetadataFrame>>,
+}
+
+impl MockSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes and returns the video frames sent so far.
+    pub fn take_video(&self) -> Vec<VideoFrame> {
+        std::mem::take(&mut self.video.lock().unwrap())
+    }
+
+    pub fn take_audio(&self) -> Vec<AudioFrame> {
+        std::mem::take(&mut self.audio.lock().unwrap())
+    }
+
+    pub fn take_metadata(&self) -> Vec<MetadataFrame> {
+        std::mem::take(&mut self.metadata.lock().unwrap())
+    }
+
+    pub fn video_count(&self) -> usize {
+        self.video.lock().unwrap().len()
+    }
+
+    pub fn audio_count(&self) -> usize {
+        self.audio.lock().unwrap().len()
+    }
+
+    pub fn metadata_count(&self) -> usize {
+        self.metadata.lock().unwrap().len()
+    }
+}
+
+impl FrameSink for MockSender {
+    fn send_video(&self, frame: &VideoFrame) {
+        self.video.lock().unwrap().push(frame.clone());
+    }
+
+    fn send_audio(&self, frame: &AudioFrame) {
+        self.audio.lock().unwrap().push(frame.clone());
+    }
+
+    fn send_metadata(&self, frame: &MetadataFrame) {
+        self.metadata.lock().unwrap().push(frame.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(xml: &str) -> MetadataFrame {
+        MetadataFrame::from_xml(xml, 0).unwrap()
+    }
+
+    #[test]
+    fn script_is_replayed_in_time_order() {
+        let receiver = MockReceiver::new();
+        receiver.push_metadata(metadata("<late/>"), Duration::from_millis(20));
+        receiver.push_metadata(metadata("<first/>"), Duration::from_millis(10));
+        receiver.push_metadata(metadata("<second/>"), Duration::from_millis(10));
+        assert_eq!(receiver.remaining(), 3);
+
+        let captured = (0..3)
+            .map(|_| {
+                let frame = receiver.capture_metadata(Duration::ZERO).unwrap();
+                frame.as_str().unwrap().to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(captured, ["<first/>", "<second/>", "<late/>"]);
+        assert_eq!(receiver.remaining(), 0);
+    }
+
+    #[test]
+    fn scripted_errors_are_returned_in_place() {
+        let receiver = MockReceiver::new();
+        receiver.push_metadata_error(Error::ErrorFrame, Duration::ZERO);
+        receiver.push_metadata(metadata("<after/>"), Duration::from_millis(1));
+
+        assert!(matches!(
+            receiver.capture_metadata(Duration::ZERO),
+            Err(Error::ErrorFrame)
+        ));
+        assert!(receiver.capture_metadata(Duration::ZERO).is_ok());
+    }
+
+    #[test]
+    fn empty_script_times_out_immediately() {
+        let receiver = MockReceiver::new();
+        let started = Instant::now();
+        let result = receiver.capture_video(Duration::from_secs(10));
+        assert!(matches!(
+            result,
+            Err(Error::Timeout {
+                timeout_ms: 10_000,
+                ..
+            })
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn frame_types_have_separate_scripts() {
+        let receiver = MockReceiver::new();
+        receiver.push_metadata(metadata("<only/>"), Duration::ZERO);
+        assert!(receiver.capture_audio(Duration::ZERO).is_err());
+        assert!(receiver.capture_video(Duration::ZERO).is_err());
+        assert!(receiver.capture_metadata(Duration::ZERO).is_ok());
+    }
+
+    #[test]
+    fn realtime_waits_for_entries_and_timeouts() {
+        let receiver = MockReceiver::new().realtime();
+        receiver.push_metadata(metadata("<due/>"), Duration::from_millis(30));
+
+        // Not due within the timeout: waits the full timeout and leaves the entry.
+        let started = Instant::now();
+        assert!(receiver.capture_metadata(Duration::from_millis(5)).is_err());
+        assert!(started.elapsed() >= Duration::from_millis(5));
+        assert_eq!(receiver.remaining(), 1);
+
+        // Due within the timeout: returned once its time comes.
+        let frame = receiver.capture_metadata(Duration::from_secs(1)).unwrap();
+        assert_eq!(frame.as_str().unwrap(), "<due/>");
+        assert!(receiver.started.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn sender_records_frames_until_taken() {
+        let sender = MockSender::new();
+        sender.send_metadata(&metadata("<a/>"));
+        sender.send_metadata(&metadata("<b/>"));
+        sender.send_audio(&AudioFrame::new());
+        assert_eq!(sender.metadata_count(), 2);
+        assert_eq!(sender.audio_count(), 1);
+        assert_eq!(sender.video_count(), 0);
+
+        let sent = sender.take_metadata();
+        assert_eq!(sent[1].as_str().unwrap(), "<b/>");
+        assert_eq!(sender.metadata_count(), 0);
+        assert_eq!(sender.take_audio().len(), 1);
+        assert!(sender.take_video().is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{Error, Recv};
+
+/// Scheduling priority for a capture thread, mapped onto the platform's own classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThreadPriority {
+    /// Whatever the thread was started with.
+    #[default]
+    Normal,
+    /// Round-robin real-time scheduling at a middling priority on Linux and macOS, or
+    /// `THREAD_PRIORITY_HIGHEST` on Windows.
+    High,
+    /// First-in first-out real-time scheduling at the highest priority on Linux and macOS,
+    /// or `THREAD_PRIORITY_TIME_CRITICAL` on Windows. A busy thread at this priority can
+    /// starve the rest of the system, so keep its callbacks short.
+    Realtime,
+}
+
+/// Priority and core pinning for the threads the crate captures on, as started by
+/// [`Recv::start_callbacks`] and [`Receiver::into_channel_with_thread`](crate::Receiver::into_channel_with_thread).
+///
+/// Real-time priority keeps other work from delaying capture and pinning keeps the thread's
+/// cache warm; both are opt-in because they need care. Raising priority usually needs
+/// privileges: `CAP_SYS_NICE` or an `rtprio` limit on Linux. A thread the settings can't be
+/// applied to logs a warning and carries on as it is; call
+/// [`CaptureThreadConfig::apply_to_current`] on a thread of your own to find out up front.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CaptureThreadConfig {
+    pub priority: ThreadPriority,
+    /// Cores, numbered from zero, the thread may run on; empty for any. Pinning isn't
+    /// supported on macOS.
+    pub affinity: Vec<usize>,
+}
+
+impl CaptureThreadConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_priority(mut self, priority: ThreadPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Pins the thread to `cores`.
+    pub fn with_affinity(mut self, cores: impl IntoIterator<Item = usize>) -> Self {
+        self.affinity = cores.into_iter().collect();
+        self
+    }
+
+    /// Applies the settings to the calling thread.
+    pub fn apply_to_current(&self) -> Result<(), Error> {
+        if self.priority != ThreadPriority::Normal {
+            platform::set_priority(self.priority)?;
+        }
+        if !self.affinity.is_empty() {
+            platform::set_affinity(&self.affinity)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the settings to a thread the crate started, warning instead of failing.
+    pub(crate) fn apply_or_warn(&self) {
+        if let Err(_e) = self.apply_to_current() {
+            ndi_warn!(error = %_e, config = ?self, "could not configure capture thread");
+        }
+    }
+}
+
+impl<'a> Recv<'a> {
+    /// Sets the priority and pinning of the threads started by [`Recv::start_callbacks`]
+    /// from now on.
+    pub fn with_capture_thread(mut self, config: CaptureThreadConfig) -> Self {
+        self.capture_thread = config;
+        self
+    }
+
+    pub fn capture_thread(&self) -> &CaptureThreadConfig {
+        &self.capture_thread
+    }
+}
+
+#[cfg(any(target_os = "linux", windows))]
+fn invalid_core(core: usize, cores: usize) -> Error {
+    Error::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("core {core} is beyond the {cores} that can be pinned to"),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::io;
+
+    use super::{invalid_core, ThreadPriority};
+    use crate::Error;
+
+    const SCHED_FIFO: i32 = 1;
+    const SCHED_RR: i32 = 2;
+    /// Cores in a glibc `cpu_set_t`.
+    const CPU_SETSIZE: usize = 1024;
+
+    #[repr(C)]
+    struct SchedParam {
+        sched_priority: i32,
+    }
+
+    extern "C" {
+        fn pthread_self() -> usize;
+        fn pthread_setschedparam(thread: usize, policy: i32, param: *const SchedParam) -> i32;
+        fn sched_get_priority_min(policy: i32) -> i32;
+        fn sched_get_priority_max(policy: i32) -> i32;
+        fn sched_setaffinity(pid: i32, cpusetsize: usize, mask: *const u64) -> i32;
+    }
+
+    pub(super) fn set_priority(priority: ThreadPriority) -> Result<(), Error> {
+        let (policy, level) = match priority {
+            ThreadPriority::Normal => return Ok(()),
+            ThreadPriority::High => {
+                let (min, max) = unsafe {
+                    (
+                        sched_get_priority_min(SCHED_RR),
+                        sched_get_priority_max(SCHED_RR),
+                    )
+                };
+                (SCHED_RR, min + (max - min) / 2)
+            }
+            ThreadPriority::Realtime => (SCHED_FIFO, unsafe { sched_get_priority_max(SCHED_FIFO) }),
+        };
+        let param = SchedParam {
+            sched_priority: level,
+        };
+        match unsafe { pthread_setschedparam(pthread_self(), policy, &param) } {
+            0 => Ok(()),
+            code => Err(Error::Io(io::Error::from_raw_os_error(code))),
+        }
+    }
+
+    pub(super) fn set_affinity(cores: &[usize]) -> Result<(), Error> {
+        let mut mask = [0u64; CPU_SETSIZE / 64];
+        for &core in cores {
+            let word = mask
+                .get_mut(core / 64)
+                .ok_or_else(|| invalid_core(core, CPU_SETSIZE))?;
+            *word |= 1 << (core % 64);
+        }
+        // A pid of zero means the calling thread.
+        match unsafe { sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr()) } {
+            0 => Ok(()),
+            _ => Err(Error::Io(io::Error::last_os_error())),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::{ffi::c_void, io};
+
+    use super::ThreadPriority;
+    use crate::Error;
+
+    const SCHED_RR: i32 = 2;
+    const SCHED_FIFO: i32 = 4;
+
+    #[repr(C)]
+    struct SchedParam {
+        sched_priority: i32,
+        _opaque: [u8; 4],
+    }
+
+    extern "C" {
+        fn pthread_self() -> *mut c_void;
+        fn pthread_setschedparam(thread: *mut c_void, policy: i32, param: *const SchedParam)
+            -> i32;
+        fn sched_get_priority_min(policy: i32) -> i32;
+        fn sched_get_priority_max(policy: i32) -> i32;
+    }
+
+    pub(super) fn set_priority(priority: ThreadPriority) -> Result<(), Error> {
+        let (policy, level) = match priority {
+            ThreadPriority::Normal => return Ok(()),
+            ThreadPriority::High => {
+                let (min, max) = unsafe {
+                    (
+                        sched_get_priority_min(SCHED_RR),
+                        sched_get_priority_max(SCHED_RR),
+                    )
+                };
+                (SCHED_RR, min + (max - min) / 2)
+            }
+            ThreadPriority::Realtime => (SCHED_FIFO, unsafe { sched_get_priority_max(SCHED_FIFO) }),
+        };
+        let param = SchedParam {
+            sched_priority: level,
+            _opaque: [0; 4],
+        };
+        match unsafe { pthread_setschedparam(pthread_self(), policy, &param) } {
+            0 => Ok(()),
+            code => Err(Error::Io(io::Error::from_raw_os_error(code))),
+        }
+    }
+
+    pub(super) fn set_affinity(_cores: &[usize]) -> Result<(), Error> {
+        Err(Error::Io(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "macOS doesn't support pinning threads to cores",
+        )))
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{ffi::c_void, io};
+
+    use super::{invalid_core, ThreadPriority};
+    use crate::Error;
+
+    const THREAD_PRIORITY_HIGHEST: i32 = 2;
+    const THREAD_PRIORITY_TIME_CRITICAL: i32 = 15;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentThread() -> *mut c_void;
+        fn SetThreadPriority(thread: *mut c_void, priority: i32) -> i32;
+        fn SetThreadAffinityMask(thread: *mut c_void, mask: usize) -> usize;
+    }
+
+    pub(super) fn set_priority(priority: ThreadPriority) -> Result<(), Error> {
+        let level = match priority {
+            ThreadPriority::Normal => return Ok(()),
+            ThreadPriority::High => THREAD_PRIORITY_HIGHEST,
+            ThreadPriority::Realtime => THREAD_PRIORITY_TIME_CRITICAL,
+        };
+        match unsafe { SetThreadPriority(GetCurrentThread(), level) } {
+            0 => Err(Error::Io(io::Error::last_os_error())),
+            _ => Ok(()),
+        }
+    }
+
+    pub(super) fn set_affinity(cores: &[usize]) -> Result<(), Error> {
+        let mut mask = 0usize;
+        for &core in cores {
+            if core >= usize::BITS as usize {
+                return Err(invalid_core(core, usize::BITS as usize));
+            }
+            mask |= 1 << core;
+        }
+        match unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } {
+            0 => Err(Error::Io(io::Error::last_os_error())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+mod platform {
+    use std::io;
+
+    use super::ThreadPriority;
+    use crate::Error;
+
+    fn unsupported() -> Error {
+        Error::Io(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "capture thread configuration isn't supported on this platform",
+        ))
+    }
+
+    pub(super) fn set_priority(_priority: ThreadPriority) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    pub(super) fn set_affinity(_cores: &[usize]) -> Result<(), Error> {
+        Err(unsupported())
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Receiver, Recv, RetryPolicy, Source, NDI};
+
+const DEFAULT_QUALITY: u8 = 75;
+const DEFAULT_CAPTURE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A still captured by a [`ThumbnailService`].
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    pub source: Source,
+    pub jpeg: Vec<u8>,
+    pub xres: i32,
+    pub yres: i32,
+    pub captured_at: Instant,
+}
+
+/// Keeps a low-bandwidth receiver open to each of a set of sources and captures a JPEG still
+/// from them in turn, for multiviewers and source pickers.
+///
+/// Captures are spaced so every source is refreshed once per `interval`. The service does no
+/// work on its own; call [`ThumbnailService::capture_next`] in a loop, typically on a dedicated
+/// thread.
+pub struct ThumbnailService<'a> {
+    ndi: &'a NDI,
+    receivers: Vec<Recv<'a>>,
+    latest: HashMap<String, Thumbnail>,
+    interval: Duration,
+    quality: u8,
+    capture_timeout: Duration,
+    next: usize,
+    next_at: Instant,
+}
+
+impl<'a> ThumbnailService<'a> {
+    /// Connects to each source with [`Receiver::snapshot_preset`].
+    pub fn new(
+        ndi: &'a NDI,
+        sources: impl IntoIterator<Item = Source>,
+        interval: Duration,
+    ) -> Result<Self, Error> {
+        let receivers = sources
+            .into_iter()
+            .map(|source| Recv::new(ndi, Receiver::snapshot_preset(source)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ThumbnailService {
+            ndi,
+            receivers,
+            latest: HashMap::new(),
+            interval,
+            quality: DEFAULT_QUALITY,
+            capture_timeout: DEFAULT_CAPTURE_TIMEOUT,
+            next: 0,
+            next_at: Instant::now(),
+        })
+    }
+
+    /// JPEG quality from 1 to 100; defaults to 75.
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.quality = quality.clamp(1, 100);
+        self
+    }
+
+    /// How long to wait for a frame from a source before moving on; defaults to one second.
+    pub fn with_capture_timeout(mut self, timeout: Duration) -> Self {
+        self.capture_timeout = timeout;
+        self
+    }
+
+    /// Starts capturing from `source` as well, from the next round.
+    pub fn add_source(&mut self, source: Source) -> Result<(), Error> {
+        self.receivers
+            .push(Recv::new(self.ndi, Receiver::snapshot_preset(source))?);
+        Ok(())
+    }
+
+    /// Stops capturing from the source named `name` and forgets its last thumbnail.
+    pub fn remove_source(&mut self, name: &str) -> bool {
+        let before = self.receivers.len();
+        self.receivers.retain(|recv| recv.source().name != name);
+        self.latest.remove(name);
+        if self.next >= self.receivers.len() {
+            self.next = 0;
+        }
+        self.receivers.len() != before
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &Source> {
+        self.receivers.iter().map(Recv::source)
+    }
+
+    /// The most recent thumbnail captured from the source named `name`.
+    pub fn latest(&self, name: &str) -> Option<&Thumbnail> {
+        self.latest.get(name)
+    }
+
+    /// Waits for the next slot and captures from the next source in turn.
+    ///
+    /// Returns the source together with its new thumbnail, or the error that prevented one; a
+    /// failing source keeps its previous thumbnail and is tried again on the next round. Returns
+    /// `None` when there are no sources.
+    pub fn capture_next(&mut self) -> Option<(Source, Result<Thumbnail, Error>)> {
+        if self.receivers.is_empty() {
+            return None;
+        }
+        let now = Instant::now();
+        if self.next_at > now {
+            thread::sleep(self.next_at - now);
+        }
+        let slot = self.interval / self.receivers.len() as u32;
+        self.next_at = self.next_at.max(now) + slot;
+
+        let recv = &self.receivers[self.next];
+        self.next = (self.next + 1) % self.receivers.len();
+        let source = recv.source().clone();
+        let quality = self.quality;
+        // Frames queued since this source's last turn are skipped, so the still is current.
+        let result =
+            RetryPolicy::default().run(self.capture_timeout, "a thumbnail frame", |timeout_ms| {
+                match recv.capture_latest_video_ref(timeout_ms)? {
+                    Some(frame) => Ok(Some(Thumbnail {
+                        source: source.clone(),
+                        jpeg: frame.encode_jpeg(quality)?,
+                        xres: frame.xres(),
+                        yres: frame.yres(),
+                        captured_at: Instant::now(),
+                    })),
+                    None => Ok(None),
+                }
+            });
+        if let Ok(thumbnail) = &result {
+            self.latest.insert(source.name.clone(), thumbnail.clone());
+        }
+        Some((source, result))
+    }
+}
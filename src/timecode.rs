@@ -0,0 +1,261 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use crate::Error;
+
+const TICKS_PER_SECOND: i128 = 10_000_000;
+
+/// `NDIlib_recv_timestamp_undefined`: the received frame carries no timestamp.
+pub(crate) const TIMESTAMP_UNDEFINED: i64 = i64::MAX;
+
+/// An NDI timecode, counted in 100 ns units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timecode(pub i64);
+
+impl Timecode {
+    /// Asks the SDK to generate the timecode when the frame is sent
+    /// (`NDIlib_send_timecode_synthesize`).
+    pub const SYNTHESIZE: Timecode = Timecode(i64::MAX);
+    /// No timecode (`NDIlib_send_timecode_empty`).
+    pub const EMPTY: Timecode = Timecode(0);
+
+    pub fn from_duration(duration: Duration) -> Self {
+        Timecode((duration.as_nanos() / 100).min(i64::MAX as u128) as i64)
+    }
+
+    /// The time since midnight this timecode represents, or `None` for the special values.
+    pub fn as_duration(self) -> Option<Duration> {
+        if self == Self::SYNTHESIZE || self.0 < 0 {
+            None
+        } else {
+            Some(Duration::from_nanos(self.0 as u64 * 100))
+        }
+    }
+
+    /// Timecode of the start of frame number `frame` at `frame_rate_n / frame_rate_d`, rounded
+    /// up to the first tick within the frame so that [`Timecode::to_frames`] gives `frame` back.
+    pub fn from_frames(frame: u64, frame_rate_n: i32, frame_rate_d: i32) -> Self {
+        let (n, d) = rate(frame_rate_n, frame_rate_d);
+        let ticks = (frame as i128 * d * TICKS_PER_SECOND + n - 1) / n;
+        Timecode(ticks.min(i64::MAX as i128) as i64)
+    }
+
+    /// Number of the frame this timecode falls in at `frame_rate_n / frame_rate_d`.
+    pub fn to_frames(self, frame_rate_n: i32, frame_rate_d: i32) -> u64 {
+        let (n, d) = rate(frame_rate_n, frame_rate_d);
+        (self.0.max(0) as i128 * n / (d * TICKS_PER_SECOND)) as u64
+    }
+
+    /// The timecode `smpte` denotes at `frame_rate_n / frame_rate_d`.
+    ///
+    /// Fails if a field is out of range, including hours past 23, if `smpte` is drop-frame at
+    /// a rate that doesn't drop frames, or if it names a frame number drop-frame skips.
+    pub fn from_smpte(
+        smpte: &SmpteTimecode,
+        frame_rate_n: i32,
+        frame_rate_d: i32,
+    ) -> Result<Self, Error> {
+        let nominal = nominal_rate(frame_rate_n, frame_rate_d);
+        if smpte.hours >= 24
+            || smpte.minutes >= 60
+            || smpte.seconds >= 60
+            || smpte.frames as u64 >= nominal
+        {
+            return Err(Error::InvalidTimecode(format!(
+                "{} is out of range at {}/{} fps",
+                smpte, frame_rate_n, frame_rate_d
+            )));
+        }
+        if smpte.drop_frame && !is_drop_frame(frame_rate_n, frame_rate_d) {
+            return Err(Error::InvalidTimecode(format!(
+                "{} is drop-frame, which {}/{} fps doesn't use",
+                smpte, frame_rate_n, frame_rate_d
+            )));
+        }
+        let total_minutes = smpte.hours as u64 * 60 + smpte.minutes as u64;
+        let mut frame = (total_minutes * 60 + smpte.seconds as u64) * nominal + smpte.frames as u64;
+        if smpte.drop_frame {
+            let drop = drop_frames(nominal);
+            if smpte.seconds == 0
+                && !smpte.minutes.is_multiple_of(10)
+                && (smpte.frames as u64) < drop
+            {
+                return Err(Error::InvalidTimecode(format!(
+                    "{} is skipped in drop-frame timecode",
+                    smpte
+                )));
+            }
+            frame -= drop * (total_minutes - total_minutes / 10);
+        }
+        Ok(Self::from_frames(frame, frame_rate_n, frame_rate_d))
+    }
+
+    /// Converts to hours, minutes, seconds and frames, using drop-frame counting for the
+    /// 1001-denominator rates that need it (29.97, 59.94).
+    pub fn to_smpte(self, frame_rate_n: i32, frame_rate_d: i32) -> SmpteTimecode {
+        let nominal = nominal_rate(frame_rate_n, frame_rate_d);
+        let mut frame = self.to_frames(frame_rate_n, frame_rate_d);
+        let drop_frame = is_drop_frame(frame_rate_n, frame_rate_d);
+        if drop_frame {
+            let drop = drop_frames(nominal);
+            let per_ten_minutes = nominal * 600 - drop * 9;
+            let per_minute = nominal * 60 - drop;
+            let tens = frame / per_ten_minutes;
+            let rest = frame % per_ten_minutes;
+            frame += drop * 9 * tens;
+            if rest > drop {
+                frame += drop * ((rest - drop) / per_minute);
+            }
+        }
+        let seconds = frame / nominal;
+        SmpteTimecode {
+            hours: ((seconds / 3600) % 24) as u8,
+            minutes: ((seconds / 60) % 60) as u8,
+            seconds: (seconds % 60) as u8,
+            frames: (frame % nominal) as u8,
+            drop_frame,
+        }
+    }
+}
+
+impl From<i64> for Timecode {
+    fn from(value: i64) -> Self {
+        Timecode(value)
+    }
+}
+
+impl From<Timecode> for i64 {
+    fn from(timecode: Timecode) -> Self {
+        timecode.0
+    }
+}
+
+fn rate(frame_rate_n: i32, frame_rate_d: i32) -> (i128, i128) {
+    (frame_rate_n.max(1) as i128, frame_rate_d.max(1) as i128)
+}
+
+fn nominal_rate(frame_rate_n: i32, frame_rate_d: i32) -> u64 {
+    let (n, d) = rate(frame_rate_n, frame_rate_d);
+    ((n + d / 2) / d).max(1) as u64
+}
+
+fn is_drop_frame(frame_rate_n: i32, frame_rate_d: i32) -> bool {
+    frame_rate_d == 1001 && nominal_rate(frame_rate_n, frame_rate_d).is_multiple_of(30)
+}
+
+/// Frame numbers skipped at the start of each minute: 2 at 29.97, 4 at 59.94.
+fn drop_frames(nominal: u64) -> u64 {
+    nominal / 15
+}
+
+/// SMPTE-style `HH:MM:SS:FF` timecode. Drop-frame timecode uses `;` before the frame count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SmpteTimecode {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub frames: u8,
+    pub drop_frame: bool,
+}
+
+impl fmt::Display for SmpteTimecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            if self.drop_frame { ';' } else { ':' },
+            self.frames
+        )
+    }
+}
+
+impl FromStr for SmpteTimecode {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidTimecode(format!("{:?} is not HH:MM:SS:FF", value));
+        let drop_frame = value.contains(';');
+        let mut parts = value.trim().split([':', ';']).map(|p| p.parse::<u8>().ok());
+        let mut next = || parts.next().flatten().ok_or_else(invalid);
+        let timecode = SmpteTimecode {
+            hours: next()?,
+            minutes: next()?,
+            seconds: next()?,
+            frames: next()?,
+            drop_frame,
+        };
+        match parts.next() {
+            None => Ok(timecode),
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smpte(value: &str) -> SmpteTimecode {
+        value.parse().unwrap()
+    }
+
+    /// Frames either side of the first minute, the tenth minute and the hour.
+    fn boundary_frames(nominal: u64) -> impl Iterator<Item = u64> {
+        let drop = drop_frames(nominal);
+        let minute = nominal * 60 - drop;
+        let ten_minutes = nominal * 600 - drop * 9;
+        [minute, ten_minutes, ten_minutes * 6]
+            .into_iter()
+            .flat_map(|frame| frame - 8..frame + 8)
+    }
+
+    #[test]
+    fn drop_frame_skips_frame_numbers_at_each_minute_but_every_tenth() {
+        for ((n, d), last, next) in [
+            ((30_000, 1001), "00:00:59;29", "00:01:00;02"),
+            ((30_000, 1001), "00:09:59;29", "00:10:00;00"),
+            ((60_000, 1001), "00:00:59;59", "00:01:00;04"),
+            ((60_000, 1001), "00:09:59;59", "00:10:00;00"),
+        ] {
+            let frame = Timecode::from_smpte(&smpte(last), n, d)
+                .unwrap()
+                .to_frames(n, d);
+            let following = Timecode::from_frames(frame + 1, n, d).to_smpte(n, d);
+            assert_eq!(following, smpte(next), "after {last} at {n}/{d}");
+        }
+    }
+
+    #[test]
+    fn drop_frame_round_trips_around_boundaries() {
+        for (n, d) in [(30_000, 1001), (60_000, 1001)] {
+            for frame in boundary_frames(nominal_rate(n, d)) {
+                let smpte = Timecode::from_frames(frame, n, d).to_smpte(n, d);
+                assert!(smpte.drop_frame);
+                let back = Timecode::from_smpte(&smpte, n, d).unwrap().to_frames(n, d);
+                assert_eq!(back, frame, "{smpte} at {n}/{d}");
+            }
+        }
+    }
+
+    #[test]
+    fn skipped_and_out_of_range_timecodes_are_rejected() {
+        for (value, n, d) in [
+            ("00:01:00;00", 30_000, 1001),
+            ("00:01:00;01", 30_000, 1001),
+            ("00:01:00;03", 60_000, 1001),
+            ("24:00:00:00", 25, 1),
+            ("00:00:00;00", 25, 1),
+            ("00:00:00;00", 30, 1),
+        ] {
+            let result = Timecode::from_smpte(&smpte(value), n, d);
+            assert!(
+                matches!(result, Err(Error::InvalidTimecode(_))),
+                "{value} at {n}/{d}"
+            );
+        }
+        assert!(Timecode::from_smpte(&smpte("00:10:00;00"), 30_000, 1001).is_ok());
+        assert!(Timecode::from_smpte(&smpte("23:59:59:24"), 25, 1).is_ok());
+    }
+}
@@ -0,0 +1,72 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use tokio::{sync::mpsc, task};
+
+use crate::{async_find::SharedFind, Error, Finder, Source};
+
+/// Source discovery for tokio applications.
+///
+/// The SDK's blocking waits run on tokio's blocking pool, so awaiting them never stalls the
+/// executor. The finder holds its own reference to the NDI runtime and can be cloned cheaply
+/// and moved between tasks.
+#[derive(Clone)]
+pub struct AsyncFinder {
+    find: Arc<SharedFind>,
+}
+
+impl AsyncFinder {
+    pub fn new(settings: Finder) -> Result<Self, Error> {
+        Ok(AsyncFinder {
+            find: SharedFind::new(settings)?,
+        })
+    }
+
+    /// Waits up to `timeout` for the source list to change; `false` if it didn't.
+    pub async fn wait_for_sources(&self, timeout: Duration) -> bool {
+        let find = Arc::clone(&self.find);
+        task::spawn_blocking(move || find.wait_for_sources(timeout))
+            .await
+            .unwrap_or(false)
+    }
+
+    /// The sources found so far, waiting up to `timeout` for the first one if there are none.
+    pub async fn find_sources(&self, timeout: Duration) -> Result<Vec<Source>, Error> {
+        let find = Arc::clone(&self.find);
+        task::spawn_blocking(move || find.find_sources(timeout))
+            .await
+            .map_err(|e| Error::InitializationFailed(format!("discovery task failed: {}", e)))?
+    }
+
+    /// A stream yielding the source list now and again whenever it changes.
+    ///
+    /// Changes are watched on a dedicated thread, which ends shortly after the stream is
+    /// dropped.
+    pub fn source_changes(&self) -> SourceChanges {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let closed = sender.clone();
+        self.find.watch(
+            move |sources| sender.send(sources).is_ok(),
+            move || closed.is_closed(),
+        );
+        SourceChanges { receiver }
+    }
+}
+
+/// The stream returned by [`AsyncFinder::source_changes`].
+pub struct SourceChanges {
+    receiver: mpsc::UnboundedReceiver<Vec<Source>>,
+}
+
+impl Stream for SourceChanges {
+    type Item = Vec<Source>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
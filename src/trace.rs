@@ -0,0 +1,30 @@
+// Logging macros that forward to `tracing` when the `tracing` feature is enabled and expand to
+// nothing otherwise, so call sites don't need their own `cfg` attributes.
+
+macro_rules! ndi_trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)*);
+    };
+}
+
+macro_rules! ndi_debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! ndi_info {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::info!($($arg)*);
+    };
+}
+
+macro_rules! ndi_warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)*);
+    };
+}
@@ -0,0 +1,134 @@
+use crate::{Error, FourCCVideoType, VideoFrame, VideoFrameBuilder};
+
+/// Resampling used by [`VideoFrame::scale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleFilter {
+    /// Picks the closest source pixel; fast and keeps hard edges.
+    Nearest,
+    /// Blends the four surrounding source pixels.
+    #[default]
+    Bilinear,
+}
+
+const BYTES_PER_PIXEL: usize = 4;
+
+impl VideoFrame {
+    /// Copies the `width` x `height` region starting at (`x`, `y`) into a new frame.
+    ///
+    /// Supports the 8-bit RGB-family formats (RGBA, RGBX, BGRA, BGRX).
+    pub fn crop(&self, x: i32, y: i32, width: i32, height: i32) -> Result<VideoFrame, Error> {
+        let stride = self.rgb_stride("crop")?;
+        if x < 0
+            || y < 0
+            || width <= 0
+            || height <= 0
+            || x.saturating_add(width) > self.xres
+            || y.saturating_add(height) > self.yres
+        {
+            return Err(Error::InvalidRegion {
+                x,
+                y,
+                width,
+                height,
+                xres: self.xres,
+                yres: self.yres,
+            });
+        }
+
+        let (x, y, width, height) = (x as usize, y as usize, width as usize, height as usize);
+        let row_bytes = width * BYTES_PER_PIXEL;
+        let mut data = Vec::with_capacity(row_bytes * height);
+        for row in self.data.chunks(stride).skip(y).take(height) {
+            let start = x * BYTES_PER_PIXEL;
+            data.extend_from_slice(&row[start..start + row_bytes]);
+        }
+        self.derive(width as i32, height as i32, data)
+    }
+
+    /// Resamples the frame to `width` x `height`.
+    ///
+    /// Supports the 8-bit RGB-family formats (RGBA, RGBX, BGRA, BGRX).
+    pub fn scale(&self, width: i32, height: i32, filter: ScaleFilter) -> Result<VideoFrame, Error> {
+        let stride = self.rgb_stride("scale")?;
+        if width <= 0 || height <= 0 {
+            return Err(Error::InvalidResolution {
+                xres: width,
+                yres: height,
+            });
+        }
+
+        let (src_w, src_h) = (self.xres as usize, self.yres as usize);
+        let (dst_w, dst_h) = (width as usize, height as usize);
+        let pixel = |x: usize, y: usize| {
+            let offset = y * stride + x * BYTES_PER_PIXEL;
+            &self.data[offset..offset + BYTES_PER_PIXEL]
+        };
+
+        let mut data = Vec::with_capacity(dst_w * dst_h * BYTES_PER_PIXEL);
+        match filter {
+            ScaleFilter::Nearest => {
+                for dy in 0..dst_h {
+                    let sy = (dy * src_h / dst_h).min(src_h - 1);
+                    for dx in 0..dst_w {
+                        let sx = (dx * src_w / dst_w).min(src_w - 1);
+                        data.extend_from_slice(pixel(sx, sy));
+                    }
+                }
+            }
+            ScaleFilter::Bilinear => {
+                // Sample at pixel centres so edges aren't shifted by half a pixel.
+                let x_ratio = src_w as f32 / dst_w as f32;
+                let y_ratio = src_h as f32 / dst_h as f32;
+                for dy in 0..dst_h {
+                    let fy = ((dy as f32 + 0.5) * y_ratio - 0.5).max(0.0);
+                    let y0 = (fy as usize).min(src_h - 1);
+                    let y1 = (y0 + 1).min(src_h - 1);
+                    let wy = fy - y0 as f32;
+                    for dx in 0..dst_w {
+                        let fx = ((dx as f32 + 0.5) * x_ratio - 0.5).max(0.0);
+                        let x0 = (fx as usize).min(src_w - 1);
+                        let x1 = (x0 + 1).min(src_w - 1);
+                        let wx = fx - x0 as f32;
+                        let (p00, p10, p01, p11) =
+                            (pixel(x0, y0), pixel(x1, y0), pixel(x0, y1), pixel(x1, y1));
+                        for c in 0..BYTES_PER_PIXEL {
+                            let top = p00[c] as f32 * (1.0 - wx) + p10[c] as f32 * wx;
+                            let bottom = p01[c] as f32 * (1.0 - wx) + p11[c] as f32 * wx;
+                            data.push((top * (1.0 - wy) + bottom * wy).round() as u8);
+                        }
+                    }
+                }
+            }
+        }
+        self.derive(width, height, data)
+    }
+
+    /// Checks the frame is in a 4-byte RGB format with valid data and returns its line stride.
+    pub(crate) fn rgb_stride(&self, operation: &'static str) -> Result<usize, Error> {
+        match self.fourcc {
+            FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX
+            | FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX => {}
+            fourcc => return Err(Error::UnsupportedFormat { fourcc, operation }),
+        }
+        self.validate()?;
+        Ok(unsafe { self.line_stride_or_size.line_stride_in_bytes } as usize)
+    }
+
+    /// A tightly packed frame of the same format and timing carrying `data`.
+    pub(crate) fn derive(&self, xres: i32, yres: i32, data: Vec<u8>) -> Result<VideoFrame, Error> {
+        let mut frame = VideoFrameBuilder::new()
+            .resolution(xres, yres)
+            .fourcc(self.fourcc)
+            .frame_rate(self.frame_rate_n, self.frame_rate_d)
+            .aspect_ratio(xres as f32 / yres as f32)
+            .format(self.frame_format_type)
+            .timecode(self.timecode)
+            .timestamp(self.timestamp)
+            .data(data)
+            .build()?;
+        frame.metadata = self.metadata.clone();
+        Ok(frame)
+    }
+}
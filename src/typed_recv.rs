@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use crate::{
+    AudioFrame, AudioFrameInfo, AudioFrameRef, ConnectionStats, Error, FieldMode, MetadataFrame,
+    MetadataFrameRef, Receiver, Recv, RecvBandwidth, RecvColorFormat, RetryPolicy, Source, NDI,
+};
+
+impl Receiver {
+    /// Settings for a receiver that only wants the source's audio (and metadata).
+    pub fn audio_preset(source: Source) -> Self {
+        Receiver::new(
+            source,
+            RecvColorFormat::BGRX_BGRA,
+            RecvBandwidth::AudioOnly,
+            FieldMode::Progressive,
+            None,
+        )
+    }
+
+    /// Settings for a receiver that only wants the source's metadata, e.g. for control or
+    /// monitoring.
+    pub fn metadata_preset(source: Source) -> Self {
+        Receiver::new(
+            source,
+            RecvColorFormat::BGRX_BGRA,
+            RecvBandwidth::MetadataOnly,
+            FieldMode::Progressive,
+            None,
+        )
+    }
+}
+
+/// A receiver that only captures audio, created with [`Receiver::audio_preset`] settings.
+///
+/// Video is never requested from the source, and there is no way to ask for it here, so an
+/// `AudioReceiver` in a signature says exactly what the callee will get.
+pub struct AudioReceiver<'a> {
+    recv: Recv<'a>,
+}
+
+impl<'a> AudioReceiver<'a> {
+    pub fn new(ndi: &'a NDI, source: Source) -> Result<Self, Error> {
+        Self::with_settings(ndi, Receiver::audio_preset(source))
+    }
+
+    /// Creates the receiver from `settings`, with the bandwidth forced to audio only.
+    pub fn with_settings(ndi: &'a NDI, mut settings: Receiver) -> Result<Self, Error> {
+        settings.bandwidth = RecvBandwidth::AudioOnly;
+        Ok(AudioReceiver {
+            recv: Recv::new(ndi, settings)?,
+        })
+    }
+
+    pub fn source(&self) -> &Source {
+        self.recv.source()
+    }
+
+    pub fn connections(&self) -> i32 {
+        self.recv.connections()
+    }
+
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.recv.connection_stats()
+    }
+
+    /// Sets the policy used by [`capture_audio`](Self::capture_audio).
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.recv.set_retry_policy(policy);
+    }
+
+    /// Waits up to `timeout` for an audio frame, polling and retrying per the receiver's policy.
+    pub fn capture_audio(&self, timeout: Duration) -> Result<AudioFrame, Error> {
+        self.recv.capture_audio(timeout)
+    }
+
+    /// See [`Recv::capture_audio_ref`].
+    pub fn capture_audio_ref(&self, timeout_ms: u32) -> Result<Option<AudioFrameRef<'_>>, Error> {
+        self.recv.capture_audio_ref(timeout_ms)
+    }
+
+    /// See [`Recv::capture_audio_into`].
+    pub fn capture_audio_into(
+        &self,
+        samples: &mut Vec<f32>,
+        timeout_ms: u32,
+    ) -> Result<Option<AudioFrameInfo>, Error> {
+        self.recv.capture_audio_into(samples, timeout_ms)
+    }
+
+    /// Gives up the audio-only view, e.g. to hand the receiver to code taking a [`Recv`].
+    pub fn into_inner(self) -> Recv<'a> {
+        self.recv
+    }
+}
+
+/// A receiver that only captures metadata, created with [`Receiver::metadata_preset`]
+/// settings.
+///
+/// Neither video nor audio is requested from the source. Metadata can also be sent upstream,
+/// which makes this the natural handle for control connections.
+pub struct MetadataReceiver<'a> {
+    recv: Recv<'a>,
+}
+
+impl<'a> MetadataReceiver<'a> {
+    pub fn new(ndi: &'a NDI, source: Source) -> Result<Self, Error> {
+        Self::with_settings(ndi, Receiver::metadata_preset(source))
+    }
+
+    /// Creates the receiver from `settings`, with the bandwidth forced to metadata only.
+    pub fn with_settings(ndi: &'a NDI, mut settings: Receiver) -> Result<Self, Error> {
+        settings.bandwidth = RecvBandwidth::MetadataOnly;
+        Ok(MetadataReceiver {
+            recv: Recv::new(ndi, settings)?,
+        })
+    }
+
+    pub fn source(&self) -> &Source {
+        self.recv.source()
+    }
+
+    pub fn connections(&self) -> i32 {
+        self.recv.connections()
+    }
+
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.recv.connection_stats()
+    }
+
+    /// Sets the policy used by [`capture_metadata`](Self::capture_metadata).
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.recv.set_retry_policy(policy);
+    }
+
+    /// Waits up to `timeout` for a metadata frame, polling and retrying per the receiver's
+    /// policy.
+    pub fn capture_metadata(&self, timeout: Duration) -> Result<MetadataFrame, Error> {
+        self.recv.capture_metadata(timeout)
+    }
+
+    /// See [`Recv::capture_metadata_ref`].
+    pub fn capture_metadata_ref(
+        &self,
+        timeout_ms: u32,
+    ) -> Result<Option<MetadataFrameRef<'_>>, Error> {
+        self.recv.capture_metadata_ref(timeout_ms)
+    }
+
+    /// See [`Recv::send_metadata`].
+    pub fn send_metadata(&self, metadata_frame: &MetadataFrame) -> bool {
+        self.recv.send_metadata(metadata_frame)
+    }
+
+    /// Gives up the metadata-only view, e.g. to hand the receiver to code taking a [`Recv`].
+    pub fn into_inner(self) -> Recv<'a> {
+        self.recv
+    }
+}
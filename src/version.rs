@@ -0,0 +1,56 @@
+use std::{fmt, str::FromStr};
+
+use crate::Error;
+
+/// A parsed NDI SDK version, e.g. `6.0.1.0`.
+///
+/// `NDIlib_version()` reports a string such as `NDI SDK LINUX 15:07:17 Mar 19 2024 6.0.1.0`;
+/// the dotted number at the end is the version and its fourth component the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NdiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+impl NdiVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        NdiVersion {
+            major,
+            minor,
+            patch,
+            build: 0,
+        }
+    }
+}
+
+impl fmt::Display for NdiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+impl FromStr for NdiVersion {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidVersion(value.to_string());
+        let token = value
+            .split_whitespace()
+            .rev()
+            .find(|t| t.contains('.') && t.split('.').all(|p| p.parse::<u32>().is_ok()))
+            .ok_or_else(invalid)?;
+        let mut parts = token.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+        Ok(NdiVersion {
+            major: parts.next().unwrap_or(0),
+            minor: parts.next().unwrap_or(0),
+            patch: parts.next().unwrap_or(0),
+            build: parts.next().unwrap_or(0),
+        })
+    }
+}
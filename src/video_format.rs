@@ -0,0 +1,133 @@
+use std::sync::Mutex;
+
+use crate::{FourCCVideoType, FrameFormatType, Recv, VideoFrame, VideoFrameRef, VideoMode};
+
+/// The shape of a receiver's video: what an encoder or display needs to be configured for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoFormat {
+    pub xres: i32,
+    pub yres: i32,
+    pub frame_rate_n: i32,
+    pub frame_rate_d: i32,
+    pub fourcc: FourCCVideoType,
+    pub frame_format_type: FrameFormatType,
+}
+
+impl VideoFormat {
+    /// Frames per second, or 0 if the source didn't give a rate.
+    pub fn frame_rate(&self) -> f64 {
+        if self.frame_rate_d == 0 {
+            0.0
+        } else {
+            self.frame_rate_n as f64 / self.frame_rate_d as f64
+        }
+    }
+
+    /// The standard mode this format is, if any.
+    pub fn video_mode(&self) -> Option<VideoMode> {
+        VideoMode::detect(
+            self.xres,
+            self.yres,
+            self.frame_rate_n,
+            self.frame_rate_d,
+            self.frame_format_type,
+        )
+    }
+}
+
+impl From<&VideoFrame> for VideoFormat {
+    fn from(frame: &VideoFrame) -> Self {
+        VideoFormat {
+            xres: frame.xres,
+            yres: frame.yres,
+            frame_rate_n: frame.frame_rate_n,
+            frame_rate_d: frame.frame_rate_d,
+            fourcc: frame.fourcc,
+            frame_format_type: frame.frame_format_type,
+        }
+    }
+}
+
+impl From<&VideoFrameRef<'_>> for VideoFormat {
+    fn from(frame: &VideoFrameRef<'_>) -> Self {
+        let (frame_rate_n, frame_rate_d) = frame.frame_rate();
+        VideoFormat {
+            xres: frame.xres(),
+            yres: frame.yres(),
+            frame_rate_n,
+            frame_rate_d,
+            fourcc: frame.fourcc(),
+            frame_format_type: frame.frame_format_type(),
+        }
+    }
+}
+
+/// A receiver's video format changing, as passed to [`Recv::on_video_format_change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoFormatChange {
+    /// The format before, or `None` for the first frame captured.
+    pub from: Option<VideoFormat>,
+    pub to: VideoFormat,
+}
+
+type FormatListener = Box<dyn FnMut(&VideoFormatChange) + std::marker::Send>;
+
+/// The format of the last video frame a receiver captured, shared with its capture threads.
+/// Checking a frame against it doesn't allocate, so it can sit on the zero-copy capture paths.
+#[derive(Default)]
+pub(crate) struct VideoFormatTracker {
+    current: Mutex<Option<VideoFormat>>,
+    listener: Mutex<Option<FormatListener>>,
+}
+
+impl VideoFormatTracker {
+    pub(crate) fn current(&self) -> Option<VideoFormat> {
+        *self.current.lock().ok()?
+    }
+
+    /// Records the format of a captured frame, telling the listener if it differs from the
+    /// last.
+    pub(crate) fn observe(&self, format: VideoFormat) {
+        let from = match self.current.lock() {
+            Ok(mut current) if *current != Some(format) => current.replace(format),
+            _ => return,
+        };
+        ndi_info!(
+            xres = format.xres,
+            yres = format.yres,
+            frame_rate_n = format.frame_rate_n,
+            frame_rate_d = format.frame_rate_d,
+            fourcc = %format.fourcc,
+            frame_format_type = ?format.frame_format_type,
+            "video format changed"
+        );
+        if let Ok(mut listener) = self.listener.lock() {
+            if let Some(listener) = listener.as_mut() {
+                listener(&VideoFormatChange { from, to: format });
+            }
+        }
+    }
+}
+
+impl<'a> Recv<'a> {
+    /// The format of the last video frame captured, or `None` before the first.
+    pub fn current_video_format(&self) -> Option<VideoFormat> {
+        self.state.video_format.current()
+    }
+
+    /// Calls `listener` whenever a captured video frame differs in resolution, frame rate,
+    /// pixel format or scan from the one before, including for the first frame, so encoders
+    /// can be reconfigured before frames of the new format reach them.
+    ///
+    /// Frames are checked by the receiver's capture methods and by [`Recv::start_callbacks`],
+    /// and the listener is called on the capturing thread before the frame is handed on.
+    pub fn on_video_format_change(
+        self,
+        listener: impl FnMut(&VideoFormatChange) + std::marker::Send + 'static,
+    ) -> Self {
+        if let Ok(mut slot) = self.state.video_format.listener.lock() {
+            *slot = Some(Box::new(listener));
+        }
+        self
+    }
+}
@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use crate::{FrameFormatType, FramePacer, VideoFrame, VideoFrameBuilder, VideoFrameRef};
+
+/// A standard broadcast video mode: resolution, frame rate, aspect ratio and scan, set
+/// together so fractional rates such as 30000/1001 don't have to be entered by hand.
+///
+/// Interlaced modes are named by field rate, as is customary, but like every NDI frame carry
+/// their frame rate: [`VideoMode::Hd1080i5994`] is 30000/1001.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VideoMode {
+    Sd480i5994,
+    Sd576i50,
+    Hd720p50,
+    Hd720p5994,
+    Hd720p60,
+    Hd1080i50,
+    Hd1080i5994,
+    Hd1080p2398,
+    Hd1080p24,
+    Hd1080p25,
+    Hd1080p2997,
+    Hd1080p30,
+    Hd1080p50,
+    Hd1080p5994,
+    Hd1080p60,
+    Uhd2160p2398,
+    Uhd2160p24,
+    Uhd2160p25,
+    Uhd2160p2997,
+    Uhd2160p30,
+    Uhd2160p50,
+    Uhd2160p5994,
+    Uhd2160p60,
+}
+
+impl VideoMode {
+    pub const ALL: [VideoMode; 23] = [
+        VideoMode::Sd480i5994,
+        VideoMode::Sd576i50,
+        VideoMode::Hd720p50,
+        VideoMode::Hd720p5994,
+        VideoMode::Hd720p60,
+        VideoMode::Hd1080i50,
+        VideoMode::Hd1080i5994,
+        VideoMode::Hd1080p2398,
+        VideoMode::Hd1080p24,
+        VideoMode::Hd1080p25,
+        VideoMode::Hd1080p2997,
+        VideoMode::Hd1080p30,
+        VideoMode::Hd1080p50,
+        VideoMode::Hd1080p5994,
+        VideoMode::Hd1080p60,
+        VideoMode::Uhd2160p2398,
+        VideoMode::Uhd2160p24,
+        VideoMode::Uhd2160p25,
+        VideoMode::Uhd2160p2997,
+        VideoMode::Uhd2160p30,
+        VideoMode::Uhd2160p50,
+        VideoMode::Uhd2160p5994,
+        VideoMode::Uhd2160p60,
+    ];
+
+    /// Width and height in pixels.
+    pub fn resolution(self) -> (i32, i32) {
+        match self {
+            VideoMode::Sd480i5994 => (720, 486),
+            VideoMode::Sd576i50 => (720, 576),
+            VideoMode::Hd720p50 | VideoMode::Hd720p5994 | VideoMode::Hd720p60 => (1280, 720),
+            VideoMode::Hd1080i50
+            | VideoMode::Hd1080i5994
+            | VideoMode::Hd1080p2398
+            | VideoMode::Hd1080p24
+            | VideoMode::Hd1080p25
+            | VideoMode::Hd1080p2997
+            | VideoMode::Hd1080p30
+            | VideoMode::Hd1080p50
+            | VideoMode::Hd1080p5994
+            | VideoMode::Hd1080p60 => (1920, 1080),
+            VideoMode::Uhd2160p2398
+            | VideoMode::Uhd2160p24
+            | VideoMode::Uhd2160p25
+            | VideoMode::Uhd2160p2997
+            | VideoMode::Uhd2160p30
+            | VideoMode::Uhd2160p50
+            | VideoMode::Uhd2160p5994
+            | VideoMode::Uhd2160p60 => (3840, 2160),
+        }
+    }
+
+    /// Frames per second as the numerator and denominator NDI frames carry.
+    pub fn frame_rate(self) -> (i32, i32) {
+        match self {
+            VideoMode::Hd1080p2398 | VideoMode::Uhd2160p2398 => (24000, 1001),
+            VideoMode::Hd1080p24 | VideoMode::Uhd2160p24 => (24, 1),
+            VideoMode::Sd576i50
+            | VideoMode::Hd1080i50
+            | VideoMode::Hd1080p25
+            | VideoMode::Uhd2160p25 => (25, 1),
+            VideoMode::Sd480i5994
+            | VideoMode::Hd1080i5994
+            | VideoMode::Hd1080p2997
+            | VideoMode::Uhd2160p2997 => (30000, 1001),
+            VideoMode::Hd1080p30 | VideoMode::Uhd2160p30 => (30, 1),
+            VideoMode::Hd720p50 | VideoMode::Hd1080p50 | VideoMode::Uhd2160p50 => (50, 1),
+            VideoMode::Hd720p5994 | VideoMode::Hd1080p5994 | VideoMode::Uhd2160p5994 => {
+                (60000, 1001)
+            }
+            VideoMode::Hd720p60 | VideoMode::Hd1080p60 | VideoMode::Uhd2160p60 => (60, 1),
+        }
+    }
+
+    /// Picture aspect ratio: 4:3 for SD, 16:9 otherwise.
+    pub fn aspect_ratio(self) -> f32 {
+        match self {
+            VideoMode::Sd480i5994 | VideoMode::Sd576i50 => 4.0 / 3.0,
+            _ => 16.0 / 9.0,
+        }
+    }
+
+    pub fn format(self) -> FrameFormatType {
+        if self.is_interlaced() {
+            FrameFormatType::Interlaced
+        } else {
+            FrameFormatType::Progressive
+        }
+    }
+
+    pub fn is_interlaced(self) -> bool {
+        matches!(
+            self,
+            VideoMode::Sd480i5994
+                | VideoMode::Sd576i50
+                | VideoMode::Hd1080i50
+                | VideoMode::Hd1080i5994
+        )
+    }
+
+    /// How long each frame lasts, rounded to the nanosecond.
+    pub fn frame_duration(self) -> Duration {
+        let (n, d) = self.frame_rate();
+        Duration::from_nanos(d as u64 * 1_000_000_000 / n as u64)
+    }
+
+    /// The mode matching a frame's resolution, exact frame rate and scan, if it is a standard
+    /// one.
+    pub fn detect(
+        xres: i32,
+        yres: i32,
+        frame_rate_n: i32,
+        frame_rate_d: i32,
+        format: FrameFormatType,
+    ) -> Option<Self> {
+        let interlaced = format != FrameFormatType::Progressive;
+        Self::ALL.into_iter().find(|mode| {
+            let (n, d) = mode.frame_rate();
+            mode.resolution() == (xres, yres)
+                && mode.is_interlaced() == interlaced
+                // Compare as fractions, so 60/1 and 60000/1000 both match.
+                && n as i64 * frame_rate_d as i64 == d as i64 * frame_rate_n as i64
+        })
+    }
+}
+
+/// The frame rate whose frames last `duration`, snapped to the NTSC-family fraction (such as
+/// 30000/1001) or whole rate it is within a microsecond of, otherwise exact to the nanosecond.
+fn frame_rate_from_duration(duration: Duration) -> (i32, i32) {
+    let nanos = duration.as_nanos();
+    if nanos == 0 || nanos > i32::MAX as u128 {
+        return (0, 1);
+    }
+    let close = |n: u128, d: u128| (d * 1_000_000_000 / n).abs_diff(nanos) <= 1_000;
+    for base in [24u128, 30, 48, 60, 120] {
+        if close(base * 1000, 1001) {
+            return ((base * 1000) as i32, 1001);
+        }
+    }
+    let whole = (1_000_000_000 + nanos / 2) / nanos;
+    if whole > 0 && close(whole, 1) {
+        return (whole as i32, 1);
+    }
+    let divisor = gcd(1_000_000_000, nanos);
+    ((1_000_000_000 / divisor) as i32, (nanos / divisor) as i32)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl VideoFrameBuilder {
+    /// Sets the resolution, frame rate, aspect ratio and scan of `mode`.
+    pub fn mode(self, mode: VideoMode) -> Self {
+        let (xres, yres) = mode.resolution();
+        let (n, d) = mode.frame_rate();
+        self.resolution(xres, yres)
+            .frame_rate(n, d)
+            .aspect_ratio(mode.aspect_ratio())
+            .format(mode.format())
+    }
+
+    /// Sets the frame rate from the length of one frame; durations of NTSC-family rates, e.g.
+    /// `Duration::from_nanos(33_366_667)`, become their exact fraction (30000/1001).
+    ///
+    /// A zero duration, or one longer than about two seconds, makes
+    /// [`build`](Self::build) fail with [`Error::InvalidFrameRate`](crate::Error::InvalidFrameRate).
+    pub fn frame_duration(self, duration: Duration) -> Self {
+        let (n, d) = frame_rate_from_duration(duration);
+        self.frame_rate(n, d)
+    }
+}
+
+impl VideoFrame {
+    /// The standard mode this frame is in, if any.
+    pub fn video_mode(&self) -> Option<VideoMode> {
+        VideoMode::detect(
+            self.xres,
+            self.yres,
+            self.frame_rate_n,
+            self.frame_rate_d,
+            self.frame_format_type,
+        )
+    }
+}
+
+impl VideoFrameRef<'_> {
+    /// The standard mode this frame is in, if any.
+    pub fn video_mode(&self) -> Option<VideoMode> {
+        let (n, d) = self.frame_rate();
+        VideoMode::detect(self.xres(), self.yres(), n, d, self.frame_format_type())
+    }
+}
+
+impl FramePacer {
+    /// Paces at the frame rate of `mode`.
+    pub fn for_mode(mode: VideoMode) -> Self {
+        let (n, d) = mode.frame_rate();
+        FramePacer::new(n as u32, d as u32)
+    }
+}
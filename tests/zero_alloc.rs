@@ -0,0 +1,135 @@
+//! Checks that the zero-copy capture path makes no heap allocations once running.
+//!
+//! The test sends frames to itself through the NDI runtime, so it is skipped when the runtime
+//! can't be loaded or the local source isn't discovered.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use grafton_ndi::{
+    FieldMode, Find, Finder, FourCCVideoType, Receiver, Recv, RecvBandwidth, RecvColorFormat,
+    Send, Sender, VideoFrameBuilder, NDI,
+};
+
+// Counts heap allocations made by threads that opt in, so the sending thread doesn't count.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn count() {
+    if COUNTING.with(Cell::get) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const WARM_UP_FRAMES: usize = 10;
+const MEASURED_FRAMES: usize = 60;
+
+#[test]
+fn capture_video_ref_does_not_allocate() {
+    let Ok(ndi) = NDI::new() else {
+        eprintln!("skipping: the NDI runtime isn't available");
+        return;
+    };
+    let name = format!("grafton-ndi zero-alloc {}", std::process::id());
+    let sender = Sender {
+        name: name.clone(),
+        groups: None,
+        clock_video: true,
+        clock_audio: false,
+        config: None,
+    };
+    let send = Send::new(&ndi, sender).unwrap();
+    let frame = VideoFrameBuilder::new()
+        .resolution(320, 180)
+        .fourcc(FourCCVideoType::BGRA)
+        .frame_rate(60, 1)
+        .build()
+        .unwrap();
+
+    let find = Find::new(&ndi, Finder::new(true, None, None)).unwrap();
+    let source = match find.find_source(&name, Instant::now() + Duration::from_secs(10)) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("skipping: the local source wasn't discovered ({})", e);
+            return;
+        }
+    };
+    let receiver = Receiver::new(
+        source,
+        RecvColorFormat::BGRX_BGRA,
+        RecvBandwidth::Highest,
+        FieldMode::Progressive,
+        None,
+    );
+    let recv = Recv::new(&ndi, receiver).unwrap();
+
+    let done = AtomicBool::new(false);
+    let allocations = thread::scope(|scope| {
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                send.send_video(&frame);
+            }
+        });
+
+        // The connection and the first frames may allocate, in the SDK and in the receiver's
+        // per-connection state, so let them settle before measuring.
+        let mut frames = 0;
+        while frames < WARM_UP_FRAMES {
+            if recv.capture_video_ref(1000).unwrap().is_some() {
+                frames += 1;
+            }
+        }
+
+        COUNTING.with(|counting| counting.set(true));
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let mut frames = 0;
+        let mut checksum = 0u64;
+        while frames < MEASURED_FRAMES {
+            if let Some(frame) = recv.capture_video_ref(1000).unwrap() {
+                checksum = checksum.wrapping_add(frame.data()[0] as u64);
+                frames += 1;
+            }
+        }
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        COUNTING.with(|counting| counting.set(false));
+
+        done.store(true, Ordering::Relaxed);
+        std::hint::black_box(checksum);
+        allocations
+    });
+
+    assert_eq!(
+        allocations, 0,
+        "capture_video_ref allocated {} times over {} frames",
+        allocations, MEASURED_FRAMES
+    );
+}